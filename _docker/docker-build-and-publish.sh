@@ -28,6 +28,9 @@ docker push --all-tags helikon/subvt-app-service
 # notification processor
 docker build -t helikon/subvt-notification-processor:"$1" -t helikon/subvt-notification-processor:latest --no-cache --build-arg version="$1" -f ./app/03-subvt-notification-processor.dockerfile ..
 docker push --all-tags helikon/subvt-notification-processor
+# ws gateway
+docker build -t helikon/subvt-ws-gateway:"$1" -t helikon/subvt-ws-gateway:latest --no-cache --build-arg version="$1" -f ./app/04-subvt-ws-gateway.dockerfile ..
+docker push --all-tags helikon/subvt-ws-gateway
 
 # network postgres
 docker build -t helikon/subvt-network-postgres:"$1" -t helikon/subvt-network-postgres:latest --no-cache --build-arg version="$1" -f ./network/01-subvt-network-postgres.dockerfile ..
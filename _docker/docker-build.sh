@@ -25,6 +25,8 @@ docker build -t helikon/subvt-app-postgres:"$1" --no-cache --build-arg version="
 docker build -t helikon/subvt-app-service:"$1" --no-cache --build-arg version="$1" -f ./app/02-subvt-app-service.dockerfile ..
 # notification processor
 docker build -t helikon/subvt-notification-processor:"$1" --no-cache --build-arg version="$1" -f ./app/03-subvt-notification-processor.dockerfile ..
+# ws gateway
+docker build -t helikon/subvt-ws-gateway:"$1" --no-cache --build-arg version="$1" -f ./app/04-subvt-ws-gateway.dockerfile ..
 
 # network postgres
 docker build -t helikon/subvt-network-postgres:"$1" --no-cache --build-arg version="$1" -f ./network/01-subvt-network-postgres.dockerfile ..
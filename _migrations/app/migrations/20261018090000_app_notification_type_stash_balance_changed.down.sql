@@ -0,0 +1 @@
+DELETE FROM app_notification_type WHERE code = 'chain_validator_stash_balance_changed';
\ No newline at end of file
@@ -0,0 +1,21 @@
+INSERT INTO app_notification_type(code) VALUES('chain_validator_stash_balance_changed') ON CONFLICT(code) DO NOTHING;
+-- chain_validator_stash_balance_changed
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'chain_validator_stash_balance_changed',
+    'minimum_amount',
+    0,
+    'balance',
+    '0',
+    NULL,
+    true,
+    'Minimum transferred, withdrawn, reserved, unreserved, locked or slashed amount in native token.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
\ No newline at end of file
@@ -0,0 +1 @@
+DELETE FROM app_notification_type WHERE code IN ('chain_validator_proxy_added', 'chain_validator_proxy_removed', 'chain_validator_pure_proxy_created', 'chain_validator_proxy_announced', 'chain_validator_new_multisig');
\ No newline at end of file
@@ -0,0 +1,105 @@
+INSERT INTO app_notification_type(code) VALUES('chain_validator_proxy_added') ON CONFLICT(code) DO NOTHING;
+INSERT INTO app_notification_type(code) VALUES('chain_validator_proxy_removed') ON CONFLICT(code) DO NOTHING;
+INSERT INTO app_notification_type(code) VALUES('chain_validator_pure_proxy_created') ON CONFLICT(code) DO NOTHING;
+INSERT INTO app_notification_type(code) VALUES('chain_validator_proxy_announced') ON CONFLICT(code) DO NOTHING;
+INSERT INTO app_notification_type(code) VALUES('chain_validator_new_multisig') ON CONFLICT(code) DO NOTHING;
+-- chain_validator_proxy_added
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'chain_validator_proxy_added',
+    'delegate_whitelist',
+    0,
+    'string',
+    '0',
+    '4096',
+    true,
+    'Comma-separated list of expected delegate (proxy) addresses, no notification is sent for these accounts.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
+-- chain_validator_proxy_removed
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'chain_validator_proxy_removed',
+    'delegate_whitelist',
+    0,
+    'string',
+    '0',
+    '4096',
+    true,
+    'Comma-separated list of expected delegate (proxy) addresses, no notification is sent for these accounts.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
+-- chain_validator_pure_proxy_created
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'chain_validator_pure_proxy_created',
+    'delegate_whitelist',
+    0,
+    'string',
+    '0',
+    '4096',
+    true,
+    'Comma-separated list of expected delegate (proxy) addresses, no notification is sent for these accounts.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
+-- chain_validator_proxy_announced
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'chain_validator_proxy_announced',
+    'delegate_whitelist',
+    0,
+    'string',
+    '0',
+    '4096',
+    true,
+    'Comma-separated list of expected delegate (proxy) addresses, no notification is sent for these accounts.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
+-- chain_validator_new_multisig
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'chain_validator_new_multisig',
+    'delegate_whitelist',
+    0,
+    'string',
+    '0',
+    '4096',
+    true,
+    'Comma-separated list of expected approving signatory addresses, no notification is sent for operations initiated by these accounts.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
\ No newline at end of file
@@ -0,0 +1 @@
+DELETE FROM app_notification_type WHERE code IN ('chain_validator_dispute_initiated', 'chain_validator_dispute_concluded');
\ No newline at end of file
@@ -0,0 +1,22 @@
+INSERT INTO app_notification_type(code) VALUES('chain_validator_dispute_initiated') ON CONFLICT(code) DO NOTHING;
+INSERT INTO app_notification_type(code) VALUES('chain_validator_dispute_concluded') ON CONFLICT(code) DO NOTHING;
+-- chain_validator_dispute_concluded
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'chain_validator_dispute_concluded',
+    'only_against_supermajority',
+    0,
+    'boolean',
+    NULL,
+    NULL,
+    true,
+    'Notify only when the validator''s statement contradicts the concluded result of the dispute, which is a slashable offence.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
\ No newline at end of file
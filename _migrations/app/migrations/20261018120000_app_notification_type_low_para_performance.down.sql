@@ -0,0 +1 @@
+DELETE FROM app_notification_type WHERE code = 'chain_validator_low_para_performance';
\ No newline at end of file
@@ -0,0 +1,40 @@
+INSERT INTO app_notification_type(code) VALUES('chain_validator_low_para_performance') ON CONFLICT(code) DO NOTHING;
+-- chain_validator_low_para_performance
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'chain_validator_low_para_performance',
+    'missed_attestation_percent',
+    0,
+    'float',
+    '0',
+    '100',
+    false,
+    'Notification happens if the ratio of missed attestations to all attestation slots is at or above this percentage.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'chain_validator_low_para_performance',
+    'session_count',
+    1,
+    'integer',
+    '1',
+    '24',
+    true,
+    'Number of consecutive sessions the threshold should be crossed for. Defaults to 1.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT INTO app_notification_type(code) VALUES('chain_validator_predicted_election') ON CONFLICT(code) DO NOTHING;
\ No newline at end of file
@@ -0,0 +1,40 @@
+INSERT INTO app_notification_type(code) VALUES('chain_validator_missed_blocks') ON CONFLICT(code) DO NOTHING;
+-- chain_validator_missed_blocks
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'chain_validator_missed_blocks',
+    'missed_block_percent',
+    0,
+    'float',
+    '0',
+    '100',
+    false,
+    'Notification happens if the validator authored fewer blocks than statistically expected by at least this percentage.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
+INSERT INTO app_notification_param_type(
+    notification_type_code,
+    code,
+    "order",
+    type,
+    "min",
+    "max",
+    is_optional,
+    description
+) VALUES(
+    'chain_validator_missed_blocks',
+    'session_count',
+    1,
+    'integer',
+    '1',
+    '24',
+    true,
+    'Number of consecutive sessions the threshold should be crossed for. Defaults to 1.'
+) ON CONFLICT(notification_type_code, code) DO NOTHING;
\ No newline at end of file
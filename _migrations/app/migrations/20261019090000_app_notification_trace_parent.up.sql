@@ -0,0 +1 @@
+ALTER TABLE app_notification ADD COLUMN IF NOT EXISTS trace_parent VARCHAR(55);
\ No newline at end of file
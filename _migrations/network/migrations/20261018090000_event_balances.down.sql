@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS sub_era_validator_idx_controller_account_id;
+DROP TABLE sub_event_balances CASCADE;
\ No newline at end of file
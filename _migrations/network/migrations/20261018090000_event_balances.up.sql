@@ -0,0 +1,40 @@
+CREATE TABLE IF NOT EXISTS sub_event_balances
+(
+    id                      SERIAL PRIMARY KEY,
+    block_hash              VARCHAR(66) NOT NULL,
+    extrinsic_index         INTEGER,
+    nesting_index           text,
+    event_index             INTEGER NOT NULL,
+    event_name              VARCHAR(64) NOT NULL,
+    account_id              VARCHAR(66) NOT NULL,
+    counterparty_account_id VARCHAR(66),
+    amount                  VARCHAR(128) NOT NULL,
+    created_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT sub_event_balances_u_event
+        UNIQUE (block_hash, event_index),
+    CONSTRAINT sub_event_balances_fk_block
+        FOREIGN KEY (block_hash)
+            REFERENCES sub_block (hash)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+    CONSTRAINT sub_event_balances_fk_account
+        FOREIGN KEY (account_id)
+            REFERENCES sub_account (id)
+            ON DELETE RESTRICT
+            ON UPDATE CASCADE,
+    CONSTRAINT sub_event_balances_fk_counterparty_account
+        FOREIGN KEY (counterparty_account_id)
+            REFERENCES sub_account (id)
+            ON DELETE RESTRICT
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS sub_event_balances_idx_block_hash
+    ON sub_event_balances (block_hash);
+CREATE INDEX IF NOT EXISTS sub_event_balances_idx_account_id
+    ON sub_event_balances (account_id);
+CREATE INDEX IF NOT EXISTS sub_event_balances_idx_counterparty_account_id
+    ON sub_event_balances (counterparty_account_id);
+-- balances events are persisted only for validator stash and controller accounts
+CREATE INDEX IF NOT EXISTS sub_era_validator_idx_controller_account_id
+    ON sub_era_validator (controller_account_id);
\ No newline at end of file
@@ -0,0 +1,2 @@
+DROP TABLE sub_event_new_multisig CASCADE;
+DROP TABLE sub_event_proxy CASCADE;
\ No newline at end of file
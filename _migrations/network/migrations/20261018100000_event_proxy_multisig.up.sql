@@ -0,0 +1,72 @@
+CREATE TABLE IF NOT EXISTS sub_event_proxy
+(
+    id                      SERIAL PRIMARY KEY,
+    block_hash              VARCHAR(66) NOT NULL,
+    extrinsic_index         INTEGER,
+    nesting_index           text,
+    event_index             INTEGER NOT NULL,
+    event_name              VARCHAR(64) NOT NULL,
+    account_id              VARCHAR(66) NOT NULL,
+    delegate_account_id     VARCHAR(66) NOT NULL,
+    proxy_type              VARCHAR(64),
+    delay                   bigint,
+    call_hash               VARCHAR(66),
+    created_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT sub_event_proxy_u_event
+        UNIQUE (block_hash, event_index),
+    CONSTRAINT sub_event_proxy_fk_block
+        FOREIGN KEY (block_hash)
+            REFERENCES sub_block (hash)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+    CONSTRAINT sub_event_proxy_fk_account
+        FOREIGN KEY (account_id)
+            REFERENCES sub_account (id)
+            ON DELETE RESTRICT
+            ON UPDATE CASCADE,
+    CONSTRAINT sub_event_proxy_fk_delegate_account
+        FOREIGN KEY (delegate_account_id)
+            REFERENCES sub_account (id)
+            ON DELETE RESTRICT
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS sub_event_proxy_idx_block_hash
+    ON sub_event_proxy (block_hash);
+CREATE INDEX IF NOT EXISTS sub_event_proxy_idx_account_id
+    ON sub_event_proxy (account_id);
+
+CREATE TABLE IF NOT EXISTS sub_event_new_multisig
+(
+    id                      SERIAL PRIMARY KEY,
+    block_hash              VARCHAR(66) NOT NULL,
+    extrinsic_index         INTEGER,
+    nesting_index           text,
+    event_index             INTEGER NOT NULL,
+    multisig_account_id     VARCHAR(66) NOT NULL,
+    approving_account_id    VARCHAR(66) NOT NULL,
+    call_hash               VARCHAR(66) NOT NULL,
+    created_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT sub_event_new_multisig_u_event
+        UNIQUE (block_hash, event_index),
+    CONSTRAINT sub_event_new_multisig_fk_block
+        FOREIGN KEY (block_hash)
+            REFERENCES sub_block (hash)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+    CONSTRAINT sub_event_new_multisig_fk_multisig_account
+        FOREIGN KEY (multisig_account_id)
+            REFERENCES sub_account (id)
+            ON DELETE RESTRICT
+            ON UPDATE CASCADE,
+    CONSTRAINT sub_event_new_multisig_fk_approving_account
+        FOREIGN KEY (approving_account_id)
+            REFERENCES sub_account (id)
+            ON DELETE RESTRICT
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS sub_event_new_multisig_idx_block_hash
+    ON sub_event_new_multisig (block_hash);
+CREATE INDEX IF NOT EXISTS sub_event_new_multisig_idx_multisig_account_id
+    ON sub_event_new_multisig (multisig_account_id);
\ No newline at end of file
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS sub_para_dispute_statement;
+DROP TABLE IF EXISTS sub_para_dispute;
\ No newline at end of file
@@ -0,0 +1,56 @@
+CREATE TABLE IF NOT EXISTS sub_para_dispute
+(
+    id                          SERIAL PRIMARY KEY,
+    candidate_hash              VARCHAR(66) NOT NULL,
+    initiated_block_hash        VARCHAR(66),
+    initiated_event_index       integer,
+    is_local                    boolean,
+    concluded_block_hash        VARCHAR(66),
+    concluded_event_index       integer,
+    is_valid                    boolean,
+    is_initiation_notified      boolean NOT NULL DEFAULT false,
+    is_conclusion_notified      boolean NOT NULL DEFAULT false,
+    created_at                  TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    updated_at                  TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT sub_para_dispute_u_candidate_hash
+        UNIQUE (candidate_hash),
+    CONSTRAINT sub_para_dispute_fk_initiated_block
+        FOREIGN KEY (initiated_block_hash)
+            REFERENCES sub_block (hash)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+    CONSTRAINT sub_para_dispute_fk_concluded_block
+        FOREIGN KEY (concluded_block_hash)
+            REFERENCES sub_block (hash)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS sub_para_dispute_idx_initiated_block_hash
+    ON sub_para_dispute (initiated_block_hash);
+CREATE INDEX IF NOT EXISTS sub_para_dispute_idx_concluded_block_hash
+    ON sub_para_dispute (concluded_block_hash);
+
+CREATE TABLE IF NOT EXISTS sub_para_dispute_statement
+(
+    id                      SERIAL PRIMARY KEY,
+    block_hash              VARCHAR(66) NOT NULL,
+    session_index           bigint NOT NULL,
+    candidate_hash          VARCHAR(66) NOT NULL,
+    para_validator_index    bigint NOT NULL,
+    is_valid                boolean NOT NULL,
+    kind                    VARCHAR(64) NOT NULL,
+    created_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT sub_para_dispute_statement_u_candidate_validator
+        UNIQUE (session_index, candidate_hash, para_validator_index),
+    CONSTRAINT sub_para_dispute_statement_fk_block
+        FOREIGN KEY (block_hash)
+            REFERENCES sub_block (hash)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS sub_para_dispute_statement_idx_candidate_hash
+    ON sub_para_dispute_statement (candidate_hash);
+CREATE INDEX IF NOT EXISTS sub_para_dispute_statement_idx_session_para_validator
+    ON sub_para_dispute_statement (session_index, para_validator_index);
\ No newline at end of file
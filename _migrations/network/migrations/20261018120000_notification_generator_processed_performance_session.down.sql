@@ -0,0 +1 @@
+DROP TABLE sub_notification_generator_processed_performance_session CASCADE;
\ No newline at end of file
@@ -0,0 +1,13 @@
+DROP INDEX IF EXISTS sub_kline_historical_idx_tickers_interval_open_time;
+
+DELETE FROM sub_kline_historical WHERE "interval" != '1d';
+
+ALTER TABLE sub_kline_historical
+    DROP CONSTRAINT IF EXISTS sub_kline_historical_u_interval_open_time_tickers;
+ALTER TABLE sub_kline_historical
+    ADD CONSTRAINT sub_kline_historical_u_open_time_source_ticker_target_ticker
+    UNIQUE (open_time, source_ticker, target_ticker);
+
+ALTER TABLE sub_kline_historical
+    DROP COLUMN IF EXISTS source,
+    DROP COLUMN IF EXISTS "interval";
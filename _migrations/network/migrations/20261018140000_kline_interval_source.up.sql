@@ -0,0 +1,12 @@
+ALTER TABLE sub_kline_historical
+    ADD COLUMN IF NOT EXISTS "interval" VARCHAR(8) NOT NULL DEFAULT '1d',
+    ADD COLUMN IF NOT EXISTS source VARCHAR(32) NOT NULL DEFAULT 'binance';
+
+ALTER TABLE sub_kline_historical
+    DROP CONSTRAINT IF EXISTS sub_kline_historical_u_open_time_source_ticker_target_ticker;
+ALTER TABLE sub_kline_historical
+    ADD CONSTRAINT sub_kline_historical_u_interval_open_time_tickers
+    UNIQUE ("interval", open_time, source_ticker, target_ticker);
+
+CREATE INDEX IF NOT EXISTS sub_kline_historical_idx_tickers_interval_open_time
+    ON sub_kline_historical (source_ticker, target_ticker, "interval", open_time);
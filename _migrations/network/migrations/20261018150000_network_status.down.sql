@@ -0,0 +1 @@
+DROP TABLE IF EXISTS sub_network_status;
\ No newline at end of file
@@ -0,0 +1,23 @@
+CREATE TABLE IF NOT EXISTS sub_network_status
+(
+    epoch_index                 bigint PRIMARY KEY,
+    era_index                   bigint NOT NULL,
+    block_number                bigint NOT NULL,
+    block_hash                  VARCHAR(66) NOT NULL,
+    "timestamp"                 bigint NOT NULL,
+    active_validator_count      bigint NOT NULL,
+    inactive_validator_count    bigint NOT NULL,
+    last_era_total_reward       VARCHAR(128) NOT NULL,
+    total_stake                 VARCHAR(128) NOT NULL,
+    return_rate_per_million     bigint NOT NULL,
+    min_stake                   VARCHAR(128) NOT NULL,
+    max_stake                   VARCHAR(128) NOT NULL,
+    average_stake               VARCHAR(128) NOT NULL,
+    median_stake                VARCHAR(128) NOT NULL,
+    era_reward_points           bigint NOT NULL,
+    created_at                  TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    updated_at                  TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS sub_network_status_idx_era_index
+    ON sub_network_status (era_index);
\ No newline at end of file
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS sub_era_validator_details;
\ No newline at end of file
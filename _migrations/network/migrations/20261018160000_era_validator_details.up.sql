@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS sub_era_validator_details
+(
+    id                      SERIAL PRIMARY KEY,
+    era_index               bigint NOT NULL,
+    validator_account_id    VARCHAR(66) NOT NULL,
+    block_number            bigint NOT NULL,
+    block_hash              VARCHAR(66) NOT NULL,
+    is_full                 boolean NOT NULL,
+    details                 TEXT NOT NULL,
+    created_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT sub_era_validator_details_u_era_index_validator_account_id
+        UNIQUE (era_index, validator_account_id)
+);
+
+CREATE INDEX IF NOT EXISTS sub_era_validator_details_idx_validator_account_id_era_index
+    ON sub_era_validator_details (validator_account_id, era_index);
\ No newline at end of file
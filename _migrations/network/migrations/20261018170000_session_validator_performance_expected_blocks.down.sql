@@ -0,0 +1,2 @@
+ALTER TABLE sub_session_validator_performance DROP COLUMN IF EXISTS expected_primary_slot_count;
+ALTER TABLE sub_session_validator_performance DROP COLUMN IF EXISTS expected_block_count;
\ No newline at end of file
@@ -0,0 +1,2 @@
+ALTER TABLE sub_session_validator_performance ADD COLUMN IF NOT EXISTS expected_block_count double precision;
+ALTER TABLE sub_session_validator_performance ADD COLUMN IF NOT EXISTS expected_primary_slot_count double precision;
\ No newline at end of file
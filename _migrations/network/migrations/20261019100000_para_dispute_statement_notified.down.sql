@@ -0,0 +1 @@
+ALTER TABLE sub_para_dispute_statement DROP COLUMN IF EXISTS is_initiation_notified;
@@ -0,0 +1 @@
+ALTER TABLE sub_para_dispute_statement ADD COLUMN IF NOT EXISTS is_initiation_notified boolean NOT NULL DEFAULT false;
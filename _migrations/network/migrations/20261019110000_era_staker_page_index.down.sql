@@ -0,0 +1 @@
+ALTER TABLE sub_era_staker DROP COLUMN IF EXISTS page_index;
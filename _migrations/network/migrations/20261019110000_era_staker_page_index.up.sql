@@ -0,0 +1 @@
+ALTER TABLE sub_era_staker ADD COLUMN IF NOT EXISTS page_index INTEGER;
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS sub_kline_unavailable_idx_tickers_interval_open_time;
+DROP TABLE IF EXISTS sub_kline_unavailable;
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS sub_kline_unavailable
+(
+    id                      SERIAL PRIMARY KEY,
+    "interval"              VARCHAR(8) NOT NULL,
+    open_time               BIGINT NOT NULL,
+    source_ticker           VARCHAR(16) NOT NULL,
+    target_ticker           VARCHAR(16) NOT NULL,
+    created_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT sub_kline_unavailable_u_interval_open_time_tickers
+    UNIQUE ("interval", open_time, source_ticker, target_ticker)
+);
+
+CREATE INDEX IF NOT EXISTS sub_kline_unavailable_idx_tickers_interval_open_time
+    ON sub_kline_unavailable (source_ticker, target_ticker, "interval", open_time);
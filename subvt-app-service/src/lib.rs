@@ -10,6 +10,7 @@ use rustc_hash::FxHashSet as HashSet;
 use serde::Deserialize;
 use std::sync::Arc;
 use subvt_config::Config;
+use subvt_metrics::health;
 use subvt_persistence::postgres::app::PostgreSQLAppStorage;
 use subvt_service_common::{err::InternalServerError, Service};
 use subvt_types::app::{
@@ -520,6 +521,7 @@ async fn delete_user_notification_rule(
 
 async fn on_server_ready() {
     log::debug!("HTTP service started.");
+    health::set_ready(true);
 }
 
 #[derive(Default)]
@@ -0,0 +1,49 @@
+use rustc_hash::FxHashSet as HashSet;
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::crypto::AccountId;
+use subvt_types::substrate::event::balances::BalancesEvent;
+
+/// Persists the event only if the account or the counterparty is one of the tracked validator
+/// stash or controller accounts.
+pub(crate) async fn process_balances_event(
+    postgres: &PostgreSQLNetworkStorage,
+    block_hash: &str,
+    event_index: usize,
+    event: &BalancesEvent,
+    tracked_account_ids: &HashSet<AccountId>,
+) -> anyhow::Result<()> {
+    let extrinsic_index = event
+        .get_extrinsic_index()
+        .map(|extrinsic_index| extrinsic_index as i32);
+    let (account_id, maybe_counterparty_account_id, amount) = event.get_account_ids_and_amount();
+    if !tracked_account_ids.contains(&account_id)
+        && !maybe_counterparty_account_id
+            .as_ref()
+            .is_some_and(|account_id| tracked_account_ids.contains(account_id))
+    {
+        return Ok(());
+    }
+    postgres
+        .save_balances_event(
+            block_hash,
+            extrinsic_index,
+            event_index as i32,
+            event.get_name(),
+            &account_id,
+            maybe_counterparty_account_id.as_ref(),
+            amount,
+        )
+        .await?;
+    Ok(())
+}
+
+pub(crate) async fn update_balances_event_nesting_index(
+    postgres: &PostgreSQLNetworkStorage,
+    block_hash: &str,
+    maybe_nesting_index: &Option<String>,
+    event_index: i32,
+) -> anyhow::Result<()> {
+    postgres
+        .update_balances_event_nesting_index(block_hash, maybe_nesting_index, event_index)
+        .await
+}
@@ -1,15 +1,34 @@
+use crate::event::balances::{process_balances_event, update_balances_event_nesting_index};
 use crate::event::democracy::{process_democracy_event, update_democracy_event_nesting_index};
+use crate::event::multisig::{process_multisig_event, update_multisig_event_nesting_index};
+use crate::event::paras_disputes::process_paras_disputes_event;
+use crate::event::proxy::{process_proxy_event, update_proxy_event_nesting_index};
 use crate::event::referenda::{process_referenda_event, update_referenda_event_nesting_index};
 use crate::event::staking::{process_staking_event, update_staking_event_nesting_index};
 use crate::event::system::{process_system_event, update_system_event_nesting_index};
+use rustc_hash::FxHashSet as HashSet;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::crypto::AccountId;
 use subvt_types::substrate::event::SubstrateEvent;
 
+mod balances;
 mod democracy;
+mod multisig;
+mod paras_disputes;
+mod proxy;
 mod referenda;
 mod staking;
 mod system;
 
+/// Data of the block that's loaded once, before its events get processed.
+#[derive(Default)]
+pub(crate) struct BlockEventContext {
+    /// Validator stash and controller accounts among the accounts of the balances events.
+    pub tracked_account_ids: HashSet<AccountId>,
+    /// Indices of the extrinsics that make a multisig call with a staking call.
+    pub staking_multisig_extrinsic_indices: HashSet<u32>,
+}
+
 pub(crate) async fn process_event(
     postgres: &PostgreSQLNetworkStorage,
     block_hash: &str,
@@ -17,11 +36,39 @@ pub(crate) async fn process_event(
     block_timestamp: u64,
     event_index: usize,
     event: &SubstrateEvent,
+    context: &BlockEventContext,
 ) -> anyhow::Result<()> {
     match event {
+        SubstrateEvent::Balances(balances_event) => {
+            process_balances_event(
+                postgres,
+                block_hash,
+                event_index,
+                balances_event,
+                &context.tracked_account_ids,
+            )
+            .await?
+        }
         SubstrateEvent::Democracy(democracy_event) => {
             process_democracy_event(postgres, block_hash, event_index, democracy_event).await?
         }
+        SubstrateEvent::Multisig(multisig_event) => {
+            process_multisig_event(
+                postgres,
+                block_hash,
+                event_index,
+                multisig_event,
+                &context.staking_multisig_extrinsic_indices,
+            )
+            .await?
+        }
+        SubstrateEvent::ParasDisputes(paras_disputes_event) => {
+            process_paras_disputes_event(postgres, block_hash, event_index, paras_disputes_event)
+                .await?
+        }
+        SubstrateEvent::Proxy(proxy_event) => {
+            process_proxy_event(postgres, block_hash, event_index, proxy_event).await?
+        }
         SubstrateEvent::Referenda(referenda_event) => {
             process_referenda_event(postgres, block_hash, event_index, referenda_event).await?
         }
@@ -53,6 +100,15 @@ pub(crate) async fn update_event_nesting_indices(
 ) -> anyhow::Result<()> {
     for (event_index, event) in events {
         match event {
+            SubstrateEvent::Balances(_) => {
+                update_balances_event_nesting_index(
+                    postgres,
+                    block_hash,
+                    maybe_nesting_index,
+                    *event_index as i32,
+                )
+                .await?;
+            }
             SubstrateEvent::Democracy(democracy_event) => {
                 update_democracy_event_nesting_index(
                     postgres,
@@ -95,8 +151,27 @@ pub(crate) async fn update_event_nesting_indices(
             }
             SubstrateEvent::Utility(_) => (),
             SubstrateEvent::Identity(_) => {}
-            SubstrateEvent::Multisig(_) => {}
-            SubstrateEvent::Proxy(_) => {}
+            SubstrateEvent::Multisig(multisig_event) => {
+                update_multisig_event_nesting_index(
+                    postgres,
+                    block_hash,
+                    maybe_nesting_index,
+                    *event_index as i32,
+                    multisig_event,
+                )
+                .await?
+            }
+            SubstrateEvent::ParasDisputes(_) => {}
+            SubstrateEvent::Proxy(proxy_event) => {
+                update_proxy_event_nesting_index(
+                    postgres,
+                    block_hash,
+                    maybe_nesting_index,
+                    *event_index as i32,
+                    proxy_event,
+                )
+                .await?
+            }
             SubstrateEvent::Other { .. } => {}
         }
     }
@@ -0,0 +1,64 @@
+use rustc_hash::FxHashSet as HashSet;
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::substrate::event::multisig::MultisigEvent;
+
+/// `NewMultisig` events are persisted only for the multisig operations with a staking call, i.e.
+/// when the extrinsic of the event is in `staking_multisig_extrinsic_indices`. Operations started
+/// with only the call hash (`approve_as_multi`) don't carry the call and are skipped.
+pub(crate) async fn process_multisig_event(
+    postgres: &PostgreSQLNetworkStorage,
+    block_hash: &str,
+    event_index: usize,
+    event: &MultisigEvent,
+    staking_multisig_extrinsic_indices: &HashSet<u32>,
+) -> anyhow::Result<()> {
+    match event {
+        MultisigEvent::NewMultisig {
+            extrinsic_index,
+            approving_account_id,
+            multisig_account_id,
+            call_hash,
+        } => {
+            if !extrinsic_index.is_some_and(|extrinsic_index| {
+                staking_multisig_extrinsic_indices.contains(&extrinsic_index)
+            }) {
+                return Ok(());
+            }
+            let extrinsic_index = extrinsic_index.map(|extrinsic_index| extrinsic_index as i32);
+            postgres
+                .save_new_multisig_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    multisig_account_id,
+                    approving_account_id,
+                    &format!("0x{}", hex::encode(call_hash)),
+                )
+                .await?;
+        }
+        MultisigEvent::MultisigExecuted { .. } => (),
+    }
+    Ok(())
+}
+
+pub(crate) async fn update_multisig_event_nesting_index(
+    postgres: &PostgreSQLNetworkStorage,
+    block_hash: &str,
+    maybe_nesting_index: &Option<String>,
+    event_index: i32,
+    event: &MultisigEvent,
+) -> anyhow::Result<()> {
+    match event {
+        MultisigEvent::NewMultisig { .. } => {
+            postgres
+                .update_new_multisig_event_nesting_index(
+                    block_hash,
+                    maybe_nesting_index,
+                    event_index,
+                )
+                .await?
+        }
+        MultisigEvent::MultisigExecuted { .. } => (),
+    }
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::substrate::event::paras_disputes::ParasDisputesEvent;
+
+pub(crate) async fn process_paras_disputes_event(
+    postgres: &PostgreSQLNetworkStorage,
+    block_hash: &str,
+    event_index: usize,
+    event: &ParasDisputesEvent,
+) -> anyhow::Result<()> {
+    match event {
+        ParasDisputesEvent::DisputeInitiated {
+            candidate_hash,
+            is_local,
+            ..
+        } => {
+            postgres
+                .save_para_dispute_initiated(
+                    &format!("0x{}", hex::encode(candidate_hash.0)),
+                    block_hash,
+                    event_index as i32,
+                    *is_local,
+                )
+                .await?;
+        }
+        ParasDisputesEvent::DisputeConcluded {
+            candidate_hash,
+            is_valid,
+            ..
+        } => {
+            postgres
+                .save_para_dispute_concluded(
+                    &format!("0x{}", hex::encode(candidate_hash.0)),
+                    block_hash,
+                    event_index as i32,
+                    *is_valid,
+                )
+                .await?;
+        }
+        ParasDisputesEvent::Revert { block_number, .. } => {
+            log::warn!("Para disputes revert to block #{block_number} in block {block_hash}.");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::substrate::event::proxy::ProxyEvent;
+
+pub(crate) async fn process_proxy_event(
+    postgres: &PostgreSQLNetworkStorage,
+    block_hash: &str,
+    event_index: usize,
+    event: &ProxyEvent,
+) -> anyhow::Result<()> {
+    let extrinsic_index = event
+        .get_extrinsic_index()
+        .map(|extrinsic_index| extrinsic_index as i32);
+    match event {
+        ProxyEvent::Announced {
+            real_account_id,
+            proxy_account_id,
+            call_hash,
+            ..
+        } => {
+            postgres
+                .save_proxy_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    "Announced",
+                    real_account_id,
+                    proxy_account_id,
+                    None,
+                    None,
+                    Some(&format!("0x{}", hex::encode(call_hash))),
+                )
+                .await?;
+        }
+        ProxyEvent::ProxyAdded {
+            delegator_account_id,
+            delegatee_account_id,
+            proxy_type,
+            delay,
+            ..
+        } => {
+            postgres
+                .save_proxy_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    "ProxyAdded",
+                    delegator_account_id,
+                    delegatee_account_id,
+                    Some(proxy_type.as_str()),
+                    Some(*delay),
+                    None,
+                )
+                .await?;
+        }
+        ProxyEvent::ProxyRemoved {
+            delegator_account_id,
+            delegatee_account_id,
+            proxy_type,
+            delay,
+            ..
+        } => {
+            postgres
+                .save_proxy_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    "ProxyRemoved",
+                    delegator_account_id,
+                    delegatee_account_id,
+                    Some(proxy_type.as_str()),
+                    Some(*delay),
+                    None,
+                )
+                .await?;
+        }
+        ProxyEvent::PureCreated {
+            pure_account_id,
+            spawner_account_id,
+            proxy_type,
+            ..
+        } => {
+            postgres
+                .save_proxy_event(
+                    block_hash,
+                    extrinsic_index,
+                    event_index as i32,
+                    "PureCreated",
+                    spawner_account_id,
+                    pure_account_id,
+                    Some(proxy_type.as_str()),
+                    None,
+                    None,
+                )
+                .await?;
+        }
+        ProxyEvent::ProxyExecuted { .. } => (),
+    }
+    Ok(())
+}
+
+pub(crate) async fn update_proxy_event_nesting_index(
+    postgres: &PostgreSQLNetworkStorage,
+    block_hash: &str,
+    maybe_nesting_index: &Option<String>,
+    event_index: i32,
+    event: &ProxyEvent,
+) -> anyhow::Result<()> {
+    match event {
+        ProxyEvent::ProxyExecuted { .. } => (),
+        _ => {
+            postgres
+                .update_proxy_event_nesting_index(block_hash, maybe_nesting_index, event_index)
+                .await?
+        }
+    }
+    Ok(())
+}
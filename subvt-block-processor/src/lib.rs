@@ -1,6 +1,6 @@
 //! Indexes historical block data into the PostreSQL database instance.
 #![warn(clippy::disallowed_types)]
-use crate::event::process_event;
+use crate::event::{process_event, BlockEventContext};
 use async_lock::Mutex;
 use async_trait::async_trait;
 use lazy_static::lazy_static;
@@ -11,12 +11,16 @@ use std::sync::{
     Arc, RwLock,
 };
 use subvt_config::Config;
+use subvt_logging::trace::{self, FinalizedAt, FutureExt, KeyValue};
+use subvt_metrics::health;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
-use subvt_service_common::Service;
+use subvt_service_common::{shutdown, Service};
 use subvt_substrate_client::SubstrateClient;
+use subvt_types::rdb::BlockProcessedNotification;
 use subvt_types::substrate::error::DecodeError;
 use subvt_types::substrate::event::SubstrateEvent;
 use subvt_types::substrate::metadata::get_metadata_expected_block_time_millis;
+use subvt_types::substrate::para::ParaDisputeStatement;
 use subvt_types::substrate::ValidityAttestation;
 use subvt_types::{
     crypto::AccountId,
@@ -160,6 +164,8 @@ impl BlockProcessor {
                 substrate_client.last_runtime_upgrade_info.spec_version
             );
         }
+        // fetched once, with the metadata of the block
+        let event_results = substrate_client.get_block_events(&block_hash).await?;
         let active_validator_account_ids = substrate_client
             .get_active_validator_account_ids(&block_hash)
             .await?;
@@ -266,16 +272,59 @@ impl BlockProcessor {
                     }
                 }
             }
-            // save disputes
-            for statement_set in votes.disputes {
-                for _statement in statement_set.statements {}
+            // save dispute statements
+            let dispute_statements = ParaDisputeStatement::from_on_chain_votes(&votes);
+            for statement in &dispute_statements {
+                postgres
+                    .save_para_dispute_statement(
+                        &block_hash,
+                        statement.session_index,
+                        &statement.candidate_hash,
+                        statement.para_validator_index,
+                        statement.is_valid,
+                        &statement.kind,
+                    )
+                    .await?;
             }
             log::debug!(
-                "Processed {} para votes for {} paras.",
+                "Processed {} para votes for {} paras and {} dispute statements.",
                 total_vote_count,
                 votes.backing_validators_per_candidate.len(),
+                dispute_statements.len(),
             );
         }
+        // para disputes
+        for (event_index, event_result) in event_results.iter().enumerate() {
+            match event_result {
+                Ok(event @ SubstrateEvent::ParasDisputes(_)) => {
+                    process_event(
+                        postgres,
+                        &block_hash,
+                        block_number,
+                        block_timestamp,
+                        event_index,
+                        event,
+                        &BlockEventContext::default(),
+                    )
+                    .await?;
+                }
+                Ok(_) => (),
+                Err(DecodeError::Error(error_log)) => {
+                    // the events after an undecodable event cannot be decoded either
+                    log::error!("RELAY {error_log}");
+                    metrics::event_process_error_count().inc();
+                    postgres
+                        .save_event_process_error_log(
+                            &block_hash,
+                            block_number,
+                            event_index,
+                            "decode",
+                            error_log,
+                        )
+                        .await?;
+                }
+            }
+        }
         Ok(())
     }
 
@@ -472,6 +521,39 @@ impl BlockProcessor {
             )
             .await?;
         log::info!("Saved finalized block {}.", block_number);
+        // preload the validator stash and controller accounts among the accounts of the balances
+        // events, so that each event doesn't need a separate query, and find the multisig calls
+        // with a staking call
+        let tracked_account_ids = {
+            let account_ids: Vec<AccountId> = event_results
+                .iter()
+                .filter_map(|event_result| match event_result {
+                    Ok(SubstrateEvent::Balances(balances_event)) => Some(balances_event),
+                    _ => None,
+                })
+                .flat_map(|balances_event| {
+                    let (account_id, maybe_counterparty_account_id, _) =
+                        balances_event.get_account_ids_and_amount();
+                    std::iter::once(account_id).chain(maybe_counterparty_account_id)
+                })
+                .collect();
+            postgres
+                .get_validator_stash_or_controller_account_ids(&account_ids)
+                .await?
+        };
+        let event_context = BlockEventContext {
+            tracked_account_ids,
+            staking_multisig_extrinsic_indices: extrinsic_results
+                .iter()
+                .enumerate()
+                .filter(|(_, extrinsic_result)| {
+                    extrinsic_result
+                        .as_ref()
+                        .is_ok_and(|extrinsic| extrinsic.has_staking_multisig_call())
+                })
+                .map(|(index, _)| index as u32)
+                .collect(),
+        };
         // process/persist events
         let mut extrinsic_event_map: HashMap<u32, Vec<(usize, SubstrateEvent)>> =
             HashMap::default();
@@ -496,6 +578,7 @@ impl BlockProcessor {
                         block_timestamp,
                         index,
                         event,
+                        &event_context,
                     )
                     .await
                     {
@@ -592,12 +675,63 @@ impl BlockProcessor {
                 },
             }
         }
-        // notify
+        // notify the notification generator, continuing the pipeline trace of the block. the
+        // past blocks processed on catch-up, including the skipped ones, weren't observed
+        // finalized, their timestamp is the closest time to their finalization
+        let finalized_at = trace::get_finalized_at()
+            .map(|finalized_at| finalized_at.0)
+            .unwrap_or(block_timestamp);
         postgres
-            .notify_block_processed(block_number, &block_hash)
+            .notify_block_processed(&BlockProcessedNotification {
+                block_number,
+                block_hash: block_hash.clone(),
+                trace_parent: trace::get_trace_parent(&trace::Context::current()),
+                finalized_at: Some(finalized_at),
+            })
             .await?;
         Ok(())
     }
+
+    /// Processes the current finalized relay and Asset Hub blocks once, outside the finalized
+    /// block subscriptions, and returns their numbers. The replay tests run the processor through
+    /// this.
+    pub async fn process_finalized_blocks(
+        &'static self,
+        relay_substrate_client: &mut SubstrateClient,
+        asset_hub_substrate_client: &mut SubstrateClient,
+        postgres: &PostgreSQLNetworkStorage,
+    ) -> anyhow::Result<(u64, u64)> {
+        let relay_block_hash = relay_substrate_client.get_finalized_block_hash().await?;
+        let relay_block_number = relay_substrate_client
+            .get_block_header(&relay_block_hash)
+            .await?
+            .get_number()?;
+        self.process_relay_block(
+            relay_substrate_client,
+            asset_hub_substrate_client,
+            postgres,
+            relay_block_number,
+        )
+        .await?;
+        let asset_hub_block_hash = asset_hub_substrate_client
+            .get_finalized_block_hash()
+            .await?;
+        let asset_hub_block_number = asset_hub_substrate_client
+            .get_block_header(&asset_hub_block_hash)
+            .await?
+            .get_number()?;
+        let runtime_information = Arc::new(RwLock::new(RuntimeInformation::default()));
+        self.process_asset_hub_block(
+            asset_hub_substrate_client,
+            relay_substrate_client,
+            &runtime_information,
+            postgres,
+            asset_hub_block_number,
+            false,
+        )
+        .await?;
+        Ok((relay_block_number, asset_hub_block_number))
+    }
 }
 
 impl BlockProcessor {
@@ -643,6 +777,7 @@ impl BlockProcessor {
                 .set(postgres.get_extrinsic_process_error_log_count().await? as i64);
             metrics::event_process_error_count()
                 .set(postgres.get_event_process_error_log_count().await? as i64);
+            health::set_ready(true);
 
             relay_finalized_block_subscription_substrate_client.subscribe_to_finalized_blocks(
                 CONFIG.substrate.request_timeout_seconds,
@@ -659,15 +794,22 @@ impl BlockProcessor {
                         }
                     };
                     metrics::target_finalized_block_number().set(finalized_block_number as i64);
+                    if shutdown::is_requested() {
+                        log::debug!("RELAY Shutting down. Skip block #{finalized_block_number}.");
+                        return Ok(());
+                    }
                     if RELAY_IS_BUSY.load(Ordering::SeqCst) {
                         log::debug!("RELAY Busy processing past blocks. Skip block #{finalized_block_number} for now.");
                         return Ok(());
                     }
                     RELAY_IS_BUSY.store(true, Ordering::SeqCst);
+                    // the shutdown waits for the blocks being processed
+                    let in_flight = shutdown::in_flight();
                     let block_processor_substrate_client = relay_substrate_client.clone();
                     let asset_hub_substrate_client = asset_hub_substrate_client.clone();
                     let postgres = postgres.clone();
                     tokio::spawn(async move {
+                        let _in_flight = in_flight;
                         let mut block_processor_substrate_client = block_processor_substrate_client.lock().await;
                         let mut asset_hub_substrate_client = asset_hub_substrate_client.lock().await;
                         let processed_block_height = match postgres.get_processed_block_height("relay").await {
@@ -684,7 +826,7 @@ impl BlockProcessor {
                                 processed_block_height,
                                 CONFIG.block_processor.relay_start_block_number
                             );
-                            while block_number <= finalized_block_number {
+                            while block_number <= finalized_block_number && !shutdown::is_requested() {
                                 log::info!(
                                     "RELAY Process block #{block_number}. Target #{finalized_block_number}.",
                                 );
@@ -790,6 +932,7 @@ impl BlockProcessor {
                 .set(postgres.get_extrinsic_process_error_log_count().await? as i64);
             metrics::event_process_error_count()
                 .set(postgres.get_event_process_error_log_count().await? as i64);
+            health::set_ready(true);
 
             finalized_block_subscription_substrate_client.subscribe_to_finalized_blocks(
                 CONFIG.substrate.request_timeout_seconds,
@@ -806,16 +949,30 @@ impl BlockProcessor {
                         }
                     };
                     metrics::target_asset_hub_finalized_block_number().set(finalized_block_number as i64);
+                    if shutdown::is_requested() {
+                        log::debug!("ASSET_HUB Shutting down. Skip block #{finalized_block_number}.");
+                        return Ok(());
+                    }
                     if ASSET_HUB_IS_BUSY.load(Ordering::SeqCst) {
                         log::debug!("ASSET_HUB Busy processing past blocks. Skip block #{finalized_block_number} for now.");
                         return Ok(());
                     }
                     ASSET_HUB_IS_BUSY.store(true, Ordering::SeqCst);
+                    // the shutdown waits for the blocks being processed
+                    let in_flight = shutdown::in_flight();
+                    // the notification pipeline trace of the block starts at its finalization
+                    let finalized_block_context = trace::start_span(
+                        "process_block",
+                        None,
+                        vec![KeyValue::new("block_number", finalized_block_number as i64)],
+                    )
+                    .with_value(FinalizedAt::now());
                     let block_processor_substrate_client = substrate_client.clone();
                     let relay_substrate_client = relay_substrate_client.clone();
                     let postgres = postgres.clone();
                     let runtime_information = runtime_information.clone();
                     tokio::spawn(async move {
+                        let _in_flight = in_flight;
                         let mut block_processor_substrate_client = block_processor_substrate_client.lock().await;
                         let mut relay_substrate_client = relay_substrate_client.lock().await;
                         let processed_block_height = match postgres.get_processed_block_height("asset_hub").await {
@@ -833,10 +990,19 @@ impl BlockProcessor {
                                 processed_block_height,
                                 CONFIG.block_processor.asset_hub_start_block_number
                             );
-                            while block_number <= finalized_block_number {
+                            while block_number <= finalized_block_number && !shutdown::is_requested() {
                                 log::info!(
                                     "ASSET_HUB Process block #{block_number}. Target #{finalized_block_number}.",
                                 );
+                                let context = if block_number == finalized_block_number {
+                                    finalized_block_context.clone()
+                                } else {
+                                    trace::start_span(
+                                        "process_block",
+                                        None,
+                                        vec![KeyValue::new("block_number", block_number as i64)],
+                                    )
+                                };
                                 let start = std::time::Instant::now();
                                 let process_result = self.process_asset_hub_block(
                                     &mut block_processor_substrate_client,
@@ -845,7 +1011,7 @@ impl BlockProcessor {
                                     &postgres,
                                     block_number,
                                     false,
-                                ).await;
+                                ).with_context(context).await;
                                 metrics::asset_hub_block_processing_time_ms().observe(start.elapsed().as_millis() as f64);
                                 match process_result {
                                     Ok(_) => {
@@ -875,7 +1041,7 @@ impl BlockProcessor {
                                 &postgres,
                                 finalized_block_number,
                                 finalized_block_number % blocks_per_3_minutes == 0,
-                            ).await;
+                            ).with_context(finalized_block_context).await;
                             metrics::block_processing_time_ms().observe(start.elapsed().as_millis() as f64);
                             match update_result {
                                 Ok(_) => {
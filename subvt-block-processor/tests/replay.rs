@@ -0,0 +1,106 @@
+//! Processes recorded real Kusama blocks end to end, against the RPC replay server.
+use subvt_block_processor::BlockProcessor;
+use subvt_config::Config;
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_substrate_client::fixture::{
+    get_recorded_fixture_path, load_recorded_fixture, RpcRecorder, RpcReplayServer,
+};
+use subvt_substrate_client::SubstrateClient;
+
+static BLOCK_PROCESSOR: BlockProcessor = BlockProcessor;
+const RELAY_FIXTURE_NAME: &str = "kusama_block_processor_relay";
+const ASSET_HUB_FIXTURE_NAME: &str = "kusama_block_processor_asset_hub";
+
+/// Records the fixtures of the current finalized blocks from the network nodes. Run with
+/// `cargo test -p subvt-block-processor --test replay -- --ignored` to (re-)record them.
+#[tokio::test]
+#[ignore]
+async fn record_block_processor_fixtures() {
+    let config = Config::test().expect("Cannot get test config.");
+    let relay_recorder = RpcRecorder::default();
+    let asset_hub_recorder = RpcRecorder::default();
+    let mut relay_substrate_client = SubstrateClient::new_recording(
+        config.substrate.rpc_url.as_str(),
+        config.substrate.network_id,
+        config.substrate.connection_timeout_seconds,
+        config.substrate.request_timeout_seconds,
+        &relay_recorder,
+    )
+    .await
+    .expect("Cannot initialize recording relay client.");
+    let mut asset_hub_substrate_client = SubstrateClient::new_recording(
+        config.substrate.asset_hub_rpc_url.as_str(),
+        config.substrate.network_id,
+        config.substrate.connection_timeout_seconds,
+        config.substrate.request_timeout_seconds,
+        &asset_hub_recorder,
+    )
+    .await
+    .expect("Cannot initialize recording Asset Hub client.");
+    let postgres = PostgreSQLNetworkStorage::new(&config, config.get_network_postgres_url())
+        .await
+        .expect("Cannot connect to the network database.");
+    BLOCK_PROCESSOR
+        .process_finalized_blocks(
+            &mut relay_substrate_client,
+            &mut asset_hub_substrate_client,
+            &postgres,
+        )
+        .await
+        .expect("Cannot process blocks.");
+    relay_recorder
+        .save(get_recorded_fixture_path(RELAY_FIXTURE_NAME))
+        .unwrap();
+    asset_hub_recorder
+        .save(get_recorded_fixture_path(ASSET_HUB_FIXTURE_NAME))
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_process_recorded_blocks() {
+    let config = Config::test().expect("Cannot get test config.");
+    let mut clients = Vec::new();
+    let mut server_handles = Vec::new();
+    for fixture_name in [RELAY_FIXTURE_NAME, ASSET_HUB_FIXTURE_NAME] {
+        let fixture = load_recorded_fixture(fixture_name).expect("Cannot load fixture.");
+        let (address, server_handle) = RpcReplayServer::start(fixture, "127.0.0.1", 0)
+            .await
+            .expect("Cannot start replay server.");
+        let client = SubstrateClient::new(
+            &format!("ws://{address}"),
+            config.substrate.network_id,
+            config.substrate.connection_timeout_seconds,
+            config.substrate.request_timeout_seconds,
+        )
+        .await
+        .expect("Cannot connect to replay server.");
+        clients.push(client);
+        server_handles.push(server_handle);
+    }
+    let mut asset_hub_substrate_client = clients.pop().unwrap();
+    let mut relay_substrate_client = clients.pop().unwrap();
+    let postgres = PostgreSQLNetworkStorage::new(&config, config.get_network_postgres_url())
+        .await
+        .expect("Cannot connect to the network database.");
+    let (relay_block_number, asset_hub_block_number) = BLOCK_PROCESSOR
+        .process_finalized_blocks(
+            &mut relay_substrate_client,
+            &mut asset_hub_substrate_client,
+            &postgres,
+        )
+        .await
+        .expect("Cannot process recorded blocks.");
+    for (chain, block_number) in [
+        ("relay", relay_block_number),
+        ("asset_hub", asset_hub_block_number),
+    ] {
+        let block = postgres
+            .get_block_by_number(chain, block_number)
+            .await
+            .expect("Cannot get block.");
+        assert!(block.is_some(), "{chain} block #{block_number} not saved.");
+    }
+    for server_handle in server_handles {
+        server_handle.stop().unwrap();
+    }
+}
@@ -41,6 +41,17 @@ impl From<&str> for Environment {
 pub struct CommonConfig {
     /// Wait this many seconds before retrying to recover from a fatal error condition.
     pub recovery_retry_seconds: u64,
+    /// The retry wait is doubled after each consecutive failure up to this many seconds.
+    pub recovery_max_retry_seconds: u64,
+    /// The service is reported unhealthy after this many consecutive failures.
+    pub unhealthy_failure_count: u32,
+    /// Wait at most this many seconds for the in-flight work to finish on shutdown.
+    pub shutdown_timeout_seconds: u64,
+    /// Comma-separated chains of the networks served by the multi-network services, e.g.
+    /// `kusama,polkadot`. Each network is configured by its `network/{chain}` file and the
+    /// `SUBVT_{CHAIN}__` environment variables. Only the `SUBVT_NETWORK` network is served when
+    /// empty.
+    pub networks: String,
 }
 
 /// Substrate configuration.
@@ -96,6 +107,24 @@ pub struct RPCConfig {
     pub inactive_validator_list_port: u16,
     /// Validator details WS RPC server TCP port.
     pub validator_details_port: u16,
+    /// Number of past best block network statuses kept in memory to resume subscriptions.
+    pub network_status_history_depth: usize,
+    /// Maximum number of WS connections per server.
+    pub max_connections: u32,
+    /// Number of messages buffered per connection before a send has to wait for the client.
+    pub subscriber_queue_size: u32,
+    /// Number of updates a subscriber can fall behind before it's disconnected.
+    pub bus_capacity: usize,
+    /// A subscriber that cannot receive a message within this time is disconnected.
+    pub subscriber_send_timeout_millis: u64,
+    /// Period of the keep-alive comments on the idle Server-Sent Events streams.
+    pub sse_keep_alive_seconds: u64,
+    /// WS gateway TCP port. The gateway serves the feeds of all the networks over one connection.
+    pub gateway_port: u16,
+    /// Maximum number of subscriptions on a single gateway connection.
+    pub gateway_max_subscriptions_per_connection: u32,
+    /// Number of connections from the gateway to each upstream WS server.
+    pub gateway_upstream_connection_count: usize,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -144,6 +173,29 @@ pub struct ValidatorListUpdaterConfig {
     /// Keep this many records in Redis for the past validator list updates.
     pub history_record_depth: u64,
     pub db_fetch_batch_size: usize,
+    /// Cache storage values by block hash in the Substrate clients during an update. Disabled
+    /// when not configured.
+    #[serde(default)]
+    pub storage_cache_enabled: bool,
+    /// The per-era validator details snapshots are saved in full once in this many eras, and as
+    /// diffs from the previous era's snapshot in between.
+    pub details_snapshot_full_interval_eras: u32,
+}
+
+/// Next election prediction configuration, used by the validator list updater.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ElectionConfig {
+    /// Either `phragmen` (sequential Phragmén) or `phragmms`.
+    pub algorithm: String,
+    /// Used when the runtime doesn't expose the balancing iterations of its election miner. Zero
+    /// iterations disables balancing.
+    pub balancing_iterations: usize,
+    pub balancing_tolerance: u64,
+    /// Run the prediction at most once in this many relay chain blocks.
+    pub prediction_block_interval: u64,
+    /// A change in the predicted election status of a validator gets notified only after it
+    /// persists in this many consecutive predictions.
+    pub prediction_change_confirmation_count: u32,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -158,6 +210,14 @@ pub struct DNConfig {
 pub struct ReportConfig {
     pub max_era_index_range: u32,
     pub max_session_index_range: u32,
+    pub max_comparison_validator_count: usize,
+    /// Default weights of the validator comparison composite score, as comma-separated
+    /// `metric:weight` pairs.
+    pub validator_score_weights: String,
+    /// Comma-separated addresses of the validators exported by `/validator/metrics` when the
+    /// request doesn't list any.
+    pub exporter_validators: String,
+    pub max_exporter_validator_count: usize,
 }
 
 /// Telemetry processor configuration.
@@ -170,6 +230,9 @@ pub struct TelemetryConfig {
 #[derive(Clone, Debug, Deserialize)]
 pub struct NotificationGeneratorConfig {
     pub unclaimed_payout_check_delay_hours: u32,
+    /// Missed blocks are notified only if the probability of authoring that few blocks by
+    /// chance is at most this value.
+    pub missed_blocks_max_probability: f64,
 }
 
 /// Notification sender configuration.
@@ -239,6 +302,18 @@ pub struct MetricsConfig {
     pub referendum_updater_port: u16,
     pub kline_updater_port: u16,
     pub session_validator_performance_updater_port: u16,
+    pub ws_gateway_port: u16,
+}
+
+/// OpenTelemetry tracing config.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TracingConfig {
+    /// Export the spans to the OTLP collector if `true`.
+    pub enabled: bool,
+    /// OTLP/HTTP traces endpoint of the collector.
+    pub otlp_endpoint: String,
+    /// Ratio of the traces that are sampled at the start of the pipeline, between 0 and 1.
+    pub sample_ratio: f64,
 }
 
 /// Plotter config.
@@ -275,12 +350,51 @@ pub struct KLineUpdaterConfig {
     pub begin_year: i32,
     pub begin_month: u32,
     pub begin_day: u32,
+    /// Comma-separated tickers of the currencies the token price is quoted in.
+    quote_currencies: String,
+    /// Comma-separated price sources in the order of preference. Supported sources are
+    /// `binance`, `kraken` and `coingecko`.
+    sources: String,
+    /// Hourly k-lines are fetched for this many days before now.
+    pub hourly_history_days: u64,
+    /// A source's close price is an outlier if it differs from the median close of all the
+    /// sources by more than this percentage.
+    pub outlier_threshold_percent: f64,
+    pub binance_base_url: String,
+    pub kraken_base_url: String,
+    pub coingecko_base_url: String,
+    /// CoinGecko id of the network token, empty if it's not listed.
+    pub coingecko_coin_id: String,
+}
+
+impl KLineUpdaterConfig {
+    fn split_list(list: &str) -> Vec<String> {
+        list.split(',')
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect()
+    }
+
+    pub fn get_quote_currencies(&self) -> Vec<String> {
+        Self::split_list(&self.quote_currencies)
+            .into_iter()
+            .map(|quote_currency| quote_currency.to_uppercase())
+            .collect()
+    }
+
+    pub fn get_sources(&self) -> Vec<String> {
+        Self::split_list(&self.sources)
+    }
 }
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct SessionValidatorPerformanceUpdaterConfig {
     pub start_session_index: u64,
     pub sleep_seconds: u64,
+    /// BABE slot duration.
+    pub slot_duration_millis: u64,
+    /// BABE `c` constant, the probability of a slot having at least one primary slot claim.
+    pub babe_primary_probability: f64,
 }
 
 /// Whole configuration.
@@ -293,6 +407,7 @@ pub struct Config {
     pub http: HTTPConfig,
     pub log: LogConfig,
     pub dn: DNConfig,
+    pub election: ElectionConfig,
     pub app_postgres: PostgreSQLConfig,
     pub network_postgres: PostgreSQLConfig,
     pub redis: RedisConfig,
@@ -304,6 +419,7 @@ pub struct Config {
     pub notification_processor: NotificationProcessorConfig,
     pub telegram_bot: TelegramBotConfig,
     pub metrics: MetricsConfig,
+    pub tracing: TracingConfig,
     pub plotter: PlotterConfig,
     pub sub_id: SubIDConfig,
     pub app_service: AppServiceConfig,
@@ -330,50 +446,77 @@ impl Config {
         config.try_deserialize()
     }
 
-    fn new() -> Result<Self, config::ConfigError> {
+    /// Loads the configuration of the given network. The `SUBVT_{NETWORK}__` environment
+    /// variables override the common `SUBVT__` ones, so that each network of a multi-network
+    /// deployment can have its own e.g. RPC URL and database.
+    fn load(network: &str) -> Result<Self, config::ConfigError> {
         let env = Environment::from(
             std::env::var("SUBVT_ENV")
                 .unwrap_or_else(|_| "Production".into())
                 .as_str(),
         );
+        let default_config_dir = if cfg!(debug_assertions) {
+            DEV_CONFIG_DIR
+        } else {
+            DEFAULT_CONFIG_DIR
+        };
+        let config_dir =
+            std::env::var("SUBVT_CONFIG_DIR").unwrap_or_else(|_| default_config_dir.into());
+        let config = config::Config::builder()
+            .set_default("env", env.to_string())?
+            .add_source(config::File::with_name(&format!("{config_dir}/base")))
+            .add_source(config::File::with_name(&format!(
+                "{config_dir}/network/{network}",
+            )))
+            .add_source(config::File::with_name(&format!(
+                "{}/env/{}",
+                config_dir,
+                env.to_string().to_lowercase()
+            )))
+            .add_source(config::Environment::with_prefix("subvt").separator("__"))
+            .add_source(
+                config::Environment::with_prefix(&format!("subvt_{network}")).separator("__"),
+            )
+            .build()?;
+        config.try_deserialize()
+    }
+
+    fn new() -> Result<Self, config::ConfigError> {
         let network = std::env::var("SUBVT_NETWORK").unwrap_or_else(|_| DEFAULT_NETWORK.into());
-        if cfg!(debug_assertions) {
-            let config_dir =
-                std::env::var("SUBVT_CONFIG_DIR").unwrap_or_else(|_| DEV_CONFIG_DIR.into());
-            let config = config::Config::builder()
-                .set_default("env", env.to_string())?
-                .add_source(config::File::with_name(&format!("{config_dir}/base")))
-                .add_source(config::File::with_name(&format!(
-                    "{config_dir}/network/{network}",
-                )))
-                .add_source(config::File::with_name(&format!(
-                    "{}/env/{}",
-                    config_dir,
-                    env.to_string().to_lowercase()
-                )))
-                .add_source(config::Environment::with_prefix("subvt").separator("__"))
-                .build()?;
-            config.try_deserialize()
+        Self::load(&network)
+    }
+
+    /// Chains of the networks in `common.networks`, or this configuration's chain if the list
+    /// is empty. The first one is the default network of the multi-network services.
+    pub fn get_network_chains(&self) -> Vec<String> {
+        let chains: Vec<String> = self
+            .common
+            .networks
+            .split(',')
+            .map(|chain| chain.trim().to_lowercase())
+            .filter(|chain| !chain.is_empty())
+            .collect();
+        if chains.is_empty() {
+            vec![self.substrate.chain.clone()]
         } else {
-            let config_dir =
-                std::env::var("SUBVT_CONFIG_DIR").unwrap_or_else(|_| DEFAULT_CONFIG_DIR.into());
-            let config = config::Config::builder()
-                .set_default("env", env.to_string())?
-                .add_source(config::File::with_name(&format!("{config_dir}/base")))
-                .add_source(config::File::with_name(&format!(
-                    "{config_dir}/network/{network}",
-                )))
-                .add_source(config::File::with_name(&format!(
-                    "{}/env/{}",
-                    config_dir,
-                    env.to_string().to_lowercase()
-                )))
-                .add_source(config::Environment::with_prefix("subvt").separator("__"))
-                .build()?;
-            config.try_deserialize()
+            chains
         }
     }
 
+    /// Configurations of the networks in `common.networks`, in the same order.
+    pub fn get_network_configs(&self) -> Result<Vec<Config>, config::ConfigError> {
+        self.get_network_chains()
+            .iter()
+            .map(|chain| {
+                if *chain == self.substrate.chain {
+                    Ok(self.clone())
+                } else {
+                    Self::load(chain)
+                }
+            })
+            .collect()
+    }
+
     pub fn get_app_postgres_url(&self) -> String {
         format!(
             "postgres://{}:{}@{}:{}/{}?sslmode=disable",
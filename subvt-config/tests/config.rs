@@ -0,0 +1,16 @@
+use subvt_config::Config;
+
+#[test]
+fn test_network_chains() {
+    let mut config = Config::default();
+    let chain = config.substrate.chain.clone();
+    config.common.networks = String::new();
+    assert_eq!(config.get_network_chains(), vec![chain.clone()]);
+    config.common.networks = " , ".to_string();
+    assert_eq!(config.get_network_chains(), vec![chain]);
+    config.common.networks = " Polkadot,kusama ,,".to_string();
+    assert_eq!(
+        config.get_network_chains(),
+        vec!["polkadot".to_string(), "kusama".to_string()]
+    );
+}
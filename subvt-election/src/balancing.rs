@@ -0,0 +1,87 @@
+//! Balancing of the stake distribution among the elected candidates, which makes the backings
+//! of the winners as even as possible (the star balancing in `sp-npos-elections`).
+use crate::{BalancingConfig, Candidate, Voter};
+
+pub(crate) fn balance(
+    candidates: &mut [Candidate],
+    voters: &mut [Voter],
+    config: &BalancingConfig,
+) {
+    let tolerance = config.tolerance as f64;
+    for _ in 0..config.iterations {
+        let mut max_difference = 0.0f64;
+        for voter in voters.iter_mut() {
+            let difference = balance_voter(candidates, voter, tolerance);
+            max_difference = max_difference.max(difference);
+        }
+        if max_difference <= tolerance {
+            break;
+        }
+    }
+}
+
+/// Redistributes the stake of a single voter among its elected edges so that the lowest backed
+/// ones get filled up first. Returns the difference between the maximum and minimum backing of
+/// the candidates the voter backs, plus the unused stake of the voter.
+fn balance_voter(candidates: &mut [Candidate], voter: &mut Voter, tolerance: f64) -> f64 {
+    let mut elected_edges: Vec<_> = voter
+        .edges
+        .iter_mut()
+        .filter(|edge| candidates[edge.candidate_index].elected)
+        .collect();
+    if elected_edges.is_empty() {
+        return 0.0;
+    }
+    let used_stake: f64 = elected_edges.iter().map(|edge| edge.weight).sum();
+    let backing_backed_stakes: Vec<f64> = elected_edges
+        .iter()
+        .filter(|edge| edge.weight > 0.0)
+        .map(|edge| candidates[edge.candidate_index].backed_stake)
+        .collect();
+    let mut difference = if backing_backed_stakes.is_empty() {
+        0.0
+    } else {
+        let max = backing_backed_stakes
+            .iter()
+            .cloned()
+            .fold(f64::MIN, f64::max);
+        let min = backing_backed_stakes
+            .iter()
+            .cloned()
+            .fold(f64::MAX, f64::min);
+        max - min
+    };
+    difference += (voter.budget - used_stake).max(0.0);
+    if difference <= tolerance {
+        return difference;
+    }
+    // take the voter's stake out, then fill the lowest backed candidates up to an equal level
+    for edge in elected_edges.iter_mut() {
+        candidates[edge.candidate_index].backed_stake -= edge.weight;
+        edge.weight = 0.0;
+    }
+    elected_edges.sort_by(|a, b| {
+        candidates[a.candidate_index]
+            .backed_stake
+            .total_cmp(&candidates[b.candidate_index].backed_stake)
+    });
+    let mut cumulative_backed_stake = 0.0;
+    let mut last_index = elected_edges.len() - 1;
+    for (index, edge) in elected_edges.iter().enumerate() {
+        let backed_stake = candidates[edge.candidate_index].backed_stake;
+        if backed_stake * index as f64 - cumulative_backed_stake > voter.budget {
+            last_index = index - 1;
+            break;
+        }
+        cumulative_backed_stake += backed_stake;
+    }
+    let last_stake = candidates[elected_edges[last_index].candidate_index].backed_stake;
+    let split_count = (last_index + 1) as f64;
+    let excess = voter.budget + cumulative_backed_stake - last_stake * split_count;
+    for edge in elected_edges.iter_mut().take(last_index + 1) {
+        let candidate = &mut candidates[edge.candidate_index];
+        edge.weight = excess / split_count + last_stake - candidate.backed_stake;
+        candidate.backed_stake += edge.weight;
+    }
+    difference
+}
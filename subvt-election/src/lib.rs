@@ -0,0 +1,222 @@
+//! Predicts the result of the next validator election by running sequential Phragmén or
+//! PhragMMS on an election snapshot read from the chain, followed by the balancing of the
+//! stake distribution. Follows the implementations in `sp-npos-elections`, using floating point
+//! arithmetic in place of rationals, which is accurate enough for a prediction.
+#![warn(clippy::disallowed_types)]
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use subvt_types::crypto::AccountId;
+use subvt_types::election::{ElectionPrediction, ElectionSnapshot, ElectionWinner};
+use subvt_types::substrate::Balance;
+
+mod balancing;
+mod phragmen;
+mod phragmms;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ElectionAlgorithm {
+    SequentialPhragmen,
+    PhragMMS,
+}
+
+impl Display for ElectionAlgorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let display = match self {
+            Self::SequentialPhragmen => "phragmen",
+            Self::PhragMMS => "phragmms",
+        };
+        write!(f, "{display}")
+    }
+}
+
+impl FromStr for ElectionAlgorithm {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "phragmen" => Ok(Self::SequentialPhragmen),
+            "phragmms" => Ok(Self::PhragMMS),
+            _ => Err(anyhow::anyhow!("Unknown election algorithm: {s}")),
+        }
+    }
+}
+
+/// Balancing configuration, as in the election provider's solution miner.
+#[derive(Clone, Copy, Debug)]
+pub struct BalancingConfig {
+    pub iterations: usize,
+    pub tolerance: Balance,
+}
+
+pub(crate) struct Candidate {
+    pub account_id: AccountId,
+    pub approval_stake: f64,
+    pub backed_stake: f64,
+    pub score: f64,
+    /// Used only by PhragMMS.
+    pub score_denominator: f64,
+    pub elected: bool,
+}
+
+pub(crate) struct Edge {
+    pub candidate_index: usize,
+    /// Used only by sequential Phragmén.
+    pub load: f64,
+    pub weight: f64,
+}
+
+pub(crate) struct Voter {
+    pub account_id: AccountId,
+    pub budget: f64,
+    /// Used only by sequential Phragmén.
+    pub load: f64,
+    pub edges: Vec<Edge>,
+}
+
+fn setup_inputs(snapshot: &ElectionSnapshot) -> (Vec<Candidate>, Vec<Voter>) {
+    let mut candidate_index_map: HashMap<AccountId, usize> = HashMap::default();
+    let mut candidates = Vec::with_capacity(snapshot.target_account_ids.len());
+    for account_id in &snapshot.target_account_ids {
+        if candidate_index_map.contains_key(account_id) {
+            continue;
+        }
+        candidate_index_map.insert(*account_id, candidates.len());
+        candidates.push(Candidate {
+            account_id: *account_id,
+            approval_stake: 0.0,
+            backed_stake: 0.0,
+            score: 0.0,
+            score_denominator: 0.0,
+            elected: false,
+        });
+    }
+    let mut voters = Vec::with_capacity(snapshot.voters.len());
+    for snapshot_voter in &snapshot.voters {
+        if snapshot_voter.stake == 0 {
+            continue;
+        }
+        let budget = snapshot_voter.stake as f64;
+        let mut edges = Vec::with_capacity(snapshot_voter.target_account_ids.len());
+        let mut seen_candidate_indices: HashSet<usize> = HashSet::default();
+        for target_account_id in &snapshot_voter.target_account_ids {
+            // votes for accounts that are not validators (anymore) are ignored
+            if let Some(candidate_index) = candidate_index_map.get(target_account_id) {
+                if seen_candidate_indices.insert(*candidate_index) {
+                    candidates[*candidate_index].approval_stake += budget;
+                    edges.push(Edge {
+                        candidate_index: *candidate_index,
+                        load: 0.0,
+                        weight: 0.0,
+                    });
+                }
+            }
+        }
+        if edges.is_empty() {
+            continue;
+        }
+        voters.push(Voter {
+            account_id: snapshot_voter.account_id,
+            budget,
+            load: 0.0,
+            edges,
+        });
+    }
+    (candidates, voters)
+}
+
+/// Runs the election on the snapshot and returns the winners in descending order of backing.
+pub fn predict_winners(
+    snapshot: &ElectionSnapshot,
+    algorithm: ElectionAlgorithm,
+    balancing: Option<BalancingConfig>,
+) -> Vec<ElectionWinner> {
+    let (mut candidates, mut voters) = setup_inputs(snapshot);
+    let to_elect = snapshot.desired_target_count as usize;
+    let winner_indices = match algorithm {
+        ElectionAlgorithm::SequentialPhragmen => {
+            let winner_indices = phragmen::seq_phragmen(&mut candidates, &mut voters, to_elect);
+            if let Some(config) = balancing {
+                balancing::balance(&mut candidates, &mut voters, &config);
+            }
+            winner_indices
+        }
+        ElectionAlgorithm::PhragMMS => {
+            phragmms::phragmms(&mut candidates, &mut voters, to_elect, balancing.as_ref())
+        }
+    };
+    let mut winner_map: HashMap<usize, ElectionWinner> = winner_indices
+        .iter()
+        .map(|index| {
+            (
+                *index,
+                ElectionWinner {
+                    account_id: candidates[*index].account_id,
+                    ..Default::default()
+                },
+            )
+        })
+        .collect();
+    for voter in &voters {
+        for edge in &voter.edges {
+            if edge.weight <= 0.0 {
+                continue;
+            }
+            if let Some(winner) = winner_map.get_mut(&edge.candidate_index) {
+                let backing = edge.weight as Balance;
+                winner.total_backing += backing;
+                if voter.account_id == winner.account_id {
+                    winner.self_backing += backing;
+                } else {
+                    winner.backer_count += 1;
+                }
+            }
+        }
+    }
+    let mut winners: Vec<(usize, ElectionWinner)> = winner_map.into_iter().collect();
+    winners.sort_by(|a, b| {
+        b.1.total_backing
+            .cmp(&a.1.total_backing)
+            .then_with(|| a.0.cmp(&b.0))
+    });
+    winners
+        .into_iter()
+        .enumerate()
+        .map(|(index, (_, winner))| ElectionWinner {
+            rank: index as u32 + 1,
+            ..winner
+        })
+        .collect()
+}
+
+/// Runs the election on the snapshot and builds the prediction. Block number, era index and
+/// timestamp are left to the caller.
+pub fn predict(
+    snapshot: &ElectionSnapshot,
+    algorithm: ElectionAlgorithm,
+    balancing: Option<BalancingConfig>,
+) -> ElectionPrediction {
+    let start = std::time::Instant::now();
+    let winners = predict_winners(snapshot, algorithm, balancing);
+    log::info!(
+        "Predicted {} winners out of {} candidates with {algorithm} in {} ms.",
+        winners.len(),
+        snapshot.target_account_ids.len(),
+        start.elapsed().as_millis(),
+    );
+    ElectionPrediction {
+        algorithm: algorithm.to_string(),
+        block_hash: snapshot.block_hash.clone(),
+        desired_target_count: snapshot.desired_target_count,
+        candidate_count: snapshot.target_account_ids.len() as u32,
+        voter_count: snapshot.voters.len() as u32,
+        total_backing: winners.iter().map(|winner| winner.total_backing).sum(),
+        minimum_backing: winners
+            .iter()
+            .map(|winner| winner.total_backing)
+            .min()
+            .unwrap_or(0),
+        winners,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,72 @@
+//! Sequential Phragmén.
+use crate::{Candidate, Voter};
+
+/// Elects `to_elect` candidates in rounds, each round electing the candidate that would end up
+/// with the minimum maximum voter load. Sets the edge weights of the voters at the end, and
+/// returns the indices of the winners in the order of election.
+pub(crate) fn seq_phragmen(
+    candidates: &mut [Candidate],
+    voters: &mut [Voter],
+    to_elect: usize,
+) -> Vec<usize> {
+    let mut winner_indices = Vec::with_capacity(to_elect);
+    for _ in 0..to_elect {
+        for candidate in candidates.iter_mut().filter(|c| !c.elected) {
+            candidate.score = if candidate.approval_stake > 0.0 {
+                1.0 / candidate.approval_stake
+            } else {
+                f64::INFINITY
+            };
+        }
+        for voter in voters.iter() {
+            for edge in &voter.edges {
+                let candidate = &mut candidates[edge.candidate_index];
+                if !candidate.elected && candidate.approval_stake > 0.0 {
+                    candidate.score += voter.budget * voter.load / candidate.approval_stake;
+                }
+            }
+        }
+        let maybe_winner_index = candidates
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.elected && c.score.is_finite())
+            .min_by(|(_, a), (_, b)| a.score.total_cmp(&b.score))
+            .map(|(index, _)| index);
+        let winner_index = match maybe_winner_index {
+            Some(winner_index) => winner_index,
+            None => break,
+        };
+        let winner_score = candidates[winner_index].score;
+        candidates[winner_index].elected = true;
+        for voter in voters.iter_mut() {
+            if let Some(edge) = voter
+                .edges
+                .iter_mut()
+                .find(|edge| edge.candidate_index == winner_index)
+            {
+                edge.load = winner_score - voter.load;
+                voter.load = winner_score;
+            }
+        }
+        winner_indices.push(winner_index);
+    }
+    // convert the loads into stake distribution
+    for voter in voters.iter_mut() {
+        for edge in voter.edges.iter_mut() {
+            edge.weight = if candidates[edge.candidate_index].elected && voter.load > 0.0 {
+                voter.budget * edge.load / voter.load
+            } else {
+                0.0
+            };
+        }
+    }
+    for candidate in candidates.iter_mut() {
+        candidate.backed_stake = 0.0;
+    }
+    for voter in voters.iter() {
+        for edge in &voter.edges {
+            candidates[edge.candidate_index].backed_stake += edge.weight;
+        }
+    }
+    winner_indices
+}
@@ -0,0 +1,98 @@
+//! PhragMMS, with the approximate score calculation used in `sp-npos-elections`.
+use crate::balancing::balance;
+use crate::{BalancingConfig, Candidate, Voter};
+
+/// Elects `to_elect` candidates in rounds, each round electing the candidate with the maximum
+/// score and redistributing the stake of its voters towards it. Balances the stake distribution
+/// after each round if a balancing configuration is given. Returns the indices of the winners
+/// in the order of election.
+pub(crate) fn phragmms(
+    candidates: &mut [Candidate],
+    voters: &mut [Voter],
+    to_elect: usize,
+    balancing: Option<&BalancingConfig>,
+) -> Vec<usize> {
+    let mut winner_indices = Vec::with_capacity(to_elect);
+    for _ in 0..to_elect {
+        let winner_index = match calculate_max_score(candidates, voters) {
+            Some(winner_index) => winner_index,
+            None => break,
+        };
+        apply_elected(candidates, voters, winner_index);
+        winner_indices.push(winner_index);
+        if let Some(config) = balancing {
+            balance(candidates, voters, config);
+        }
+    }
+    winner_indices
+}
+
+/// Score of a candidate is `approval_stake / (1 + Σ weight / backed_stake)`, where the sum is
+/// over the elected edges of all its voters. Returns the unelected candidate with the maximum
+/// score.
+fn calculate_max_score(candidates: &mut [Candidate], voters: &[Voter]) -> Option<usize> {
+    for candidate in candidates.iter_mut() {
+        candidate.score_denominator = 1.0;
+    }
+    for voter in voters {
+        let denominator_contribution: f64 = voter
+            .edges
+            .iter()
+            .filter(|edge| {
+                let candidate = &candidates[edge.candidate_index];
+                candidate.elected && candidate.backed_stake > 0.0
+            })
+            .map(|edge| edge.weight / candidates[edge.candidate_index].backed_stake)
+            .sum();
+        for edge in &voter.edges {
+            let candidate = &mut candidates[edge.candidate_index];
+            if !candidate.elected {
+                candidate.score_denominator += denominator_contribution;
+            }
+        }
+    }
+    for candidate in candidates.iter_mut().filter(|c| !c.elected) {
+        candidate.score = candidate.approval_stake / candidate.score_denominator;
+    }
+    candidates
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| !c.elected && c.approval_stake > 0.0)
+        .max_by(|(_, a), (_, b)| a.score.total_cmp(&b.score))
+        .map(|(index, _)| index)
+}
+
+/// Moves the unused stake of the winner's voters, and the stake above the winner's score from
+/// their other elected edges, to the winner.
+fn apply_elected(candidates: &mut [Candidate], voters: &mut [Voter], winner_index: usize) {
+    let cutoff = candidates[winner_index].score;
+    candidates[winner_index].elected = true;
+    // stakes to take from are calculated relative to the backings before the insertion
+    let backed_stakes: Vec<f64> = candidates.iter().map(|c| c.backed_stake).collect();
+    for voter in voters.iter_mut() {
+        let winner_edge_index = match voter
+            .edges
+            .iter()
+            .position(|edge| edge.candidate_index == winner_index)
+        {
+            Some(winner_edge_index) => winner_edge_index,
+            None => continue,
+        };
+        let used_stake: f64 = voter.edges.iter().map(|edge| edge.weight).sum();
+        let mut new_stake = (voter.budget - used_stake).max(0.0);
+        for edge in voter.edges.iter_mut() {
+            let backed_stake = backed_stakes[edge.candidate_index];
+            let candidate = &mut candidates[edge.candidate_index];
+            if edge.candidate_index == winner_index || !candidate.elected || backed_stake <= cutoff
+            {
+                continue;
+            }
+            let stake_to_take = edge.weight * (backed_stake - cutoff) / backed_stake;
+            edge.weight -= stake_to_take;
+            candidate.backed_stake -= stake_to_take;
+            new_stake += stake_to_take;
+        }
+        voter.edges[winner_edge_index].weight += new_stake;
+        candidates[winner_index].backed_stake += new_stake;
+    }
+}
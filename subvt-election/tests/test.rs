@@ -0,0 +1,76 @@
+use subvt_election::{predict_winners, BalancingConfig, ElectionAlgorithm};
+use subvt_types::crypto::AccountId;
+use subvt_types::election::{ElectionSnapshot, ElectionVoter};
+
+fn account_id(id: u8) -> AccountId {
+    AccountId::from([id; 32])
+}
+
+fn voter(id: u8, stake: u128, target_ids: &[u8]) -> ElectionVoter {
+    ElectionVoter {
+        account_id: account_id(id),
+        stake,
+        target_account_ids: target_ids.iter().map(|id| account_id(*id)).collect(),
+    }
+}
+
+/// The proof of concept example from `sp-npos-elections`.
+fn get_snapshot() -> ElectionSnapshot {
+    ElectionSnapshot {
+        block_hash: "0x00".to_string(),
+        desired_target_count: 2,
+        target_account_ids: vec![account_id(1), account_id(2), account_id(3)],
+        voters: vec![
+            voter(10, 10, &[1, 2]),
+            voter(20, 20, &[1, 3]),
+            voter(30, 30, &[2, 3]),
+        ],
+        balancing_iterations: None,
+    }
+}
+
+#[test]
+fn test_seq_phragmen() {
+    let winners = predict_winners(&get_snapshot(), ElectionAlgorithm::SequentialPhragmen, None);
+    assert_eq!(winners.len(), 2);
+    assert_eq!(winners[0].account_id, account_id(3));
+    assert_eq!(winners[0].total_backing, 35);
+    assert_eq!(winners[0].backer_count, 2);
+    assert_eq!(winners[1].account_id, account_id(2));
+    assert_eq!(winners[1].total_backing, 25);
+}
+
+#[test]
+fn test_balancing() {
+    let balancing = BalancingConfig {
+        iterations: 10,
+        tolerance: 0,
+    };
+    for algorithm in [
+        ElectionAlgorithm::SequentialPhragmen,
+        ElectionAlgorithm::PhragMMS,
+    ] {
+        let winners = predict_winners(&get_snapshot(), algorithm, Some(balancing));
+        assert_eq!(winners.len(), 2);
+        assert!(winners.iter().all(|winner| winner.total_backing == 30));
+        assert!(winners
+            .iter()
+            .all(|winner| winner.account_id != account_id(1)));
+    }
+}
+
+#[test]
+fn test_self_vote() {
+    let snapshot = ElectionSnapshot {
+        block_hash: "0x00".to_string(),
+        desired_target_count: 1,
+        target_account_ids: vec![account_id(1), account_id(2)],
+        voters: vec![voter(1, 50, &[1]), voter(2, 20, &[2]), voter(10, 20, &[2])],
+        balancing_iterations: None,
+    };
+    let winners = predict_winners(&snapshot, ElectionAlgorithm::PhragMMS, None);
+    assert_eq!(winners.len(), 1);
+    assert_eq!(winners[0].account_id, account_id(1));
+    assert_eq!(winners[0].self_backing, 50);
+    assert_eq!(winners[0].backer_count, 0);
+}
@@ -1,33 +1,29 @@
 #![warn(clippy::disallowed_types)]
+use crate::source::PriceSource;
 use async_trait::async_trait;
-use chrono::{DateTime, Datelike, Days, NaiveDate, NaiveDateTime, Utc};
+use chrono::{NaiveDate, Utc};
 use lazy_static::lazy_static;
-use std::str::FromStr;
+use rustc_hash::FxHashMap as HashMap;
 use subvt_config::Config;
+use subvt_metrics::health;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
 use subvt_service_common::Service;
-use subvt_types::kline::{BigDecimal, KLine};
+use subvt_types::kline::{KLine, KLineInterval};
 
 mod metrics;
+pub mod reconcile;
+pub mod source;
 
 lazy_static! {
     static ref CONFIG: Config = Config::default();
 }
 
-type KLineRecord = (
-    u64,
-    String,
-    String,
-    String,
-    String,
-    String,
-    u64,
-    String,
-    u32,
-    String,
-    String,
-    String,
-);
+/// Missing candles are fetched in ranges of at most this many candles, the maximum that the
+/// Kraken OHLC endpoint returns in a single response.
+const MAX_RANGE_CANDLE_COUNT: u64 = 720;
+/// A candle that no source has is recorded as unavailable only after this long past its close
+/// time, since the sources may publish the recent candles with a delay.
+const UNAVAILABLE_GRACE_PERIOD_MILLIS: u64 = 7 * 24 * 60 * 60 * 1000;
 
 pub struct KLineUpdater {
     http_client: reqwest::Client,
@@ -47,6 +43,197 @@ impl Default for KLineUpdater {
     }
 }
 
+/// K-lines of a range of candles, fetched from all the sources.
+pub struct FetchedRange {
+    /// One k-line per candle that at least one source has, in the order of open time.
+    pub klines: Vec<KLine>,
+    /// Open times of the candles that no source has, in ascending order.
+    pub missing_open_times: Vec<u64>,
+    /// Whether all the sources responded. A missing candle may still be available from a source
+    /// that failed.
+    pub is_complete: bool,
+}
+
+/// Fetches the candles in the closed range of open times from all the sources, given in the
+/// order of preference, and selects one k-line per candle.
+pub async fn fetch_range(
+    sources: &[Box<dyn PriceSource>],
+    source_ticker: &str,
+    quote_currency: &str,
+    interval: KLineInterval,
+    range: (u64, u64),
+    outlier_threshold_percent: f64,
+) -> FetchedRange {
+    // open time -> k-lines in the order of source preference
+    let mut candidates: HashMap<u64, Vec<KLine>> = HashMap::default();
+    let mut is_complete = true;
+    for source in sources {
+        match source
+            .fetch_klines(source_ticker, quote_currency, interval, range.0, range.1)
+            .await
+        {
+            Ok(klines) => {
+                for kline in klines {
+                    if kline.open_time >= range.0 && kline.open_time <= range.1 {
+                        candidates.entry(kline.open_time).or_default().push(kline);
+                    }
+                }
+            }
+            Err(error) => {
+                log::error!(
+                    "Error while fetching {source_ticker}-{quote_currency} {interval} k-lines from {}: {:?}",
+                    source.name(),
+                    error,
+                );
+                metrics::source_error_counter(source.name()).inc();
+                is_complete = false;
+            }
+        }
+    }
+    let mut klines = Vec::with_capacity(candidates.len());
+    let mut missing_open_times = Vec::new();
+    let mut open_time = range.0;
+    while open_time <= range.1 {
+        match candidates.remove(&open_time) {
+            Some(candidate_klines) => {
+                let (kline, outlier_sources) =
+                    reconcile::select_kline(candidate_klines, outlier_threshold_percent);
+                for outlier_source in outlier_sources.iter() {
+                    log::warn!(
+                        "{source_ticker}-{quote_currency} {interval} k-line at {open_time} from {outlier_source} deviates from the other sources.",
+                    );
+                    metrics::outlier_counter(outlier_source).inc();
+                }
+                klines.push(kline);
+            }
+            None => missing_open_times.push(open_time),
+        }
+        open_time += interval.get_duration_millis();
+    }
+    FetchedRange {
+        klines,
+        missing_open_times,
+        is_complete,
+    }
+}
+
+impl KLineUpdater {
+    /// Fetches the candles in the range from all sources, then saves the selected k-lines. The
+    /// candles that no source has are recorded as unavailable if all the sources responded and
+    /// the grace period has passed. Returns the number of candles in the range that are still
+    /// missing.
+    async fn fill_range(
+        postgres: &PostgreSQLNetworkStorage,
+        sources: &[Box<dyn PriceSource>],
+        quote_currency: &str,
+        interval: KLineInterval,
+        range: (u64, u64),
+    ) -> anyhow::Result<u64> {
+        let source_ticker = &CONFIG.substrate.token_ticker;
+        let fetched_range = fetch_range(
+            sources,
+            source_ticker,
+            quote_currency,
+            interval,
+            range,
+            CONFIG.kline_updater.outlier_threshold_percent,
+        )
+        .await;
+        for kline in fetched_range.klines.iter() {
+            postgres.save_kline(kline).await?;
+        }
+        if !fetched_range.is_complete {
+            return Ok(fetched_range.missing_open_times.len() as u64);
+        }
+        let unavailable_open_times = reconcile::get_unavailable_open_times(
+            &fetched_range.missing_open_times,
+            interval.get_duration_millis(),
+            Utc::now().timestamp_millis() as u64,
+            UNAVAILABLE_GRACE_PERIOD_MILLIS,
+        );
+        if !unavailable_open_times.is_empty() {
+            log::warn!(
+                "Record {} {source_ticker}-{quote_currency} {interval} k-lines between {} and {} as unavailable.",
+                unavailable_open_times.len(),
+                range.0,
+                range.1,
+            );
+            postgres
+                .save_unavailable_kline_open_times(
+                    source_ticker,
+                    quote_currency,
+                    interval,
+                    &unavailable_open_times,
+                )
+                .await?;
+        }
+        Ok((fetched_range.missing_open_times.len() - unavailable_open_times.len()) as u64)
+    }
+
+    /// Fills the gaps of the k-lines of the interval in the quote currency between the start
+    /// open time and the last complete candle, skipping the candles recorded as unavailable.
+    /// Returns the number of candles that are still missing.
+    async fn update_klines(
+        postgres: &PostgreSQLNetworkStorage,
+        sources: &[Box<dyn PriceSource>],
+        quote_currency: &str,
+        interval: KLineInterval,
+        start_timestamp: u64,
+    ) -> anyhow::Result<u64> {
+        let source_ticker = &CONFIG.substrate.token_ticker;
+        let now = Utc::now().timestamp_millis() as u64;
+        let end_timestamp = interval.get_open_time(now) - interval.get_duration_millis();
+        if start_timestamp > end_timestamp {
+            return Ok(0);
+        }
+        let mut existing_open_times = postgres
+            .get_kline_open_times(
+                source_ticker,
+                quote_currency,
+                interval,
+                start_timestamp,
+                end_timestamp,
+            )
+            .await?;
+        let unavailable_open_times = postgres
+            .get_unavailable_kline_open_times(
+                source_ticker,
+                quote_currency,
+                interval,
+                start_timestamp,
+                end_timestamp,
+            )
+            .await?;
+        existing_open_times.extend(unavailable_open_times);
+        let missing_ranges = reconcile::get_missing_ranges(
+            &existing_open_times,
+            start_timestamp,
+            end_timestamp,
+            interval.get_duration_millis(),
+            MAX_RANGE_CANDLE_COUNT,
+        );
+        let mut missing_count = 0;
+        for range in missing_ranges {
+            log::info!(
+                "Fetch {source_ticker}-{quote_currency} {interval} k-lines between {} and {}.",
+                range.0,
+                range.1,
+            );
+            let range_missing_count =
+                Self::fill_range(postgres, sources, quote_currency, interval, range).await?;
+            if range_missing_count > 0 {
+                log::warn!(
+                    "{range_missing_count} {source_ticker}-{quote_currency} {interval} k-lines between {} and {} are not available from any source.",
+                    range.0,
+                    range.1,
+                );
+            }
+            missing_count += range_missing_count;
+        }
+        Ok(missing_count)
+    }
+}
+
 #[async_trait(?Send)]
 impl Service for KLineUpdater {
     fn get_metrics_server_addr() -> (&'static str, u16) {
@@ -57,7 +244,6 @@ impl Service for KLineUpdater {
     }
 
     async fn run(&'static self) -> anyhow::Result<()> {
-        let target_ticker = "USDT";
         let sleep_seconds = CONFIG.kline_updater.sleep_seconds;
         log::info!(
             "KLine updater has started with {} seconds sleep period.",
@@ -65,84 +251,44 @@ impl Service for KLineUpdater {
         );
         let postgres =
             PostgreSQLNetworkStorage::new(&CONFIG, CONFIG.get_network_postgres_url()).await?;
+        let sources = source::get_price_sources(&self.http_client, &CONFIG.kline_updater)?;
+        let begin_timestamp = NaiveDate::from_ymd_opt(
+            CONFIG.kline_updater.begin_year,
+            CONFIG.kline_updater.begin_month,
+            CONFIG.kline_updater.begin_day,
+        )
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc()
+        .timestamp_millis() as u64;
+        health::set_ready(true);
         loop {
-            let now: DateTime<Utc> = Utc::now();
-            let begin_date = NaiveDate::from_ymd_opt(
-                CONFIG.kline_updater.begin_year,
-                CONFIG.kline_updater.begin_month,
-                CONFIG.kline_updater.begin_day,
-            )
-            .unwrap();
-            let mut day = NaiveDate::from_ymd_opt(now.year(), now.month(), now.day()).unwrap();
-            loop {
-                day = day.checked_sub_days(Days::new(1)).unwrap();
-                let start_of_day = NaiveDateTime::from(day);
-                let year = day.year();
-                let month = day.month();
-                let month_padded = if month < 10 {
-                    format!("0{month}")
-                } else {
-                    month.to_string()
-                };
-                let day = day.day();
-                let day_padded = if day < 10 {
-                    format!("0{day}")
-                } else {
-                    day.to_string()
-                };
-                log::info!("Process {day_padded}-{month_padded}-{year}.");
-                if start_of_day.date().lt(&begin_date) {
-                    log::info!(
-                        "{day}.{month}.{year} is before the updater begin date {}.{}.{}.",
-                        CONFIG.kline_updater.begin_day,
-                        CONFIG.kline_updater.begin_month,
-                        CONFIG.kline_updater.begin_year,
-                    );
-                    break;
-                }
-                let timestamp = start_of_day.and_utc().timestamp_millis();
-                if postgres
-                    .kline_exists(timestamp, &CONFIG.substrate.token_ticker, target_ticker)
-                    .await?
-                {
-                    log::info!(
-                        "{}-{} k-line record exists for {day}.{month}.{year}.",
-                        CONFIG.substrate.token_ticker,
-                        target_ticker,
-                    );
-                    continue;
+            let now = Utc::now().timestamp_millis() as u64;
+            let hourly_start_timestamp = KLineInterval::Hour.get_open_time(now.saturating_sub(
+                CONFIG.kline_updater.hourly_history_days * KLineInterval::Day.get_duration_millis(),
+            ));
+            let mut missing_count = 0;
+            for quote_currency in CONFIG.kline_updater.get_quote_currencies() {
+                for (interval, start_timestamp) in [
+                    (KLineInterval::Day, begin_timestamp),
+                    (
+                        KLineInterval::Hour,
+                        hourly_start_timestamp.max(begin_timestamp),
+                    ),
+                ] {
+                    missing_count += Self::update_klines(
+                        &postgres,
+                        &sources,
+                        &quote_currency,
+                        interval,
+                        start_timestamp,
+                    )
+                    .await?;
                 }
-                let pair = format!("{}{}", CONFIG.substrate.token_ticker, target_ticker);
-
-                // https://api.binance.com/api/v3/klines?symbol=KSMUSDT&interval=1d&limit=1&startTime=1732838400000
-                let url = format!("https://api.binance.com/api/v3/klines?symbol={pair}&interval=1d&limit=1&startTime={timestamp}");
-                let response = self.http_client.get(&url).send().await?;
-                let records: Vec<KLineRecord> = response.json().await?;
-                let fields = records.first().unwrap();
-                // save record
-                let kline = KLine {
-                    id: 0,
-                    open_time: fields.0,
-                    source_ticker: CONFIG.substrate.token_ticker.clone(),
-                    target_ticker: target_ticker.to_string(),
-                    open: BigDecimal::from_str(&fields.1)?,
-                    high: BigDecimal::from_str(&fields.2)?,
-                    low: BigDecimal::from_str(&fields.3)?,
-                    close: BigDecimal::from_str(&fields.4)?,
-                    volume: BigDecimal::from_str(&fields.5)?,
-                    close_time: fields.6,
-                    quote_volume: BigDecimal::from_str(&fields.7)?,
-                    count: fields.8,
-                    taker_buy_volume: BigDecimal::from_str(&fields.9)?,
-                    taker_buy_quote_volume: BigDecimal::from_str(&fields.10)?,
-                };
-                postgres.save_kline(&kline).await?;
-                log::info!(
-                    "Saved {}-{target_ticker} {day_padded}-{month_padded}-{year}.",
-                    CONFIG.substrate.token_ticker,
-                );
             }
             // publish metrics
+            metrics::missing_kline_count().set(missing_count as i64);
             metrics::kline_count().set(postgres.get_kline_count().await? as i64);
             log::info!("K-line updater completed. Will sleep for {sleep_seconds} seconds",);
             tokio::time::sleep(std::time::Duration::from_secs(sleep_seconds)).await;
@@ -1,5 +1,5 @@
 use once_cell::sync::Lazy;
-use subvt_metrics::registry::IntGauge;
+use subvt_metrics::registry::{IntCounter, IntCounterVec, IntGauge};
 
 const METRIC_PREFIX: &str = "subvt_kline_updater";
 
@@ -14,3 +14,41 @@ pub fn kline_count() -> IntGauge {
     });
     METER.clone()
 }
+
+pub fn missing_kline_count() -> IntGauge {
+    static METER: Lazy<IntGauge> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_gauge(
+            METRIC_PREFIX,
+            "missing_kline_count",
+            "Number of k-lines that could not be fetched from any source in the last run",
+        )
+        .unwrap()
+    });
+    METER.clone()
+}
+
+pub fn source_error_counter(source: &str) -> IntCounter {
+    static METER: Lazy<IntCounterVec> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_counter_vec(
+            METRIC_PREFIX,
+            "source_error_count",
+            "The number of failed k-line fetches per price source",
+            &["source"],
+        )
+        .unwrap()
+    });
+    METER.with_label_values(&[source])
+}
+
+pub fn outlier_counter(source: &str) -> IntCounter {
+    static METER: Lazy<IntCounterVec> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_counter_vec(
+            METRIC_PREFIX,
+            "outlier_count",
+            "The number of k-lines per price source that deviate from the other sources",
+            &["source"],
+        )
+        .unwrap()
+    });
+    METER.with_label_values(&[source])
+}
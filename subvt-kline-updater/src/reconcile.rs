@@ -0,0 +1,116 @@
+//! Gap detection and the cross-source selection of the k-lines.
+use rustc_hash::FxHashSet as HashSet;
+use subvt_types::kline::KLine;
+
+/// Closed ranges of the open times that are missing between the start and end open times,
+/// each range having at most `max_range_candle_count` candles.
+pub fn get_missing_ranges(
+    existing_open_times: &[u64],
+    start_timestamp: u64,
+    end_timestamp: u64,
+    interval_millis: u64,
+    max_range_candle_count: u64,
+) -> Vec<(u64, u64)> {
+    let existing_open_times: HashSet<u64> = existing_open_times.iter().cloned().collect();
+    let mut ranges: Vec<(u64, u64)> = Vec::new();
+    let mut current_range: Option<(u64, u64)> = None;
+    let mut open_time = start_timestamp;
+    while open_time <= end_timestamp {
+        if existing_open_times.contains(&open_time) {
+            if let Some(range) = current_range.take() {
+                ranges.push(range);
+            }
+        } else {
+            current_range = match current_range {
+                Some((range_start, _))
+                    if (open_time - range_start) / interval_millis < max_range_candle_count =>
+                {
+                    Some((range_start, open_time))
+                }
+                Some(range) => {
+                    ranges.push(range);
+                    Some((open_time, open_time))
+                }
+                None => Some((open_time, open_time)),
+            };
+        }
+        open_time += interval_millis;
+    }
+    if let Some(range) = current_range {
+        ranges.push(range);
+    }
+    ranges
+}
+
+/// Open times of the missing candles that are older than the grace period, i.e. that are not
+/// expected to be published by the sources anymore.
+pub fn get_unavailable_open_times(
+    missing_open_times: &[u64],
+    interval_millis: u64,
+    now: u64,
+    grace_period_millis: u64,
+) -> Vec<u64> {
+    missing_open_times
+        .iter()
+        .filter(|open_time| **open_time + interval_millis + grace_period_millis <= now)
+        .cloned()
+        .collect()
+}
+
+fn get_median(values: &[f64]) -> f64 {
+    let mut values = values.to_vec();
+    values.sort_by(|a, b| a.total_cmp(b));
+    let middle = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[middle - 1] + values[middle]) / 2.0
+    } else {
+        values[middle]
+    }
+}
+
+/// Selects the k-line to be saved among the k-lines of the same candle from different sources,
+/// given in the order of source preference. A k-line is an outlier if its close price deviates
+/// from the median close price of all sources by more than the threshold. The most preferred
+/// k-line that is not an outlier is selected, and the names of the outlier sources are returned
+/// along with it. Falls back to the most preferred k-line if all are outliers.
+pub fn select_kline(
+    candidates: Vec<KLine>,
+    outlier_threshold_percent: f64,
+) -> (KLine, Vec<String>) {
+    assert!(
+        !candidates.is_empty(),
+        "No k-line candidate to select from."
+    );
+    let closes: Vec<f64> = candidates
+        .iter()
+        .map(|kline| kline.close_to_f64().unwrap_or_default())
+        .collect();
+    let median = get_median(&closes);
+    let is_outlier = |close: f64| {
+        median > 0.0 && ((close - median).abs() / median) * 100.0 > outlier_threshold_percent
+    };
+    let outlier_sources: Vec<String> = candidates
+        .iter()
+        .zip(closes.iter())
+        .filter(|(_, close)| is_outlier(**close))
+        .map(|(kline, _)| kline.source.clone())
+        .collect();
+    let maybe_selected_index = closes.iter().position(|close| !is_outlier(*close));
+    let selected_index = match maybe_selected_index {
+        Some(index) => index,
+        None => {
+            let kline = &candidates[0];
+            log::warn!(
+                "All sources deviate from the median for {}-{} {} k-line at {}. Use {}.",
+                kline.source_ticker,
+                kline.target_ticker,
+                kline.interval,
+                kline.open_time,
+                kline.source,
+            );
+            0
+        }
+    };
+    let selected = candidates.into_iter().nth(selected_index).unwrap();
+    (selected, outlier_sources)
+}
@@ -0,0 +1,114 @@
+//! Binance spot market k-lines.
+use crate::source::PriceSource;
+use async_trait::async_trait;
+use std::str::FromStr;
+use subvt_types::kline::{BigDecimal, KLine, KLineInterval};
+
+const NAME: &str = "binance";
+const MAX_CANDLE_COUNT: u64 = 1000;
+
+type KLineRecord = (
+    u64,
+    String,
+    String,
+    String,
+    String,
+    String,
+    u64,
+    String,
+    u32,
+    String,
+    String,
+    String,
+);
+
+pub struct BinanceSource {
+    http_client: reqwest::Client,
+    base_url: String,
+}
+
+impl BinanceSource {
+    pub fn new(http_client: reqwest::Client, base_url: &str) -> Self {
+        Self {
+            http_client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+/// Parses the response of the `/api/v3/klines` endpoint.
+pub fn parse_klines(
+    json: &str,
+    source_ticker: &str,
+    quote_currency: &str,
+    interval: KLineInterval,
+) -> anyhow::Result<Vec<KLine>> {
+    let records: Vec<KLineRecord> = serde_json::from_str(json)?;
+    let mut klines = Vec::with_capacity(records.len());
+    for record in records {
+        klines.push(KLine {
+            id: 0,
+            interval,
+            open_time: record.0,
+            source_ticker: source_ticker.to_string(),
+            target_ticker: quote_currency.to_string(),
+            open: BigDecimal::from_str(&record.1)?,
+            high: BigDecimal::from_str(&record.2)?,
+            low: BigDecimal::from_str(&record.3)?,
+            close: BigDecimal::from_str(&record.4)?,
+            volume: BigDecimal::from_str(&record.5)?,
+            close_time: record.6,
+            quote_volume: BigDecimal::from_str(&record.7)?,
+            count: record.8,
+            taker_buy_volume: BigDecimal::from_str(&record.9)?,
+            taker_buy_quote_volume: BigDecimal::from_str(&record.10)?,
+            source: NAME.to_string(),
+        });
+    }
+    Ok(klines)
+}
+
+#[async_trait]
+impl PriceSource for BinanceSource {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    async fn fetch_klines(
+        &self,
+        source_ticker: &str,
+        quote_currency: &str,
+        interval: KLineInterval,
+        start_timestamp: u64,
+        end_timestamp: u64,
+    ) -> anyhow::Result<Vec<KLine>> {
+        let pair = format!("{source_ticker}{quote_currency}");
+        let mut klines = Vec::new();
+        let mut start_timestamp = start_timestamp;
+        while start_timestamp <= end_timestamp {
+            // e.g. https://api.binance.com/api/v3/klines?symbol=KSMUSDT&interval=1d&startTime=1732838400000&endTime=1732924800000&limit=1000
+            let url = format!(
+                "{}/api/v3/klines?symbol={pair}&interval={interval}&startTime={start_timestamp}&endTime={end_timestamp}&limit={MAX_CANDLE_COUNT}",
+                self.base_url,
+            );
+            let response = self.http_client.get(&url).send().await?;
+            if response.status() == reqwest::StatusCode::BAD_REQUEST {
+                log::debug!("{pair} is not listed on Binance.");
+                break;
+            }
+            let page = parse_klines(
+                &response.error_for_status()?.text().await?,
+                source_ticker,
+                quote_currency,
+                interval,
+            )?;
+            let last_open_time = match page.last() {
+                Some(kline) => kline.open_time,
+                None => break,
+            };
+            klines.extend(page);
+            start_timestamp = last_open_time + interval.get_duration_millis();
+        }
+        Ok(klines)
+    }
+}
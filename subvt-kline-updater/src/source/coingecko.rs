@@ -0,0 +1,145 @@
+//! CoinGecko-style aggregated market chart API. It only reports prices, so the k-lines are
+//! built from the price points in each candle period and have no volume data.
+use crate::source::{get_price_only_kline, PriceSource};
+use async_trait::async_trait;
+use rustc_hash::FxHashMap as HashMap;
+use std::str::FromStr;
+use subvt_types::kline::{BigDecimal, KLine, KLineInterval};
+
+const NAME: &str = "coingecko";
+/// The API returns hourly points for ranges up to 90 days and daily points for longer ranges,
+/// so longer ranges are fetched in 90-day pieces to be able to build daily candles.
+const MAX_RANGE_MILLIS: u64 = 90 * 24 * 60 * 60 * 1000;
+
+pub struct CoinGeckoSource {
+    http_client: reqwest::Client,
+    base_url: String,
+    coin_id: String,
+}
+
+impl CoinGeckoSource {
+    pub fn new(http_client: reqwest::Client, base_url: &str, coin_id: &str) -> Self {
+        Self {
+            http_client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            coin_id: coin_id.to_string(),
+        }
+    }
+}
+
+/// CoinGecko has no USDT quotes, USD is used for USDT as the two are pegged.
+fn get_vs_currency(quote_currency: &str) -> String {
+    match quote_currency {
+        "USDT" => "usd".to_string(),
+        _ => quote_currency.to_lowercase(),
+    }
+}
+
+/// Parses the response of the `/api/v3/coins/{id}/market_chart/range` endpoint, and builds the
+/// candles of the given interval from the price points. Only the candles with open times in the
+/// given closed range are returned.
+pub fn parse_klines(
+    json: &str,
+    source_ticker: &str,
+    quote_currency: &str,
+    interval: KLineInterval,
+    start_timestamp: u64,
+    end_timestamp: u64,
+) -> anyhow::Result<Vec<KLine>> {
+    let response: serde_json::Value = serde_json::from_str(json)?;
+    let points = match response["prices"].as_array() {
+        Some(points) => points,
+        None => return Ok(vec![]),
+    };
+    // open time -> prices in the order of time
+    let mut candle_prices: HashMap<u64, Vec<BigDecimal>> = HashMap::default();
+    for point in points {
+        let (timestamp, price) = match (point[0].as_f64(), point[1].as_f64()) {
+            (Some(timestamp), Some(_)) => (timestamp as u64, &point[1]),
+            _ => return Err(anyhow::anyhow!("Unexpected CoinGecko price point: {point}")),
+        };
+        let open_time = interval.get_open_time(timestamp);
+        if open_time < start_timestamp || open_time > end_timestamp {
+            continue;
+        }
+        candle_prices
+            .entry(open_time)
+            .or_default()
+            .push(BigDecimal::from_str(&price.to_string())?);
+    }
+    let mut open_times: Vec<u64> = candle_prices.keys().cloned().collect();
+    open_times.sort_unstable();
+    let mut klines = Vec::with_capacity(open_times.len());
+    for open_time in open_times {
+        let prices = &candle_prices[&open_time];
+        let open = prices.first().unwrap().clone();
+        let close = prices.last().unwrap().clone();
+        let high = prices.iter().max().unwrap().clone();
+        let low = prices.iter().min().unwrap().clone();
+        klines.push(get_price_only_kline(
+            NAME,
+            source_ticker,
+            quote_currency,
+            interval,
+            open_time,
+            [open, high, low, close],
+        ));
+    }
+    Ok(klines)
+}
+
+#[async_trait]
+impl PriceSource for CoinGeckoSource {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    async fn fetch_klines(
+        &self,
+        source_ticker: &str,
+        quote_currency: &str,
+        interval: KLineInterval,
+        start_timestamp: u64,
+        end_timestamp: u64,
+    ) -> anyhow::Result<Vec<KLine>> {
+        if self.coin_id.is_empty() {
+            return Ok(vec![]);
+        }
+        let vs_currency = get_vs_currency(quote_currency);
+        let mut klines = Vec::new();
+        let mut range_start = start_timestamp;
+        // the last candle ends one interval after its open time
+        let range_end = end_timestamp + interval.get_duration_millis();
+        while range_start < range_end {
+            let piece_end = range_end.min(range_start + MAX_RANGE_MILLIS);
+            // e.g. https://api.coingecko.com/api/v3/coins/polkadot/market_chart/range?vs_currency=eur&from=1732838400&to=1732924800
+            let url = format!(
+                "{}/api/v3/coins/{}/market_chart/range?vs_currency={vs_currency}&from={}&to={}",
+                self.base_url,
+                self.coin_id,
+                range_start / 1000,
+                piece_end / 1000,
+            );
+            let response = self.http_client.get(&url).send().await?;
+            if response.status() == reqwest::StatusCode::BAD_REQUEST
+                || response.status() == reqwest::StatusCode::NOT_FOUND
+            {
+                log::debug!(
+                    "{} is not quoted in {vs_currency} on CoinGecko.",
+                    self.coin_id
+                );
+                break;
+            }
+            klines.extend(parse_klines(
+                &response.error_for_status()?.text().await?,
+                source_ticker,
+                quote_currency,
+                interval,
+                range_start,
+                end_timestamp.min(piece_end.saturating_sub(1)),
+            )?);
+            range_start = piece_end;
+        }
+        Ok(klines)
+    }
+}
@@ -0,0 +1,125 @@
+//! Kraken spot market OHLC data. Kraken only serves the last 720 candles of an interval, so
+//! older gaps have to be filled by the other sources.
+use crate::source::PriceSource;
+use async_trait::async_trait;
+use std::str::FromStr;
+use subvt_types::kline::{BigDecimal, KLine, KLineInterval};
+
+const NAME: &str = "kraken";
+
+pub struct KrakenSource {
+    http_client: reqwest::Client,
+    base_url: String,
+}
+
+impl KrakenSource {
+    pub fn new(http_client: reqwest::Client, base_url: &str) -> Self {
+        Self {
+            http_client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+fn get_interval_minutes(interval: KLineInterval) -> u64 {
+    interval.get_duration_millis() / 60_000
+}
+
+fn parse_decimal(value: &serde_json::Value) -> anyhow::Result<BigDecimal> {
+    match value.as_str() {
+        Some(value) => Ok(BigDecimal::from_str(value)?),
+        None => Err(anyhow::anyhow!("Unexpected Kraken OHLC value: {value}")),
+    }
+}
+
+/// Parses the response of the `/0/public/OHLC` endpoint. Returns an empty list if the pair is
+/// unknown to Kraken.
+pub fn parse_klines(
+    json: &str,
+    source_ticker: &str,
+    quote_currency: &str,
+    interval: KLineInterval,
+) -> anyhow::Result<Vec<KLine>> {
+    let response: serde_json::Value = serde_json::from_str(json)?;
+    if let Some(errors) = response["error"].as_array() {
+        if !errors.is_empty() {
+            log::debug!("Kraken OHLC error for {source_ticker}{quote_currency}: {errors:?}");
+            return Ok(vec![]);
+        }
+    }
+    // the result has the OHLC data under the Kraken pair name, along with a `last` field
+    let records = match response["result"].as_object().and_then(|result| {
+        result
+            .iter()
+            .find(|(key, _)| key.as_str() != "last")
+            .and_then(|(_, records)| records.as_array())
+    }) {
+        Some(records) => records,
+        None => return Ok(vec![]),
+    };
+    let mut klines = Vec::with_capacity(records.len());
+    for record in records {
+        // [time, open, high, low, close, vwap, volume, count]
+        let open_time = match record[0].as_u64() {
+            Some(time) => time * 1000,
+            None => return Err(anyhow::anyhow!("Unexpected Kraken OHLC record: {record}")),
+        };
+        let vwap = parse_decimal(&record[5])?;
+        let volume = parse_decimal(&record[6])?;
+        klines.push(KLine {
+            id: 0,
+            interval,
+            open_time,
+            source_ticker: source_ticker.to_string(),
+            target_ticker: quote_currency.to_string(),
+            open: parse_decimal(&record[1])?,
+            high: parse_decimal(&record[2])?,
+            low: parse_decimal(&record[3])?,
+            close: parse_decimal(&record[4])?,
+            quote_volume: &volume * &vwap,
+            volume,
+            close_time: open_time + interval.get_duration_millis() - 1,
+            count: record[7].as_u64().unwrap_or(0) as u32,
+            taker_buy_volume: Default::default(),
+            taker_buy_quote_volume: Default::default(),
+            source: NAME.to_string(),
+        });
+    }
+    Ok(klines)
+}
+
+#[async_trait]
+impl PriceSource for KrakenSource {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    async fn fetch_klines(
+        &self,
+        source_ticker: &str,
+        quote_currency: &str,
+        interval: KLineInterval,
+        start_timestamp: u64,
+        end_timestamp: u64,
+    ) -> anyhow::Result<Vec<KLine>> {
+        // `since` is exclusive and in seconds
+        let since = (start_timestamp / 1000).saturating_sub(1);
+        // e.g. https://api.kraken.com/0/public/OHLC?pair=DOTUSD&interval=1440&since=1732838400
+        let url = format!(
+            "{}/0/public/OHLC?pair={source_ticker}{quote_currency}&interval={}&since={since}",
+            self.base_url,
+            get_interval_minutes(interval),
+        );
+        let response = self.http_client.get(&url).send().await?;
+        let klines = parse_klines(
+            &response.error_for_status()?.text().await?,
+            source_ticker,
+            quote_currency,
+            interval,
+        )?;
+        Ok(klines
+            .into_iter()
+            .filter(|kline| kline.open_time >= start_timestamp && kline.open_time <= end_timestamp)
+            .collect())
+    }
+}
@@ -0,0 +1,87 @@
+//! Price sources for the k-line updater. Each source fetches the candles of the token in a quote
+//! currency from an exchange or an aggregator API with a configurable base URL.
+use async_trait::async_trait;
+use subvt_config::KLineUpdaterConfig;
+use subvt_types::kline::{KLine, KLineInterval};
+
+pub mod binance;
+pub mod coingecko;
+pub mod kraken;
+
+#[async_trait]
+pub trait PriceSource: Send + Sync {
+    /// Name of the source, saved with the k-lines fetched from it.
+    fn name(&self) -> &'static str;
+
+    /// Fetch the candles with open times in the given closed range. Candles the source doesn't
+    /// have, such as for an unlisted pair or for a range before the listing, are not returned.
+    async fn fetch_klines(
+        &self,
+        source_ticker: &str,
+        quote_currency: &str,
+        interval: KLineInterval,
+        start_timestamp: u64,
+        end_timestamp: u64,
+    ) -> anyhow::Result<Vec<KLine>>;
+}
+
+/// Creates the configured price sources in the order of preference.
+pub fn get_price_sources(
+    http_client: &reqwest::Client,
+    config: &KLineUpdaterConfig,
+) -> anyhow::Result<Vec<Box<dyn PriceSource>>> {
+    let mut sources: Vec<Box<dyn PriceSource>> = Vec::new();
+    for name in config.get_sources() {
+        match name.as_str() {
+            "binance" => sources.push(Box::new(binance::BinanceSource::new(
+                http_client.clone(),
+                &config.binance_base_url,
+            ))),
+            "kraken" => sources.push(Box::new(kraken::KrakenSource::new(
+                http_client.clone(),
+                &config.kraken_base_url,
+            ))),
+            "coingecko" => sources.push(Box::new(coingecko::CoinGeckoSource::new(
+                http_client.clone(),
+                &config.coingecko_base_url,
+                &config.coingecko_coin_id,
+            ))),
+            _ => return Err(anyhow::anyhow!("Unknown price source: {name}")),
+        }
+    }
+    if sources.is_empty() {
+        return Err(anyhow::anyhow!("No price source is configured."));
+    }
+    Ok(sources)
+}
+
+/// A k-line that has only the open, high, low and close prices, for sources that don't report
+/// the trade volumes.
+pub(crate) fn get_price_only_kline(
+    source: &str,
+    source_ticker: &str,
+    quote_currency: &str,
+    interval: KLineInterval,
+    open_time: u64,
+    prices: [subvt_types::kline::BigDecimal; 4],
+) -> KLine {
+    let [open, high, low, close] = prices;
+    KLine {
+        id: 0,
+        interval,
+        open_time,
+        source_ticker: source_ticker.to_string(),
+        target_ticker: quote_currency.to_string(),
+        open,
+        high,
+        low,
+        close,
+        volume: Default::default(),
+        close_time: open_time + interval.get_duration_millis() - 1,
+        quote_volume: Default::default(),
+        count: 0,
+        taker_buy_volume: Default::default(),
+        taker_buy_quote_volume: Default::default(),
+        source: source.to_string(),
+    }
+}
@@ -0,0 +1,219 @@
+//! Tests of the price sources and the range fetch against a stub exchange server.
+use std::sync::Arc;
+use subvt_kline_updater::fetch_range;
+use subvt_kline_updater::reconcile::get_unavailable_open_times;
+use subvt_kline_updater::source::binance::BinanceSource;
+use subvt_kline_updater::source::kraken::KrakenSource;
+use subvt_kline_updater::source::PriceSource;
+use subvt_types::kline::KLineInterval;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+const DAY: u64 = 24 * 60 * 60 * 1000;
+const START: u64 = 1732838400000;
+
+type Handler = Arc<dyn Fn(&str) -> (u16, String) + Send + Sync>;
+
+/// Starts an HTTP server that responds to each request with the status and the body returned by
+/// the handler for the request path, and returns the base URL of the server.
+async fn start_stub_server(handler: Handler) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let handler = handler.clone();
+            tokio::spawn(async move {
+                let mut buffer = vec![0; 8192];
+                let length = stream.read(&mut buffer).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buffer[..length]).to_string();
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+                let (status, body) = handler(path);
+                let response = format!(
+                    "HTTP/1.1 {status} Stub\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len(),
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            });
+        }
+    });
+    format!("http://{address}")
+}
+
+fn get_query_parameter(path: &str, name: &str) -> Option<u64> {
+    path.split(['?', '&'])
+        .find_map(|pair| pair.strip_prefix(&format!("{name}=")))
+        .and_then(|value| value.parse().ok())
+}
+
+fn binance_record(open_time: u64, close: &str) -> String {
+    format!(
+        r#"[{open_time},"8.0","9.0","7.0","{close}","100.0",{},"800.0",10,"50.0","400.0","0"]"#,
+        open_time + DAY - 1,
+    )
+}
+
+/// Binance stub that has the daily candles of the given open times, and returns at most two
+/// candles per response to exercise the pagination.
+fn binance_handler(open_times: Vec<u64>) -> Handler {
+    Arc::new(move |path| {
+        if !path.starts_with("/api/v3/klines") {
+            return (404, "".to_string());
+        }
+        if !path.contains("symbol=DOTUSDT") {
+            return (400, r#"{"code":-1121,"msg":"Invalid symbol."}"#.to_string());
+        }
+        let start_time = get_query_parameter(path, "startTime").unwrap();
+        let end_time = get_query_parameter(path, "endTime").unwrap();
+        let records: Vec<String> = open_times
+            .iter()
+            .filter(|open_time| **open_time >= start_time && **open_time <= end_time)
+            .take(2)
+            .map(|open_time| binance_record(*open_time, "8.5"))
+            .collect();
+        (200, format!("[{}]", records.join(",")))
+    })
+}
+
+fn kraken_handler(open_times: Vec<u64>) -> Handler {
+    Arc::new(move |path| {
+        if !path.starts_with("/0/public/OHLC") {
+            return (404, "".to_string());
+        }
+        let since = get_query_parameter(path, "since").unwrap() * 1000;
+        let records: Vec<String> = open_times
+            .iter()
+            .filter(|open_time| **open_time > since)
+            .map(|open_time| {
+                format!(
+                    r#"[{},"8.0","9.0","7.0","8.6","8.3","100.0",12]"#,
+                    open_time / 1000
+                )
+            })
+            .collect();
+        (
+            200,
+            format!(
+                r#"{{"error":[],"result":{{"DOTUSDT":[{}],"last":{}}}}}"#,
+                records.join(","),
+                since / 1000,
+            ),
+        )
+    })
+}
+
+fn failing_handler() -> Handler {
+    Arc::new(|_| (500, "".to_string()))
+}
+
+#[tokio::test]
+async fn test_binance_pagination() {
+    let open_times: Vec<u64> = (0..5).map(|index| START + index * DAY).collect();
+    let base_url = start_stub_server(binance_handler(open_times.clone())).await;
+    let source = BinanceSource::new(reqwest::Client::new(), &base_url);
+    let klines = source
+        .fetch_klines("DOT", "USDT", KLineInterval::Day, START, START + 4 * DAY)
+        .await
+        .unwrap();
+    let fetched_open_times: Vec<u64> = klines.iter().map(|kline| kline.open_time).collect();
+    assert_eq!(fetched_open_times, open_times);
+    // unlisted pair
+    let klines = source
+        .fetch_klines("DOT", "GBP", KLineInterval::Day, START, START + 4 * DAY)
+        .await
+        .unwrap();
+    assert!(klines.is_empty());
+}
+
+#[tokio::test]
+async fn test_kraken_range() {
+    let open_times: Vec<u64> = (0..5).map(|index| START + index * DAY).collect();
+    let base_url = start_stub_server(kraken_handler(open_times)).await;
+    let source = KrakenSource::new(reqwest::Client::new(), &base_url);
+    let klines = source
+        .fetch_klines(
+            "DOT",
+            "USDT",
+            KLineInterval::Day,
+            START + DAY,
+            START + 3 * DAY,
+        )
+        .await
+        .unwrap();
+    let fetched_open_times: Vec<u64> = klines.iter().map(|kline| kline.open_time).collect();
+    assert_eq!(
+        fetched_open_times,
+        vec![START + DAY, START + 2 * DAY, START + 3 * DAY]
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_range() {
+    // binance misses the 3rd candle, kraken has it, and no source has the 4th candle
+    let binance_base_url =
+        start_stub_server(binance_handler(vec![START, START + DAY, START + 4 * DAY])).await;
+    let kraken_base_url = start_stub_server(kraken_handler(vec![START, START + 2 * DAY])).await;
+    let sources: Vec<Box<dyn PriceSource>> = vec![
+        Box::new(BinanceSource::new(
+            reqwest::Client::new(),
+            &binance_base_url,
+        )),
+        Box::new(KrakenSource::new(reqwest::Client::new(), &kraken_base_url)),
+    ];
+    let range = (START, START + 4 * DAY);
+    let fetched_range = fetch_range(&sources, "DOT", "USDT", KLineInterval::Day, range, 5.0).await;
+    assert!(fetched_range.is_complete);
+    let sources: Vec<(u64, &str)> = fetched_range
+        .klines
+        .iter()
+        .map(|kline| (kline.open_time, kline.source.as_str()))
+        .collect();
+    assert_eq!(
+        sources,
+        vec![
+            (START, "binance"),
+            (START + DAY, "binance"),
+            (START + 2 * DAY, "kraken"),
+            (START + 4 * DAY, "binance"),
+        ]
+    );
+    assert_eq!(fetched_range.missing_open_times, vec![START + 3 * DAY]);
+}
+
+#[tokio::test]
+async fn test_fetch_range_with_failing_source() {
+    let binance_base_url = start_stub_server(binance_handler(vec![START])).await;
+    let kraken_base_url = start_stub_server(failing_handler()).await;
+    let sources: Vec<Box<dyn PriceSource>> = vec![
+        Box::new(BinanceSource::new(
+            reqwest::Client::new(),
+            &binance_base_url,
+        )),
+        Box::new(KrakenSource::new(reqwest::Client::new(), &kraken_base_url)),
+    ];
+    let range = (START, START + DAY);
+    let fetched_range = fetch_range(&sources, "DOT", "USDT", KLineInterval::Day, range, 5.0).await;
+    // the missing candle may still be available from the failing source
+    assert!(!fetched_range.is_complete);
+    assert_eq!(fetched_range.klines.len(), 1);
+    assert_eq!(fetched_range.missing_open_times, vec![START + DAY]);
+}
+
+#[test]
+fn test_get_unavailable_open_times() {
+    let missing_open_times = [START, START + DAY, START + 2 * DAY];
+    let grace_period = 2 * DAY;
+    // the 1st candle closed exactly a grace period ago
+    assert_eq!(
+        get_unavailable_open_times(&missing_open_times, DAY, START + 3 * DAY, grace_period),
+        vec![START]
+    );
+    assert!(
+        get_unavailable_open_times(&missing_open_times, DAY, START + 2 * DAY, grace_period)
+            .is_empty()
+    );
+    assert_eq!(
+        get_unavailable_open_times(&missing_open_times, DAY, START + 10 * DAY, grace_period),
+        missing_open_times.to_vec()
+    );
+}
@@ -0,0 +1,116 @@
+use std::str::FromStr;
+use subvt_kline_updater::reconcile::{get_missing_ranges, select_kline};
+use subvt_kline_updater::source::{binance, coingecko, kraken};
+use subvt_types::kline::{BigDecimal, KLine, KLineInterval};
+
+const HOUR: u64 = 60 * 60 * 1000;
+const DAY: u64 = 24 * HOUR;
+
+fn kline(source: &str, close: &str) -> KLine {
+    let close = BigDecimal::from_str(close).unwrap();
+    KLine {
+        id: 0,
+        interval: KLineInterval::Day,
+        open_time: 0,
+        source_ticker: "DOT".to_string(),
+        target_ticker: "USDT".to_string(),
+        open: close.clone(),
+        high: close.clone(),
+        low: close.clone(),
+        close,
+        volume: Default::default(),
+        close_time: DAY - 1,
+        quote_volume: Default::default(),
+        count: 0,
+        taker_buy_volume: Default::default(),
+        taker_buy_quote_volume: Default::default(),
+        source: source.to_string(),
+    }
+}
+
+#[test]
+fn test_parse_binance_klines() {
+    let json = r#"[[1732838400000,"8.10","8.50","7.90","8.40","1000.5",1732924799999,"8300.0",120,"500.2","4150.0","0"]]"#;
+    let klines = binance::parse_klines(json, "DOT", "EUR", KLineInterval::Day).unwrap();
+    assert_eq!(klines.len(), 1);
+    assert_eq!(klines[0].open_time, 1732838400000);
+    assert_eq!(klines[0].close, BigDecimal::from_str("8.40").unwrap());
+    assert_eq!(klines[0].count, 120);
+    assert_eq!(klines[0].target_ticker, "EUR");
+    assert_eq!(klines[0].source, "binance");
+}
+
+#[test]
+fn test_parse_kraken_klines() {
+    let json = r#"{"error":[],"result":{"DOTEUR":[[1732838400,"8.1","8.5","7.9","8.4","8.2","100.0",12]],"last":1732838400}}"#;
+    let klines = kraken::parse_klines(json, "DOT", "EUR", KLineInterval::Day).unwrap();
+    assert_eq!(klines.len(), 1);
+    assert_eq!(klines[0].open_time, 1732838400000);
+    assert_eq!(klines[0].close_time, 1732838400000 + DAY - 1);
+    assert_eq!(klines[0].high, BigDecimal::from_str("8.5").unwrap());
+    assert_eq!(klines[0].quote_volume, BigDecimal::from_str("820").unwrap());
+    let json = r#"{"error":["EQuery:Unknown asset pair"]}"#;
+    assert!(kraken::parse_klines(json, "DOT", "GBP", KLineInterval::Day)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_parse_coingecko_klines() {
+    let start = 1732838400000u64;
+    let json = format!(
+        r#"{{"prices":[[{},8.1],[{},8.6],[{},7.8],[{},8.3],[{},9.0]]}}"#,
+        start + 1000,
+        start + HOUR + 1000,
+        start + 2 * HOUR + 1000,
+        start + 3 * HOUR + 1000,
+        start + DAY + 1000,
+    );
+    let klines =
+        coingecko::parse_klines(&json, "DOT", "USDT", KLineInterval::Day, start, start).unwrap();
+    assert_eq!(klines.len(), 1);
+    assert_eq!(klines[0].open_time, start);
+    assert_eq!(klines[0].open, BigDecimal::from_str("8.1").unwrap());
+    assert_eq!(klines[0].high, BigDecimal::from_str("8.6").unwrap());
+    assert_eq!(klines[0].low, BigDecimal::from_str("7.8").unwrap());
+    assert_eq!(klines[0].close, BigDecimal::from_str("8.3").unwrap());
+    assert_eq!(klines[0].source, "coingecko");
+}
+
+#[test]
+fn test_get_missing_ranges() {
+    let existing = [2 * HOUR, 3 * HOUR, 6 * HOUR];
+    assert_eq!(
+        get_missing_ranges(&existing, 0, 9 * HOUR, HOUR, 2),
+        vec![
+            (0, HOUR),
+            (4 * HOUR, 5 * HOUR),
+            (7 * HOUR, 8 * HOUR),
+            (9 * HOUR, 9 * HOUR),
+        ]
+    );
+    assert!(get_missing_ranges(&existing, 2 * HOUR, 3 * HOUR, HOUR, 10).is_empty());
+}
+
+#[test]
+fn test_select_kline() {
+    let (selected, outliers) = select_kline(
+        vec![
+            kline("binance", "12.0"),
+            kline("kraken", "8.1"),
+            kline("coingecko", "8.0"),
+        ],
+        5.0,
+    );
+    assert_eq!(selected.source, "kraken");
+    assert_eq!(outliers, vec!["binance".to_string()]);
+    let (selected, outliers) =
+        select_kline(vec![kline("binance", "8.0"), kline("kraken", "8.1")], 5.0);
+    assert_eq!(selected.source, "binance");
+    assert!(outliers.is_empty());
+    // all deviate, falls back to the preferred source
+    let (selected, outliers) =
+        select_kline(vec![kline("binance", "8.0"), kline("kraken", "12.0")], 5.0);
+    assert_eq!(selected.source, "binance");
+    assert_eq!(outliers.len(), 2);
+}
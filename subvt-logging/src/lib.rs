@@ -3,7 +3,9 @@
 use env_logger::{Builder, Env, Target, WriteStyle};
 use std::str::FromStr;
 
-/// Initializes the logging facade using the application configuration reference.
+pub mod trace;
+
+/// Initializes the logging facade and the tracing using the application configuration reference.
 pub fn init(config: &subvt_config::Config) {
     let other_modules_log_level = log::LevelFilter::from_str(config.log.other_level.as_str())
         .expect("Cannot read log level configuration for outside modules.");
@@ -17,6 +19,7 @@ pub fn init(config: &subvt_config::Config) {
     builder.filter(Some("subvt_block_processor"), log_level);
     builder.filter(Some("subvt_governance"), log_level);
     builder.filter(Some("subvt_kline_updater"), log_level);
+    builder.filter(Some("subvt_logging"), log_level);
     builder.filter(Some("subvt_metrics"), log_level);
     builder.filter(Some("subvt_metrics_server"), log_level);
     builder.filter(Some("subvt_nft"), log_level);
@@ -43,4 +46,5 @@ pub fn init(config: &subvt_config::Config) {
     builder.filter(Some("subvt_validator_list_updater"), log_level);
     builder.write_style(WriteStyle::Always);
     builder.init();
+    trace::init(config);
 }
@@ -0,0 +1,171 @@
+//! OpenTelemetry tracing of the notification pipeline, from the finalization of a block to the
+//! sending of the notifications generated for it. The spans are exported over OTLP/HTTP to the
+//! collector at `tracing.otlp_endpoint` when `tracing.enabled` is `true`.
+//!
+//! The trace context is carried between the services as a W3C `traceparent`, in the payload of
+//! the `block_processed` PostgreSQL notification and in the notification rows. Within a service
+//! it's carried by the `Context` of the current task, see `FutureExt::with_context`. The context
+//! also holds the time the block at the start of the pipeline was observed finalized, see
+//! `FinalizedAt`, for the pipeline latency metrics, which work whether the tracing is enabled or
+//! not.
+use once_cell::sync::OnceCell;
+use opentelemetry::propagation::{Extractor, Injector, TextMapPropagator};
+use opentelemetry::trace::{Status, TraceContextExt, Tracer};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{Sampler, SdkTracerProvider};
+use opentelemetry_sdk::Resource;
+use std::time::{SystemTime, UNIX_EPOCH};
+use subvt_config::Config;
+
+pub use opentelemetry::context::FutureExt;
+pub use opentelemetry::{Context, KeyValue};
+
+const TRACER_NAME: &str = "subvt";
+const TRACE_PARENT_KEY: &str = "traceparent";
+
+static TRACER_PROVIDER: OnceCell<SdkTracerProvider> = OnceCell::new();
+
+/// Time the block at the start of the pipeline was observed finalized, or its timestamp if it was
+/// processed on catch-up, in milliseconds since the Unix epoch.
+#[derive(Clone, Copy, Debug)]
+pub struct FinalizedAt(pub u64);
+
+impl FinalizedAt {
+    pub fn now() -> Self {
+        Self(get_current_time_millis())
+    }
+
+    pub fn get_elapsed_millis(&self) -> u64 {
+        get_current_time_millis().saturating_sub(self.0)
+    }
+}
+
+fn get_current_time_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Carrier of the W3C trace context. Only the `traceparent` is propagated.
+#[derive(Default)]
+struct TraceParent(Option<String>);
+
+impl Injector for TraceParent {
+    fn set(&mut self, key: &str, value: String) {
+        if key == TRACE_PARENT_KEY {
+            self.0 = Some(value);
+        }
+    }
+}
+
+impl Extractor for TraceParent {
+    fn get(&self, key: &str) -> Option<&str> {
+        if key == TRACE_PARENT_KEY {
+            self.0.as_deref()
+        } else {
+            None
+        }
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        vec![TRACE_PARENT_KEY]
+    }
+}
+
+fn get_service_name() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|path| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| TRACER_NAME.to_string())
+}
+
+/// Sets up the span exporter if the tracing is enabled. The spans are no-op otherwise.
+pub(crate) fn init(config: &Config) {
+    if !config.tracing.enabled {
+        return;
+    }
+    let exporter = match opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(config.tracing.otlp_endpoint.as_str())
+        .build()
+    {
+        Ok(exporter) => exporter,
+        Err(error) => {
+            log::error!("Cannot build the span exporter, tracing is disabled: {error:?}");
+            return;
+        }
+    };
+    let service_name = get_service_name();
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            config.tracing.sample_ratio,
+        ))))
+        .with_resource(
+            Resource::builder()
+                .with_service_name(service_name.clone())
+                .build(),
+        )
+        .build();
+    opentelemetry::global::set_tracer_provider(provider.clone());
+    let _ = TRACER_PROVIDER.set(provider);
+    log::info!(
+        "Export the traces of {service_name} to {}.",
+        config.tracing.otlp_endpoint
+    );
+}
+
+/// Exports the remaining spans.
+pub fn shutdown() {
+    if let Some(provider) = TRACER_PROVIDER.get() {
+        if let Err(error) = provider.shutdown() {
+            log::warn!("Error while shutting down the span exporter: {error:?}");
+        }
+    }
+}
+
+/// Starts a span as the child of the span in the `traceparent` if given, or of the span in the
+/// current context otherwise. The returned context has the values of the current context, and
+/// the span ends when the last clone of the context is dropped.
+pub fn start_span(
+    name: &'static str,
+    maybe_trace_parent: Option<&str>,
+    attributes: Vec<KeyValue>,
+) -> Context {
+    let parent = match maybe_trace_parent {
+        Some(trace_parent) => TraceContextPropagator::new().extract_with_context(
+            &Context::current(),
+            &TraceParent(Some(trace_parent.to_string())),
+        ),
+        None => Context::current(),
+    };
+    let tracer = opentelemetry::global::tracer(TRACER_NAME);
+    let span = tracer
+        .span_builder(name)
+        .with_attributes(attributes)
+        .start_with_context(&tracer, &parent);
+    parent.with_span(span)
+}
+
+/// Marks the span in the context as failed.
+pub fn set_span_error(context: &Context, description: String) {
+    context.span().set_status(Status::error(description));
+}
+
+/// `traceparent` of the span in the context, `None` if there's no span, e.g. when the tracing is
+/// disabled.
+pub fn get_trace_parent(context: &Context) -> Option<String> {
+    let mut trace_parent = TraceParent::default();
+    TraceContextPropagator::new().inject_context(context, &mut trace_parent);
+    trace_parent.0
+}
+
+/// Finalization time of the block at the start of the pipeline of the current context.
+pub fn get_finalized_at() -> Option<FinalizedAt> {
+    Context::current().get::<FinalizedAt>().copied()
+}
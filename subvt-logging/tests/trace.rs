@@ -0,0 +1,84 @@
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use std::sync::Once;
+use subvt_logging::trace::{self, FinalizedAt, KeyValue};
+
+const TRACE_PARENT: &str = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+
+static INIT: Once = Once::new();
+
+/// Records the spans without exporting them, as the service does when the tracing is enabled.
+fn init_tracing() {
+    INIT.call_once(|| {
+        opentelemetry::global::set_tracer_provider(SdkTracerProvider::builder().build());
+    });
+}
+
+/// Trace id, span id and flags of a `traceparent`.
+fn parse_trace_parent(trace_parent: &str) -> (String, String, String) {
+    let parts: Vec<&str> = trace_parent.split('-').collect();
+    assert_eq!(parts.len(), 4);
+    assert_eq!(parts[0], "00");
+    assert_eq!(parts[1].len(), 32);
+    assert_eq!(parts[2].len(), 16);
+    (
+        parts[1].to_string(),
+        parts[2].to_string(),
+        parts[3].to_string(),
+    )
+}
+
+#[test]
+fn test_trace_parent_round_trip() {
+    init_tracing();
+    // the span continues the trace of the traceparent it's extracted from
+    let context = trace::start_span(
+        "process_block",
+        Some(TRACE_PARENT),
+        vec![KeyValue::new("block_number", 10)],
+    );
+    let trace_parent = trace::get_trace_parent(&context).unwrap();
+    let (trace_id, span_id, flags) = parse_trace_parent(&trace_parent);
+    let (parent_trace_id, parent_span_id, parent_flags) = parse_trace_parent(TRACE_PARENT);
+    assert_eq!(trace_id, parent_trace_id);
+    assert_ne!(span_id, parent_span_id);
+    assert_eq!(flags, parent_flags);
+    // and its injected traceparent is extracted by the next service
+    let child_context = trace::start_span("inspect_block", Some(&trace_parent), vec![]);
+    let child_trace_parent = trace::get_trace_parent(&child_context).unwrap();
+    let (child_trace_id, child_span_id, _) = parse_trace_parent(&child_trace_parent);
+    assert_eq!(child_trace_id, trace_id);
+    assert_ne!(child_span_id, span_id);
+}
+
+#[test]
+fn test_new_trace() {
+    init_tracing();
+    let context = trace::start_span("process_block", None, vec![]);
+    let trace_parent = trace::get_trace_parent(&context).unwrap();
+    let (trace_id, _, _) = parse_trace_parent(&trace_parent);
+    let (parent_trace_id, _, _) = parse_trace_parent(TRACE_PARENT);
+    assert_ne!(trace_id, parent_trace_id);
+    // an invalid traceparent starts a new trace
+    let context = trace::start_span("process_block", Some("invalid"), vec![]);
+    let trace_parent = trace::get_trace_parent(&context).unwrap();
+    let (trace_id, _, _) = parse_trace_parent(&trace_parent);
+    assert_ne!(trace_id, parent_trace_id);
+}
+
+#[test]
+fn test_no_trace_parent_without_span() {
+    assert_eq!(trace::get_trace_parent(&trace::Context::new()), None);
+}
+
+#[test]
+fn test_finalized_at() {
+    init_tracing();
+    assert!(trace::get_finalized_at().is_none());
+    let _guard = trace::Context::current()
+        .with_value(FinalizedAt(1_000))
+        .attach();
+    // the spans keep the finalization time of the current context
+    let context = trace::start_span("inspect_block", Some(TRACE_PARENT), vec![]);
+    let _guard = context.attach();
+    assert_eq!(trace::get_finalized_at().unwrap().0, 1_000);
+}
@@ -0,0 +1,24 @@
+//! Health and readiness state of the service, served by the metrics server at `/health` and
+//! `/ready`. A service is healthy unless its main loop keeps failing, and it's ready once the main
+//! loop has initialized until it exits, i.e. not while starting, backing off after a failure or
+//! shutting down.
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static IS_HEALTHY: AtomicBool = AtomicBool::new(true);
+static IS_READY: AtomicBool = AtomicBool::new(false);
+
+pub fn is_healthy() -> bool {
+    IS_HEALTHY.load(Ordering::SeqCst)
+}
+
+pub fn set_healthy(is_healthy: bool) {
+    IS_HEALTHY.store(is_healthy, Ordering::SeqCst);
+}
+
+pub fn is_ready() -> bool {
+    IS_READY.load(Ordering::SeqCst)
+}
+
+pub fn set_ready(is_ready: bool) {
+    IS_READY.store(is_ready, Ordering::SeqCst);
+}
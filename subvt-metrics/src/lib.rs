@@ -1,3 +1,4 @@
 #![warn(clippy::disallowed_types)]
+pub mod health;
 pub mod registry;
 pub mod server;
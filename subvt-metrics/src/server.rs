@@ -1,10 +1,11 @@
 use std::fmt::Debug;
 use std::{convert::Infallible, net::ToSocketAddrs, string::FromUtf8Error};
 
+use crate::health;
 use crate::registry::get_default_registry;
 use prometheus::{Encoder, Registry, TextEncoder};
 use tokio::{task, task::JoinError};
-use warp::{reject::Reject, Filter, Rejection, Reply};
+use warp::{http::StatusCode, reject::Reject, Filter, Rejection, Reply};
 
 #[derive(Debug, thiserror::Error)]
 enum Error {
@@ -18,12 +19,17 @@ enum Error {
 
 pub async fn start<T: ToSocketAddrs + Debug>(address: T) {
     let registry = get_default_registry();
-    let route = warp::path!("metrics")
+    let metrics_route = warp::path!("metrics")
         .and(with(registry))
         .and_then(metrics_text_handler);
+    let health_route = warp::path!("health").map(|| get_status_reply(health::is_healthy()));
+    let ready_route = warp::path!("ready").map(|| get_status_reply(health::is_ready()));
 
     log::info!("Metrics server started on {address:?}");
-    let routes = route.with(warp::log("subvt_metrics_server"));
+    let routes = metrics_route
+        .or(health_route)
+        .or(ready_route)
+        .with(warp::log("subvt_metrics_server"));
     let socket_addr = address
         .to_socket_addrs()
         .expect("Invalid server address.")
@@ -45,6 +51,15 @@ async fn metrics_text_handler(registry: Registry) -> Result<impl Reply, Rejectio
     .map_err(Into::into)
 }
 
+/// `200` if the check passes, `503` otherwise, for the liveness and readiness probes.
+fn get_status_reply(is_ok: bool) -> impl Reply {
+    if is_ok {
+        warp::reply::with_status("OK", StatusCode::OK)
+    } else {
+        warp::reply::with_status("UNAVAILABLE", StatusCode::SERVICE_UNAVAILABLE)
+    }
+}
+
 fn with<T: Clone + Send>(t: T) -> impl Filter<Extract = (T,), Error = Infallible> + Clone {
     warp::any().map(move || t.clone())
 }
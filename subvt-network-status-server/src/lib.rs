@@ -1,19 +1,45 @@
 //! Subscribes to the network status data on Redis and publishes the data through
 //! websocket pub/sub.
+//!
+//! `subscribe_networkStatus` optionally accepts the last best block number that the client has
+//! the status for. If that block is within the last `rpc.network_status_history_depth` blocks,
+//! the subscription starts with a single diff from that block's status instead of the complete
+//! status.
+//!
+//! The same updates are served as Server-Sent Events at `GET /sse` on the same port, with the
+//! best block number as the event id, see `subvt_service_common::sse`. They are also served as
+//! binary WebSocket frames at `/binary`, where the `encoding` (`json`, `msgpack` or `cbor`) and
+//! `compression` (`none`, `deflate` or `gzip`) query parameters select a compact format, see
+//! `subvt_service_common::binary_ws`. The JSON-RPC subscription messages are JSON, and the
+//! subscriptions that ask for a binary format are rejected in favor of `/binary`.
+//!
+//! The server serves all the networks in `common.networks` from one process. The network of a
+//! connection is selected by the path, e.g. `ws://host:7888/polkadot` or `/polkadot/sse`, and the
+//! first network is served at the root path, see `subvt_service_common::network`.
 #![warn(clippy::disallowed_types)]
 
 use anyhow::Context;
 use async_trait::async_trait;
-use bus::Bus;
+use futures_util::stream::BoxStream;
 use futures_util::StreamExt as _;
 use jsonrpsee::server::{RpcModule, ServerBuilder, ServerHandle};
-use jsonrpsee::SubscriptionMessage;
+use jsonrpsee::types::error::INVALID_PARAMS_CODE;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::{SubscriptionMessage, SubscriptionSink};
 use lazy_static::lazy_static;
 use redis::aio::MultiplexedConnection;
-use std::sync::{Arc, Mutex, RwLock};
+use std::collections::VecDeque;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use subvt_config::Config;
+use subvt_metrics::health;
+use subvt_service_common::binary_ws::BinaryWsLayer;
+use subvt_service_common::encoding::MessageFormat;
+use subvt_service_common::network::{keep_running, NetworkLayer, Networks};
+use subvt_service_common::sse::{SseEvent, SseHandler, SseLayer, SseRequest};
 use subvt_service_common::Service;
 use subvt_types::subvt::{NetworkStatus, NetworkStatusDiff, NetworkStatusUpdate};
+use tokio::sync::broadcast;
 
 mod metrics;
 
@@ -23,18 +49,42 @@ lazy_static! {
 
 #[derive(Clone, Debug)]
 pub enum BusEvent {
-    NewBlock(Box<NetworkStatusDiff>),
+    /// Best block number and the diff from the status at the previous best block.
+    NewBlock(u64, Arc<NetworkStatusDiff>),
     Error,
 }
 
+/// Status history and update bus of a network.
+#[derive(Clone)]
+struct NetworkState {
+    chain: String,
+    // the last element is the current status
+    status_history: Arc<RwLock<VecDeque<NetworkStatus>>>,
+    bus: broadcast::Sender<BusEvent>,
+}
+
+impl NetworkState {
+    fn new(chain: &str) -> Self {
+        let (bus, _) = broadcast::channel(CONFIG.rpc.bus_capacity);
+        Self {
+            chain: chain.to_string(),
+            status_history: Arc::new(RwLock::new(VecDeque::with_capacity(
+                CONFIG.rpc.network_status_history_depth + 1,
+            ))),
+            bus,
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct NetworkStatusServer;
 
 impl NetworkStatusServer {
     async fn read_current_network_status(
+        chain: &str,
         connection: &mut MultiplexedConnection,
     ) -> anyhow::Result<NetworkStatus> {
-        let key = format!("subvt:{}:network_status", CONFIG.substrate.chain);
+        let key = format!("subvt:{chain}:network_status");
         let status_json_string: String = redis::cmd("GET")
             .arg(key)
             .query_async(connection)
@@ -45,81 +95,265 @@ impl NetworkStatusServer {
         Ok(status)
     }
 
-    async fn run_rpc_server(
-        current_status: &Arc<RwLock<NetworkStatus>>,
-        bus: &Arc<Mutex<Bus<BusEvent>>>,
-    ) -> anyhow::Result<ServerHandle> {
+    /// The diff from the status at the given best block to the current status, if the status at
+    /// that block is still in the history.
+    fn get_resume_update(
+        chain: &str,
+        status_history: &VecDeque<NetworkStatus>,
+        base_block_number: u64,
+    ) -> Option<NetworkStatusUpdate> {
+        let current_status = status_history.back()?;
+        let base_status = status_history
+            .iter()
+            .find(|status| status.best_block_number == base_block_number)?;
+        Some(NetworkStatusUpdate {
+            network: chain.to_string(),
+            status: None,
+            diff_base_block_number: Some(base_block_number),
+            diff: Some(base_status.get_diff(current_status)),
+        })
+    }
+
+    /// The first update of a subscription with its best block number: the diff from the status
+    /// at the client's best block if it's still in the history, the complete status otherwise.
+    /// `None` if the server has no status yet.
+    fn get_first_update(
+        network: &NetworkState,
+        maybe_base_block_number: Option<u64>,
+    ) -> Option<(u64, NetworkStatusUpdate)> {
+        let status_history = network.status_history.read().unwrap();
+        let current_status = status_history.back()?;
+        let update = match maybe_base_block_number.and_then(|base_block_number| {
+            NetworkStatusServer::get_resume_update(
+                &network.chain,
+                &status_history,
+                base_block_number,
+            )
+        }) {
+            Some(update) => {
+                log::info!(
+                    "Resume subscription from best block #{}.",
+                    maybe_base_block_number.unwrap_or_default(),
+                );
+                update
+            }
+            None => NetworkStatusUpdate {
+                network: network.chain.clone(),
+                status: Some(current_status.clone()),
+                diff_base_block_number: None,
+                diff: None,
+            },
+        };
+        Some((current_status.best_block_number, update))
+    }
+
+    fn get_diff_update(chain: &str, status_diff: &NetworkStatusDiff) -> NetworkStatusUpdate {
+        NetworkStatusUpdate {
+            network: chain.to_string(),
+            status: None,
+            diff_base_block_number: None,
+            diff: Some(status_diff.clone()),
+        }
+    }
+
+    /// Receives the new best block numbers of the network from Redis and publishes the diff of
+    /// each new status to the network's subscribers. Returns only on error.
+    async fn run_updater(config: &Config, network: NetworkState) -> anyhow::Error {
+        match NetworkStatusServer::update_network_status(config, &network).await {
+            Ok(()) => anyhow::anyhow!("{} network status updates ended.", network.chain),
+            Err(error) => error,
+        }
+    }
+
+    async fn update_network_status(config: &Config, network: &NetworkState) -> anyhow::Result<()> {
+        let redis_client = redis::Client::open(config.redis.url.as_str()).context(format!(
+            "Cannot connect to Redis at URL {}.",
+            config.redis.url
+        ))?;
+        let mut pubsub_connection = redis_client.get_async_pubsub().await?;
+        pubsub_connection
+            .subscribe(format!(
+                "subvt:{}:network_status:publish:best_block_number",
+                network.chain
+            ))
+            .await?;
+        let mut data_connection = redis_client.get_multiplexed_async_connection().await?;
+        let mut pubsub_stream = pubsub_connection.on_message();
+        while let Some(message) = pubsub_stream.next().await {
+            let best_block_number: u64 = message.get_payload()?;
+            {
+                let status_history = network.status_history.read().unwrap();
+                if status_history
+                    .back()
+                    .map(|status| status.best_block_number == best_block_number)
+                    .unwrap_or(false)
+                {
+                    log::warn!(
+                        "Skip duplicate {} best block #{best_block_number}.",
+                        network.chain
+                    );
+                    continue;
+                }
+            }
+            log::info!("New {} best block #{best_block_number}.", network.chain);
+            metrics::target_best_block_number()
+                .with_label_values(&[&network.chain])
+                .set(best_block_number as i64);
+            let new_status = NetworkStatusServer::read_current_network_status(
+                &network.chain,
+                &mut data_connection,
+            )
+            .await?;
+            let mut status_history = network.status_history.write().unwrap();
+            if let Some(current_status) = status_history.back() {
+                let diff = current_status.get_diff(&new_status);
+                // fails only when there are no subscribers
+                let _ = network
+                    .bus
+                    .send(BusEvent::NewBlock(best_block_number, Arc::new(diff)));
+                metrics::processed_best_block_number()
+                    .with_label_values(&[&network.chain])
+                    .set(best_block_number as i64);
+            }
+            status_history.push_back(new_status);
+            while status_history.len() > CONFIG.rpc.network_status_history_depth + 1 {
+                status_history.pop_front();
+            }
+        }
+        Ok(())
+    }
+
+    /// Each subscription runs as a task that receives the diffs from the broadcast channel. A
+    /// subscriber that falls more than `rpc.bus_capacity` blocks behind, or that cannot receive
+    /// a message within `rpc.subscriber_send_timeout_millis`, is disconnected.
+    async fn run_rpc_server(networks: &Networks<NetworkState>) -> anyhow::Result<ServerHandle> {
+        let sse_handler = NetworkStatusSseHandler {
+            networks: networks.clone(),
+        };
         let rpc_ws_server = ServerBuilder::default()
+            .max_connections(CONFIG.rpc.max_connections)
+            .set_message_buffer_capacity(CONFIG.rpc.subscriber_queue_size)
+            .set_http_middleware(
+                tower::ServiceBuilder::new()
+                    .layer(NetworkLayer::new(CONFIG.get_network_chains()))
+                    .layer(SseLayer::new(
+                        "/sse",
+                        sse_handler.clone(),
+                        Duration::from_secs(CONFIG.rpc.sse_keep_alive_seconds),
+                        metrics::sse_connection_count(),
+                    ))
+                    .layer(BinaryWsLayer::new(
+                        "/binary",
+                        sse_handler,
+                        Duration::from_secs(CONFIG.rpc.sse_keep_alive_seconds),
+                        metrics::binary_ws_connection_count(),
+                    )),
+            )
             .build(format!(
                 "{}:{}",
                 CONFIG.rpc.host, CONFIG.rpc.network_status_port
             ))
             .await?;
         let mut rpc_module = RpcModule::new(());
-        let current_status = current_status.clone();
-        let bus = bus.clone();
+        let networks = networks.clone();
         rpc_module.register_subscription(
             "subscribe_networkStatus",
             "subscribe_networkStatus",
             "unsubscribe_networkStatus",
-            move |_params, pending, _, _| {
-                let current_status = current_status.clone();
-                let bus = bus.clone();
+            move |params, pending, _, extensions| {
+                let maybe_network = networks.get_for_request(&extensions);
                 async move {
-                    let mut sink = pending.accept().await?;
-                    log::info!("New subscription.");
-                    metrics::subscription_count().inc();
-                    let mut bus_receiver = bus.lock().unwrap().add_rx();
-                    {
-                        let current_status = current_status.read().unwrap();
-                        if current_status.best_block_number != 0 {
-                            let update = NetworkStatusUpdate {
-                                network: CONFIG.substrate.chain.clone(),
-                                status: Some(current_status.clone()),
-                                diff_base_block_number: None,
-                                diff: None,
-                            };
-                            let subscription_message =
-                                SubscriptionMessage::from_json(&update).unwrap();
-                            sink.try_send(subscription_message)?;
+                    let network = match maybe_network {
+                        Some(network) => network,
+                        None => {
+                            pending
+                                .reject(ErrorObjectOwned::owned(
+                                    INVALID_PARAMS_CODE,
+                                    "Network is not available.",
+                                    None::<()>,
+                                ))
+                                .await;
+                            return Ok(());
                         }
+                    };
+                    let mut bus_receiver = network.bus.subscribe();
+                    let mut params = params.sequence();
+                    let maybe_base_block_number =
+                        match params
+                            .optional_next::<u64>()
+                            .and_then(|maybe_base_block_number| {
+                                match params.optional_next::<MessageFormat>()? {
+                                    Some(format) if format.is_binary() => {
+                                        Err(ErrorObjectOwned::owned(
+                                            INVALID_PARAMS_CODE,
+                                            "Binary message formats are served at /binary.",
+                                            None::<()>,
+                                        ))
+                                    }
+                                    _ => Ok(maybe_base_block_number),
+                                }
+                            }) {
+                            Ok(maybe_base_block_number) => maybe_base_block_number,
+                            Err(error) => {
+                                log::warn!("Invalid subscription parameters: {}", error.message());
+                                pending.reject(error).await;
+                                return Ok(());
+                            }
+                        };
+                    let sink = pending.accept().await?;
+                    log::info!("New {} subscription.", network.chain);
+                    let mut subscription_metrics = SubscriptionMetrics::new(&network.chain);
+                    let maybe_update =
+                        NetworkStatusServer::get_first_update(&network, maybe_base_block_number);
+                    // the bus is subscribed to before the first update, skip the blocks it includes
+                    let mut first_block_number = 0;
+                    if let Some((best_block_number, update)) = maybe_update {
+                        let subscription_message =
+                            subscription_metrics.get_subscription_message(&update)?;
+                        send(&sink, subscription_message).await?;
+                        first_block_number = best_block_number;
                     }
-                    std::thread::spawn(move || loop {
-                        if let Ok(status_diff) = bus_receiver.recv() {
-                            if sink.is_closed() {
+                    loop {
+                        let event = tokio::select! {
+                            _ = sink.closed() => {
                                 log::info!("Subscription connection closed.");
-                                metrics::subscription_count().dec();
-                                return;
+                                return Ok(());
                             }
-                            match status_diff {
-                                BusEvent::NewBlock(status_diff) => {
-                                    let update = NetworkStatusUpdate {
-                                        network: CONFIG.substrate.chain.clone(),
-                                        status: None,
-                                        diff_base_block_number: None,
-                                        diff: Some(*status_diff.clone()),
-                                    };
-                                    let subscription_message =
-                                        SubscriptionMessage::from_json(&update).unwrap();
-                                    let send_result = sink.try_send(subscription_message);
-                                    match send_result {
+                            event = bus_receiver.recv() => event,
+                        };
+                        match event {
+                            Ok(BusEvent::NewBlock(best_block_number, _))
+                                if best_block_number <= first_block_number => {}
+                            Ok(BusEvent::NewBlock(_, status_diff)) => {
+                                let update = NetworkStatusServer::get_diff_update(
+                                    &network.chain,
+                                    &status_diff,
+                                );
+                                let subscription_message =
+                                    match subscription_metrics.get_subscription_message(&update) {
+                                        Ok(subscription_message) => subscription_message,
                                         Err(error) => {
-                                            log::warn!("Error during publish: {error:?}");
-                                            metrics::subscription_count().dec();
-                                            return;
-                                        }
-                                        Ok(()) => {
-                                            log::debug!("Diff published.");
+                                            log::error!("Error while encoding update: {error:?}");
+                                            continue;
                                         }
-                                    }
-                                }
-                                BusEvent::Error => {
-                                    return;
+                                    };
+                                if let Err(error) = send(&sink, subscription_message).await {
+                                    log::warn!("Error during publish: {error}");
+                                    return Err(error.into());
                                 }
+                                log::debug!("Diff published.");
+                            }
+                            Ok(BusEvent::Error) => return Err("Server error.".into()),
+                            Err(broadcast::error::RecvError::Lagged(skipped_diff_count)) => {
+                                log::warn!(
+                                    "Disconnect slow subscriber, {skipped_diff_count} diffs behind."
+                                );
+                                metrics::slow_subscriber_disconnect_count().inc();
+                                return Err("Subscriber cannot keep up with the updates.".into());
                             }
+                            Err(broadcast::error::RecvError::Closed) => return Ok(()),
                         }
-                    });
-                    Ok(())
+                    }
                 }
             },
         )?;
@@ -127,6 +361,151 @@ impl NetworkStatusServer {
     }
 }
 
+/// Encodes the update in the format of the event stream and records the size of the event.
+fn get_event(
+    best_block_number: u64,
+    update: &NetworkStatusUpdate,
+    format: MessageFormat,
+) -> anyhow::Result<SseEvent> {
+    let event = SseEvent::encode(Some(best_block_number), update, format)?;
+    metrics::sent_byte_count()
+        .with_label_values(&[&format.label()])
+        .inc_by(event.data.len() as u64);
+    Ok(event)
+}
+
+/// Serves the same updates as the JSON-RPC subscriptions as Server-Sent Events at `/sse` and as
+/// binary WebSocket frames at `/binary`. The id of each event is its best block number.
+#[derive(Clone)]
+struct NetworkStatusSseHandler {
+    networks: Networks<NetworkState>,
+}
+
+#[async_trait]
+impl SseHandler for NetworkStatusSseHandler {
+    async fn get_event_stream(
+        &self,
+        request: SseRequest,
+    ) -> Result<BoxStream<'static, SseEvent>, String> {
+        let network = self
+            .networks
+            .get(request.network.as_deref())
+            .ok_or_else(|| "Network is not available.".to_string())?;
+        let bus_receiver = network.bus.subscribe();
+        let format = request.format;
+        let maybe_first_update =
+            NetworkStatusServer::get_first_update(&network, request.last_event_id);
+        // the bus is subscribed to before the first update, skip the blocks it includes
+        let first_block_number = maybe_first_update
+            .as_ref()
+            .map(|(best_block_number, _)| *best_block_number)
+            .unwrap_or_default();
+        let maybe_first_event = maybe_first_update
+            .map(|(best_block_number, update)| get_event(best_block_number, &update, format))
+            .transpose()
+            .map_err(|error| error.to_string())?;
+        log::info!(
+            "New {} event stream subscription in {} format.",
+            network.chain,
+            format.label()
+        );
+        let chain = network.chain;
+        let events = futures_util::stream::unfold(bus_receiver, move |mut bus_receiver| {
+            let chain = chain.clone();
+            async move {
+                loop {
+                    match bus_receiver.recv().await {
+                        Ok(BusEvent::NewBlock(best_block_number, _))
+                            if best_block_number <= first_block_number =>
+                        {
+                            continue
+                        }
+                        Ok(BusEvent::NewBlock(best_block_number, status_diff)) => {
+                            let update = NetworkStatusServer::get_diff_update(&chain, &status_diff);
+                            return match get_event(best_block_number, &update, format) {
+                                Ok(event) => Some((event, bus_receiver)),
+                                Err(error) => {
+                                    log::error!("Error while encoding update: {error:?}");
+                                    None
+                                }
+                            };
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped_diff_count)) => {
+                            log::warn!("End slow event stream subscription, {skipped_diff_count} diffs behind.");
+                            metrics::slow_subscriber_disconnect_count().inc();
+                            return None;
+                        }
+                        Ok(BusEvent::Error) | Err(broadcast::error::RecvError::Closed) => {
+                            return None
+                        }
+                    }
+                }
+            }
+        });
+        Ok(futures_util::stream::iter(maybe_first_event)
+            .chain(events)
+            .boxed())
+    }
+}
+
+/// Keeps the metrics of a subscription, and updates them when the subscription ends, however it
+/// ends.
+struct SubscriptionMetrics {
+    chain: String,
+    sent_byte_count: u64,
+}
+
+impl SubscriptionMetrics {
+    fn new(chain: &str) -> Self {
+        metrics::subscription_count()
+            .with_label_values(&[chain])
+            .inc();
+        Self {
+            chain: chain.to_string(),
+            sent_byte_count: 0,
+        }
+    }
+
+    /// Encodes the update as JSON and records the size of the message.
+    fn get_subscription_message(
+        &mut self,
+        update: &NetworkStatusUpdate,
+    ) -> anyhow::Result<SubscriptionMessage> {
+        let json = serde_json::value::to_raw_value(update)?;
+        let size = json.get().len() as u64;
+        self.sent_byte_count += size;
+        metrics::sent_byte_count()
+            .with_label_values(&[&MessageFormat::default().label()])
+            .inc_by(size);
+        Ok(SubscriptionMessage::from_json(&json)?)
+    }
+}
+
+impl Drop for SubscriptionMetrics {
+    fn drop(&mut self) {
+        metrics::subscription_sent_bytes()
+            .with_label_values(&[&MessageFormat::default().label()])
+            .observe(self.sent_byte_count as f64);
+        metrics::subscription_count()
+            .with_label_values(&[&self.chain])
+            .dec();
+    }
+}
+
+/// Sends the message, waiting for room in the subscriber's queue for at most the configured
+/// timeout. A subscriber that cannot keep up is disconnected.
+async fn send(sink: &SubscriptionSink, message: SubscriptionMessage) -> Result<(), String> {
+    let timeout = Duration::from_millis(CONFIG.rpc.subscriber_send_timeout_millis);
+    match sink.send_timeout(message, timeout).await {
+        Ok(()) => Ok(()),
+        Err(_) if sink.is_closed() => Err("Subscription connection closed.".to_string()),
+        Err(_) => {
+            metrics::slow_subscriber_disconnect_count().inc();
+            Err("Subscriber cannot keep up with the updates.".to_string())
+        }
+    }
+}
+
 /// Service implementation.
 #[async_trait(? Send)]
 impl Service for NetworkStatusServer {
@@ -138,72 +517,33 @@ impl Service for NetworkStatusServer {
     }
 
     async fn run(&'static self) -> anyhow::Result<()> {
-        let bus = Arc::new(Mutex::new(Bus::new(100)));
-        let current_status = Arc::new(RwLock::new(NetworkStatus::default()));
-        let redis_client = redis::Client::open(CONFIG.redis.url.as_str()).context(format!(
-            "Cannot connect to Redis at URL {}.",
-            CONFIG.redis.url
-        ))?;
-
-        let mut pubsub_connection = redis_client.get_async_pubsub().await?;
-        pubsub_connection
-            .subscribe(format!(
-                "subvt:{}:network_status:publish:best_block_number",
-                CONFIG.substrate.chain
-            ))
-            .await?;
-        let mut data_connection = redis_client.get_multiplexed_async_connection().await?;
-        metrics::subscription_count().set(0);
-        let server_stop_handle = NetworkStatusServer::run_rpc_server(&current_status, &bus).await?;
-
-        let mut pubsub_stream = pubsub_connection.on_message();
-        let error: anyhow::Error = loop {
-            let maybe_message = pubsub_stream.next().await;
-            let payload = if let Some(message) = maybe_message {
-                message.get_payload()
-            } else {
-                continue;
-            };
-            if let Err(error) = payload {
-                break error.into();
+        let network_configs = CONFIG.get_network_configs()?;
+        let networks = Networks::new(&CONFIG.get_network_chains());
+        let server_stop_handle = NetworkStatusServer::run_rpc_server(&networks).await?;
+        health::set_ready(true);
+        // each network is served and updated independently of the others
+        let network_runs = network_configs.iter().map(|config| {
+            let networks = networks.clone();
+            async move {
+                let network = NetworkState::new(&config.substrate.chain);
+                metrics::subscription_count()
+                    .with_label_values(&[&network.chain])
+                    .set(0);
+                networks.insert(&network.chain, network.clone());
+                log::info!("Serving the {} network status.", network.chain);
+                let network = &network;
+                keep_running(&CONFIG, &network.chain, move || async move {
+                    let error = NetworkStatusServer::run_updater(config, network.clone()).await;
+                    let _ = network.bus.send(BusEvent::Error);
+                    error
+                })
+                .await;
             }
-            let best_block_number: u64 = payload.unwrap();
-            {
-                let current_status = current_status.read().unwrap();
-                if current_status.best_block_number == best_block_number {
-                    log::warn!("Skip duplicate best block #{best_block_number}.");
-                    continue;
-                }
-            }
-            log::info!("New best block #{best_block_number}.");
-            metrics::target_best_block_number().set(best_block_number as i64);
-            match NetworkStatusServer::read_current_network_status(&mut data_connection).await {
-                Ok(new_status) => {
-                    {
-                        let current_status = current_status.read().unwrap();
-                        if current_status.best_block_number != 0 {
-                            let diff = current_status.get_diff(&new_status);
-                            let mut bus = bus.lock().unwrap();
-                            bus.broadcast(BusEvent::NewBlock(Box::new(diff)));
-                            metrics::processed_best_block_number().set(best_block_number as i64);
-                        }
-                    }
-                    let mut current_status = current_status.write().unwrap();
-                    *current_status = new_status;
-                }
-                Err(error) => {
-                    break error;
-                }
-            }
-        };
-        log::error!("{:?}", error);
-        {
-            let mut bus = bus.lock().unwrap();
-            bus.broadcast(BusEvent::Error);
-        }
+        });
+        futures_util::future::join_all(network_runs).await;
         log::info!("Stop RPC server.");
         server_stop_handle.clone().stop()?;
         log::info!("RPC server stopped fully.");
-        Err(error)
+        Ok(())
     }
 }
@@ -1,38 +1,104 @@
 use once_cell::sync::Lazy;
-use subvt_metrics::registry::IntGauge;
+use subvt_metrics::registry::{HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec};
 
 const METRIC_PREFIX: &str = "subvt_network_status_server";
 
-pub fn target_best_block_number() -> IntGauge {
-    static METER: Lazy<IntGauge> = Lazy::new(|| {
-        subvt_metrics::registry::register_int_gauge(
+pub fn target_best_block_number() -> IntGaugeVec {
+    static METER: Lazy<IntGaugeVec> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_gauge_vec(
             METRIC_PREFIX,
             "target_best_block_number",
-            "Number of the target finalized block on the node",
+            "Number of the target best block by network",
+            &["network"],
         )
         .unwrap()
     });
     METER.clone()
 }
 
-pub fn processed_best_block_number() -> IntGauge {
+pub fn processed_best_block_number() -> IntGaugeVec {
+    static METER: Lazy<IntGaugeVec> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_gauge_vec(
+            METRIC_PREFIX,
+            "processed_best_block_number",
+            "Number of the last processed best block by network",
+            &["network"],
+        )
+        .unwrap()
+    });
+    METER.clone()
+}
+
+pub fn subscription_count() -> IntGaugeVec {
+    static METER: Lazy<IntGaugeVec> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_gauge_vec(
+            METRIC_PREFIX,
+            "subscription_count",
+            "Number of subscribers to the service by network",
+            &["network"],
+        )
+        .unwrap()
+    });
+    METER.clone()
+}
+
+pub fn slow_subscriber_disconnect_count() -> IntCounter {
+    static METER: Lazy<IntCounter> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_counter(
+            METRIC_PREFIX,
+            "slow_subscriber_disconnect_count",
+            "Number of subscribers disconnected for not keeping up with the updates",
+        )
+        .unwrap()
+    });
+    METER.clone()
+}
+
+pub fn sent_byte_count() -> IntCounterVec {
+    static METER: Lazy<IntCounterVec> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_counter_vec(
+            METRIC_PREFIX,
+            "sent_byte_count",
+            "Number of bytes sent to the subscribers by message format",
+            &["format"],
+        )
+        .unwrap()
+    });
+    METER.clone()
+}
+
+pub fn subscription_sent_bytes() -> HistogramVec {
+    static METER: Lazy<HistogramVec> = Lazy::new(|| {
+        subvt_metrics::registry::register_histogram_vec(
+            METRIC_PREFIX,
+            "subscription_sent_bytes",
+            "Number of bytes sent to a subscriber during its subscription by message format",
+            &["format"],
+            vec![1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9],
+        )
+        .unwrap()
+    });
+    METER.clone()
+}
+
+pub fn sse_connection_count() -> IntGauge {
     static METER: Lazy<IntGauge> = Lazy::new(|| {
         subvt_metrics::registry::register_int_gauge(
             METRIC_PREFIX,
-            "processed_best_block_number",
-            "Number of the last processed block",
+            "sse_connection_count",
+            "Number of open Server-Sent Events streams",
         )
         .unwrap()
     });
     METER.clone()
 }
 
-pub fn subscription_count() -> IntGauge {
+pub fn binary_ws_connection_count() -> IntGauge {
     static METER: Lazy<IntGauge> = Lazy::new(|| {
         subvt_metrics::registry::register_int_gauge(
             METRIC_PREFIX,
-            "subscription_count",
-            "Number of subscribers to the service",
+            "binary_ws_connection_count",
+            "Number of open binary WebSocket streams",
         )
         .unwrap()
     });
@@ -1,4 +1,5 @@
-//! Updates the Redis database after every block with network status data.
+//! Updates the Redis database after every block with network status data, and keeps the
+//! history of the network status per session in the PostgreSQL network database.
 //! Subscribes to the new blocks using the Substrate client in `subvt-substrate-client`.
 #![warn(clippy::disallowed_types)]
 use anyhow::Context;
@@ -9,8 +10,11 @@ use once_cell::sync::OnceCell;
 use redis::Pipeline;
 use std::sync::{Arc, Mutex};
 use subvt_config::Config;
+use subvt_metrics::health;
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
 use subvt_service_common::Service;
 use subvt_substrate_client::SubstrateClient;
+use subvt_types::report::NetworkStatusSnapshot;
 use subvt_types::substrate::metadata::get_metadata_era_duration_millis;
 use subvt_types::{substrate::BlockHeader, subvt::NetworkStatus};
 
@@ -56,6 +60,7 @@ impl NetworkStatusUpdater {
 
     async fn fetch_and_update_network_status(
         &self,
+        postgres: &PostgreSQLNetworkStorage,
         relay_client: &SubstrateClient,
         asset_hub_client: &SubstrateClient,
         relay_best_block_header: &BlockHeader,
@@ -245,6 +250,18 @@ impl NetworkStatusUpdater {
         // write to redis
         NetworkStatusUpdater::update_redis(&network_status).await?;
         log::debug!("Redis updated.");
+        // save the session snapshot
+        postgres
+            .save_network_status_snapshot(&NetworkStatusSnapshot::from_network_status(
+                &network_status,
+                Utc::now().timestamp_millis() as u64,
+            ))
+            .await
+            .context("Error while saving network status snapshot.")?;
+        log::debug!(
+            "Network status snapshot saved for epoch {}.",
+            network_status.current_epoch.index
+        );
         Ok(network_status)
     }
 }
@@ -252,6 +269,7 @@ impl NetworkStatusUpdater {
 impl NetworkStatusUpdater {
     async fn on_new_relay_block(
         &self,
+        postgres: Arc<PostgreSQLNetworkStorage>,
         relay_substrate_client: Arc<SubstrateClient>,
         asset_hub_substrate_client: Arc<SubstrateClient>,
         relay_best_block_header: BlockHeader,
@@ -263,6 +281,7 @@ impl NetworkStatusUpdater {
         let start = std::time::Instant::now();
         let update_result = self
             .fetch_and_update_network_status(
+                &postgres,
                 &relay_substrate_client,
                 &asset_hub_substrate_client,
                 &relay_best_block_header,
@@ -303,6 +322,9 @@ impl Service for NetworkStatusUpdater {
     }
 
     async fn run(&'static self) -> anyhow::Result<()> {
+        let postgres = Arc::new(
+            PostgreSQLNetworkStorage::new(&CONFIG, CONFIG.get_network_postgres_url()).await?,
+        );
         loop {
             let relay_substrate_client = Arc::new(
                 SubstrateClient::new(
@@ -323,6 +345,7 @@ impl Service for NetworkStatusUpdater {
                 .await?,
             );
             let error_cell: Arc<OnceCell<anyhow::Error>> = Arc::new(OnceCell::new());
+            health::set_ready(true);
             relay_substrate_client
                 .subscribe_to_new_blocks(
                     CONFIG.substrate.request_timeout_seconds,
@@ -331,11 +354,13 @@ impl Service for NetworkStatusUpdater {
                         if let Some(error) = error_cell.get() {
                             return Err(anyhow::anyhow!("{:?}", error));
                         }
+                        let postgres = postgres.clone();
                         let relay_substrate_client = relay_substrate_client.clone();
                         let asset_hub_substrate_client = asset_hub_substrate_client.clone();
                         tokio::spawn(async move {
                             if let Err(error) = self
                                 .on_new_relay_block(
+                                    postgres,
                                     relay_substrate_client,
                                     asset_hub_substrate_client,
                                     best_block_header,
@@ -0,0 +1,65 @@
+use crate::{NotificationGenerator, CONFIG};
+use std::sync::Arc;
+use subvt_persistence::postgres::app::PostgreSQLAppStorage;
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::app::{notification::NotificationTypeCode, Block};
+use subvt_types::substrate::Balance;
+
+impl NotificationGenerator {
+    /// Checks `Balances` events for validator stash and controller accounts, i.e. funds moving in
+    /// and out of a validator's accounts. The rules are those of the validator stash, which the
+    /// controller accounts are resolved to.
+    pub(crate) async fn inspect_stash_balance_changes(
+        &self,
+        network_postgres: Arc<PostgreSQLNetworkStorage>,
+        app_postgres: Arc<PostgreSQLAppStorage>,
+        block: &Block,
+    ) -> anyhow::Result<()> {
+        log::debug!("Inspect block #{} for balance changes.", block.number);
+        for event in network_postgres
+            .get_balances_events_in_block(&block.hash)
+            .await?
+        {
+            let mut stash_account_ids = Vec::new();
+            for account_id in
+                std::iter::once(&event.account_id).chain(event.counterparty_account_id.as_ref())
+            {
+                for stash_account_id in network_postgres
+                    .get_validator_stash_account_ids(account_id)
+                    .await?
+                {
+                    // e.g. a transfer between the controller and the stash of the same validator
+                    if !stash_account_ids.contains(&stash_account_id) {
+                        stash_account_ids.push(stash_account_id);
+                    }
+                }
+            }
+            for stash_account_id in stash_account_ids {
+                let rules = app_postgres
+                    .get_notification_rules_for_validator(
+                        &NotificationTypeCode::ChainValidatorStashBalanceChanged.to_string(),
+                        CONFIG.substrate.network_id,
+                        &stash_account_id,
+                    )
+                    .await?;
+                for rule in rules {
+                    if let Some(min_param) = rule.parameters.first() {
+                        if let Ok(min_amount) = min_param.value.parse::<Balance>() {
+                            if event.amount < min_amount {
+                                continue;
+                            }
+                        }
+                    }
+                    self.generate_notifications(
+                        app_postgres.clone(),
+                        &[rule],
+                        &Some(stash_account_id),
+                        Some(&event.clone()),
+                    )
+                    .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{NotificationGenerator, CONFIG};
+use std::sync::Arc;
+use subvt_persistence::postgres::app::PostgreSQLAppStorage;
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::app::notification::NotificationTypeCode;
+
+impl NotificationGenerator {
+    /// Checks the parachain disputes initiated or concluded in the relay chain blocks processed
+    /// since the last inspection, and notifies the validators that have a statement in them. The
+    /// initiation is notified to each validator once, as its statement gets included, until the
+    /// dispute concludes.
+    pub(crate) async fn inspect_para_disputes(
+        &self,
+        network_postgres: Arc<PostgreSQLNetworkStorage>,
+        app_postgres: Arc<PostgreSQLAppStorage>,
+        is_conclusion: bool,
+    ) -> anyhow::Result<()> {
+        let notification_type_code = if is_conclusion {
+            NotificationTypeCode::ChainValidatorDisputeConcluded
+        } else {
+            NotificationTypeCode::ChainValidatorDisputeInitiated
+        };
+        for dispute in network_postgres
+            .get_para_disputes_pending_notification(is_conclusion)
+            .await?
+        {
+            log::info!(
+                "Inspect dispute {} for {}.",
+                dispute.candidate_hash,
+                notification_type_code,
+            );
+            let statements = network_postgres
+                .get_para_dispute_statements(&dispute, !is_conclusion)
+                .await?;
+            for statement in &statements {
+                let rules = app_postgres
+                    .get_notification_rules_for_validator(
+                        &notification_type_code.to_string(),
+                        CONFIG.substrate.network_id,
+                        &statement.validator_account_id,
+                    )
+                    .await?;
+                for rule in rules {
+                    if is_conclusion {
+                        let only_against_supermajority = rule
+                            .parameters
+                            .first()
+                            .and_then(|param| param.value.parse::<bool>().ok())
+                            .unwrap_or(false);
+                        if only_against_supermajority
+                            && statement.is_against_supermajority() != Some(true)
+                        {
+                            continue;
+                        }
+                    }
+                    self.generate_notifications(
+                        app_postgres.clone(),
+                        &[rule],
+                        &Some(statement.validator_account_id),
+                        Some(statement),
+                    )
+                    .await?;
+                }
+            }
+            if !is_conclusion {
+                network_postgres
+                    .set_para_dispute_statements_initiation_notified(&statements)
+                    .await?;
+            }
+            // new statements of an ongoing dispute get notified in the next inspections
+            if is_conclusion || dispute.concluded_block.is_some() {
+                network_postgres
+                    .set_para_dispute_notified(dispute.id, is_conclusion)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
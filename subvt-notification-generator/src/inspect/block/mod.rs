@@ -5,13 +5,18 @@ use crate::{metrics, NotificationGenerator, CONFIG};
 use async_lock::Mutex;
 use once_cell::sync::OnceCell;
 use std::sync::Arc;
+use subvt_logging::trace::{self, FinalizedAt, FutureExt, KeyValue};
 use subvt_persistence::postgres::app::PostgreSQLAppStorage;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::app::Block;
 use subvt_types::rdb::BlockProcessedNotification;
 
 mod authorship;
+mod balances;
 mod chilling;
+mod dispute;
 mod payout;
+mod proxy;
 mod referenda;
 mod validate;
 
@@ -33,25 +38,65 @@ impl NotificationGenerator {
                 return Ok(());
             }
         };
-        self.inspect_block_authorship(app_postgres.clone(), &block)
+        // the skipped blocks weren't observed finalized, their timestamp is the closest time to
+        // their finalization
+        let context = match (trace::get_finalized_at(), block.timestamp) {
+            (None, Some(timestamp)) => trace::Context::current().with_value(FinalizedAt(timestamp)),
+            _ => trace::Context::current(),
+        };
+        self.inspect_block_contents(network_postgres.clone(), app_postgres, &block)
+            .with_context(context)
             .await?;
-        self.inspect_chillings(network_postgres.clone(), app_postgres.clone(), &block)
+        network_postgres
+            .save_notification_generator_state(&block.hash, block_number)
             .await?;
-        self.inspect_validate_extrinsics(network_postgres.clone(), app_postgres.clone(), &block)
+        log::info!("Completed the inspection of block #{block_number}.");
+        Ok(())
+    }
+
+    /// Inspects a processed Asset Hub block once, outside the block processor notifications. The
+    /// replay tests run the generator through this.
+    pub async fn inspect_processed_block(
+        &self,
+        network_postgres: Arc<PostgreSQLNetworkStorage>,
+        app_postgres: Arc<PostgreSQLAppStorage>,
+        block_number: u64,
+    ) -> anyhow::Result<()> {
+        self.inspect_block(network_postgres, app_postgres, block_number)
+            .await
+    }
+
+    async fn inspect_block_contents(
+        &self,
+        network_postgres: Arc<PostgreSQLNetworkStorage>,
+        app_postgres: Arc<PostgreSQLAppStorage>,
+        block: &Block,
+    ) -> anyhow::Result<()> {
+        self.inspect_block_authorship(app_postgres.clone(), block)
+            .await?;
+        self.inspect_chillings(network_postgres.clone(), app_postgres.clone(), block)
+            .await?;
+        self.inspect_stash_balance_changes(network_postgres.clone(), app_postgres.clone(), block)
+            .await?;
+        self.inspect_validate_extrinsics(network_postgres.clone(), app_postgres.clone(), block)
             .await?;
         self.inspect_payout_stakers_extrinsics(
             network_postgres.clone(),
             app_postgres.clone(),
-            &block,
+            block,
         )
         .await?;
-        self.inspect_referenda_events(network_postgres.clone(), app_postgres.clone(), &block)
+        self.inspect_referenda_events(network_postgres.clone(), app_postgres.clone(), block)
             .await?;
-
-        network_postgres
-            .save_notification_generator_state(&block.hash, block_number)
+        self.inspect_proxy_events(network_postgres.clone(), app_postgres.clone(), block)
+            .await?;
+        self.inspect_new_multisig_events(network_postgres.clone(), app_postgres.clone(), block)
+            .await?;
+        // disputes happen on the relay chain, so they're inspected independently of the block
+        self.inspect_para_disputes(network_postgres.clone(), app_postgres.clone(), false)
+            .await?;
+        self.inspect_para_disputes(network_postgres, app_postgres, true)
             .await?;
-        log::info!("Completed the inspection of block #{block_number}.");
         Ok(())
     }
 
@@ -71,8 +116,28 @@ impl NotificationGenerator {
                 new_block_number
             };
         for block_number in start_block_number..=new_block_number {
+            // the trace of the notified block continues from the block processor, the skipped
+            // blocks get their own traces
+            let context = if block_number == new_block_number {
+                let context = trace::start_span(
+                    "inspect_block",
+                    postgres_notification.trace_parent.as_deref(),
+                    vec![KeyValue::new("block_number", block_number as i64)],
+                );
+                match postgres_notification.finalized_at {
+                    Some(finalized_at) => context.with_value(FinalizedAt(finalized_at)),
+                    None => context,
+                }
+            } else {
+                trace::start_span(
+                    "inspect_block",
+                    None,
+                    vec![KeyValue::new("block_number", block_number as i64)],
+                )
+            };
             match self
                 .inspect_block(network_postgres.clone(), app_postgres.clone(), block_number)
+                .with_context(context)
                 .await
             {
                 Ok(()) => {
@@ -0,0 +1,123 @@
+use crate::{NotificationGenerator, CONFIG};
+use serde::Serialize;
+use std::str::FromStr;
+use std::sync::Arc;
+use subvt_persistence::postgres::app::PostgreSQLAppStorage;
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::app::notification::{NotificationTypeCode, UserNotificationRule};
+use subvt_types::app::Block;
+use subvt_types::crypto::AccountId;
+
+/// Checks whether the account is in the comma or whitespace-separated whitelist of
+/// the rule. Unparseable whitelist entries are ignored.
+fn is_whitelisted(rule: &UserNotificationRule, account_id: &AccountId) -> bool {
+    let Some(param) = rule.parameters.first() else {
+        return false;
+    };
+    param
+        .value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|address| !address.is_empty())
+        .filter_map(|address| AccountId::from_str(address).ok())
+        .any(|whitelisted_account_id| whitelisted_account_id == *account_id)
+}
+
+impl NotificationGenerator {
+    /// Generates a notification for each rule of the validators (stash or controller) affected
+    /// by the event, unless the delegate/signatory account is whitelisted in the rule.
+    async fn generate_proxy_multisig_notifications<T: Clone + Serialize>(
+        &self,
+        network_postgres: Arc<PostgreSQLNetworkStorage>,
+        app_postgres: Arc<PostgreSQLAppStorage>,
+        notification_type_code: NotificationTypeCode,
+        account_id: &AccountId,
+        delegate_account_id: &AccountId,
+        event: &T,
+    ) -> anyhow::Result<()> {
+        for stash_account_id in network_postgres
+            .get_validator_stash_account_ids(account_id)
+            .await?
+        {
+            let rules = app_postgres
+                .get_notification_rules_for_validator(
+                    &notification_type_code.to_string(),
+                    CONFIG.substrate.network_id,
+                    &stash_account_id,
+                )
+                .await?;
+            for rule in rules {
+                if is_whitelisted(&rule, delegate_account_id) {
+                    continue;
+                }
+                self.generate_notifications(
+                    app_postgres.clone(),
+                    &[rule],
+                    &Some(stash_account_id),
+                    Some(event),
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks `Proxy` events for proxies added to or removed from validator stash and
+    /// controller accounts, pure proxies spawned by them and announced proxy calls.
+    pub(crate) async fn inspect_proxy_events(
+        &self,
+        network_postgres: Arc<PostgreSQLNetworkStorage>,
+        app_postgres: Arc<PostgreSQLAppStorage>,
+        block: &Block,
+    ) -> anyhow::Result<()> {
+        log::debug!("Inspect block #{} for proxy events.", block.number);
+        for event in network_postgres
+            .get_proxy_events_in_block(&block.hash)
+            .await?
+        {
+            let notification_type_code = match event.event_name.as_str() {
+                "ProxyAdded" => NotificationTypeCode::ChainValidatorProxyAdded,
+                "ProxyRemoved" => NotificationTypeCode::ChainValidatorProxyRemoved,
+                "PureCreated" => NotificationTypeCode::ChainValidatorPureProxyCreated,
+                "Announced" => NotificationTypeCode::ChainValidatorProxyAnnounced,
+                _ => continue,
+            };
+            self.generate_proxy_multisig_notifications(
+                network_postgres.clone(),
+                app_postgres.clone(),
+                notification_type_code,
+                &event.account_id,
+                &event.delegate_account_id,
+                &event,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Checks `Multisig.NewMultisig` events for multisig operations initiated on behalf of
+    /// a validator stash or controller account. The block processor persists these events only
+    /// for the operations with a `Staking` call, or a `Proxy` call that wraps a `Staking` call.
+    pub(crate) async fn inspect_new_multisig_events(
+        &self,
+        network_postgres: Arc<PostgreSQLNetworkStorage>,
+        app_postgres: Arc<PostgreSQLAppStorage>,
+        block: &Block,
+    ) -> anyhow::Result<()> {
+        log::debug!("Inspect block #{} for new multisig events.", block.number);
+        for event in network_postgres
+            .get_new_multisig_events_in_block(&block.hash)
+            .await?
+        {
+            self.generate_proxy_multisig_notifications(
+                network_postgres.clone(),
+                app_postgres.clone(),
+                NotificationTypeCode::ChainValidatorNewMultisig,
+                &event.multisig_account_id,
+                &event.approving_account_id,
+                &event,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
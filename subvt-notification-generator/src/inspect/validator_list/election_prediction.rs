@@ -0,0 +1,144 @@
+use crate::{NotificationGenerator, CONFIG};
+use redis::aio::MultiplexedConnection as RedisConnection;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use std::sync::Arc;
+use subvt_persistence::postgres::app::PostgreSQLAppStorage;
+use subvt_types::app::notification::NotificationTypeCode;
+use subvt_types::crypto::AccountId;
+use subvt_types::election::{ElectionPrediction, ValidatorElectionPredictionChange};
+
+/// Predicted winner set as of the last notified changes, and the changes that are yet to be
+/// confirmed by the consecutive predictions.
+#[derive(Default)]
+pub(crate) struct ElectionPredictionState {
+    last_block_number: Option<u64>,
+    /// `None` until the first prediction after start.
+    winner_account_ids: Option<HashSet<AccountId>>,
+    /// Number of consecutive predictions in which the election status of a validator differs
+    /// from its status in `winner_account_ids`.
+    pending_change_counts: HashMap<AccountId, u32>,
+}
+
+impl ElectionPredictionState {
+    /// Applies the prediction and returns the validators whose change of election status has
+    /// persisted in `confirmation_count` consecutive predictions, and is to be notified.
+    fn apply(
+        &mut self,
+        prediction: &ElectionPrediction,
+        confirmation_count: u32,
+    ) -> Vec<AccountId> {
+        if self.last_block_number == Some(prediction.block_number) {
+            return vec![];
+        }
+        self.last_block_number = Some(prediction.block_number);
+        let predicted_winner_account_ids: HashSet<AccountId> = prediction
+            .winners
+            .iter()
+            .map(|winner| winner.account_id)
+            .collect();
+        let winner_account_ids = match self.winner_account_ids.as_mut() {
+            Some(winner_account_ids) => winner_account_ids,
+            None => {
+                self.winner_account_ids = Some(predicted_winner_account_ids);
+                return vec![];
+            }
+        };
+        let changed_account_ids: HashSet<AccountId> = winner_account_ids
+            .symmetric_difference(&predicted_winner_account_ids)
+            .cloned()
+            .collect();
+        // a change that didn't persist starts over
+        self.pending_change_counts
+            .retain(|account_id, _| changed_account_ids.contains(account_id));
+        let mut confirmed_account_ids = vec![];
+        for account_id in changed_account_ids {
+            let count = self.pending_change_counts.entry(account_id).or_default();
+            *count += 1;
+            if *count < confirmation_count {
+                continue;
+            }
+            self.pending_change_counts.remove(&account_id);
+            if predicted_winner_account_ids.contains(&account_id) {
+                winner_account_ids.insert(account_id);
+            } else {
+                winner_account_ids.remove(&account_id);
+            }
+            confirmed_account_ids.push(account_id);
+        }
+        confirmed_account_ids
+    }
+}
+
+impl NotificationGenerator {
+    /// Compares the latest next election prediction by `subvt-validator-list-updater` with the
+    /// previous ones, and notifies the validators that are predicted to enter or leave the
+    /// active set, once the change persists in the configured number of consecutive
+    /// predictions. The first prediction after start only initializes the state.
+    pub(crate) async fn inspect_election_prediction(
+        &self,
+        app_postgres: Arc<PostgreSQLAppStorage>,
+        redis_connection: &mut RedisConnection,
+        election_prediction_state: &mut ElectionPredictionState,
+    ) -> anyhow::Result<()> {
+        let maybe_prediction_json: Option<String> = redis::cmd("GET")
+            .arg(format!(
+                "subvt:{}:election_prediction",
+                CONFIG.substrate.chain
+            ))
+            .query_async(redis_connection)
+            .await?;
+        let prediction: ElectionPrediction = match maybe_prediction_json {
+            Some(prediction_json) => serde_json::from_str(&prediction_json)?,
+            None => return Ok(()),
+        };
+        let changed_account_ids = election_prediction_state.apply(
+            &prediction,
+            CONFIG.election.prediction_change_confirmation_count,
+        );
+        if changed_account_ids.is_empty() {
+            return Ok(());
+        }
+        log::debug!(
+            "Inspect election prediction at block #{}.",
+            prediction.block_number
+        );
+        for account_id in &changed_account_ids {
+            let rules = app_postgres
+                .get_notification_rules_for_validator(
+                    &NotificationTypeCode::ChainValidatorPredictedElection.to_string(),
+                    CONFIG.substrate.network_id,
+                    account_id,
+                )
+                .await?;
+            if rules.is_empty() {
+                continue;
+            }
+            let change = ValidatorElectionPredictionChange {
+                validator_account_id: *account_id,
+                is_predicted_to_be_elected: prediction.get_winner(account_id).is_some(),
+                winner: prediction.get_winner(account_id).cloned(),
+                active_era_index: prediction.active_era_index,
+                block_number: prediction.block_number,
+                desired_target_count: prediction.desired_target_count,
+                minimum_backing: prediction.minimum_backing,
+            };
+            log::info!(
+                "Validator {} is {} predicted to be elected.",
+                account_id.to_ss58_check(),
+                if change.is_predicted_to_be_elected {
+                    "now"
+                } else {
+                    "no longer"
+                },
+            );
+            self.generate_notifications(
+                app_postgres.clone(),
+                &rules,
+                &Some(*account_id),
+                Some(&change),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
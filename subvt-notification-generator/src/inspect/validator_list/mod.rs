@@ -4,18 +4,22 @@
 
 use crate::{metrics, NotificationGenerator, CONFIG};
 use anyhow::Context;
+use election_prediction::ElectionPredictionState;
 use futures_util::StreamExt as _;
 use redis::aio::MultiplexedConnection as RedisConnection;
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use std::sync::atomic::AtomicU32;
 use std::sync::Arc;
+use subvt_metrics::health;
 use subvt_persistence::postgres::app::PostgreSQLAppStorage;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
 use subvt_types::subvt::ValidatorDetails;
 
 mod add;
+mod election_prediction;
 mod init;
 mod remove;
+mod session_performance;
 mod unclaimed_payout;
 mod update;
 
@@ -29,6 +33,7 @@ impl NotificationGenerator {
         validator_map: &mut HashMap<String, ValidatorDetails>,
         finalized_block_number: u64,
         last_active_era_index: &AtomicU32,
+        election_prediction_state: &mut ElectionPredictionState,
     ) -> anyhow::Result<()> {
         log::info!(
             "Process new update from validator list updater. Block #{finalized_block_number}."
@@ -111,6 +116,20 @@ impl NotificationGenerator {
                 validator_map.insert(validator_id.clone(), updated);
             }
         }
+        // low para validation performance and missed blocks
+        self.inspect_session_performance(
+            network_postgres.clone(),
+            app_postgres.clone(),
+            validator_map,
+        )
+        .await?;
+        // predicted election status changes
+        self.inspect_election_prediction(
+            app_postgres.clone(),
+            redis_connection,
+            election_prediction_state,
+        )
+        .await?;
         // unclaimed payouts
         self.inspect_unclaimed_payouts(
             network_postgres,
@@ -150,7 +169,10 @@ impl NotificationGenerator {
             // keep track of validators
             let mut validator_map: HashMap<String, ValidatorDetails> = HashMap::default();
             let last_active_era_index = AtomicU32::new(0);
+            // keep track of the next election prediction
+            let mut election_prediction_state = ElectionPredictionState::default();
             let mut pubsub_stream = redis_pubsub_connection.on_message();
+            health::set_ready(true);
             let error: anyhow::Error = loop {
                 let maybe_message = pubsub_stream.next().await;
                 let payload = if let Some(message) = maybe_message {
@@ -175,6 +197,7 @@ impl NotificationGenerator {
                         &mut validator_map,
                         finalized_block_number,
                         &last_active_era_index,
+                        &mut election_prediction_state,
                     )
                     .await
                 {
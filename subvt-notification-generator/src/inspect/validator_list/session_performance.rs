@@ -0,0 +1,224 @@
+use crate::{NotificationGenerator, CONFIG};
+use rustc_hash::FxHashMap as HashMap;
+use std::sync::Arc;
+use subvt_persistence::postgres::app::PostgreSQLAppStorage;
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::app::notification::{NotificationTypeCode, UserNotificationRule};
+use subvt_types::crypto::AccountId;
+use subvt_types::performance::{
+    BlockProduction, LowParaPerformance, MissedBlocks, SessionValidatorPerformance,
+};
+use subvt_types::subvt::ValidatorDetails;
+
+const MISSED_ATTESTATION_PERCENT_PARAM_CODE: &str = "missed_attestation_percent";
+const MISSED_BLOCK_PERCENT_PARAM_CODE: &str = "missed_block_percent";
+const SESSION_COUNT_PARAM_CODE: &str = "session_count";
+
+fn get_rule_param<T: std::str::FromStr>(rule: &UserNotificationRule, code: &str) -> Option<T> {
+    rule.parameters
+        .iter()
+        .find(|param| param.parameter_type_code == code)
+        .and_then(|param| param.value.parse::<T>().ok())
+}
+
+/// Gets the performances of the validator in the rule's number of consecutive sessions ending
+/// with the given session, `None` if the validator wasn't active in all of them.
+async fn get_rule_session_performances(
+    network_postgres: &PostgreSQLNetworkStorage,
+    rule: &UserNotificationRule,
+    validator_account_id: &AccountId,
+    session_index: u64,
+) -> anyhow::Result<Option<Vec<SessionValidatorPerformance>>> {
+    let session_count = get_rule_param::<u64>(rule, SESSION_COUNT_PARAM_CODE).unwrap_or(1);
+    if session_count == 0 || session_count > session_index {
+        return Ok(None);
+    }
+    let performances = network_postgres
+        .get_validator_session_performances(
+            validator_account_id,
+            session_index + 1 - session_count,
+            session_index,
+        )
+        .await?;
+    Ok(if performances.len() as u64 == session_count {
+        Some(performances)
+    } else {
+        None
+    })
+}
+
+/// Whether the missed attestation ratio is at or above the threshold in all the sessions.
+fn is_low_para_performance(
+    performances: &[SessionValidatorPerformance],
+    threshold_percent: f64,
+) -> bool {
+    performances.iter().all(|performance| {
+        performance
+            .get_missed_attestation_ratio()
+            .map(|ratio| ratio * 100.0 >= threshold_percent)
+            .unwrap_or(false)
+    })
+}
+
+/// Whether the block production aggregated over the sessions missed at least the threshold of
+/// the expected blocks, and it's unlikely to be due to chance.
+fn is_missing_blocks(
+    performances: &[SessionValidatorPerformance],
+    threshold_percent: f64,
+    max_probability: f64,
+) -> bool {
+    let Some(block_production) = BlockProduction::from_performances(performances) else {
+        return false;
+    };
+    block_production
+        .get_missed_block_ratio()
+        .map(|ratio| ratio * 100.0 >= threshold_percent)
+        .unwrap_or(false)
+        && block_production.get_probability() <= max_probability
+}
+
+impl NotificationGenerator {
+    /// Checks the session performances once the session validator performance updater has
+    /// processed a new session, and notifies the validators whose missed attestation ratio has
+    /// been over the rule's threshold in each of the rule's number of consecutive sessions, or
+    /// whose missed block ratio over these sessions is over the threshold.
+    pub(crate) async fn inspect_session_performance(
+        &self,
+        network_postgres: Arc<PostgreSQLNetworkStorage>,
+        app_postgres: Arc<PostgreSQLAppStorage>,
+        validator_map: &HashMap<String, ValidatorDetails>,
+    ) -> anyhow::Result<()> {
+        let session_index = match network_postgres
+            .get_session_validator_performance_updater_last_processed_session_id()
+            .await?
+        {
+            Some(session_index) => session_index,
+            None => return Ok(()),
+        };
+        if network_postgres
+            .notification_generator_has_processed_performance_session(session_index)
+            .await?
+        {
+            return Ok(());
+        }
+        log::debug!("Process session #{session_index} for low para performance and missed blocks.");
+        for validator in validator_map.values() {
+            self.inspect_low_para_performance(
+                &network_postgres,
+                app_postgres.clone(),
+                &validator.account.id,
+                session_index,
+            )
+            .await?;
+            self.inspect_missed_blocks(
+                &network_postgres,
+                app_postgres.clone(),
+                &validator.account.id,
+                session_index,
+            )
+            .await?;
+        }
+        network_postgres
+            .save_notification_generator_processed_performance_session(session_index)
+            .await?;
+        Ok(())
+    }
+
+    async fn inspect_low_para_performance(
+        &self,
+        network_postgres: &PostgreSQLNetworkStorage,
+        app_postgres: Arc<PostgreSQLAppStorage>,
+        validator_account_id: &AccountId,
+        session_index: u64,
+    ) -> anyhow::Result<()> {
+        let rules = app_postgres
+            .get_notification_rules_for_validator(
+                &NotificationTypeCode::ChainValidatorLowParaPerformance.to_string(),
+                CONFIG.substrate.network_id,
+                validator_account_id,
+            )
+            .await?;
+        for rule in rules {
+            let Some(threshold_percent) =
+                get_rule_param::<f64>(&rule, MISSED_ATTESTATION_PERCENT_PARAM_CODE)
+            else {
+                continue;
+            };
+            let Some(performances) = get_rule_session_performances(
+                network_postgres,
+                &rule,
+                validator_account_id,
+                session_index,
+            )
+            .await?
+            else {
+                continue;
+            };
+            if !is_low_para_performance(&performances, threshold_percent) {
+                continue;
+            }
+            self.generate_notifications(
+                app_postgres.clone(),
+                &[rule],
+                &Some(*validator_account_id),
+                Some(&LowParaPerformance {
+                    missed_attestation_percent_threshold: threshold_percent,
+                    performances,
+                }),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn inspect_missed_blocks(
+        &self,
+        network_postgres: &PostgreSQLNetworkStorage,
+        app_postgres: Arc<PostgreSQLAppStorage>,
+        validator_account_id: &AccountId,
+        session_index: u64,
+    ) -> anyhow::Result<()> {
+        let rules = app_postgres
+            .get_notification_rules_for_validator(
+                &NotificationTypeCode::ChainValidatorMissedBlocks.to_string(),
+                CONFIG.substrate.network_id,
+                validator_account_id,
+            )
+            .await?;
+        for rule in rules {
+            let Some(threshold_percent) =
+                get_rule_param::<f64>(&rule, MISSED_BLOCK_PERCENT_PARAM_CODE)
+            else {
+                continue;
+            };
+            let Some(performances) = get_rule_session_performances(
+                network_postgres,
+                &rule,
+                validator_account_id,
+                session_index,
+            )
+            .await?
+            else {
+                continue;
+            };
+            if !is_missing_blocks(
+                &performances,
+                threshold_percent,
+                CONFIG.notification_generator.missed_blocks_max_probability,
+            ) {
+                continue;
+            }
+            self.generate_notifications(
+                app_postgres.clone(),
+                &[rule],
+                &Some(*validator_account_id),
+                Some(&MissedBlocks {
+                    missed_block_percent_threshold: threshold_percent,
+                    performances,
+                }),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
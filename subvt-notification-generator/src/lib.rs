@@ -5,6 +5,10 @@
 //!    finishing of the processing of a block is signalled by the processor by means of PostgreSQL
 //!    notifications.
 //! 3. Regular Telemetry checks (this is work in progress still).
+//!
+//! The notifications generated for a block continue the pipeline trace of the block that's
+//! started by the block processor, and carry it on to `subvt-notification-processor` in the
+//! notification rows, see `subvt_logging::trace`.
 #![warn(clippy::disallowed_types)]
 
 use async_trait::async_trait;
@@ -12,6 +16,7 @@ use lazy_static::lazy_static;
 use serde::Serialize;
 use std::sync::Arc;
 use subvt_config::Config;
+use subvt_logging::trace::{self, KeyValue};
 use subvt_persistence::postgres::app::PostgreSQLAppStorage;
 use subvt_service_common::Service;
 use subvt_substrate_client::SubstrateClient;
@@ -66,6 +71,18 @@ impl NotificationGenerator {
         } else {
             None
         };
+        // child of the block inspection span for the block notifications, and the parent of the
+        // send spans of the notifications
+        let context = trace::start_span(
+            "generate_notifications",
+            None,
+            vec![KeyValue::new(
+                "notification_type",
+                rules[0].notification_type.code.clone(),
+            )],
+        );
+        let trace_parent = trace::get_trace_parent(&context);
+        let maybe_finalized_at = trace::get_finalized_at();
         // create separate notifications for each rule and notification channel
         for rule in rules {
             if let Some(validator_account_id) = maybe_validator_account_id {
@@ -92,6 +109,7 @@ impl NotificationGenerator {
                     notification_channel: channel.channel,
                     notification_target: channel.target.clone(),
                     error_log: None,
+                    trace_parent: trace_parent.clone(),
                     created_at: None,
                     sent_at: None,
                     delivered_at: None,
@@ -99,11 +117,15 @@ impl NotificationGenerator {
                     data_json: serde_json::to_string(&notification_data).ok(),
                 };
                 let _ = app_postgres.save_notification(&notification).await?;
-                metrics::notification_counter(
-                    &rule.notification_type.code,
-                    &format!("{}", channel.channel),
-                )
-                .inc();
+                let channel_code = format!("{}", channel.channel);
+                metrics::notification_counter(&rule.notification_type.code, &channel_code).inc();
+                if let Some(finalized_at) = maybe_finalized_at {
+                    metrics::finalization_to_generation_time_ms(
+                        &rule.notification_type.code,
+                        &channel_code,
+                    )
+                    .observe(finalized_at.get_elapsed_millis() as f64);
+                }
             }
         }
         Ok(())
@@ -1,5 +1,5 @@
 use once_cell::sync::Lazy;
-use subvt_metrics::registry::{Histogram, IntCounter, IntCounterVec, IntGauge};
+use subvt_metrics::registry::{Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge};
 
 const METRIC_PREFIX: &str = "subvt_notification_generator";
 
@@ -19,6 +19,26 @@ pub(crate) fn notification_counter(
     METER.with_label_values(&[notification_type, notification_channel])
 }
 
+pub(crate) fn finalization_to_generation_time_ms(
+    notification_type: &str,
+    notification_channel: &str,
+) -> Histogram {
+    static METER: Lazy<HistogramVec> = Lazy::new(|| {
+        subvt_metrics::registry::register_histogram_vec(
+            METRIC_PREFIX,
+            "finalization_to_generation_time_ms",
+            "Time from the finalization of the block to the generation of the notification in milliseconds, by type and channel",
+            &["notification_type", "notification_channel"],
+            vec![
+                1_000.0, 2_500.0, 5_000.0, 10_000.0, 15_000.0, 30_000.0, 60_000.0, 120_000.0,
+                300_000.0, 600_000.0,
+            ],
+        )
+        .unwrap()
+    });
+    METER.with_label_values(&[notification_type, notification_channel])
+}
+
 pub(crate) fn block_processor_error_counter() -> IntCounter {
     static METER: Lazy<IntCounter> = Lazy::new(|| {
         subvt_metrics::registry::register_int_counter(
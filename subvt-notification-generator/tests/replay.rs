@@ -0,0 +1,89 @@
+//! Inspects a recorded real Kusama block end to end: the block gets processed by the block
+//! processor against the RPC replay server, then inspected by the notification generator.
+use std::sync::Arc;
+use subvt_block_processor::BlockProcessor;
+use subvt_config::Config;
+use subvt_notification_generator::NotificationGenerator;
+use subvt_persistence::postgres::app::PostgreSQLAppStorage;
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_substrate_client::fixture::{load_recorded_fixture, RpcFixture, RpcReplayServer};
+use subvt_substrate_client::SubstrateClient;
+
+static BLOCK_PROCESSOR: BlockProcessor = BlockProcessor;
+/// Recorded by `record_block_processor_fixtures` in `subvt-block-processor`.
+const FIXTURE_NAMES: [&str; 2] = [
+    "kusama_block_processor_relay",
+    "kusama_block_processor_asset_hub",
+];
+
+#[tokio::test]
+async fn test_inspect_recorded_block() {
+    let config = Config::test().expect("Cannot get test config.");
+    let mut clients = Vec::new();
+    let mut server_handles = Vec::new();
+    for fixture_name in FIXTURE_NAMES {
+        let fixture = load_recorded_fixture(fixture_name).expect("Cannot load fixture.");
+        let (address, server_handle) = RpcReplayServer::start(fixture, "127.0.0.1", 0)
+            .await
+            .expect("Cannot start replay server.");
+        let client = SubstrateClient::new(
+            &format!("ws://{address}"),
+            config.substrate.network_id,
+            config.substrate.connection_timeout_seconds,
+            config.substrate.request_timeout_seconds,
+        )
+        .await
+        .expect("Cannot connect to replay server.");
+        clients.push(client);
+        server_handles.push(server_handle);
+    }
+    // the test app database has no notification rules, so the generator doesn't query the
+    // people chain, and any query fails the test with a missing recorded response
+    let (people_address, people_server_handle) =
+        RpcReplayServer::start(RpcFixture::default(), "127.0.0.1", 0)
+            .await
+            .expect("Cannot start replay server.");
+    std::env::set_var("SUBVT_ENV", "test");
+    std::env::set_var(
+        "SUBVT__SUBSTRATE__PEOPLE_RPC_URL",
+        format!("ws://{people_address}"),
+    );
+    let mut asset_hub_substrate_client = clients.pop().unwrap();
+    let mut relay_substrate_client = clients.pop().unwrap();
+    let network_postgres = Arc::new(
+        PostgreSQLNetworkStorage::new(&config, config.get_network_postgres_url())
+            .await
+            .expect("Cannot connect to the network database."),
+    );
+    let app_postgres = Arc::new(
+        PostgreSQLAppStorage::new(&config, config.get_app_postgres_url())
+            .await
+            .expect("Cannot connect to the app database."),
+    );
+    let (_, asset_hub_block_number) = BLOCK_PROCESSOR
+        .process_finalized_blocks(
+            &mut relay_substrate_client,
+            &mut asset_hub_substrate_client,
+            &network_postgres,
+        )
+        .await
+        .expect("Cannot process recorded blocks.");
+    NotificationGenerator
+        .inspect_processed_block(
+            network_postgres.clone(),
+            app_postgres,
+            asset_hub_block_number,
+        )
+        .await
+        .expect("Cannot inspect recorded block.");
+    let (_, inspected_block_number) = network_postgres
+        .get_notification_generator_state()
+        .await
+        .expect("Cannot get notification generator state.")
+        .expect("Notification generator state not saved.");
+    assert_eq!(inspected_block_number, asset_hub_block_number);
+    for server_handle in server_handles {
+        server_handle.stop().unwrap();
+    }
+    people_server_handle.stop().unwrap();
+}
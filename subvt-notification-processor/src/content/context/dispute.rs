@@ -0,0 +1,44 @@
+use subvt_types::app::notification::Notification;
+use subvt_types::report::ValidatorParaDisputeStatement;
+use tera::Context;
+
+pub(crate) fn set_para_dispute_context(notification: &Notification, context: &mut Context) {
+    if let Some(notification_data_json) = &notification.data_json {
+        if let Ok(statement) =
+            serde_json::from_str::<ValidatorParaDisputeStatement>(notification_data_json.as_str())
+        {
+            context.insert("candidate_hash", &statement.dispute.candidate_hash);
+            context.insert("session_index", &statement.session_index);
+            context.insert("statement_is_valid", &statement.is_valid);
+            context.insert("statement_kind", &statement.kind);
+            if let Some(is_local) = statement.dispute.is_local {
+                context.insert("is_local", &is_local);
+            }
+            if let Some(initiated_block) = &statement.dispute.initiated_block {
+                context.insert("initiated_block_number", &initiated_block.number);
+                context.insert("initiated_block_hash", &initiated_block.hash);
+            }
+            if let Some(concluded_block) = &statement.dispute.concluded_block {
+                context.insert("concluded_block_number", &concluded_block.number);
+                context.insert("concluded_block_hash", &concluded_block.hash);
+            }
+            if let Some(dispute_is_valid) = statement.dispute.is_valid {
+                context.insert("dispute_is_valid", &dispute_is_valid);
+            }
+            context.insert(
+                "is_against_supermajority",
+                &statement.is_against_supermajority().unwrap_or(false),
+            );
+        } else {
+            log::error!(
+                "Cannot deserialize para dispute statement notification data for notification #{}.",
+                notification.id,
+            );
+        }
+    } else {
+        log::error!(
+            "Para dispute statement data does not exist in notification #{}.",
+            notification.id,
+        );
+    }
+}
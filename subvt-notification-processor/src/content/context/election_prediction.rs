@@ -0,0 +1,52 @@
+use subvt_types::app::{notification::Notification, Network};
+use subvt_types::election::ValidatorElectionPredictionChange;
+use subvt_utility::numeric::format_decimal;
+use tera::Context;
+
+pub(crate) fn set_election_prediction_context(
+    network: &Network,
+    notification: &Notification,
+    context: &mut Context,
+) {
+    if let Some(notification_data_json) = &notification.data_json {
+        if let Ok(change) =
+            serde_json::from_str::<ValidatorElectionPredictionChange>(notification_data_json)
+        {
+            context.insert(
+                "is_predicted_to_be_elected",
+                &change.is_predicted_to_be_elected,
+            );
+            context.insert("desired_target_count", &change.desired_target_count);
+            context.insert(
+                "minimum_backing",
+                &format_decimal(
+                    change.minimum_backing,
+                    network.token_decimal_count as usize,
+                    4,
+                ),
+            );
+            if let Some(winner) = &change.winner {
+                context.insert("rank", &winner.rank);
+                context.insert(
+                    "total_backing",
+                    &format_decimal(
+                        winner.total_backing,
+                        network.token_decimal_count as usize,
+                        4,
+                    ),
+                );
+                context.insert("backer_count", &winner.backer_count);
+            }
+        } else {
+            log::error!(
+                "Cannot deserialize election prediction notification data for notification #{}.",
+                notification.id,
+            );
+        }
+    } else {
+        log::error!(
+            "Election prediction data does not exist in notification #{}.",
+            notification.id,
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use subvt_types::app::notification::Notification;
+use subvt_types::performance::LowParaPerformance;
+use tera::Context;
+
+pub(crate) fn set_low_para_performance_context(notification: &Notification, context: &mut Context) {
+    if let Some(notification_data_json) = &notification.data_json {
+        if let Ok(low_para_performance) =
+            serde_json::from_str::<LowParaPerformance>(notification_data_json.as_str())
+        {
+            context.insert(
+                "threshold_percent",
+                &format!(
+                    "{:.2}",
+                    low_para_performance.missed_attestation_percent_threshold
+                ),
+            );
+            context.insert("session_count", &low_para_performance.performances.len());
+            if let (Some(first), Some(last)) = (
+                low_para_performance.performances.first(),
+                low_para_performance.performances.last(),
+            ) {
+                context.insert("start_session_index", &first.session_index);
+                context.insert("end_session_index", &last.session_index);
+            }
+            let missed_percents: Vec<String> = low_para_performance
+                .performances
+                .iter()
+                .map(|performance| {
+                    format!(
+                        "{:.2}",
+                        performance.get_missed_attestation_ratio().unwrap_or(0.0) * 100.0
+                    )
+                })
+                .collect();
+            context.insert("missed_percents", &missed_percents.join(", "));
+            let missed_count: u32 = low_para_performance
+                .performances
+                .iter()
+                .map(|performance| performance.missed_attestation_count.unwrap_or(0))
+                .sum();
+            context.insert("missed_count", &missed_count);
+        } else {
+            log::error!(
+                "Cannot deserialize low para performance notification data for notification #{}.",
+                notification.id,
+            );
+        }
+    } else {
+        log::error!(
+            "Low para performance data does not exist in notification #{}.",
+            notification.id,
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use subvt_types::app::notification::Notification;
+use subvt_types::performance::MissedBlocks;
+use tera::Context;
+
+pub(crate) fn set_missed_blocks_context(notification: &Notification, context: &mut Context) {
+    if let Some(notification_data_json) = &notification.data_json {
+        if let Ok(missed_blocks) =
+            serde_json::from_str::<MissedBlocks>(notification_data_json.as_str())
+        {
+            context.insert(
+                "threshold_percent",
+                &format!("{:.2}", missed_blocks.missed_block_percent_threshold),
+            );
+            context.insert("session_count", &missed_blocks.performances.len());
+            if let (Some(first), Some(last)) = (
+                missed_blocks.performances.first(),
+                missed_blocks.performances.last(),
+            ) {
+                context.insert("start_session_index", &first.session_index);
+                context.insert("end_session_index", &last.session_index);
+            }
+            let authored_count: u32 = missed_blocks
+                .performances
+                .iter()
+                .map(|performance| performance.authored_block_count)
+                .sum();
+            context.insert("authored_count", &authored_count);
+            let expected_count: f64 = missed_blocks
+                .performances
+                .iter()
+                .filter_map(|performance| performance.expected_block_count)
+                .sum();
+            context.insert("expected_count", &format!("{expected_count:.1}"));
+            let missed_count: u32 = missed_blocks
+                .performances
+                .iter()
+                .filter_map(|performance| performance.get_missed_block_count())
+                .sum();
+            context.insert("missed_count", &missed_count);
+        } else {
+            log::error!(
+                "Cannot deserialize missed blocks notification data for notification #{}.",
+                notification.id,
+            );
+        }
+    } else {
+        log::error!(
+            "Missed blocks data does not exist in notification #{}.",
+            notification.id,
+        );
+    }
+}
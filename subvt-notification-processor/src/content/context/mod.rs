@@ -10,11 +10,22 @@ use crate::content::context::referenda::{
     set_referendum_submitted_context, set_referendum_timed_out_context,
 };
 use crate::content::context::{
-    basic::set_basic_context, block_authorship::set_block_authorship_context,
-    identity::set_identity_changed_context, lost_nomination::set_lost_nomination_context,
-    new_nomination::set_new_nomination_context, offline_offence::set_offline_offence_context,
-    payout::set_payout_context, session_keys::set_session_keys_changed_context,
-    unclaimed_payout::set_unclaimed_payout_context, validate::set_validate_extrinsic_context,
+    basic::set_basic_context,
+    block_authorship::set_block_authorship_context,
+    dispute::set_para_dispute_context,
+    election_prediction::set_election_prediction_context,
+    identity::set_identity_changed_context,
+    lost_nomination::set_lost_nomination_context,
+    low_para_performance::set_low_para_performance_context,
+    missed_blocks::set_missed_blocks_context,
+    new_nomination::set_new_nomination_context,
+    offline_offence::set_offline_offence_context,
+    payout::set_payout_context,
+    proxy::{set_new_multisig_context, set_proxy_context},
+    session_keys::set_session_keys_changed_context,
+    stash_balance::set_stash_balance_changed_context,
+    unclaimed_payout::set_unclaimed_payout_context,
+    validate::set_validate_extrinsic_context,
     validator_active::set_validator_active_context,
     validator_chilled::set_validator_chilled_context,
 };
@@ -26,13 +37,19 @@ use tera::Context;
 
 mod basic;
 mod block_authorship;
+mod dispute;
+mod election_prediction;
 mod identity;
 mod lost_nomination;
+mod low_para_performance;
+mod missed_blocks;
 mod new_nomination;
 mod offline_offence;
 mod payout;
+mod proxy;
 mod referenda;
 mod session_keys;
+mod stash_balance;
 mod unclaimed_payout;
 mod validate;
 mod validator_active;
@@ -106,6 +123,31 @@ pub(crate) fn get_renderer_context(
         NotificationTypeCode::ChainValidatorPayoutStakers => {
             set_payout_context(network, notification, &mut context);
         }
+        NotificationTypeCode::ChainValidatorStashBalanceChanged => {
+            set_stash_balance_changed_context(network, notification, &mut context);
+        }
+        NotificationTypeCode::ChainValidatorProxyAdded
+        | NotificationTypeCode::ChainValidatorProxyRemoved
+        | NotificationTypeCode::ChainValidatorPureProxyCreated
+        | NotificationTypeCode::ChainValidatorProxyAnnounced => {
+            set_proxy_context(network, notification, &mut context);
+        }
+        NotificationTypeCode::ChainValidatorNewMultisig => {
+            set_new_multisig_context(network, notification, &mut context);
+        }
+        NotificationTypeCode::ChainValidatorDisputeInitiated
+        | NotificationTypeCode::ChainValidatorDisputeConcluded => {
+            set_para_dispute_context(notification, &mut context);
+        }
+        NotificationTypeCode::ChainValidatorLowParaPerformance => {
+            set_low_para_performance_context(notification, &mut context);
+        }
+        NotificationTypeCode::ChainValidatorMissedBlocks => {
+            set_missed_blocks_context(notification, &mut context);
+        }
+        NotificationTypeCode::ChainValidatorPredictedElection => {
+            set_election_prediction_context(network, notification, &mut context);
+        }
         NotificationTypeCode::ChainValidatorStartedParaValidating => (),
         NotificationTypeCode::ChainValidatorStoppedParaValidating => (),
         NotificationTypeCode::ReferendumApproved => {
@@ -0,0 +1,97 @@
+use subvt_types::app::{event, notification::Notification, Network};
+use subvt_types::crypto::AccountId;
+use subvt_utility::text::get_condensed_address;
+use tera::Context;
+
+fn insert_account(
+    network: &Network,
+    context: &mut Context,
+    key_prefix: &str,
+    account_id: &AccountId,
+) {
+    let address = account_id.to_ss58_check_with_version(network.ss58_prefix as u16);
+    context.insert(
+        format!("{key_prefix}_display"),
+        &get_condensed_address(&address, None),
+    );
+    context.insert(format!("{key_prefix}_address"), &address);
+}
+
+pub(crate) fn set_proxy_context(
+    network: &Network,
+    notification: &Notification,
+    context: &mut Context,
+) {
+    if let Some(notification_data_json) = &notification.data_json {
+        if let Ok(proxy_event) =
+            serde_json::from_str::<event::ProxyEvent>(notification_data_json.as_str())
+        {
+            context.insert("block_hash", &proxy_event.block_hash);
+            context.insert("event_index", &proxy_event.event_index);
+            insert_account(network, context, "account", &proxy_event.account_id);
+            insert_account(
+                network,
+                context,
+                "delegate",
+                &proxy_event.delegate_account_id,
+            );
+            if let Some(proxy_type) = &proxy_event.proxy_type {
+                context.insert("proxy_type", proxy_type);
+            }
+            if let Some(delay) = proxy_event.delay {
+                context.insert("delay", &delay);
+            }
+            if let Some(call_hash) = &proxy_event.call_hash {
+                context.insert("call_hash", call_hash);
+            }
+        } else {
+            log::error!(
+                "Cannot deserialize proxy event notification data for notification #{}.",
+                notification.id,
+            );
+        }
+    } else {
+        log::error!(
+            "Proxy event data does not exist in notification #{}.",
+            notification.id,
+        );
+    }
+}
+
+pub(crate) fn set_new_multisig_context(
+    network: &Network,
+    notification: &Notification,
+    context: &mut Context,
+) {
+    if let Some(notification_data_json) = &notification.data_json {
+        if let Ok(multisig_event) =
+            serde_json::from_str::<event::NewMultisigEvent>(notification_data_json.as_str())
+        {
+            context.insert("block_hash", &multisig_event.block_hash);
+            context.insert("event_index", &multisig_event.event_index);
+            context.insert("call_hash", &multisig_event.call_hash);
+            insert_account(
+                network,
+                context,
+                "multisig",
+                &multisig_event.multisig_account_id,
+            );
+            insert_account(
+                network,
+                context,
+                "approving",
+                &multisig_event.approving_account_id,
+            );
+        } else {
+            log::error!(
+                "Cannot deserialize new multisig event notification data for notification #{}.",
+                notification.id,
+            );
+        }
+    } else {
+        log::error!(
+            "New multisig event data does not exist in notification #{}.",
+            notification.id,
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use subvt_types::app::{event, notification::Notification, Network};
+use subvt_utility::numeric::format_decimal;
+use subvt_utility::text::get_condensed_address;
+use tera::Context;
+
+pub(crate) fn set_stash_balance_changed_context(
+    network: &Network,
+    notification: &Notification,
+    context: &mut Context,
+) {
+    if let Some(notification_data_json) = &notification.data_json {
+        if let Ok(balances_event) =
+            serde_json::from_str::<event::BalancesEvent>(notification_data_json.as_str())
+        {
+            context.insert("block_hash", &balances_event.block_hash);
+            context.insert("event_index", &balances_event.event_index);
+            context.insert("event_name", &balances_event.event_name);
+            context.insert(
+                "amount",
+                &format_decimal(
+                    balances_event.amount,
+                    network.token_decimal_count as usize,
+                    4,
+                ),
+            );
+            // the stash is either the source or the recipient of a transfer
+            let is_incoming = balances_event.counterparty_account_id.is_some()
+                && balances_event.counterparty_account_id == notification.validator_account_id;
+            context.insert("is_incoming", &is_incoming);
+            let maybe_counterparty_account_id = if is_incoming {
+                Some(balances_event.account_id)
+            } else {
+                balances_event.counterparty_account_id
+            };
+            if let Some(counterparty_account_id) = maybe_counterparty_account_id {
+                let counterparty_address =
+                    counterparty_account_id.to_ss58_check_with_version(network.ss58_prefix as u16);
+                context.insert(
+                    "counterparty_display",
+                    &get_condensed_address(&counterparty_address, None),
+                );
+                context.insert("counterparty_address", &counterparty_address);
+            }
+        } else {
+            log::error!(
+                "Cannot deserialize balances event notification data for notification #{}.",
+                notification.id,
+            );
+        }
+    } else {
+        log::error!(
+            "Balances event data does not exist in notification #{}.",
+            notification.id,
+        );
+    }
+}
@@ -12,6 +12,7 @@ use lazy_static::lazy_static;
 use rustc_hash::FxHashMap as HashMap;
 use std::sync::Arc;
 use subvt_config::Config;
+use subvt_metrics::health;
 use subvt_persistence::postgres::app::PostgreSQLAppStorage;
 use subvt_service_common::Service;
 use subvt_types::app::{notification::NotificationChannel, Network};
@@ -166,6 +167,7 @@ impl Service for NotificationProcessor {
                 ));
             });
         }
+        health::set_ready(true);
         self.start_immediate_notification_processor().await?;
         Ok(())
     }
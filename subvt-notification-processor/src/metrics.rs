@@ -1,5 +1,7 @@
 use once_cell::sync::Lazy;
-use subvt_metrics::registry::{HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec};
+use subvt_metrics::registry::{
+    Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec,
+};
 
 const METRIC_PREFIX: &str = "subvt_notification_processor";
 
@@ -55,6 +57,26 @@ pub(crate) fn channel_error_counter(notification_channel: &str) -> IntCounter {
     METER.with_label_values(&[notification_channel])
 }
 
+pub(crate) fn generation_to_send_time_ms(
+    notification_type: &str,
+    notification_channel: &str,
+) -> Histogram {
+    static METER: Lazy<HistogramVec> = Lazy::new(|| {
+        subvt_metrics::registry::register_histogram_vec(
+            METRIC_PREFIX,
+            "generation_to_send_time_ms",
+            "Time from the generation of the notification to its sending in milliseconds, by type and channel",
+            &["notification_type", "notification_channel"],
+            vec![
+                250.0, 500.0, 1_000.0, 2_500.0, 5_000.0, 10_000.0, 30_000.0, 60_000.0, 300_000.0,
+                900_000.0, 3_600_000.0, 86_400_000.0,
+            ],
+        )
+        .unwrap()
+    });
+    METER.with_label_values(&[notification_type, notification_channel])
+}
+
 fn notification_send_time_ms() -> HistogramVec {
     static METER: Lazy<HistogramVec> = Lazy::new(|| {
         subvt_metrics::registry::register_histogram_vec(
@@ -1,6 +1,9 @@
 //! Contains the notification processing logic.
 use crate::{metrics, NotificationProcessor};
 use rustc_hash::FxHashMap as HashMap;
+use subvt_logging::trace::{self, FutureExt, KeyValue};
+use subvt_persistence::postgres::app::PostgreSQLAppStorage;
+use subvt_service_common::shutdown;
 use subvt_types::app::notification::{
     Notification, NotificationChannel, NotificationPeriodType, NotificationTypeCode,
 };
@@ -9,6 +12,37 @@ pub(crate) mod era_epoch;
 pub(crate) mod hour_day;
 pub(crate) mod immediate;
 
+/// Starts the send span of the notification, continuing the trace of its generation.
+fn start_send_span(notification: &Notification) -> trace::Context {
+    trace::start_span(
+        "send_notification",
+        notification.trace_parent.as_deref(),
+        vec![
+            KeyValue::new(
+                "notification_type",
+                notification.notification_type_code.clone(),
+            ),
+            KeyValue::new(
+                "notification_channel",
+                notification.notification_channel.to_string(),
+            ),
+            KeyValue::new("notification_id", notification.id as i64),
+        ],
+    )
+}
+
+/// Marks the notification as sent and observes the time from its generation.
+async fn mark_notification_sent(postgres: &PostgreSQLAppStorage, notification: &Notification) {
+    if let Ok(Some(generation_to_send_ms)) = postgres.mark_notification_sent(notification.id).await
+    {
+        metrics::generation_to_send_time_ms(
+            &notification.notification_type_code,
+            &notification.notification_channel.to_string(),
+        )
+        .observe(generation_to_send_ms as f64);
+    }
+}
+
 impl NotificationProcessor {
     async fn process_notification_group(
         &self,
@@ -33,7 +67,11 @@ impl NotificationProcessor {
         let postgres = self.postgres.clone();
         let notification_type_code = notification_type_code.to_owned();
         let target = target.to_owned();
+        let in_flight = shutdown::in_flight();
+        // the group is sent in the trace of its first notification
+        let context = start_send_span(&notification_group[0]);
         tokio::spawn(async move {
+            let _in_flight = in_flight;
             let start = std::time::Instant::now();
             match sender
                 .send_grouped(
@@ -43,6 +81,7 @@ impl NotificationProcessor {
                     &target,
                     &notification_group,
                 )
+                .with_context(context.clone())
                 .await
             {
                 Ok(_success_log) => {
@@ -52,11 +91,12 @@ impl NotificationProcessor {
                         start.elapsed().as_millis() as f64,
                     );
                     for notification in notification_group.iter() {
-                        let _ = postgres.mark_notification_sent(notification.id).await;
+                        mark_notification_sent(&postgres, notification).await;
                     }
                 }
                 Err(error) => {
                     log::error!("Error while sending grouped notification: {error:?}");
+                    trace::set_span_error(&context, format!("{error:?}"));
                     metrics::channel_error_counter(&format!("{channel}")).inc();
                     for notification in notification_group.iter() {
                         let _ = postgres.mark_notification_failed(notification.id).await;
@@ -86,7 +126,10 @@ impl NotificationProcessor {
             .mark_notification_processing(notification.id)
             .await?;
         let postgres = self.postgres.clone();
+        let in_flight = shutdown::in_flight();
+        let context = start_send_span(&notification);
         tokio::spawn(async move {
+            let _in_flight = in_flight;
             let notification_id = notification.id;
             if let Some(account_id) = notification.validator_account_id {
                 log::info!(
@@ -105,7 +148,11 @@ impl NotificationProcessor {
                 );
             }
             let start = std::time::Instant::now();
-            match sender.send(&notification).await {
+            match sender
+                .send(&notification)
+                .with_context(context.clone())
+                .await
+            {
                 Ok(_success_log) => {
                     metrics::sent_notification_counter(&format!(
                         "{}",
@@ -116,7 +163,7 @@ impl NotificationProcessor {
                         &format!("{}", notification.notification_channel),
                         start.elapsed().as_millis() as f64,
                     );
-                    let _ = postgres.mark_notification_sent(notification_id).await;
+                    mark_notification_sent(&postgres, &notification).await;
                 }
                 Err(error) => {
                     log::error!(
@@ -125,6 +172,7 @@ impl NotificationProcessor {
                         notification.period_type,
                         error,
                     );
+                    trace::set_span_error(&context, format!("{error:?}"));
                     metrics::channel_error_counter(&format!(
                         "{}",
                         notification.notification_channel
@@ -146,6 +194,11 @@ impl NotificationProcessor {
         period_type: NotificationPeriodType,
         period: u32,
     ) -> anyhow::Result<()> {
+        // the pending notifications are left for the next start
+        if shutdown::is_requested() {
+            log::info!("Shutting down. Skip {period_type} notifications for period {period}.");
+            return Ok(());
+        }
         log::info!("Process {period_type} notifications for period {period}.",);
         match self
             .postgres
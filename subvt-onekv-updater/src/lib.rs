@@ -3,6 +3,7 @@
 use async_trait::async_trait;
 use lazy_static::lazy_static;
 use subvt_config::Config;
+use subvt_metrics::health;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
 use subvt_service_common::Service;
 use subvt_types::dn::DNDataResponse;
@@ -136,6 +137,7 @@ impl Service for OneKVUpdater {
         );
         let postgres =
             PostgreSQLNetworkStorage::new(&CONFIG, CONFIG.get_network_postgres_url()).await?;
+        health::set_ready(true);
         loop {
             log::info!("Update DN data.");
             metrics::last_run_timestamp_ms().set(chrono::Utc::now().timestamp_millis());
@@ -134,8 +134,8 @@ impl PostgreSQLAppStorage {
     pub async fn save_notification(&self, notification: &Notification) -> anyhow::Result<u32> {
         let result: (i32,) = sqlx::query_as(
             r#"
-            INSERT INTO app_notification (user_id, user_notification_rule_id, network_id, period_type, period, validator_account_id, validator_account_json, notification_type_code, user_notification_channel_id, notification_channel_code, notification_target, data_json, error_log)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            INSERT INTO app_notification (user_id, user_notification_rule_id, network_id, period_type, period, validator_account_id, validator_account_json, notification_type_code, user_notification_channel_id, notification_channel_code, notification_target, data_json, error_log, trace_parent)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
             RETURNING id
             "#,
         )
@@ -152,6 +152,7 @@ impl PostgreSQLAppStorage {
             .bind(&notification.notification_target)
             .bind(&notification.data_json)
             .bind(&notification.error_log)
+            .bind(&notification.trace_parent)
             .fetch_one(&self.connection_pool)
             .await?;
         Ok(result.0 as u32)
@@ -166,7 +167,7 @@ impl PostgreSQLAppStorage {
         let db_notifications: Vec<PostgresNotification> = sqlx::query_as(
             if maybe_network_id.is_some() {
                 r#"
-                SELECT id, user_id, user_notification_rule_id, network_id, period_type, period, validator_account_id, validator_account_json, notification_type_code, user_notification_channel_id, notification_channel_code, notification_target, data_json, error_log
+                SELECT id, user_id, user_notification_rule_id, network_id, period_type, period, validator_account_id, validator_account_json, notification_type_code, user_notification_channel_id, notification_channel_code, notification_target, data_json, error_log, trace_parent
                 FROM app_notification
                 WHERE processing_started_at IS NULL
                 AND period_type = $1
@@ -175,7 +176,7 @@ impl PostgreSQLAppStorage {
                 "#
             } else {
                 r#"
-                SELECT id, user_id, user_notification_rule_id, network_id, period_type, period, validator_account_id, validator_account_json, notification_type_code, user_notification_channel_id, notification_channel_code, notification_target, data_json, error_log
+                SELECT id, user_id, user_notification_rule_id, network_id, period_type, period, validator_account_id, validator_account_json, notification_type_code, user_notification_channel_id, notification_channel_code, notification_target, data_json, error_log, trace_parent
                 FROM app_notification
                 WHERE processing_started_at IS NULL
                 AND period_type = $1
@@ -253,18 +254,20 @@ impl PostgreSQLAppStorage {
         Ok(())
     }
 
-    pub async fn mark_notification_sent(&self, id: u32) -> anyhow::Result<()> {
-        sqlx::query(
+    /// Returns the time from the creation of the notification to its sending in milliseconds.
+    pub async fn mark_notification_sent(&self, id: u32) -> anyhow::Result<Option<u64>> {
+        let maybe_generation_to_send_ms: Option<(i64,)> = sqlx::query_as(
             r#"
             UPDATE app_notification
             SET sent_at = now()
             WHERE id = $1
+            RETURNING (EXTRACT(EPOCH FROM (sent_at - created_at)) * 1000)::BIGINT
             "#,
         )
         .bind(id as i32)
-        .execute(&self.connection_pool)
+        .fetch_optional(&self.connection_pool)
         .await?;
-        Ok(())
+        Ok(maybe_generation_to_send_ms.map(|result| result.0.max(0) as u64))
     }
 
     pub async fn mark_notification_delivered(&self, id: u32) -> anyhow::Result<()> {
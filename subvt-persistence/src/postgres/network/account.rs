@@ -1,6 +1,7 @@
 //! Storage related to a network supported by SubVT.
 //! Each supported network has a separate database.
 use crate::postgres::network::PostgreSQLNetworkStorage;
+use rustc_hash::FxHashSet as HashSet;
 use std::str::FromStr;
 use subvt_types::crypto::AccountId;
 
@@ -23,4 +24,71 @@ impl PostgreSQLNetworkStorage {
             Ok(None)
         }
     }
+
+    /// Checks whether any of the given accounts is a validator stash or controller account
+    /// in any of the persisted eras.
+    pub async fn is_validator_stash_or_controller(
+        &self,
+        account_ids: &[AccountId],
+    ) -> anyhow::Result<bool> {
+        Ok(!self
+            .get_validator_stash_or_controller_account_ids(account_ids)
+            .await?
+            .is_empty())
+    }
+
+    /// Returns the ones among the given accounts that are a validator stash or controller
+    /// account in any of the persisted eras.
+    pub async fn get_validator_stash_or_controller_account_ids(
+        &self,
+        account_ids: &[AccountId],
+    ) -> anyhow::Result<HashSet<AccountId>> {
+        if account_ids.is_empty() {
+            return Ok(HashSet::default());
+        }
+        let account_ids: Vec<String> = account_ids
+            .iter()
+            .map(|account_id| account_id.to_string())
+            .collect();
+        let db_account_ids: Vec<(String,)> = sqlx::query_as(
+            r#"
+            SELECT validator_account_id FROM sub_era_validator
+            WHERE validator_account_id = ANY($1)
+            UNION
+            SELECT controller_account_id FROM sub_era_validator
+            WHERE controller_account_id = ANY($1)
+            "#,
+        )
+        .bind(&account_ids)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut tracked_account_ids = HashSet::default();
+        for db_account_id in db_account_ids {
+            tracked_account_ids.insert(AccountId::from_str(&db_account_id.0)?);
+        }
+        Ok(tracked_account_ids)
+    }
+
+    /// Returns the stash account ids of the validators for which the given account is
+    /// the stash or the controller account in any of the persisted eras.
+    pub async fn get_validator_stash_account_ids(
+        &self,
+        account_id: &AccountId,
+    ) -> anyhow::Result<Vec<AccountId>> {
+        let db_account_ids: Vec<(String,)> = sqlx::query_as(
+            r#"
+            SELECT DISTINCT validator_account_id
+            FROM sub_era_validator
+            WHERE validator_account_id = $1 OR controller_account_id = $1
+            "#,
+        )
+        .bind(account_id.to_string())
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut account_ids = Vec::new();
+        for db_account_id in db_account_ids {
+            account_ids.push(AccountId::from_str(&db_account_id.0)?);
+        }
+        Ok(account_ids)
+    }
 }
@@ -0,0 +1,117 @@
+use crate::postgres::network::PostgreSQLNetworkStorage;
+use std::str::FromStr;
+use subvt_types::app::event::BalancesEvent;
+use subvt_types::{crypto::AccountId, substrate::Balance};
+
+type PostgresBalancesEvent = (
+    i32,
+    String,
+    Option<i32>,
+    i32,
+    String,
+    String,
+    Option<String>,
+    String,
+);
+
+impl PostgreSQLNetworkStorage {
+    /// Saves the event. The caller saves only the events of validator stash or controller
+    /// accounts, see `get_validator_stash_or_controller_account_ids`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn save_balances_event(
+        &self,
+        block_hash: &str,
+        extrinsic_index: Option<i32>,
+        event_index: i32,
+        event_name: &str,
+        account_id: &AccountId,
+        maybe_counterparty_account_id: Option<&AccountId>,
+        amount: Balance,
+    ) -> anyhow::Result<Option<i32>> {
+        let mut account_ids = vec![*account_id];
+        if let Some(counterparty_account_id) = maybe_counterparty_account_id {
+            account_ids.push(*counterparty_account_id);
+        }
+        for account_id in &account_ids {
+            self.save_account(account_id).await?;
+        }
+        let maybe_result: Option<(i32,)> = sqlx::query_as(
+            r#"
+            INSERT INTO sub_event_balances (block_hash, extrinsic_index, event_index, event_name, account_id, counterparty_account_id, amount)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT(block_hash, event_index) DO NOTHING
+            RETURNING id
+            "#,
+        )
+            .bind(block_hash)
+            .bind(extrinsic_index)
+            .bind(event_index)
+            .bind(event_name)
+            .bind(account_id.to_string())
+            .bind(maybe_counterparty_account_id.map(|account_id| account_id.to_string()))
+            .bind(amount.to_string())
+            .fetch_optional(&self.connection_pool)
+            .await?;
+        if let Some(result) = maybe_result {
+            Ok(Some(result.0))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn update_balances_event_nesting_index(
+        &self,
+        block_hash: &str,
+        maybe_nesting_index: &Option<String>,
+        event_index: i32,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE sub_event_balances
+            SET nesting_index = $1
+            WHERE block_hash = $2 AND event_index = $3
+            "#,
+        )
+        .bind(maybe_nesting_index)
+        .bind(block_hash)
+        .bind(event_index)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_balances_events_in_block(
+        &self,
+        block_hash: &str,
+    ) -> anyhow::Result<Vec<BalancesEvent>> {
+        let db_events: Vec<PostgresBalancesEvent> = sqlx::query_as(
+            r#"
+            SELECT "id", block_hash, extrinsic_index, event_index, event_name, account_id, counterparty_account_id, amount
+            FROM sub_event_balances
+            WHERE block_hash = $1
+            ORDER BY "id" ASC
+            "#,
+        )
+        .bind(block_hash)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut events = Vec::new();
+        for db_event in db_events {
+            events.push(BalancesEvent {
+                id: db_event.0 as u32,
+                block_hash: db_event.1.clone(),
+                extrinsic_index: db_event.2.map(|index| index as u32),
+                event_index: db_event.3 as u32,
+                event_name: db_event.4.clone(),
+                account_id: AccountId::from_str(&db_event.5)?,
+                counterparty_account_id: if let Some(account_id) = &db_event.6 {
+                    Some(AccountId::from_str(account_id)?)
+                } else {
+                    None
+                },
+                amount: db_event.7.parse()?,
+            })
+        }
+        Ok(events)
+    }
+}
@@ -1,10 +1,13 @@
+pub mod balances;
 pub mod chilled;
 pub mod democracy;
 pub mod era_paid;
 pub mod killed_account;
 pub mod new_account;
+pub mod new_multisig;
 pub mod nominator_kicked;
 pub mod payout_started;
+pub mod proxy;
 pub mod referenda;
 pub mod rewarded;
 pub mod slashed;
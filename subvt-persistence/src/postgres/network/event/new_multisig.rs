@@ -0,0 +1,99 @@
+use crate::postgres::network::PostgreSQLNetworkStorage;
+use std::str::FromStr;
+use subvt_types::app::event::NewMultisigEvent;
+use subvt_types::crypto::AccountId;
+
+impl PostgreSQLNetworkStorage {
+    /// Saves the event only if the multisig account is a validator stash or controller account.
+    pub async fn save_new_multisig_event(
+        &self,
+        block_hash: &str,
+        extrinsic_index: Option<i32>,
+        event_index: i32,
+        multisig_account_id: &AccountId,
+        approving_account_id: &AccountId,
+        call_hash: &str,
+    ) -> anyhow::Result<Option<i32>> {
+        if !self
+            .is_validator_stash_or_controller(&[*multisig_account_id])
+            .await?
+        {
+            return Ok(None);
+        }
+        self.save_account(multisig_account_id).await?;
+        self.save_account(approving_account_id).await?;
+        let maybe_result: Option<(i32,)> = sqlx::query_as(
+            r#"
+            INSERT INTO sub_event_new_multisig (block_hash, extrinsic_index, event_index, multisig_account_id, approving_account_id, call_hash)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT(block_hash, event_index) DO NOTHING
+            RETURNING id
+            "#,
+        )
+            .bind(block_hash)
+            .bind(extrinsic_index)
+            .bind(event_index)
+            .bind(multisig_account_id.to_string())
+            .bind(approving_account_id.to_string())
+            .bind(call_hash)
+            .fetch_optional(&self.connection_pool)
+            .await?;
+        if let Some(result) = maybe_result {
+            Ok(Some(result.0))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn update_new_multisig_event_nesting_index(
+        &self,
+        block_hash: &str,
+        maybe_nesting_index: &Option<String>,
+        event_index: i32,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE sub_event_new_multisig
+            SET nesting_index = $1
+            WHERE block_hash = $2 AND event_index = $3
+            "#,
+        )
+        .bind(maybe_nesting_index)
+        .bind(block_hash)
+        .bind(event_index)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_new_multisig_events_in_block(
+        &self,
+        block_hash: &str,
+    ) -> anyhow::Result<Vec<NewMultisigEvent>> {
+        let db_events: Vec<(i32, String, Option<i32>, i32, String, String, String)> =
+            sqlx::query_as(
+                r#"
+                SELECT "id", block_hash, extrinsic_index, event_index, multisig_account_id, approving_account_id, call_hash
+                FROM sub_event_new_multisig
+                WHERE block_hash = $1
+                ORDER BY "id" ASC
+                "#,
+            )
+            .bind(block_hash)
+            .fetch_all(&self.connection_pool)
+            .await?;
+        let mut events = Vec::new();
+        for db_event in db_events {
+            events.push(NewMultisigEvent {
+                id: db_event.0 as u32,
+                block_hash: db_event.1.clone(),
+                extrinsic_index: db_event.2.map(|index| index as u32),
+                event_index: db_event.3 as u32,
+                multisig_account_id: AccountId::from_str(&db_event.4)?,
+                approving_account_id: AccountId::from_str(&db_event.5)?,
+                call_hash: db_event.6.clone(),
+            })
+        }
+        Ok(events)
+    }
+}
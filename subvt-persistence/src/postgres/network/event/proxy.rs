@@ -0,0 +1,122 @@
+use crate::postgres::network::PostgreSQLNetworkStorage;
+use std::str::FromStr;
+use subvt_types::app::event::ProxyEvent;
+use subvt_types::crypto::AccountId;
+
+type PostgresProxyEvent = (
+    i32,
+    String,
+    Option<i32>,
+    i32,
+    String,
+    String,
+    String,
+    Option<String>,
+    Option<i64>,
+    Option<String>,
+);
+
+impl PostgreSQLNetworkStorage {
+    /// Saves the event only if the account (delegator, real or spawner) is a validator stash
+    /// or controller account.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn save_proxy_event(
+        &self,
+        block_hash: &str,
+        extrinsic_index: Option<i32>,
+        event_index: i32,
+        event_name: &str,
+        account_id: &AccountId,
+        delegate_account_id: &AccountId,
+        maybe_proxy_type: Option<&str>,
+        maybe_delay: Option<u32>,
+        maybe_call_hash: Option<&str>,
+    ) -> anyhow::Result<Option<i32>> {
+        if !self
+            .is_validator_stash_or_controller(&[*account_id])
+            .await?
+        {
+            return Ok(None);
+        }
+        self.save_account(account_id).await?;
+        self.save_account(delegate_account_id).await?;
+        let maybe_result: Option<(i32,)> = sqlx::query_as(
+            r#"
+            INSERT INTO sub_event_proxy (block_hash, extrinsic_index, event_index, event_name, account_id, delegate_account_id, proxy_type, delay, call_hash)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT(block_hash, event_index) DO NOTHING
+            RETURNING id
+            "#,
+        )
+            .bind(block_hash)
+            .bind(extrinsic_index)
+            .bind(event_index)
+            .bind(event_name)
+            .bind(account_id.to_string())
+            .bind(delegate_account_id.to_string())
+            .bind(maybe_proxy_type)
+            .bind(maybe_delay.map(|delay| delay as i64))
+            .bind(maybe_call_hash)
+            .fetch_optional(&self.connection_pool)
+            .await?;
+        if let Some(result) = maybe_result {
+            Ok(Some(result.0))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn update_proxy_event_nesting_index(
+        &self,
+        block_hash: &str,
+        maybe_nesting_index: &Option<String>,
+        event_index: i32,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE sub_event_proxy
+            SET nesting_index = $1
+            WHERE block_hash = $2 AND event_index = $3
+            "#,
+        )
+        .bind(maybe_nesting_index)
+        .bind(block_hash)
+        .bind(event_index)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_proxy_events_in_block(
+        &self,
+        block_hash: &str,
+    ) -> anyhow::Result<Vec<ProxyEvent>> {
+        let db_events: Vec<PostgresProxyEvent> = sqlx::query_as(
+            r#"
+            SELECT "id", block_hash, extrinsic_index, event_index, event_name, account_id, delegate_account_id, proxy_type, delay, call_hash
+            FROM sub_event_proxy
+            WHERE block_hash = $1
+            ORDER BY "id" ASC
+            "#,
+        )
+        .bind(block_hash)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut events = Vec::new();
+        for db_event in db_events {
+            events.push(ProxyEvent {
+                id: db_event.0 as u32,
+                block_hash: db_event.1.clone(),
+                extrinsic_index: db_event.2.map(|index| index as u32),
+                event_index: db_event.3 as u32,
+                event_name: db_event.4.clone(),
+                account_id: AccountId::from_str(&db_event.5)?,
+                delegate_account_id: AccountId::from_str(&db_event.6)?,
+                proxy_type: db_event.7.clone(),
+                delay: db_event.8.map(|delay| delay as u32),
+                call_hash: db_event.9.clone(),
+            })
+        }
+        Ok(events)
+    }
+}
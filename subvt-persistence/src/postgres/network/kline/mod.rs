@@ -1,9 +1,12 @@
 use crate::postgres::network::PostgreSQLNetworkStorage;
 use sqlx::types::BigDecimal;
-use subvt_types::kline::KLine;
+use sqlx::{Postgres, QueryBuilder};
+use std::str::FromStr;
+use subvt_types::kline::{KLine, KLineInterval};
 
 type DBKline = (
     i32,
+    String,
     i64,
     String,
     String,
@@ -17,39 +20,112 @@ type DBKline = (
     i32,
     BigDecimal,
     BigDecimal,
+    String,
 );
 
 impl PostgreSQLNetworkStorage {
-    pub async fn kline_exists(
+    /// Open times of the existing k-lines in the given closed range, ordered by open time.
+    pub async fn get_kline_open_times(
         &self,
-        timestamp: i64,
         source_ticker: &str,
         target_ticker: &str,
-    ) -> anyhow::Result<bool> {
-        let record_count: (i64,) = sqlx::query_as(
+        interval: KLineInterval,
+        start_timestamp: u64,
+        end_timestamp: u64,
+    ) -> anyhow::Result<Vec<u64>> {
+        let open_times: Vec<(i64,)> = sqlx::query_as(
             r#"
-            SELECT COUNT(id) FROM sub_kline_historical
-            WHERE open_time = $1 AND source_ticker = $2 AND target_ticker = $3
+            SELECT open_time FROM sub_kline_historical
+            WHERE source_ticker = $1 AND target_ticker = $2 AND "interval" = $3
+            AND open_time >= $4 AND open_time <= $5
+            ORDER BY open_time ASC
             "#,
         )
-        .bind(timestamp)
         .bind(source_ticker)
         .bind(target_ticker)
-        .fetch_one(&self.connection_pool)
+        .bind(interval.as_str())
+        .bind(start_timestamp as i64)
+        .bind(end_timestamp as i64)
+        .fetch_all(&self.connection_pool)
         .await?;
-        Ok(record_count.0 > 0)
+        Ok(open_times
+            .into_iter()
+            .map(|open_time| open_time.0 as u64)
+            .collect())
+    }
+
+    /// Open times of the candles in the given closed range that are recorded as not available
+    /// from any source, ordered by open time.
+    pub async fn get_unavailable_kline_open_times(
+        &self,
+        source_ticker: &str,
+        target_ticker: &str,
+        interval: KLineInterval,
+        start_timestamp: u64,
+        end_timestamp: u64,
+    ) -> anyhow::Result<Vec<u64>> {
+        let open_times: Vec<(i64,)> = sqlx::query_as(
+            r#"
+            SELECT open_time FROM sub_kline_unavailable
+            WHERE source_ticker = $1 AND target_ticker = $2 AND "interval" = $3
+            AND open_time >= $4 AND open_time <= $5
+            ORDER BY open_time ASC
+            "#,
+        )
+        .bind(source_ticker)
+        .bind(target_ticker)
+        .bind(interval.as_str())
+        .bind(start_timestamp as i64)
+        .bind(end_timestamp as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(open_times
+            .into_iter()
+            .map(|open_time| open_time.0 as u64)
+            .collect())
+    }
+
+    /// Records the candles as not available from any source, so that they're not fetched again.
+    pub async fn save_unavailable_kline_open_times(
+        &self,
+        source_ticker: &str,
+        target_ticker: &str,
+        interval: KLineInterval,
+        open_times: &[u64],
+    ) -> anyhow::Result<()> {
+        if open_times.is_empty() {
+            return Ok(());
+        }
+        let mut query_builder = QueryBuilder::new(
+            "INSERT INTO sub_kline_unavailable (\"interval\", open_time, source_ticker, target_ticker)",
+        );
+        query_builder.push_values(open_times, |mut query, open_time| {
+            query
+                .push_bind(interval.as_str())
+                .push_bind(*open_time as i64)
+                .push_bind(source_ticker)
+                .push_bind(target_ticker);
+        });
+        query_builder.push(
+            " ON CONFLICT (\"interval\", open_time, source_ticker, target_ticker) DO NOTHING",
+        );
+        let query: sqlx::query::Query<'_, Postgres, sqlx::postgres::PgArguments> =
+            query_builder.build();
+        query.execute(&self.connection_pool).await?;
+        Ok(())
     }
 
     pub async fn save_kline(&self, kline: &KLine) -> anyhow::Result<i32> {
         let save_result: (i32,) = sqlx::query_as(
             r#"
-            INSERT INTO sub_kline_historical (open_time, source_ticker, target_ticker, "open", high, low, "close", volume, close_time, quote_volume, "count", taker_buy_volume, taker_buy_quote_volume)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
-            ON CONFLICT (open_time, source_ticker, target_ticker)
-            DO UPDATE SET taker_buy_quote_volume = EXCLUDED.taker_buy_quote_volume
+            INSERT INTO sub_kline_historical ("interval", open_time, source_ticker, target_ticker, "open", high, low, "close", volume, close_time, quote_volume, "count", taker_buy_volume, taker_buy_quote_volume, source)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+            ON CONFLICT ("interval", open_time, source_ticker, target_ticker)
+            DO UPDATE SET "open" = EXCLUDED."open", high = EXCLUDED.high, low = EXCLUDED.low, "close" = EXCLUDED."close", volume = EXCLUDED.volume, close_time = EXCLUDED.close_time, quote_volume = EXCLUDED.quote_volume, "count" = EXCLUDED."count", taker_buy_volume = EXCLUDED.taker_buy_volume, taker_buy_quote_volume = EXCLUDED.taker_buy_quote_volume, source = EXCLUDED.source
             RETURNING id
             "#,
         )
+            .bind(kline.interval.as_str())
             .bind(kline.open_time as i64)
             .bind(kline.source_ticker.as_str())
             .bind(kline.target_ticker.as_str())
@@ -63,6 +139,7 @@ impl PostgreSQLNetworkStorage {
             .bind(kline.count as i32)
             .bind(&kline.taker_buy_volume)
             .bind(&kline.taker_buy_quote_volume)
+            .bind(kline.source.as_str())
             .fetch_one(&self.connection_pool)
             .await?;
         Ok(save_result.0)
@@ -75,39 +152,78 @@ impl PostgreSQLNetworkStorage {
         Ok(record_count.0 as u64)
     }
 
+    /// Get the k-line of the token in the quote currency that opens at the given timestamp.
     pub async fn get_kline(
         &self,
         source_ticker: &str,
-        target_ticker: &str,
+        quote_currency: &str,
+        interval: KLineInterval,
         timestamp: u64,
-    ) -> anyhow::Result<KLine> {
-        let db_kline: DBKline = sqlx::query_as(
+    ) -> anyhow::Result<Option<KLine>> {
+        let maybe_db_kline: Option<DBKline> = sqlx::query_as(
             r#"
-            SELECT id, open_time, source_ticker, target_ticker, "open", high, low, "close", volume, close_time, quote_volume, "count", taker_buy_volume, taker_buy_quote_volume
+            SELECT id, "interval", open_time, source_ticker, target_ticker, "open", high, low, "close", volume, close_time, quote_volume, "count", taker_buy_volume, taker_buy_quote_volume, source
             FROM sub_kline_historical
-            WHERE source_ticker = $1 AND target_ticker= $2 AND open_time = $3
+            WHERE source_ticker = $1 AND target_ticker = $2 AND "interval" = $3 AND open_time = $4
             "#,
         )
             .bind(source_ticker)
-            .bind(target_ticker)
+            .bind(quote_currency)
+            .bind(interval.as_str())
             .bind(timestamp as i64)
-            .fetch_one(&self.connection_pool)
+            .fetch_optional(&self.connection_pool)
             .await?;
-        Ok(KLine {
+        let db_kline = match maybe_db_kline {
+            Some(db_kline) => db_kline,
+            None => return Ok(None),
+        };
+        Ok(Some(KLine {
             id: db_kline.0 as u32,
-            open_time: db_kline.1 as u64,
-            source_ticker: db_kline.2,
-            target_ticker: db_kline.3,
-            open: db_kline.4,
-            high: db_kline.5,
-            low: db_kline.6,
-            close: db_kline.7,
-            volume: db_kline.8,
-            close_time: db_kline.9 as u64,
-            quote_volume: db_kline.10,
-            count: db_kline.11 as u32,
-            taker_buy_volume: db_kline.12,
-            taker_buy_quote_volume: db_kline.13,
-        })
+            interval: KLineInterval::from_str(&db_kline.1)?,
+            open_time: db_kline.2 as u64,
+            source_ticker: db_kline.3,
+            target_ticker: db_kline.4,
+            open: db_kline.5,
+            high: db_kline.6,
+            low: db_kline.7,
+            close: db_kline.8,
+            volume: db_kline.9,
+            close_time: db_kline.10 as u64,
+            quote_volume: db_kline.11,
+            count: db_kline.12 as u32,
+            taker_buy_volume: db_kline.13,
+            taker_buy_quote_volume: db_kline.14,
+            source: db_kline.15,
+        }))
+    }
+
+    /// Daily close prices with open times in the given closed range, ordered by open time.
+    pub async fn get_kline_close_prices(
+        &self,
+        source_ticker: &str,
+        quote_currency: &str,
+        start_timestamp: u64,
+        end_timestamp: u64,
+    ) -> anyhow::Result<Vec<(u64, f64)>> {
+        let db_closes: Vec<(i64, f64)> = sqlx::query_as(
+            r#"
+            SELECT open_time, "close"::float8
+            FROM sub_kline_historical
+            WHERE source_ticker = $1 AND target_ticker = $2 AND "interval" = $3
+            AND open_time >= $4 AND open_time <= $5
+            ORDER BY open_time ASC
+            "#,
+        )
+        .bind(source_ticker)
+        .bind(quote_currency)
+        .bind(KLineInterval::Day.as_str())
+        .bind(start_timestamp as i64)
+        .bind(end_timestamp as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(db_closes
+            .into_iter()
+            .map(|(open_time, close)| (open_time as u64, close))
+            .collect())
     }
 }
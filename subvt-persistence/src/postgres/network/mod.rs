@@ -13,6 +13,7 @@ pub mod error_log;
 pub mod event;
 pub mod extrinsic;
 pub mod kline;
+pub mod network_status;
 pub mod nft;
 pub mod notify;
 pub mod onekv;
@@ -23,6 +24,7 @@ pub mod report;
 pub mod staking;
 pub mod telegram;
 pub mod telemetry;
+pub mod validator_details;
 
 pub struct PostgreSQLNetworkStorage {
     uri: String,
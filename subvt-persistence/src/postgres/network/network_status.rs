@@ -0,0 +1,135 @@
+use crate::postgres::network::PostgreSQLNetworkStorage;
+use subvt_types::report::NetworkStatusSnapshot;
+
+type PostgresNetworkStatusSnapshot = (
+    i64,
+    i64,
+    i64,
+    String,
+    i64,
+    i64,
+    i64,
+    String,
+    String,
+    i64,
+    String,
+    String,
+    String,
+    String,
+    i64,
+);
+
+const SNAPSHOT_COLUMNS: &str = "era_index, epoch_index, block_number, block_hash, \"timestamp\", active_validator_count, inactive_validator_count, last_era_total_reward, total_stake, return_rate_per_million, min_stake, max_stake, average_stake, median_stake, era_reward_points";
+
+fn get_snapshot(
+    db_snapshot: PostgresNetworkStatusSnapshot,
+) -> anyhow::Result<NetworkStatusSnapshot> {
+    Ok(NetworkStatusSnapshot {
+        era_index: db_snapshot.0 as u32,
+        epoch_index: db_snapshot.1 as u64,
+        block_number: db_snapshot.2 as u64,
+        block_hash: db_snapshot.3,
+        timestamp: db_snapshot.4 as u64,
+        active_validator_count: db_snapshot.5 as u32,
+        inactive_validator_count: db_snapshot.6 as u32,
+        last_era_total_reward: db_snapshot.7.parse()?,
+        total_stake: db_snapshot.8.parse()?,
+        return_rate_per_million: db_snapshot.9 as u32,
+        min_stake: db_snapshot.10.parse()?,
+        max_stake: db_snapshot.11.parse()?,
+        average_stake: db_snapshot.12.parse()?,
+        median_stake: db_snapshot.13.parse()?,
+        era_reward_points: db_snapshot.14 as u32,
+    })
+}
+
+impl PostgreSQLNetworkStorage {
+    /// Saves the snapshot of the session, replacing the previous snapshot of the same session.
+    pub async fn save_network_status_snapshot(
+        &self,
+        snapshot: &NetworkStatusSnapshot,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO sub_network_status (era_index, epoch_index, block_number, block_hash, "timestamp", active_validator_count, inactive_validator_count, last_era_total_reward, total_stake, return_rate_per_million, min_stake, max_stake, average_stake, median_stake, era_reward_points)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+            ON CONFLICT (epoch_index) DO UPDATE
+            SET block_number = EXCLUDED.block_number, block_hash = EXCLUDED.block_hash, "timestamp" = EXCLUDED."timestamp", active_validator_count = EXCLUDED.active_validator_count, inactive_validator_count = EXCLUDED.inactive_validator_count, last_era_total_reward = EXCLUDED.last_era_total_reward, total_stake = EXCLUDED.total_stake, return_rate_per_million = EXCLUDED.return_rate_per_million, min_stake = EXCLUDED.min_stake, max_stake = EXCLUDED.max_stake, average_stake = EXCLUDED.average_stake, median_stake = EXCLUDED.median_stake, era_reward_points = EXCLUDED.era_reward_points, updated_at = now()
+            WHERE sub_network_status.block_number <= EXCLUDED.block_number
+            "#,
+        )
+        .bind(snapshot.era_index as i64)
+        .bind(snapshot.epoch_index as i64)
+        .bind(snapshot.block_number as i64)
+        .bind(&snapshot.block_hash)
+        .bind(snapshot.timestamp as i64)
+        .bind(snapshot.active_validator_count as i64)
+        .bind(snapshot.inactive_validator_count as i64)
+        .bind(snapshot.last_era_total_reward.to_string())
+        .bind(snapshot.total_stake.to_string())
+        .bind(snapshot.return_rate_per_million as i64)
+        .bind(snapshot.min_stake.to_string())
+        .bind(snapshot.max_stake.to_string())
+        .bind(snapshot.average_stake.to_string())
+        .bind(snapshot.median_stake.to_string())
+        .bind(snapshot.era_reward_points as i64)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Session snapshots in the given closed era range, at most `limit` of the latest, ordered
+    /// by session.
+    pub async fn get_session_network_status_snapshots(
+        &self,
+        start_era_index: u32,
+        end_era_index: u32,
+        limit: u32,
+    ) -> anyhow::Result<Vec<NetworkStatusSnapshot>> {
+        let db_snapshots: Vec<PostgresNetworkStatusSnapshot> = sqlx::query_as(&format!(
+            r#"
+            SELECT {SNAPSHOT_COLUMNS} FROM (
+                SELECT * FROM sub_network_status
+                WHERE era_index >= $1 AND era_index <= $2
+                ORDER BY epoch_index DESC
+                LIMIT $3
+            ) AS S
+            ORDER BY epoch_index ASC
+            "#
+        ))
+        .bind(start_era_index as i64)
+        .bind(end_era_index as i64)
+        .bind(limit as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        db_snapshots.into_iter().map(get_snapshot).collect()
+    }
+
+    /// The last session snapshot of each era in the given closed era range, at most `limit` of
+    /// the latest eras, ordered by era.
+    pub async fn get_era_network_status_snapshots(
+        &self,
+        start_era_index: u32,
+        end_era_index: u32,
+        limit: u32,
+    ) -> anyhow::Result<Vec<NetworkStatusSnapshot>> {
+        let db_snapshots: Vec<PostgresNetworkStatusSnapshot> = sqlx::query_as(&format!(
+            r#"
+            SELECT {SNAPSHOT_COLUMNS} FROM (
+                SELECT DISTINCT ON (era_index) *
+                FROM sub_network_status
+                WHERE era_index >= $1 AND era_index <= $2
+                ORDER BY era_index DESC, epoch_index DESC
+                LIMIT $3
+            ) AS S
+            ORDER BY era_index ASC
+            "#
+        ))
+        .bind(start_era_index as i64)
+        .bind(end_era_index as i64)
+        .bind(limit as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        db_snapshots.into_iter().map(get_snapshot).collect()
+    }
+}
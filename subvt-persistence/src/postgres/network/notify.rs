@@ -38,17 +38,10 @@ impl PostgreSQLNetworkStorage {
 
     pub async fn notify_block_processed(
         &self,
-        block_number: u64,
-        block_hash: &str,
+        notification: &BlockProcessedNotification,
     ) -> anyhow::Result<()> {
-        self.notify(
-            Channel::BlockProcessed.get_name(),
-            &BlockProcessedNotification {
-                block_number,
-                block_hash: block_hash.to_string(),
-            },
-        )
-        .await
+        self.notify(Channel::BlockProcessed.get_name(), notification)
+            .await
     }
 
     pub async fn subscribe_to_processed_blocks<F>(
@@ -160,4 +153,40 @@ impl PostgreSQLNetworkStorage {
         .await?;
         Ok(())
     }
+
+    pub async fn notification_generator_has_processed_performance_session(
+        &self,
+        session_index: u64,
+    ) -> anyhow::Result<bool> {
+        let result: (bool,) = sqlx::query_as(
+            r#"
+                SELECT EXISTS(
+                    SELECT session_index
+                    FROM sub_notification_generator_processed_performance_session
+                    WHERE session_index = $1
+                )
+                "#,
+        )
+        .bind(session_index as i64)
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(result.0)
+    }
+
+    pub async fn save_notification_generator_processed_performance_session(
+        &self,
+        session_index: u64,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO sub_notification_generator_processed_performance_session(session_index)
+            VALUES ($1)
+            ON CONFLICT(session_index) DO NOTHING
+            "#,
+        )
+        .bind(session_index as i64)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
 }
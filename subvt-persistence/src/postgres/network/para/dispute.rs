@@ -0,0 +1,255 @@
+use crate::postgres::network::PostgreSQLNetworkStorage;
+use std::str::FromStr;
+use subvt_types::crypto::AccountId;
+use subvt_types::report::{BlockSummary, ParaDispute, ValidatorParaDisputeStatement};
+
+pub(crate) type PostgresParaDispute = (
+    i32,
+    String,
+    Option<i64>,
+    Option<String>,
+    Option<i64>,
+    Option<bool>,
+    Option<i64>,
+    Option<String>,
+    Option<i64>,
+    Option<bool>,
+    i64,
+    i64,
+);
+
+pub(crate) type PostgresParaDisputeStatement =
+    (String, String, i64, i64, i64, String, i64, bool, String);
+
+pub(crate) const PARA_DISPUTE_SELECT: &str = r#"
+    SELECT D.id, D.candidate_hash, IB.number, IB.hash, IB.timestamp, D.is_local, CB.number, CB.hash, CB.timestamp, D.is_valid,
+    (SELECT COUNT(*) FROM sub_para_dispute_statement S WHERE S.candidate_hash = D.candidate_hash AND S.is_valid),
+    (SELECT COUNT(*) FROM sub_para_dispute_statement S WHERE S.candidate_hash = D.candidate_hash AND NOT S.is_valid)
+    FROM sub_para_dispute D
+    LEFT JOIN sub_block IB ON IB.hash = D.initiated_block_hash
+    LEFT JOIN sub_block CB ON CB.hash = D.concluded_block_hash
+"#;
+
+fn get_block_summary(
+    maybe_number: Option<i64>,
+    maybe_hash: &Option<String>,
+    maybe_timestamp: Option<i64>,
+) -> Option<BlockSummary> {
+    match (maybe_number, maybe_hash, maybe_timestamp) {
+        (Some(number), Some(hash), Some(timestamp)) => Some(BlockSummary {
+            number: number as u64,
+            hash: hash.clone(),
+            timestamp: timestamp as u64,
+        }),
+        _ => None,
+    }
+}
+
+pub(crate) fn para_dispute_from_db(db_dispute: &PostgresParaDispute) -> ParaDispute {
+    ParaDispute {
+        id: db_dispute.0 as u32,
+        candidate_hash: db_dispute.1.clone(),
+        initiated_block: get_block_summary(db_dispute.2, &db_dispute.3, db_dispute.4),
+        is_local: db_dispute.5,
+        concluded_block: get_block_summary(db_dispute.6, &db_dispute.7, db_dispute.8),
+        is_valid: db_dispute.9,
+        valid_statement_count: db_dispute.10 as u32,
+        invalid_statement_count: db_dispute.11 as u32,
+    }
+}
+
+impl PostgreSQLNetworkStorage {
+    pub async fn save_para_dispute_initiated(
+        &self,
+        candidate_hash: &str,
+        block_hash: &str,
+        event_index: i32,
+        is_local: bool,
+    ) -> anyhow::Result<i32> {
+        let result: (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO sub_para_dispute (candidate_hash, initiated_block_hash, initiated_event_index, is_local)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (candidate_hash) DO UPDATE
+            SET initiated_block_hash = EXCLUDED.initiated_block_hash, initiated_event_index = EXCLUDED.initiated_event_index, is_local = EXCLUDED.is_local, updated_at = now()
+            RETURNING id
+            "#,
+        )
+        .bind(candidate_hash)
+        .bind(block_hash)
+        .bind(event_index)
+        .bind(is_local)
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(result.0)
+    }
+
+    pub async fn save_para_dispute_concluded(
+        &self,
+        candidate_hash: &str,
+        block_hash: &str,
+        event_index: i32,
+        is_valid: bool,
+    ) -> anyhow::Result<i32> {
+        let result: (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO sub_para_dispute (candidate_hash, concluded_block_hash, concluded_event_index, is_valid)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (candidate_hash) DO UPDATE
+            SET concluded_block_hash = EXCLUDED.concluded_block_hash, concluded_event_index = EXCLUDED.concluded_event_index, is_valid = EXCLUDED.is_valid, updated_at = now()
+            RETURNING id
+            "#,
+        )
+        .bind(candidate_hash)
+        .bind(block_hash)
+        .bind(event_index)
+        .bind(is_valid)
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(result.0)
+    }
+
+    /// Saves a dispute statement included in the `ParaInherent` of a relay chain block.
+    /// A validator's statement for a candidate is saved only once.
+    pub async fn save_para_dispute_statement(
+        &self,
+        block_hash: &str,
+        session_index: u32,
+        candidate_hash: &str,
+        para_validator_index: u32,
+        is_valid: bool,
+        kind: &str,
+    ) -> anyhow::Result<Option<i32>> {
+        let maybe_result: Option<(i32,)> = sqlx::query_as(
+            r#"
+            INSERT INTO sub_para_dispute_statement (block_hash, session_index, candidate_hash, para_validator_index, is_valid, kind)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (session_index, candidate_hash, para_validator_index) DO NOTHING
+            RETURNING id
+            "#,
+        )
+        .bind(block_hash)
+        .bind(session_index as i64)
+        .bind(candidate_hash)
+        .bind(para_validator_index as i64)
+        .bind(is_valid)
+        .bind(kind)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        Ok(maybe_result.map(|result| result.0))
+    }
+
+    /// Disputes that have been initiated (or concluded) but not yet inspected by the
+    /// notification generator. An initiated dispute stays pending until it's concluded, as its
+    /// statements keep getting included in the relay chain blocks.
+    pub async fn get_para_disputes_pending_notification(
+        &self,
+        is_conclusion: bool,
+    ) -> anyhow::Result<Vec<ParaDispute>> {
+        let condition = if is_conclusion {
+            "WHERE D.concluded_block_hash IS NOT NULL AND NOT D.is_conclusion_notified"
+        } else {
+            "WHERE D.initiated_block_hash IS NOT NULL AND NOT D.is_initiation_notified"
+        };
+        let db_disputes: Vec<PostgresParaDispute> = sqlx::query_as(&format!(
+            "{PARA_DISPUTE_SELECT} {condition} ORDER BY D.id ASC"
+        ))
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(db_disputes.iter().map(para_dispute_from_db).collect())
+    }
+
+    pub async fn set_para_dispute_notified(
+        &self,
+        id: u32,
+        is_conclusion: bool,
+    ) -> anyhow::Result<()> {
+        let query = if is_conclusion {
+            "UPDATE sub_para_dispute SET is_conclusion_notified = true, updated_at = now() WHERE id = $1"
+        } else {
+            "UPDATE sub_para_dispute SET is_initiation_notified = true, updated_at = now() WHERE id = $1"
+        };
+        sqlx::query(query)
+            .bind(id as i32)
+            .execute(&self.connection_pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Marks the given statements as inspected for the initiation notification of their dispute.
+    pub async fn set_para_dispute_statements_initiation_notified(
+        &self,
+        statements: &[ValidatorParaDisputeStatement],
+    ) -> anyhow::Result<()> {
+        for statement in statements {
+            sqlx::query(
+                r#"
+                UPDATE sub_para_dispute_statement
+                SET is_initiation_notified = true
+                WHERE session_index = $1 AND candidate_hash = $2 AND para_validator_index = $3
+                "#,
+            )
+            .bind(statement.session_index as i64)
+            .bind(&statement.dispute.candidate_hash)
+            .bind(statement.para_validator_index as i64)
+            .execute(&self.connection_pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Statements of the validators in the given dispute, only the ones whose validators haven't
+    /// been notified of the initiation of the dispute yet if `only_pending_initiation_notification`
+    /// is `true`. Statements by validators whose para validator index cannot be mapped to an
+    /// account in the session are omitted.
+    pub async fn get_para_dispute_statements(
+        &self,
+        dispute: &ParaDispute,
+        only_pending_initiation_notification: bool,
+    ) -> anyhow::Result<Vec<ValidatorParaDisputeStatement>> {
+        let db_statements: Vec<PostgresParaDisputeStatement> = sqlx::query_as(
+            r#"
+            SELECT S.candidate_hash, SPV.validator_account_id, S.session_index, S.para_validator_index, B.number, B.hash, B.timestamp, S.is_valid, S.kind
+            FROM sub_para_dispute_statement S
+            INNER JOIN sub_block B ON B.hash = S.block_hash
+            INNER JOIN sub_session_para_validator SPV
+                ON SPV.session_index = S.session_index
+                AND SPV.para_validator_index = S.para_validator_index
+            WHERE S.candidate_hash = $1
+            AND (NOT $2 OR NOT S.is_initiation_notified)
+            ORDER BY S.para_validator_index ASC
+            "#,
+        )
+        .bind(&dispute.candidate_hash)
+        .bind(only_pending_initiation_notification)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut statements = Vec::with_capacity(db_statements.len());
+        for db_statement in &db_statements {
+            statements.push(para_dispute_statement_from_db(
+                dispute.clone(),
+                db_statement,
+            )?);
+        }
+        Ok(statements)
+    }
+}
+
+pub(crate) fn para_dispute_statement_from_db(
+    dispute: ParaDispute,
+    db_statement: &PostgresParaDisputeStatement,
+) -> anyhow::Result<ValidatorParaDisputeStatement> {
+    Ok(ValidatorParaDisputeStatement {
+        dispute,
+        validator_account_id: AccountId::from_str(&db_statement.1)?,
+        session_index: db_statement.2 as u64,
+        para_validator_index: db_statement.3 as u64,
+        block: BlockSummary {
+            number: db_statement.4 as u64,
+            hash: db_statement.5.clone(),
+            timestamp: db_statement.6 as u64,
+        },
+        is_valid: db_statement.7,
+        kind: db_statement.8.clone(),
+    })
+}
@@ -1,3 +1,5 @@
+pub mod dispute;
+
 use crate::postgres::network::PostgreSQLNetworkStorage;
 use sqlx::{Postgres, QueryBuilder};
 use subvt_types::{
@@ -1,5 +1,47 @@
 use crate::postgres::network::PostgreSQLNetworkStorage;
-use subvt_types::performance::SessionValidatorPerformance;
+use std::str::FromStr;
+use subvt_types::crypto::AccountId;
+use subvt_types::performance::{
+    EraValidatorPerformance, NetworkPerformancePercentiles, PerformancePercentiles,
+    SessionValidatorPerformance,
+};
+
+type PostgresSessionValidatorPerformance = (
+    i32,
+    String,
+    i64,
+    i64,
+    i64,
+    i32,
+    Option<i64>,
+    Option<i64>,
+    Option<i32>,
+    Option<i32>,
+    Option<i32>,
+    Option<i32>,
+    Option<f64>,
+    Option<f64>,
+);
+
+type PostgresNetworkPerformancePercentiles = (i64, i64, i64, Option<Vec<f64>>, Option<Vec<f64>>);
+
+fn network_performance_percentiles_from_db(
+    db_percentiles: &PostgresNetworkPerformancePercentiles,
+) -> NetworkPerformancePercentiles {
+    NetworkPerformancePercentiles {
+        index: db_percentiles.0 as u64,
+        validator_count: db_percentiles.1 as u32,
+        para_validator_count: db_percentiles.2 as u32,
+        authored_block_count: db_percentiles
+            .3
+            .as_ref()
+            .and_then(|values| PerformancePercentiles::from_values(values)),
+        attestations_per_billion: db_percentiles
+            .4
+            .as_ref()
+            .and_then(|values| PerformancePercentiles::from_values(values)),
+    }
+}
 
 impl PostgreSQLNetworkStorage {
     pub async fn get_session_validator_performance_updater_last_processed_session_id(
@@ -25,8 +67,8 @@ impl PostgreSQLNetworkStorage {
             log::info!("Persist {} of {}.", i + 1, performances.len());
             sqlx::query(
                 r#"
-                INSERT INTO sub_session_validator_performance (validator_account_id, era_index, session_index, active_validator_index, authored_block_count, para_validator_group_index, para_validator_index, implicit_attestation_count, explicit_attestation_count, missed_attestation_count, attestations_per_billion)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                INSERT INTO sub_session_validator_performance (validator_account_id, era_index, session_index, active_validator_index, authored_block_count, para_validator_group_index, para_validator_index, implicit_attestation_count, explicit_attestation_count, missed_attestation_count, attestations_per_billion, expected_block_count, expected_primary_slot_count)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
                 ON CONFLICT(validator_account_id, era_index, session_index) DO NOTHING
                 RETURNING id
                 "#,
@@ -42,10 +84,209 @@ impl PostgreSQLNetworkStorage {
                 .bind(performance.explicit_attestation_count.map(|i| i as i32))
                 .bind(performance.missed_attestation_count.map(|i| i as i32))
                 .bind(performance.attestations_per_billion.map(|i| i as i32))
+                .bind(performance.expected_block_count)
+                .bind(performance.expected_primary_slot_count)
                 .execute(&mut *transaction)
                 .await?;
         }
         transaction.commit().await?;
         Ok(())
     }
+
+    /// Performances of a validator in the given session range (inclusive), ordered by session.
+    pub async fn get_validator_session_performances(
+        &self,
+        validator_account_id: &AccountId,
+        start_session_index: u64,
+        end_session_index: u64,
+    ) -> anyhow::Result<Vec<SessionValidatorPerformance>> {
+        let db_performances: Vec<PostgresSessionValidatorPerformance> = sqlx::query_as(
+            r#"
+            SELECT id, validator_account_id, era_index, session_index, active_validator_index, authored_block_count, para_validator_group_index, para_validator_index, implicit_attestation_count, explicit_attestation_count, missed_attestation_count, attestations_per_billion, expected_block_count, expected_primary_slot_count
+            FROM sub_session_validator_performance
+            WHERE validator_account_id = $1
+            AND session_index BETWEEN $2 AND $3
+            ORDER BY session_index ASC
+            "#,
+        )
+        .bind(validator_account_id.to_string())
+        .bind(start_session_index as i64)
+        .bind(end_session_index as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut performances = Vec::with_capacity(db_performances.len());
+        for db_performance in &db_performances {
+            performances.push(SessionValidatorPerformance {
+                id: db_performance.0 as u64,
+                validator_account_id: AccountId::from_str(&db_performance.1)?,
+                era_index: db_performance.2 as u32,
+                session_index: db_performance.3 as u64,
+                active_validator_index: db_performance.4 as u64,
+                authored_block_count: db_performance.5 as u32,
+                para_validator_group_index: db_performance.6.map(|index| index as u64),
+                para_validator_index: db_performance.7.map(|index| index as u64),
+                implicit_attestation_count: db_performance.8.map(|count| count as u32),
+                explicit_attestation_count: db_performance.9.map(|count| count as u32),
+                missed_attestation_count: db_performance.10.map(|count| count as u32),
+                attestations_per_billion: db_performance.11.map(|apb| apb as u32),
+                expected_block_count: db_performance.12,
+                expected_primary_slot_count: db_performance.13,
+            });
+        }
+        Ok(performances)
+    }
+
+    /// Per-era aggregate performances of a validator in the given era range (inclusive).
+    pub async fn get_validator_era_performances(
+        &self,
+        validator_account_id: &AccountId,
+        start_era_index: u32,
+        end_era_index: u32,
+    ) -> anyhow::Result<Vec<EraValidatorPerformance>> {
+        let db_performances: Vec<(i64, i64, i64, i64, i64, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT era_index, COUNT(*), COUNT(para_validator_index), SUM(authored_block_count), SUM(COALESCE(implicit_attestation_count, 0)), SUM(COALESCE(explicit_attestation_count, 0)), SUM(COALESCE(missed_attestation_count, 0))
+            FROM sub_session_validator_performance
+            WHERE validator_account_id = $1
+            AND era_index BETWEEN $2 AND $3
+            GROUP BY era_index
+            ORDER BY era_index ASC
+            "#,
+        )
+        .bind(validator_account_id.to_string())
+        .bind(start_era_index as i64)
+        .bind(end_era_index as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(db_performances
+            .iter()
+            .map(|db_performance| {
+                let attestation_count = (db_performance.4 + db_performance.5) as u64;
+                let total_attestation_slots = attestation_count + db_performance.6 as u64;
+                EraValidatorPerformance {
+                    era_index: db_performance.0 as u32,
+                    active_session_count: db_performance.1 as u32,
+                    para_validator_session_count: db_performance.2 as u32,
+                    authored_block_count: db_performance.3 as u32,
+                    implicit_attestation_count: db_performance.4 as u32,
+                    explicit_attestation_count: db_performance.5 as u32,
+                    missed_attestation_count: db_performance.6 as u32,
+                    attestations_per_billion: if total_attestation_slots > 0 {
+                        Some((attestation_count * 1_000_000_000 / total_attestation_slots) as u32)
+                    } else {
+                        None
+                    },
+                }
+            })
+            .collect())
+    }
+
+    /// Per-era aggregate performances of all the validators in the given era range (inclusive),
+    /// each with the account id of its validator.
+    pub async fn get_era_validator_performances(
+        &self,
+        start_era_index: u32,
+        end_era_index: u32,
+    ) -> anyhow::Result<Vec<(AccountId, EraValidatorPerformance)>> {
+        let db_performances: Vec<(String, i64, i64, i64, i64, i64, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT validator_account_id, era_index, COUNT(*), COUNT(para_validator_index), SUM(authored_block_count), SUM(COALESCE(implicit_attestation_count, 0)), SUM(COALESCE(explicit_attestation_count, 0)), SUM(COALESCE(missed_attestation_count, 0))
+            FROM sub_session_validator_performance
+            WHERE era_index BETWEEN $1 AND $2
+            GROUP BY validator_account_id, era_index
+            ORDER BY era_index ASC
+            "#,
+        )
+        .bind(start_era_index as i64)
+        .bind(end_era_index as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut performances = Vec::with_capacity(db_performances.len());
+        for db_performance in db_performances {
+            let attestation_count = (db_performance.5 + db_performance.6) as u64;
+            let total_attestation_slots = attestation_count + db_performance.7 as u64;
+            performances.push((
+                AccountId::from_str(&db_performance.0)?,
+                EraValidatorPerformance {
+                    era_index: db_performance.1 as u32,
+                    active_session_count: db_performance.2 as u32,
+                    para_validator_session_count: db_performance.3 as u32,
+                    authored_block_count: db_performance.4 as u32,
+                    implicit_attestation_count: db_performance.5 as u32,
+                    explicit_attestation_count: db_performance.6 as u32,
+                    missed_attestation_count: db_performance.7 as u32,
+                    attestations_per_billion: if total_attestation_slots > 0 {
+                        Some((attestation_count * 1_000_000_000 / total_attestation_slots) as u32)
+                    } else {
+                        None
+                    },
+                },
+            ));
+        }
+        Ok(performances)
+    }
+
+    /// Network-wide percentiles of authored blocks and attestation rates per session.
+    pub async fn get_session_performance_percentiles(
+        &self,
+        start_session_index: u64,
+        end_session_index: u64,
+    ) -> anyhow::Result<Vec<NetworkPerformancePercentiles>> {
+        let db_percentiles: Vec<PostgresNetworkPerformancePercentiles> = sqlx::query_as(
+            r#"
+            SELECT session_index, COUNT(*), COUNT(attestations_per_billion),
+            percentile_cont(ARRAY[0.1, 0.25, 0.5, 0.75, 0.9]) WITHIN GROUP (ORDER BY authored_block_count),
+            percentile_cont(ARRAY[0.1, 0.25, 0.5, 0.75, 0.9]) WITHIN GROUP (ORDER BY attestations_per_billion)
+            FROM sub_session_validator_performance
+            WHERE session_index BETWEEN $1 AND $2
+            GROUP BY session_index
+            ORDER BY session_index ASC
+            "#,
+        )
+        .bind(start_session_index as i64)
+        .bind(end_session_index as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(db_percentiles
+            .iter()
+            .map(network_performance_percentiles_from_db)
+            .collect())
+    }
+
+    /// Network-wide percentiles of authored blocks and attestation rates per era, calculated
+    /// over the era aggregates of the validators.
+    pub async fn get_era_performance_percentiles(
+        &self,
+        start_era_index: u32,
+        end_era_index: u32,
+    ) -> anyhow::Result<Vec<NetworkPerformancePercentiles>> {
+        let db_percentiles: Vec<PostgresNetworkPerformancePercentiles> = sqlx::query_as(
+            r#"
+            SELECT era_index, COUNT(*), COUNT(attestations_per_billion),
+            percentile_cont(ARRAY[0.1, 0.25, 0.5, 0.75, 0.9]) WITHIN GROUP (ORDER BY authored_block_count),
+            percentile_cont(ARRAY[0.1, 0.25, 0.5, 0.75, 0.9]) WITHIN GROUP (ORDER BY attestations_per_billion)
+            FROM (
+                SELECT era_index, validator_account_id, SUM(authored_block_count) AS authored_block_count,
+                CASE
+                    WHEN SUM(COALESCE(implicit_attestation_count, 0) + COALESCE(explicit_attestation_count, 0) + COALESCE(missed_attestation_count, 0)) > 0
+                    THEN SUM(COALESCE(implicit_attestation_count, 0) + COALESCE(explicit_attestation_count, 0)) * 1000000000 / SUM(COALESCE(implicit_attestation_count, 0) + COALESCE(explicit_attestation_count, 0) + COALESCE(missed_attestation_count, 0))
+                    ELSE NULL
+                END AS attestations_per_billion
+                FROM sub_session_validator_performance
+                WHERE era_index BETWEEN $1 AND $2
+                GROUP BY era_index, validator_account_id
+            ) P
+            GROUP BY era_index
+            ORDER BY era_index ASC
+            "#,
+        )
+        .bind(start_era_index as i64)
+        .bind(end_era_index as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(db_percentiles
+            .iter()
+            .map(network_performance_percentiles_from_db)
+            .collect())
+    }
 }
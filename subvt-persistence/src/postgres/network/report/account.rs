@@ -0,0 +1,100 @@
+use crate::postgres::network::report::parse_maybe_string;
+use crate::postgres::network::PostgreSQLNetworkStorage;
+use std::str::FromStr;
+use subvt_types::crypto::AccountId;
+use subvt_types::report::{AccountActivity, AccountActivityType};
+
+type PostgresAccountActivity = (
+    String,
+    i64,
+    i64,
+    Option<i32>,
+    Option<i32>,
+    String,
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<bool>,
+);
+
+impl PostgreSQLNetworkStorage {
+    /// Activity feed of the account, in reverse chronological order. Consists of the
+    /// `Balances` and staking events, and the staking extrinsics that involve the account.
+    pub async fn get_account_activity(
+        &self,
+        account_id: &AccountId,
+        maybe_before_timestamp: Option<u64>,
+        limit: u32,
+    ) -> anyhow::Result<Vec<AccountActivity>> {
+        let db_activities: Vec<PostgresAccountActivity> = sqlx::query_as(
+            r#"
+            SELECT A.block_hash, B.number, B.timestamp, A.extrinsic_index, A.event_index, A.activity_type, A.module, A.name, A.counterparty_account_id, A.amount, A.is_successful
+            FROM (
+                SELECT block_hash, extrinsic_index, event_index, 'event' AS activity_type, 'Balances' AS module, event_name AS name, CASE WHEN account_id = $1 THEN counterparty_account_id ELSE account_id END AS counterparty_account_id, amount, NULL::boolean AS is_successful
+                FROM sub_event_balances
+                WHERE account_id = $1 OR counterparty_account_id = $1
+                UNION ALL
+                SELECT block_hash, extrinsic_index, event_index, 'event', 'Staking', 'Chilled', NULL, NULL, NULL
+                FROM sub_event_chilled
+                WHERE stash_account_id = $1
+                UNION ALL
+                SELECT block_hash, extrinsic_index, event_index, 'event', 'Staking', 'Slashed', NULL, amount, NULL
+                FROM sub_event_slashed
+                WHERE validator_account_id = $1
+                UNION ALL
+                SELECT block_hash, extrinsic_index, event_index, 'event', 'Staking', 'Rewarded', NULL, amount, NULL
+                FROM sub_event_rewarded
+                WHERE rewardee_account_id = $1
+                UNION ALL
+                SELECT block_hash, extrinsic_index, NULL, 'extrinsic', 'Staking', 'validate', CASE WHEN stash_account_id = $1 THEN controller_account_id ELSE stash_account_id END, NULL, is_successful
+                FROM sub_extrinsic_validate
+                WHERE stash_account_id = $1 OR controller_account_id = $1
+                UNION ALL
+                SELECT block_hash, extrinsic_index, NULL, 'extrinsic', 'Staking', 'nominate', NULL, NULL, is_successful
+                FROM sub_extrinsic_nominate
+                WHERE controller_account_id = $1
+                UNION ALL
+                SELECT block_hash, extrinsic_index, NULL, 'extrinsic', 'Staking', 'payout_stakers', CASE WHEN caller_account_id = $1 THEN validator_account_id ELSE caller_account_id END, NULL, is_successful
+                FROM sub_extrinsic_payout_stakers
+                WHERE caller_account_id = $1 OR validator_account_id = $1
+            ) A
+            INNER JOIN sub_block B
+                ON B.hash = A.block_hash
+            WHERE $2::bigint IS NULL OR B.timestamp < $2
+            ORDER BY B.timestamp DESC, A.extrinsic_index DESC NULLS LAST, A.event_index DESC NULLS LAST
+            LIMIT $3
+            "#,
+        )
+        .bind(account_id.to_string())
+        .bind(maybe_before_timestamp.map(|timestamp| timestamp as i64))
+        .bind(limit as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut activities = Vec::with_capacity(db_activities.len());
+        for db_activity in db_activities {
+            activities.push(AccountActivity {
+                block_hash: db_activity.0,
+                block_number: db_activity.1 as u64,
+                block_timestamp: db_activity.2 as u64,
+                extrinsic_index: db_activity.3.map(|index| index as u32),
+                event_index: db_activity.4.map(|index| index as u32),
+                activity_type: if db_activity.5 == "extrinsic" {
+                    AccountActivityType::Extrinsic
+                } else {
+                    AccountActivityType::Event
+                },
+                module: db_activity.6,
+                name: db_activity.7,
+                counterparty_account_id: if let Some(account_id) = &db_activity.8 {
+                    Some(AccountId::from_str(account_id)?)
+                } else {
+                    None
+                },
+                amount: parse_maybe_string(&db_activity.9)?,
+                is_successful: db_activity.10,
+            });
+        }
+        Ok(activities)
+    }
+}
@@ -0,0 +1,81 @@
+use crate::postgres::network::para::dispute::{
+    para_dispute_from_db, para_dispute_statement_from_db, PostgresParaDispute,
+    PostgresParaDisputeStatement, PARA_DISPUTE_SELECT,
+};
+use crate::postgres::network::PostgreSQLNetworkStorage;
+use rustc_hash::FxHashMap as HashMap;
+use subvt_types::crypto::AccountId;
+use subvt_types::report::{ParaDispute, ValidatorParaDisputeStatement};
+
+impl PostgreSQLNetworkStorage {
+    /// Most recent disputes, newest first. Pass the id of the last dispute of the previous page
+    /// as `maybe_before_id` to get the next page.
+    pub async fn get_para_disputes(
+        &self,
+        maybe_before_id: Option<u32>,
+        limit: u32,
+    ) -> anyhow::Result<Vec<ParaDispute>> {
+        let db_disputes: Vec<PostgresParaDispute> = sqlx::query_as(&format!(
+            "{PARA_DISPUTE_SELECT} WHERE ($1::integer IS NULL OR D.id < $1) ORDER BY D.id DESC LIMIT $2"
+        ))
+        .bind(maybe_before_id.map(|id| id as i32))
+        .bind(limit as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(db_disputes.iter().map(para_dispute_from_db).collect())
+    }
+
+    /// Dispute statements of a validator, newest first. When `only_against_supermajority` is
+    /// set, only the statements that contradict the concluded result of the dispute are returned.
+    pub async fn get_validator_para_dispute_statements(
+        &self,
+        validator_account_id: &AccountId,
+        only_against_supermajority: bool,
+        limit: u32,
+    ) -> anyhow::Result<Vec<ValidatorParaDisputeStatement>> {
+        let db_statements: Vec<PostgresParaDisputeStatement> = sqlx::query_as(
+            r#"
+            SELECT S.candidate_hash, SPV.validator_account_id, S.session_index, S.para_validator_index, B.number, B.hash, B.timestamp, S.is_valid, S.kind
+            FROM sub_para_dispute_statement S
+            INNER JOIN sub_block B ON B.hash = S.block_hash
+            INNER JOIN sub_para_dispute D ON D.candidate_hash = S.candidate_hash
+            INNER JOIN sub_session_para_validator SPV
+                ON SPV.session_index = S.session_index
+                AND SPV.para_validator_index = S.para_validator_index
+            WHERE SPV.validator_account_id = $1
+            AND (NOT $2 OR (D.is_valid IS NOT NULL AND D.is_valid <> S.is_valid))
+            ORDER BY B.number DESC
+            LIMIT $3
+            "#,
+        )
+        .bind(validator_account_id.to_string())
+        .bind(only_against_supermajority)
+        .bind(limit as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let candidate_hashes: Vec<String> = db_statements
+            .iter()
+            .map(|db_statement| db_statement.0.clone())
+            .collect();
+        let db_disputes: Vec<PostgresParaDispute> = sqlx::query_as(&format!(
+            "{PARA_DISPUTE_SELECT} WHERE D.candidate_hash = ANY($1)"
+        ))
+        .bind(&candidate_hashes)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let dispute_map: HashMap<String, ParaDispute> = db_disputes
+            .iter()
+            .map(|db_dispute| (db_dispute.1.clone(), para_dispute_from_db(db_dispute)))
+            .collect();
+        let mut statements = Vec::with_capacity(db_statements.len());
+        for db_statement in &db_statements {
+            if let Some(dispute) = dispute_map.get(&db_statement.0) {
+                statements.push(para_dispute_statement_from_db(
+                    dispute.clone(),
+                    db_statement,
+                )?);
+            }
+        }
+        Ok(statements)
+    }
+}
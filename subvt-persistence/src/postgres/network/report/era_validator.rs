@@ -20,6 +20,59 @@ type PostgresEraValidatorReport = (
     i32,
 );
 
+type PostgresAccountEraValidatorReport = (
+    String,
+    i64,
+    Option<i64>,
+    Option<i64>,
+    Option<bool>,
+    Option<i64>,
+    Option<String>,
+    Option<String>,
+    i32,
+    Option<i32>,
+    i64,
+    i64,
+    i32,
+    i64,
+    i32,
+);
+
+fn era_validator_report_from_db(
+    era_index: u32,
+    era_validator_report: &PostgresEraValidatorReport,
+) -> anyhow::Result<Option<EraValidatorReport>> {
+    let maybe_era = if era_validator_report.0.is_some() & era_validator_report.1.is_some() {
+        Some(Era {
+            index: era_index,
+            start_timestamp: era_validator_report.0.unwrap() as u64,
+            end_timestamp: era_validator_report.1.unwrap() as u64,
+        })
+    } else {
+        None
+    };
+    if let Some(era) = maybe_era {
+        Ok(Some(EraValidatorReport {
+            account_id: None,
+            address: None,
+            era: Some(era),
+            is_active: era_validator_report.2,
+            commission_per_billion: era_validator_report.3.map(|value| value as u32),
+            self_stake: super::parse_maybe_string(&era_validator_report.4)?,
+            total_stake: super::parse_maybe_string(&era_validator_report.5)?,
+            block_count: era_validator_report.6 as u32,
+            reward_points: era_validator_report.7.map(|value| value as u128),
+            self_reward: era_validator_report.8 as u128,
+            staker_reward: era_validator_report.9 as u128,
+            offline_offence_count: era_validator_report.10 as u16,
+            slashed_amount: era_validator_report.11 as u128,
+            chilling_count: era_validator_report.12 as u16,
+        }))
+    } else {
+        Ok(None)
+    }
+}
+
 impl PostgreSQLNetworkStorage {
     pub async fn get_era_validator_account_ids(
         &self,
@@ -58,35 +111,7 @@ impl PostgreSQLNetworkStorage {
             .bind(validator_account_id_hex_string)
             .fetch_one(&self.connection_pool)
             .await?;
-        let maybe_era = if era_validator_report.0.is_some() & era_validator_report.1.is_some() {
-            Some(Era {
-                index: era_index,
-                start_timestamp: era_validator_report.0.unwrap() as u64,
-                end_timestamp: era_validator_report.1.unwrap() as u64,
-            })
-        } else {
-            None
-        };
-        if let Some(era) = maybe_era {
-            Ok(Some(EraValidatorReport {
-                account_id: None,
-                address: None,
-                era: Some(era),
-                is_active: era_validator_report.2,
-                commission_per_billion: era_validator_report.3.map(|value| value as u32),
-                self_stake: super::parse_maybe_string(&era_validator_report.4)?,
-                total_stake: super::parse_maybe_string(&era_validator_report.5)?,
-                block_count: era_validator_report.6 as u32,
-                reward_points: era_validator_report.7.map(|value| value as u128),
-                self_reward: era_validator_report.8 as u128,
-                staker_reward: era_validator_report.9 as u128,
-                offline_offence_count: era_validator_report.10 as u16,
-                slashed_amount: era_validator_report.11 as u128,
-                chilling_count: era_validator_report.12 as u16,
-            }))
-        } else {
-            Ok(None)
-        }
+        era_validator_report_from_db(era_index, &era_validator_report)
     }
 
     pub async fn get_era_validator_report(
@@ -112,4 +137,54 @@ impl PostgreSQLNetworkStorage {
         };
         Ok(era_reports)
     }
+
+    /// Era reports of all the validators of the eras in the given range (inclusive), in era
+    /// order, each with the account id of its validator and its address with the given SS58
+    /// prefix.
+    pub async fn get_era_validator_reports(
+        &self,
+        start_era_index: u32,
+        end_era_index: u32,
+        ss58_prefix: u16,
+    ) -> anyhow::Result<Vec<EraValidatorReport>> {
+        let db_reports: Vec<PostgresAccountEraValidatorReport> = sqlx::query_as(
+            r#"
+            SELECT V.validator_account_id, V.era_index, R.era_start_timestamp, R.era_end_timestamp, R.is_active, R.commission_per_billion, R.self_stake, R.total_stake, R.block_count, R.reward_points, R.self_reward, R.staker_reward, R.offline_offence_count, R.slashed_amount, R.chilling_count
+            FROM sub_era_validator V, LATERAL sub_get_era_validator_report(V.era_index, V.validator_account_id) R
+            WHERE V.era_index BETWEEN $1 AND $2
+            ORDER BY V.era_index ASC
+            "#,
+        )
+        .bind(start_era_index as i64)
+        .bind(end_era_index as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut era_reports = Vec::with_capacity(db_reports.len());
+        for db_report in db_reports {
+            let era_validator_report: PostgresEraValidatorReport = (
+                db_report.2,
+                db_report.3,
+                db_report.4,
+                db_report.5,
+                db_report.6,
+                db_report.7,
+                db_report.8,
+                db_report.9,
+                db_report.10,
+                db_report.11,
+                db_report.12,
+                db_report.13,
+                db_report.14,
+            );
+            if let Some(mut report) =
+                era_validator_report_from_db(db_report.1 as u32, &era_validator_report)?
+            {
+                let account_id = AccountId::from_str(&db_report.0)?;
+                report.address = Some(account_id.to_ss58_check_with_version(ss58_prefix));
+                report.account_id = Some(account_id);
+                era_reports.push(report);
+            }
+        }
+        Ok(era_reports)
+    }
 }
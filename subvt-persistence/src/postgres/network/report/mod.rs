@@ -1,7 +1,9 @@
 //! Era and validator report storage and types.
 use std::str::FromStr;
 
+pub mod account;
 pub mod block;
+pub mod dispute;
 pub mod era;
 pub mod era_validator;
 pub mod para;
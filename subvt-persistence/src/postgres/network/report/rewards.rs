@@ -1,7 +1,10 @@
 use crate::postgres::network::PostgreSQLNetworkStorage;
 use std::str::FromStr;
 use subvt_types::crypto::AccountId;
-use subvt_types::report::{Reward, ValidatorTotalReward};
+use subvt_types::report::{
+    is_exposure_page_paid_out, NominatorEraValidatorReward, Reward, RewardExportItem,
+    ValidatorTotalReward,
+};
 use subvt_types::substrate::{Balance, Era};
 
 impl PostgreSQLNetworkStorage {
@@ -82,6 +85,110 @@ impl PostgreSQLNetworkStorage {
             .collect())
     }
 
+    /// Rewards of a nominator from each of its active validators in every era that has started
+    /// after the given timestamp and has its total validator reward set. The exposure totals are
+    /// summed over all the exposure pages of the validator.
+    pub async fn get_nominator_era_rewards(
+        &self,
+        nominator_account_id: &AccountId,
+        start_timestamp: u64,
+    ) -> anyhow::Result<Vec<(Era, NominatorEraValidatorReward)>> {
+        #[allow(clippy::type_complexity)]
+        let db_rewards: Vec<(
+            i64,
+            i64,
+            i64,
+            String,
+            i64,
+            String,
+            String,
+            Option<i64>,
+            Option<String>,
+            i64,
+            String,
+            Option<i32>,
+            Vec<i32>,
+            i64,
+        )> = sqlx::query_as(
+            r#"
+            SELECT E.index, E.start_timestamp, E.end_timestamp, E.total_validator_reward, E.total_reward_points, S.validator_account_id, S.stake, EV.commission_per_billion, EV.self_stake, EV.reward_points,
+                (
+                    SELECT COALESCE(SUM(S2.stake::numeric), 0)::text
+                    FROM sub_era_staker S2
+                    WHERE S2.era_index = S.era_index
+                    AND S2.validator_account_id = S.validator_account_id
+                ),
+                S.page_index,
+                (
+                    SELECT COALESCE(ARRAY_AGG(DISTINCT P.page_index) FILTER (WHERE P.page_index IS NOT NULL), '{}')
+                    FROM sub_extrinsic_payout_stakers P
+                    WHERE P.era_index = S.era_index
+                    AND P.validator_account_id = S.validator_account_id
+                    AND P.is_successful
+                ),
+                (
+                    SELECT COUNT(*)
+                    FROM sub_extrinsic_payout_stakers P
+                    WHERE P.era_index = S.era_index
+                    AND P.validator_account_id = S.validator_account_id
+                    AND P.is_successful
+                    AND P.page_index IS NULL
+                )
+            FROM sub_era_staker S
+            INNER JOIN sub_era E
+                ON E.index = S.era_index
+            INNER JOIN sub_era_validator EV
+                ON EV.era_index = S.era_index
+                AND EV.validator_account_id = S.validator_account_id
+            WHERE S.nominator_account_id = $1
+            AND E.start_timestamp >= $2
+            AND E.total_validator_reward IS NOT NULL
+            AND E.total_reward_points IS NOT NULL
+            ORDER BY E.index ASC, S.validator_account_id ASC
+            "#,
+        )
+        .bind(nominator_account_id.to_string())
+        .bind(start_timestamp as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut result = Vec::with_capacity(db_rewards.len());
+        for db_reward in db_rewards {
+            let self_stake: Balance = match &db_reward.8 {
+                Some(self_stake) => self_stake.parse()?,
+                None => 0,
+            };
+            let nominator_stakes_total: Balance = db_reward.10.parse()?;
+            let paid_page_indices: Vec<u32> = db_reward
+                .12
+                .iter()
+                .map(|page_index| *page_index as u32)
+                .collect();
+            let is_paid_out = is_exposure_page_paid_out(
+                db_reward.11.map(|page_index| page_index as u32),
+                &paid_page_indices,
+                db_reward.13 as u32,
+            );
+            result.push((
+                Era {
+                    index: db_reward.0 as u32,
+                    start_timestamp: db_reward.1 as u64,
+                    end_timestamp: db_reward.2 as u64,
+                },
+                NominatorEraValidatorReward::calculate(
+                    AccountId::from_str(&db_reward.5)?,
+                    db_reward.6.parse()?,
+                    self_stake + nominator_stakes_total,
+                    db_reward.7.unwrap_or(0) as u32,
+                    db_reward.9 as u32,
+                    db_reward.4 as u32,
+                    db_reward.3.parse()?,
+                    is_paid_out,
+                ),
+            ));
+        }
+        Ok(result)
+    }
+
     pub async fn get_validator_total_rewards(
         &self,
         start_timestamp: u64,
@@ -147,4 +254,82 @@ impl PostgreSQLNetworkStorage {
         }
         Ok(rewards)
     }
+
+    /// Reward payouts of the rewardee in the given time range as accounting line items, along
+    /// with the era and validator of the payout and the close price of the token in the quote
+    /// currency at the hour of the payout, or at the day of the payout if there's no hourly
+    /// price. Extrinsic hashes and fiat values are not set.
+    pub async fn get_reward_export_items(
+        &self,
+        rewardee_account_id: &AccountId,
+        start_timestamp: u64,
+        end_timestamp: u64,
+        token_ticker: &str,
+        quote_currency: &str,
+    ) -> anyhow::Result<Vec<RewardExportItem>> {
+        #[allow(clippy::type_complexity)]
+        let db_items: Vec<(
+            String,
+            i64,
+            i64,
+            Option<i32>,
+            i32,
+            String,
+            Option<i64>,
+            Option<String>,
+            Option<f64>,
+        )> = sqlx::query_as(
+            r#"
+            SELECT E.block_hash, B.number, B.timestamp, E.extrinsic_index, E.event_index, E.amount, EX.era_index, EX.validator_account_id, COALESCE(KH."close", KD."close")::float8
+            FROM sub_event_rewarded E
+            INNER JOIN sub_block B
+                ON B.hash = E.block_hash
+            LEFT JOIN sub_extrinsic_payout_stakers EX
+                ON EX.block_hash = E.block_hash
+                AND EX.extrinsic_index = E.extrinsic_index
+                AND COALESCE(EX.nesting_index, '') = COALESCE(E.nesting_index, '')
+                AND EX.is_successful = true
+            LEFT JOIN sub_kline_historical KH
+                ON KH.source_ticker = $4
+                AND KH.target_ticker = $5
+                AND KH."interval" = '1h'
+                AND KH.open_time = B.timestamp - (B.timestamp % 3600000)
+            LEFT JOIN sub_kline_historical KD
+                ON KD.source_ticker = $4
+                AND KD.target_ticker = $5
+                AND KD."interval" = '1d'
+                AND KD.open_time = B.timestamp - (B.timestamp % 86400000)
+            WHERE E.rewardee_account_id = $1
+            AND B.timestamp >= $2 AND B.timestamp < $3
+            ORDER BY B.timestamp ASC, E.event_index ASC
+            "#,
+        )
+        .bind(rewardee_account_id.to_string())
+        .bind(start_timestamp as i64)
+        .bind(end_timestamp as i64)
+        .bind(token_ticker)
+        .bind(quote_currency)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        let mut items = Vec::with_capacity(db_items.len());
+        for db_item in db_items {
+            items.push(RewardExportItem {
+                block_hash: db_item.0,
+                block_number: db_item.1 as u64,
+                block_timestamp: db_item.2 as u64,
+                era_index: db_item.6.map(|era_index| era_index as u32),
+                validator_account_id: match &db_item.7 {
+                    Some(validator_account_id) => Some(AccountId::from_str(validator_account_id)?),
+                    None => None,
+                },
+                extrinsic_index: db_item.3.map(|extrinsic_index| extrinsic_index as u32),
+                extrinsic_hash: None,
+                event_index: db_item.4 as u32,
+                amount: db_item.5.parse()?,
+                price: db_item.8,
+                fiat_value: None,
+            })
+        }
+        Ok(items)
+    }
 }
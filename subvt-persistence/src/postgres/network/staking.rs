@@ -198,6 +198,7 @@ impl PostgreSQLNetworkStorage {
                     validator_stake.account.id,
                     nominator_stake.account.id,
                     nominator_stake.stake,
+                    nominator_stake.page_index,
                 ));
             }
         }
@@ -214,14 +215,15 @@ impl PostgreSQLNetworkStorage {
         }
         for chunk in records.chunks(250) {
             let mut query_builder = QueryBuilder::new(
-                "INSERT INTO sub_era_staker (era_index, validator_account_id, nominator_account_id, stake)",
+                "INSERT INTO sub_era_staker (era_index, validator_account_id, nominator_account_id, stake, page_index)",
             );
             query_builder.push_values(chunk, |mut query, record| {
                 query
                     .push_bind(record.0)
                     .push_bind(record.1.to_string())
                     .push_bind(record.2.to_string())
-                    .push_bind(record.3.to_string());
+                    .push_bind(record.3.to_string())
+                    .push_bind(record.4.map(|page_index| page_index as i32));
             });
             query_builder.push(
                 " ON CONFLICT (era_index, validator_account_id, nominator_account_id) DO NOTHING",
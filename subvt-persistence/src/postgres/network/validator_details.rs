@@ -0,0 +1,100 @@
+use crate::postgres::network::PostgreSQLNetworkStorage;
+use sqlx::{Postgres, QueryBuilder};
+use subvt_types::crypto::AccountId;
+use subvt_types::report::ValidatorDetailsSnapshot;
+use subvt_types::subvt::{ValidatorDetails, ValidatorDetailsDiff};
+
+impl PostgreSQLNetworkStorage {
+    pub async fn validator_details_snapshot_exists(&self, era_index: u32) -> anyhow::Result<bool> {
+        let exists: (bool,) = sqlx::query_as(
+            r#"
+            SELECT EXISTS(SELECT 1 FROM sub_era_validator_details WHERE era_index = $1)
+            "#,
+        )
+        .bind(era_index as i64)
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(exists.0)
+    }
+
+    /// Saves the details snapshots of the validators at the era. Each snapshot is given as the
+    /// validator account id, whether the details JSON is the full details or the diff from the
+    /// previous snapshot of the validator, and the details JSON.
+    pub async fn save_validator_details_snapshots(
+        &self,
+        era_index: u32,
+        block_number: u64,
+        block_hash: &str,
+        snapshots: &[(AccountId, bool, String)],
+    ) -> anyhow::Result<()> {
+        let mut transaction = self.connection_pool.begin().await?;
+        for chunk in snapshots.chunks(250) {
+            let mut query_builder = QueryBuilder::new(
+                "INSERT INTO sub_era_validator_details (era_index, validator_account_id, block_number, block_hash, is_full, details) ",
+            );
+            query_builder.push_values(chunk, |mut query, snapshot| {
+                query
+                    .push_bind(era_index as i64)
+                    .push_bind(snapshot.0.to_string())
+                    .push_bind(block_number as i64)
+                    .push_bind(block_hash)
+                    .push_bind(snapshot.1)
+                    .push_bind(&snapshot.2);
+            });
+            query_builder.push(" ON CONFLICT (era_index, validator_account_id) DO NOTHING");
+            let query: sqlx::query::Query<'_, Postgres, sqlx::postgres::PgArguments> =
+                query_builder.build();
+            query.execute(&mut *transaction).await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Reconstructs the details of the validator at the era from its last full snapshot at or
+    /// before the era and the diffs after it. Returns `None` if the validator has no snapshot
+    /// at the era.
+    pub async fn get_validator_details_snapshot(
+        &self,
+        validator_account_id: &AccountId,
+        era_index: u32,
+    ) -> anyhow::Result<Option<ValidatorDetailsSnapshot>> {
+        let db_snapshots: Vec<(i64, i64, String, bool, String)> = sqlx::query_as(
+            r#"
+            SELECT era_index, block_number, block_hash, is_full, details
+            FROM sub_era_validator_details
+            WHERE validator_account_id = $1
+            AND era_index <= $2
+            AND era_index >= (
+                SELECT MAX(era_index) FROM sub_era_validator_details
+                WHERE validator_account_id = $1 AND era_index <= $2 AND is_full
+            )
+            ORDER BY era_index ASC
+            "#,
+        )
+        .bind(validator_account_id.to_string())
+        .bind(era_index as i64)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        match db_snapshots.last() {
+            Some(last_snapshot) if last_snapshot.0 as u32 == era_index => (),
+            _ => return Ok(None),
+        }
+        // the first snapshot is the last full snapshot
+        let mut db_snapshots = db_snapshots.into_iter();
+        let first_snapshot = db_snapshots.next().unwrap();
+        let mut snapshot = ValidatorDetailsSnapshot {
+            era_index: first_snapshot.0 as u32,
+            block_number: first_snapshot.1 as u64,
+            block_hash: first_snapshot.2,
+            validator_details: serde_json::from_str::<ValidatorDetails>(&first_snapshot.4)?,
+        };
+        for db_snapshot in db_snapshots {
+            let diff: ValidatorDetailsDiff = serde_json::from_str(&db_snapshot.4)?;
+            snapshot.validator_details.apply_diff(&diff);
+            snapshot.era_index = db_snapshot.0 as u32;
+            snapshot.block_number = db_snapshot.1 as u64;
+            snapshot.block_hash = db_snapshot.2;
+        }
+        Ok(Some(snapshot))
+    }
+}
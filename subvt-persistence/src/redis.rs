@@ -4,6 +4,7 @@ use redis::{Client, RedisResult};
 use rustc_hash::FxHashSet as HashSet;
 use subvt_config::Config;
 use subvt_types::crypto::AccountId;
+use subvt_types::election::ElectionPrediction;
 use subvt_types::report::BlockSummary;
 use subvt_types::subvt::{NetworkStatus, ValidatorDetails, ValidatorSummary};
 
@@ -13,12 +14,21 @@ lazy_static! {
 
 pub struct Redis {
     client: Client,
+    chain: String,
 }
 
 impl Redis {
     pub fn new() -> anyhow::Result<Self> {
-        let client = Client::open(CONFIG.redis.url.as_str())?;
-        Ok(Redis { client })
+        Self::for_network(&CONFIG)
+    }
+
+    /// Storage of the network of the given configuration, for the multi-network services.
+    pub fn for_network(config: &Config) -> anyhow::Result<Self> {
+        let client = Client::open(config.redis.url.as_str())?;
+        Ok(Redis {
+            client,
+            chain: config.substrate.chain.clone(),
+        })
     }
 }
 
@@ -31,17 +41,17 @@ impl Redis {
         () = redis::cmd("MSET")
             .arg(format!(
                 "subvt:{}:validators:finalized_block_number",
-                CONFIG.substrate.chain
+                self.chain
             ))
             .arg(block_summary.number)
             .arg(format!(
                 "subvt:{}:validators:finalized_block_hash",
-                CONFIG.substrate.chain
+                self.chain
             ))
             .arg(&block_summary.hash)
             .arg(format!(
                 "subvt:{}:validators:finalized_block_timestamp",
-                CONFIG.substrate.chain
+                self.chain
             ))
             .arg(block_summary.timestamp)
             .query_async(&mut connection)
@@ -58,7 +68,7 @@ impl Redis {
         () = redis::cmd("SADD")
             .arg(format!(
                 "subvt:{}:validators:{finalized_block_number}:active:account_id_set",
-                CONFIG.substrate.chain,
+                self.chain,
             ))
             .arg(account_id.to_string())
             .query_async(&mut connection)
@@ -76,7 +86,7 @@ impl Redis {
         () = redis::cmd("SET")
             .arg(format!(
                 "subvt:{}:validators:{finalized_block_number}:active:validator:{}",
-                CONFIG.substrate.chain, validator_details.account.id,
+                self.chain, validator_details.account.id,
             ))
             .arg(validator_details_json)
             .query_async(&mut connection)
@@ -85,18 +95,9 @@ impl Redis {
     }
 
     pub async fn get_finalized_block_summary(&self) -> anyhow::Result<BlockSummary> {
-        let number_key = format!(
-            "subvt:{}:validators:finalized_block_number",
-            CONFIG.substrate.chain
-        );
-        let hash_key = format!(
-            "subvt:{}:validators:finalized_block_hash",
-            CONFIG.substrate.chain
-        );
-        let timestamp_key = format!(
-            "subvt:{}:validators:finalized_block_timestamp",
-            CONFIG.substrate.chain
-        );
+        let number_key = format!("subvt:{}:validators:finalized_block_number", self.chain);
+        let hash_key = format!("subvt:{}:validators:finalized_block_hash", self.chain);
+        let timestamp_key = format!("subvt:{}:validators:finalized_block_timestamp", self.chain);
         let mut connection = self.client.get_multiplexed_async_connection().await?;
         let (number, hash, timestamp) = redis::cmd("MGET")
             .arg(&[number_key, hash_key, timestamp_key])
@@ -117,7 +118,7 @@ impl Redis {
         let mut connection = self.client.get_multiplexed_async_connection().await?;
         let active_set_key = format!(
             "subvt:{}:validators:{}:active:account_id_set",
-            CONFIG.substrate.chain, block_summary.number
+            self.chain, block_summary.number
         );
         let active_account_ids: Vec<String> = redis::cmd("SMEMBERS")
             .arg(active_set_key)
@@ -125,7 +126,7 @@ impl Redis {
             .await?;
         let inactive_set_key = format!(
             "subvt:{}:validators:{}:inactive:account_id_set",
-            CONFIG.substrate.chain, block_summary.number
+            self.chain, block_summary.number
         );
         let inactive_account_ids: Vec<String> = redis::cmd("SMEMBERS")
             .arg(inactive_set_key)
@@ -146,7 +147,7 @@ impl Redis {
         let mut connection = self.client.get_multiplexed_async_connection().await?;
         let active_validator_key = format!(
             "subvt:{}:validators:{finalized_block_number}:active:validator:{account_id}",
-            CONFIG.substrate.chain,
+            self.chain,
         );
         let active_validator_json_string_result: RedisResult<String> = redis::cmd("GET")
             .arg(active_validator_key)
@@ -157,7 +158,7 @@ impl Redis {
             Err(_) => {
                 let inactive_validator_key = format!(
                     "subvt:{}:validators:{finalized_block_number}:inactive:validator:{account_id}",
-                    CONFIG.substrate.chain,
+                    self.chain,
                 );
                 redis::cmd("GET")
                     .arg(inactive_validator_key)
@@ -172,7 +173,7 @@ impl Redis {
         let mut connection = self.client.get_multiplexed_async_connection().await?;
         let network_status_json = serde_json::to_string(network_status)?;
         () = redis::cmd("SET")
-            .arg(format!("subvt:{}:network_status", CONFIG.substrate.chain))
+            .arg(format!("subvt:{}:network_status", self.chain))
             .arg(network_status_json)
             .query_async(&mut connection)
             .await?;
@@ -181,7 +182,7 @@ impl Redis {
 
     pub async fn get_network_status(&self) -> anyhow::Result<NetworkStatus> {
         let mut connection = self.client.get_multiplexed_async_connection().await?;
-        let key = format!("subvt:{}:network_status", CONFIG.substrate.chain);
+        let key = format!("subvt:{}:network_status", self.chain);
         let status_json_string: String = redis::cmd("GET")
             .arg(key)
             .query_async(&mut connection)
@@ -192,6 +193,24 @@ impl Redis {
         Ok(status)
     }
 
+    /// Next election prediction, written by `subvt-validator-list-updater`.
+    pub async fn get_election_prediction(&self) -> anyhow::Result<Option<ElectionPrediction>> {
+        let mut connection = self.client.get_multiplexed_async_connection().await?;
+        let key = format!("subvt:{}:election_prediction", self.chain);
+        let maybe_prediction_json_string: Option<String> = redis::cmd("GET")
+            .arg(key)
+            .query_async(&mut connection)
+            .await
+            .context("Can't read election prediction from Redis.")?;
+        match maybe_prediction_json_string {
+            Some(prediction_json_string) => Ok(Some(
+                serde_json::from_str(&prediction_json_string)
+                    .context("Can't deserialize election prediction json.")?,
+            )),
+            None => Ok(None),
+        }
+    }
+
     pub async fn get_validator_list(
         &self,
         finalized_block_number: u64,
@@ -200,7 +219,7 @@ impl Redis {
         let mut connection = self.client.get_multiplexed_async_connection().await?;
         let prefix = format!(
             "subvt:{}:validators:{}:{}",
-            CONFIG.substrate.chain,
+            self.chain,
             finalized_block_number,
             if is_active { "active" } else { "inactive" }
         );
@@ -214,7 +233,7 @@ impl Redis {
             .map(|account_id| {
                 format!(
                     "subvt:{}:validators:{}:{}:validator:{}",
-                    CONFIG.substrate.chain,
+                    self.chain,
                     finalized_block_number,
                     if is_active { "active" } else { "inactive" },
                     account_id,
@@ -1,7 +1,12 @@
 #![warn(clippy::disallowed_types)]
 use lazy_static::lazy_static;
+use rand::Rng;
+use resvg::usvg::fontdb;
+use std::path::PathBuf;
+use std::sync::Arc;
 use subvt_config::Config;
 
+pub mod network_status;
 mod plotlib;
 pub mod rewards;
 
@@ -14,3 +19,55 @@ pub enum PlotterError {
     #[error("Provided data set is empty.")]
     EmptyData,
 }
+
+/// Output format of a chart.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ChartFormat {
+    Png,
+    Svg,
+}
+
+/// A unique file path in the temporary directory with the given extension.
+pub(crate) fn get_tmp_file_path(extension: &str) -> String {
+    let millis = chrono::Utc::now().timestamp_millis();
+    let random: u16 = rand::rng().random();
+    format!(
+        "{}{}{}_{}.{}",
+        CONFIG.plotter.tmp_dir_path,
+        std::path::MAIN_SEPARATOR,
+        millis,
+        random,
+        extension,
+    )
+}
+
+/// Renders the SVG file to a PNG file at the same path with the `png` extension, then deletes
+/// the SVG file.
+pub(crate) fn convert_svg_to_png(svg_path: &str) -> anyhow::Result<PathBuf> {
+    let png_path = PathBuf::from(svg_path).with_extension("png");
+    let mut fontdb = fontdb::Database::new();
+    fontdb.load_fonts_dir(&CONFIG.plotter.font_dir_path);
+    fontdb.set_sans_serif_family(&CONFIG.plotter.font_sans_serif_family);
+    let fontdb = Arc::new(fontdb);
+    let opt = usvg::Options {
+        resources_dir: std::fs::canonicalize(svg_path)
+            .ok()
+            .and_then(|p| p.parent().map(|p| p.to_path_buf())),
+        fontdb: fontdb.clone(),
+        ..Default::default()
+    };
+    let svg_data = std::fs::read(svg_path)?;
+    let rtree = usvg::Tree::from_data(&svg_data, &opt)?;
+    let pixmap_size = rtree.size().to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(pixmap_size.width(), pixmap_size.height())
+        .ok_or_else(|| anyhow::anyhow!("Cannot create pixmap for {svg_path}."))?;
+    resvg::render(
+        &rtree,
+        tiny_skia::Transform::default(),
+        &mut pixmap.as_mut(),
+    );
+    pixmap.save_png(&png_path)?;
+    // delete the svg file
+    std::fs::remove_file(svg_path)?;
+    Ok(png_path)
+}
@@ -0,0 +1,95 @@
+//! Line charts of the network status history.
+use crate::plotlib::{Chart, LineSeriesView, ScaleLinear};
+use crate::{convert_svg_to_png, get_tmp_file_path, ChartFormat, PlotterError, CONFIG};
+use std::path::PathBuf;
+use subvt_types::report::{NetworkStatusMetric, NetworkStatusSnapshot};
+
+/// Plots the metric over the snapshots, with the era index on the X axis if `is_era_resolution`
+/// and the session index otherwise.
+pub fn plot_network_status(
+    title: &str,
+    metric: NetworkStatusMetric,
+    snapshots: &[NetworkStatusSnapshot],
+    is_era_resolution: bool,
+    format: ChartFormat,
+) -> anyhow::Result<PathBuf> {
+    if snapshots.is_empty() {
+        return Err(PlotterError::EmptyData.into());
+    }
+    let data: Vec<(f32, f32)> = snapshots
+        .iter()
+        .map(|snapshot| {
+            let x = if is_era_resolution {
+                snapshot.era_index as f32
+            } else {
+                snapshot.epoch_index as f32
+            };
+            (
+                x,
+                metric.get_value(snapshot, CONFIG.substrate.token_decimals) as f32,
+            )
+        })
+        .collect();
+    let x_min = data.first().unwrap().0;
+    let x_max = data.last().unwrap().0.max(x_min + 1.0);
+    let y_min = data.iter().map(|point| point.1).fold(f32::MAX, f32::min);
+    let y_max = data.iter().map(|point| point.1).fold(f32::MIN, f32::max);
+    // leave some space above and below the line
+    let y_padding = ((y_max - y_min) * 0.1).max(y_max.abs() * 0.01).max(1.0);
+    let y_domain = vec![
+        (y_min - y_padding).max(0.0).floor(),
+        (y_max + y_padding).ceil(),
+    ];
+
+    let width = 1200;
+    let height = 600;
+    let (top, right, bottom, left) = (40, 30, 50, 90);
+    let x = ScaleLinear::new()
+        .set_domain(vec![x_min, x_max])
+        .set_range(vec![0, width - left - right]);
+    let y = ScaleLinear::new()
+        .set_domain(y_domain)
+        .set_range(vec![height - top - bottom, 0]);
+    let view = LineSeriesView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_label_visibility(false)
+        .load_data(&data)
+        .map_err(|error| anyhow::anyhow!("{}", error))?;
+    let last_value = data.last().unwrap().1;
+    let unit = if metric.is_balance() {
+        CONFIG.substrate.token_ticker.clone()
+    } else if metric == NetworkStatusMetric::ReturnRate {
+        "%".to_string()
+    } else {
+        String::new()
+    };
+    let svg_path = get_tmp_file_path("svg");
+    if let Err(error) = Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(top, right, bottom, left)
+        .add_title(title.to_string())
+        .set_summary(
+            format!("Last: {last_value:.2} {unit}")
+                .trim_end()
+                .to_string(),
+        )
+        .add_view(&view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .add_left_axis_label(if unit.is_empty() {
+            metric.get_title().to_string()
+        } else {
+            format!("{} ({unit})", metric.get_title())
+        })
+        .add_bottom_axis_label(if is_era_resolution { "Era" } else { "Session" })
+        .save(&svg_path)
+    {
+        return Err(anyhow::anyhow!("{}", error));
+    }
+    match format {
+        ChartFormat::Svg => Ok(PathBuf::from(svg_path)),
+        ChartFormat::Png => convert_svg_to_png(&svg_path),
+    }
+}
@@ -1,12 +1,9 @@
 use crate::plotlib::{Chart, ScaleBand, ScaleLinear, VerticalBarView};
-use crate::{PlotterError, CONFIG};
+use crate::{convert_svg_to_png, get_tmp_file_path, PlotterError, CONFIG};
 use chrono::Datelike;
 use itertools::Itertools;
-use rand::Rng;
-use resvg::usvg::fontdb;
 use rustc_hash::FxHashMap as HashMap;
 use std::path::PathBuf;
-use std::sync::Arc;
 use subvt_types::substrate::{Balance, Era};
 use subvt_utility::numeric::format_decimal;
 
@@ -75,16 +72,7 @@ pub fn plot_era_rewards(title: &str, rewards: &[(Era, Balance)]) -> anyhow::Resu
         .set_label_rounding_precision(4)
         .load_data(&data)
         .unwrap();
-    let millis = chrono::Utc::now().timestamp_millis();
-    let random: u16 = rand::rng().random();
-    // save svg
-    let svg_path = format!(
-        "{}{}{}_{}.svg",
-        CONFIG.plotter.tmp_dir_path,
-        std::path::MAIN_SEPARATOR,
-        millis,
-        random,
-    );
+    let svg_path = get_tmp_file_path("svg");
     if let Err(error) = Chart::new()
         .set_width(width)
         .set_height(height)
@@ -105,37 +93,5 @@ pub fn plot_era_rewards(title: &str, rewards: &[(Era, Balance)]) -> anyhow::Resu
         return Err(anyhow::anyhow!("{}", error));
     }
 
-    // save png
-    let png_path = format!(
-        "{}{}{}_{}.png",
-        CONFIG.plotter.tmp_dir_path,
-        std::path::MAIN_SEPARATOR,
-        millis,
-        random,
-    );
-    let mut fontdb = fontdb::Database::new();
-    fontdb.load_fonts_dir(&CONFIG.plotter.font_dir_path);
-    fontdb.set_sans_serif_family(&CONFIG.plotter.font_sans_serif_family);
-    let fontdb = Arc::new(fontdb);
-    let opt = usvg::Options {
-        resources_dir: std::fs::canonicalize(&svg_path)
-            .ok()
-            .and_then(|p| p.parent().map(|p| p.to_path_buf())),
-        fontdb: fontdb.clone(),
-        ..Default::default()
-    };
-    let svg_data = std::fs::read(&svg_path).unwrap();
-    let rtree = usvg::Tree::from_data(&svg_data, &opt).unwrap();
-    //let pixmap_size = rtree.size.to_screen_size();
-    let pixmap_size = rtree.size().to_int_size();
-    let mut pixmap = tiny_skia::Pixmap::new(pixmap_size.width(), pixmap_size.height()).unwrap();
-    resvg::render(
-        &rtree,
-        tiny_skia::Transform::default(),
-        &mut pixmap.as_mut(),
-    );
-    pixmap.save_png(&png_path)?;
-    // delete the svg file
-    std::fs::remove_file(svg_path)?;
-    Ok(PathBuf::from(&png_path))
+    convert_svg_to_png(&svg_path)
 }
@@ -4,7 +4,10 @@
 //!
 //! A `diff`d struct can use the `get_diff` function on itself to calculate
 //! the diff between itself and another instance, and the `apply_diff` function to
-//! apply a diff struct to itself.
+//! apply a diff struct to itself. Diff structs are serializable and deserializable,
+//! so that they can be persisted and applied later. A changed field is deserialized as
+//! `Some` even when its value is `null`, so that a change of an `Option` field from `Some`
+//! to `None` survives the round trip.
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Data, DataStruct, DeriveInput, Fields};
@@ -13,6 +16,9 @@ use syn::{Data, DataStruct, DeriveInput, Fields};
 pub fn derive_diff(input: DeriveInput) -> TokenStream {
     const KEY_ATTR_NAME: &str = "diff_key";
 
+    let ident = input.ident;
+    let diff_ident = syn::Ident::new(&format!("{ident}Diff"), ident.span());
+    let deserialize_field_path = format!("{diff_ident}::deserialize_field");
     let fields = match input.data {
         Data::Struct(DataStruct {
             fields: Fields::Named(fields),
@@ -69,19 +75,36 @@ pub fn derive_diff(input: DeriveInput) -> TokenStream {
             }
         } else {
             quote! {
-                #[serde(skip_serializing_if = "Option::is_none")]
+                #[serde(
+                    default,
+                    deserialize_with = #deserialize_field_path,
+                    skip_serializing_if = "Option::is_none"
+                )]
                 pub #field_name: ::std::option::Option<#field_ty>,
             }
         }
     });
-    let ident = input.ident;
-    let diff_ident = syn::Ident::new(&format!("{ident}Diff"), ident.span());
     let diff_struct = quote! {
         #[automatically_derived]
-        #[derive(Clone, Debug, Default, Serialize)]
+        #[derive(Clone, Debug, Default, Deserialize, Serialize)]
         pub struct #diff_ident {
             #(#diff_fields)*
         }
+
+        #[automatically_derived]
+        impl #diff_ident {
+            /// A field that is present in the serialized diff has changed, so it's `Some`
+            /// even when its value is `null`. Missing fields default to `None`.
+            fn deserialize_field<'de, D, T>(
+                deserializer: D,
+            ) -> ::std::result::Result<::std::option::Option<T>, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+                T: ::serde::Deserialize<'de>,
+            {
+                <T as ::serde::Deserialize>::deserialize(deserializer).map(Some)
+            }
+        }
     };
     let get_diff_impl = quote! {
         #[automatically_derived]
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use subvt_proc_macro::Diff;
+
+#[derive(Clone, Debug, Default, Deserialize, Diff, PartialEq, Serialize)]
+struct Validator {
+    #[diff_key]
+    id: u32,
+    display: Option<String>,
+    commission: u32,
+}
+
+fn round_trip(before: &Validator, after: &Validator) -> Validator {
+    let diff = before.get_diff(after);
+    let diff: ValidatorDiff = serde_json::from_str(&serde_json::to_string(&diff).unwrap()).unwrap();
+    let mut applied = before.clone();
+    applied.apply_diff(&diff);
+    applied
+}
+
+#[test]
+fn test_diff_round_trip() {
+    let before = Validator {
+        id: 1,
+        display: Some("validator".to_string()),
+        commission: 5,
+    };
+    let after = Validator {
+        commission: 10,
+        ..before.clone()
+    };
+    let diff = serde_json::to_value(before.get_diff(&after)).unwrap();
+    assert_eq!(diff, serde_json::json!({ "id": 1, "commission": 10 }));
+    assert_eq!(round_trip(&before, &after), after);
+    // some to none
+    let after = Validator {
+        display: None,
+        ..before.clone()
+    };
+    let diff = serde_json::to_value(before.get_diff(&after)).unwrap();
+    assert_eq!(diff, serde_json::json!({ "id": 1, "display": null }));
+    assert_eq!(round_trip(&before, &after), after);
+    // none to some
+    assert_eq!(round_trip(&after, &before), before);
+}
@@ -4,6 +4,7 @@ use enum_iterator::all;
 use lazy_static::lazy_static;
 use subvt_config::Config;
 use subvt_governance::polkassembly::fetch_track_referenda;
+use subvt_metrics::health;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
 use subvt_service_common::Service;
 use subvt_types::governance::track::Track;
@@ -36,6 +37,7 @@ impl Service for ReferendumUpdater {
         let tracks = all::<Track>().collect::<Vec<_>>();
 
         let _ = postgres.get_open_referenda(None).await?;
+        health::set_ready(true);
         loop {
             metrics::last_run_timestamp_ms().set(chrono::Utc::now().timestamp_millis());
             for track in &tracks {
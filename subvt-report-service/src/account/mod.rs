@@ -0,0 +1,53 @@
+use crate::util::validate_account_id_param;
+use crate::{ResultResponse, ServiceState};
+use actix_web::{get, web, HttpResponse};
+use serde::Deserialize;
+use subvt_types::err::ServiceError;
+use subvt_types::report::AccountActivityReport;
+
+const DEFAULT_ACTIVITY_LIMIT: u32 = 50;
+const MAX_ACTIVITY_LIMIT: u32 = 500;
+
+#[derive(Deserialize)]
+pub(crate) struct AccountIdPathParameter {
+    ss58_address_or_account_id: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct AccountActivityQueryParameters {
+    /// Only the activities in blocks before this timestamp (ms) are returned. Pass the
+    /// timestamp of the last item of the previous page to fetch the next page.
+    #[serde(rename(deserialize = "before_timestamp"))]
+    maybe_before_timestamp: Option<u64>,
+    #[serde(rename(deserialize = "limit"))]
+    maybe_limit: Option<u32>,
+}
+
+/// Activity feed of events and extrinsics for a validator stash or controller account.
+#[get("/account/{ss58_address_or_account_id}/activity")]
+pub(crate) async fn account_activity_service(
+    path: web::Path<AccountIdPathParameter>,
+    query: web::Query<AccountActivityQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let account_id = match validate_account_id_param(&path.into_inner().ss58_address_or_account_id)
+    {
+        Ok(account_id) => account_id,
+        Err(response) => return Ok(response),
+    };
+    let limit = query.maybe_limit.unwrap_or(DEFAULT_ACTIVITY_LIMIT);
+    if limit == 0 || limit > MAX_ACTIVITY_LIMIT {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from(&format!(
+            "Limit should be between 1 and {MAX_ACTIVITY_LIMIT}."
+        ))));
+    }
+    let activities = data
+        .postgres
+        .get_account_activity(&account_id, query.maybe_before_timestamp, limit)
+        .await?;
+    Ok(HttpResponse::Ok().json(AccountActivityReport {
+        account_id,
+        address: account_id.to_ss58_check_with_version(data.ss58_prefix),
+        activities,
+    }))
+}
@@ -0,0 +1,87 @@
+use crate::util::validate_account_id_param;
+use crate::{ResultResponse, ServiceState};
+use actix_web::{get, web, HttpResponse};
+use serde::Deserialize;
+use subvt_types::err::ServiceError;
+use subvt_types::report::{ParaDisputeReport, ValidatorParaDisputeReport};
+
+const DEFAULT_DISPUTE_LIMIT: u32 = 50;
+const MAX_DISPUTE_LIMIT: u32 = 500;
+
+fn validate_limit(maybe_limit: Option<u32>) -> Result<u32, HttpResponse> {
+    let limit = maybe_limit.unwrap_or(DEFAULT_DISPUTE_LIMIT);
+    if limit == 0 || limit > MAX_DISPUTE_LIMIT {
+        return Err(HttpResponse::BadRequest().json(ServiceError::from(&format!(
+            "Limit should be between 1 and {MAX_DISPUTE_LIMIT}."
+        ))));
+    }
+    Ok(limit)
+}
+
+#[derive(Deserialize)]
+pub(crate) struct DisputeListQueryParameters {
+    /// Only the disputes with an id less than this are returned. Pass the id of the last
+    /// dispute of the previous page to fetch the next page.
+    #[serde(rename(deserialize = "before_id"))]
+    maybe_before_id: Option<u32>,
+    #[serde(rename(deserialize = "limit"))]
+    maybe_limit: Option<u32>,
+}
+
+/// Most recent parachain candidate disputes, with the count of statements on each side.
+#[get("/report/dispute")]
+pub(crate) async fn dispute_list_service(
+    query: web::Query<DisputeListQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let limit = match validate_limit(query.maybe_limit) {
+        Ok(limit) => limit,
+        Err(response) => return Ok(response),
+    };
+    let disputes = data
+        .postgres
+        .get_para_disputes(query.maybe_before_id, limit)
+        .await?;
+    Ok(HttpResponse::Ok().json(ParaDisputeReport { disputes }))
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ValidatorDisputePathParameter {
+    ss58_address_or_account_id: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ValidatorDisputeQueryParameters {
+    /// Return only the statements that contradict the concluded result of their dispute.
+    #[serde(default)]
+    against_supermajority: bool,
+    #[serde(rename(deserialize = "limit"))]
+    maybe_limit: Option<u32>,
+}
+
+/// Dispute statements of a validator, newest first.
+#[get("/report/validator/{ss58_address_or_account_id}/dispute")]
+pub(crate) async fn validator_dispute_service(
+    path: web::Path<ValidatorDisputePathParameter>,
+    query: web::Query<ValidatorDisputeQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let account_id = match validate_account_id_param(&path.into_inner().ss58_address_or_account_id)
+    {
+        Ok(account_id) => account_id,
+        Err(response) => return Ok(response),
+    };
+    let limit = match validate_limit(query.maybe_limit) {
+        Ok(limit) => limit,
+        Err(response) => return Ok(response),
+    };
+    let statements = data
+        .postgres
+        .get_validator_para_dispute_statements(&account_id, query.against_supermajority, limit)
+        .await?;
+    Ok(HttpResponse::Ok().json(ValidatorParaDisputeReport {
+        account_id,
+        address: account_id.to_ss58_check_with_version(data.ss58_prefix),
+        statements,
+    }))
+}
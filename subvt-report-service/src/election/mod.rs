@@ -0,0 +1,16 @@
+//! Next election prediction report.
+use crate::{ResultResponse, ServiceState};
+use actix_web::{get, web, HttpResponse};
+use subvt_types::err::ServiceError;
+
+/// Predicted active validator set of the next era, with the predicted backing of each winner.
+/// The prediction is updated periodically by `subvt-validator-list-updater`.
+#[get("/election/prediction")]
+pub(crate) async fn election_prediction_service(data: web::Data<ServiceState>) -> ResultResponse {
+    match data.redis.get_election_prediction().await? {
+        Some(prediction) => Ok(HttpResponse::Ok().json(prediction)),
+        None => Ok(HttpResponse::NotFound().json(ServiceError::from(
+            "Election prediction is not available yet.",
+        ))),
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{ResultResponse, ServiceState, CONFIG};
+use crate::{ResultResponse, ServiceState};
 use actix_web::{get, web, HttpResponse};
 use serde::Deserialize;
 use std::str::FromStr;
@@ -36,7 +36,7 @@ async fn get_era_validator_list_report(
         {
             era_validator_reports.push(EraValidatorReport {
                 account_id: Some(account_id),
-                address: Some(account_id.to_ss58_check()),
+                address: Some(account_id.to_ss58_check_with_version(data.ss58_prefix)),
                 era: None,
                 ..era_validator_report
             });
@@ -92,11 +92,11 @@ pub(crate) async fn era_validator_report_service(
             )));
         }
         let era_count = end_era_index - query.start_era_index;
-        if era_count > CONFIG.report.max_era_index_range {
+        if era_count > data.network.report.max_era_index_range {
             return Ok(HttpResponse::BadRequest().json(ServiceError::from(
                 format!(
                     "Report cannot span {} eras. Maximum allowed is {}.",
-                    era_count, CONFIG.report.max_era_index_range
+                    era_count, data.network.report.max_era_index_range
                 )
                 .as_ref(),
             )));
@@ -149,11 +149,11 @@ pub(crate) async fn era_report_service(
             )));
         }
         let era_count = end_era_index - query.start_era_index;
-        if era_count > CONFIG.report.max_era_index_range {
+        if era_count > data.network.report.max_era_index_range {
             return Ok(HttpResponse::BadRequest().json(ServiceError::from(
                 format!(
                     "Report cannot span {} eras. Maximum allowed is {}.",
-                    era_count, CONFIG.report.max_era_index_range
+                    era_count, data.network.report.max_era_index_range
                 )
                 .as_ref(),
             )));
@@ -1,32 +1,43 @@
 //!  Public reporting REST services.
 #![warn(clippy::disallowed_types)]
-use actix_web::dev::Service as _;
+use actix_web::dev::{Extensions, Service as _};
 use actix_web::{web, App, HttpResponse, HttpServer};
 use anyhow::Context;
 use async_trait::async_trait;
-use futures_util::future::FutureExt;
+use futures_util::future::{Either, FutureExt};
 use futures_util::StreamExt as _;
 use lazy_static::lazy_static;
 use rustc_hash::FxHashMap as HashMap;
+use std::rc::Rc;
+use std::str::FromStr;
 use std::sync::{Arc, RwLock};
 use subvt_config::Config;
+use subvt_metrics::health;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
 use subvt_persistence::redis::Redis;
+use subvt_service_common::network::start_network;
 use subvt_service_common::{err::InternalServerError, Service};
 use subvt_substrate_client::SubstrateClient;
 use subvt_types::crypto::AccountId;
+use subvt_types::err::ServiceError;
 use subvt_types::report::BlockSummary;
-use subvt_types::substrate::Account;
+use subvt_types::substrate::{Account, Chain};
 use subvt_types::subvt::ValidatorSummary;
 
+mod account;
+mod dispute;
+mod election;
 mod era;
 mod metrics;
 mod network;
+mod nominator;
 mod onekv;
+mod performance;
+mod reward;
 mod session;
 mod staking;
 pub(crate) mod util;
-mod validator;
+pub mod validator;
 
 lazy_static! {
     static ref CONFIG: Config = Config::default();
@@ -34,53 +45,58 @@ lazy_static! {
 
 pub(crate) type ResultResponse = Result<HttpResponse, InternalServerError>;
 
+/// States of the started networks by chain.
+type NetworkStates = Arc<RwLock<HashMap<String, web::Data<ServiceState>>>>;
+
+/// State of a network served by the service. The handlers read the network's configuration from
+/// `network` rather than the global configuration.
 #[derive(Clone)]
 pub(crate) struct ServiceState {
+    network: Arc<Config>,
+    /// SS58 prefix of the addresses in the reports of the network.
+    ss58_prefix: u16,
     postgres: Arc<PostgreSQLNetworkStorage>,
     redis: Arc<Redis>,
     substrate_client: Arc<SubstrateClient>,
+    asset_hub_client: Arc<SubstrateClient>,
     account_cache: Arc<RwLock<HashMap<AccountId, Account>>>,
     finalized_block_summary: Arc<RwLock<BlockSummary>>,
     active_validator_list: Arc<RwLock<Vec<ValidatorSummary>>>,
     inactive_validator_list: Arc<RwLock<Vec<ValidatorSummary>>>,
 }
 
-async fn on_server_ready() {
-    log::info!("HTTP service started.");
-}
-
-#[derive(Default)]
-pub struct ReportService;
-
-#[async_trait(?Send)]
-impl Service for ReportService {
-    fn get_metrics_server_addr() -> (&'static str, u16) {
-        (
-            CONFIG.metrics.host.as_str(),
-            CONFIG.metrics.report_service_port,
-        )
-    }
-
-    async fn run(&'static self) -> anyhow::Result<()> {
+impl ServiceState {
+    /// Connects to the storage and the node of the network, and starts updating the finalized
+    /// block and the validator lists of the network on each finalized block.
+    async fn new(network: Config) -> anyhow::Result<Self> {
         let postgres = Arc::new(
-            PostgreSQLNetworkStorage::new(&CONFIG, CONFIG.get_network_postgres_url()).await?,
+            PostgreSQLNetworkStorage::new(&network, network.get_network_postgres_url()).await?,
         );
-        let redis = Arc::new(Redis::new()?);
+        let redis = Arc::new(Redis::for_network(&network)?);
         let account_map = Arc::new(RwLock::new(HashMap::default()));
         let finalized_block_summary = Arc::new(RwLock::new(BlockSummary::default()));
         let active_validator_list = Arc::new(RwLock::new(Vec::new()));
         let inactive_validator_list = Arc::new(RwLock::new(Vec::new()));
 
-        let redis_client = redis::Client::open(CONFIG.redis.url.as_str()).context(format!(
+        let redis_client = redis::Client::open(network.redis.url.as_str()).context(format!(
             "Cannot connect to Redis at URL {}.",
-            CONFIG.redis.url
+            network.redis.url
         ))?;
         let substrate_client = Arc::new(
             SubstrateClient::new(
-                CONFIG.substrate.rpc_url.as_str(),
-                CONFIG.substrate.network_id,
-                CONFIG.substrate.connection_timeout_seconds,
-                CONFIG.substrate.request_timeout_seconds,
+                network.substrate.rpc_url.as_str(),
+                network.substrate.network_id,
+                network.substrate.connection_timeout_seconds,
+                network.substrate.request_timeout_seconds,
+            )
+            .await?,
+        );
+        let asset_hub_client = Arc::new(
+            SubstrateClient::new(
+                network.substrate.asset_hub_rpc_url.as_str(),
+                network.substrate.network_id,
+                network.substrate.connection_timeout_seconds,
+                network.substrate.request_timeout_seconds,
             )
             .await?,
         );
@@ -88,13 +104,14 @@ impl Service for ReportService {
         pubsub_connection
             .subscribe(format!(
                 "subvt:{}:validators:publish:finalized_block_number",
-                CONFIG.substrate.chain
+                network.substrate.chain
             ))
             .await?;
+        let chain = network.substrate.chain.clone();
         let updater_finalized_block_summary = finalized_block_summary.clone();
         let updater_active_validator_list = active_validator_list.clone();
         let updater_inactive_validator_list = inactive_validator_list.clone();
-        let updater_redis = Redis::new()?;
+        let updater_redis = Redis::for_network(&network)?;
         tokio::spawn(async move {
             let mut pubsub_stream = pubsub_connection.on_message();
             let mut last_finalized_block_number = 0;
@@ -111,10 +128,10 @@ impl Service for ReportService {
                     };
                 let finalized_block_number: u64 = finalized_block_summary.number;
                 if last_finalized_block_number == finalized_block_number {
-                    log::warn!("Skip duplicate finalized block #{finalized_block_number}.");
+                    log::warn!("Skip duplicate {chain} finalized block #{finalized_block_number}.");
                     continue;
                 }
-                log::info!("New finalized block #{finalized_block_number}.");
+                log::info!("New {chain} finalized block #{finalized_block_number}.");
                 // finalized block
                 {
                     match updater_finalized_block_summary.write() {
@@ -172,66 +189,177 @@ impl Service for ReportService {
                 last_finalized_block_number = finalized_block_number;
             }
         });
+        Ok(Self {
+            ss58_prefix: Chain::from_str(&network.substrate.chain)?.get_ss58_prefix(),
+            network: Arc::new(network),
+            postgres,
+            redis,
+            substrate_client,
+            asset_hub_client,
+            account_cache: account_map,
+            finalized_block_summary,
+            active_validator_list,
+            inactive_validator_list,
+        })
+    }
+}
+
+/// Registers the services of a network.
+fn configure_services(config: &mut web::ServiceConfig) {
+    config
+        .service(era::era_validator_report_service)
+        .service(era::era_active_validator_list_report_service)
+        .service(era::era_inactive_validator_list_report_service)
+        .service(era::era_report_service)
+        .service(era::current_era_service)
+        .service(era::all_eras_service)
+        .service(session::current_session_service)
+        .service(onekv::get_onekv_nominator_summaries)
+        .service(session::validator::session_validator_report_service)
+        .service(session::validator::session_validator_para_vote_service)
+        .service(session::para::session_paras_vote_summaries_service)
+        .service(validator::validator_summary_service)
+        .service(validator::validator_details_service)
+        .service(validator::validator_details_diff_service)
+        .service(validator::validator_list_service)
+        .service(validator::active_validator_list_service)
+        .service(validator::inactive_validator_list_service)
+        .service(validator::validator_search_service)
+        .service(validator::validator_era_rewards_service)
+        .service(validator::validator_era_payouts_service)
+        .service(validator::validator_reward_chart_service)
+        .service(validator::validator_comparison_service)
+        .service(validator::validator_metrics_service)
+        .service(validator::validator_monhtly_income_service)
+        .service(staking::controller_service)
+        .service(staking::bond_service)
+        .service(network::get_network_status)
+        .service(network::network_status_history_service)
+        .service(network::network_status_history_chart_service)
+        .service(account::account_activity_service)
+        .service(dispute::dispute_list_service)
+        .service(dispute::validator_dispute_service)
+        .service(performance::validator_session_performance_service)
+        .service(performance::validator_era_performance_service)
+        .service(election::election_prediction_service)
+        .service(nominator::nominator_era_rewards_service)
+        .service(nominator::nominator_yearly_rewards_service)
+        .service(reward::reward_export_service);
+}
 
+/// Network of the request for the metrics, the network in the first segment of the path or the
+/// default (first) network.
+fn get_request_network<'a>(path: &str, chains: &'a [String]) -> &'a str {
+    let first_segment = path
+        .trim_start_matches('/')
+        .split('/')
+        .next()
+        .unwrap_or_default();
+    chains
+        .iter()
+        .find(|chain| chain.as_str() == first_segment)
+        .or(chains.first())
+        .map(String::as_str)
+        .unwrap_or_default()
+}
+
+async fn on_server_ready() {
+    health::set_ready(true);
+    log::info!("HTTP service started.");
+}
+
+#[derive(Default)]
+pub struct ReportService;
+
+#[async_trait(?Send)]
+impl Service for ReportService {
+    fn get_metrics_server_addr() -> (&'static str, u16) {
+        (
+            CONFIG.metrics.host.as_str(),
+            CONFIG.metrics.report_service_port,
+        )
+    }
+
+    /// Each network in `common.networks` is served under its chain, e.g. `/polkadot/era`, and
+    /// the first network is also served at the root, e.g. `/era`. The networks are started
+    /// independently, and a network responds with `503 Service Unavailable` until it's started.
+    async fn run(&'static self) -> anyhow::Result<()> {
+        let network_configs = CONFIG.get_network_configs()?;
+        let chains: Arc<Vec<String>> = Arc::new(CONFIG.get_network_chains());
+        let network_states: NetworkStates = Default::default();
+        let start_networks = futures_util::future::join_all(network_configs.iter().map(|config| {
+            let network_states = network_states.clone();
+            async move {
+                let chain = &config.substrate.chain;
+                let state =
+                    start_network(&CONFIG, chain, || ServiceState::new(config.clone())).await;
+                network_states
+                    .write()
+                    .unwrap()
+                    .insert(chain.clone(), web::Data::new(state));
+                log::info!("Serving the {chain} reports.");
+            }
+        }));
         log::info!("Starting HTTP service.");
         let server = HttpServer::new(move || {
-            App::new()
-                .app_data(web::Data::new(ServiceState {
-                    postgres: postgres.clone(),
-                    redis: redis.clone(),
-                    substrate_client: substrate_client.clone(),
-                    account_cache: account_map.clone(),
-                    finalized_block_summary: finalized_block_summary.clone(),
-                    active_validator_list: active_validator_list.clone(),
-                    inactive_validator_list: inactive_validator_list.clone(),
-                }))
-                .wrap_fn(|request, service| {
-                    metrics::request_counter().inc();
-                    metrics::connection_count().inc();
-                    let start = std::time::Instant::now();
-                    service.call(request).map(move |result| {
-                        match &result {
-                            Ok(response) => {
-                                let status_code = response.response().status();
-                                metrics::response_time_ms()
-                                    .observe(start.elapsed().as_millis() as f64);
-                                metrics::response_status_code_counter(status_code.as_str()).inc();
-                            }
-                            Err(error) => {
-                                let status_code = error.as_response_error().status_code();
-                                metrics::response_time_ms()
-                                    .observe(start.elapsed().as_millis() as f64);
-                                metrics::response_status_code_counter(status_code.as_str()).inc();
-                            }
+            let chains = chains.clone();
+            let metrics_chains = chains.clone();
+            let mut app = App::new().wrap_fn(move |request, service| {
+                let network = get_request_network(request.path(), &metrics_chains).to_string();
+                metrics::request_counter(&network).inc();
+                metrics::connection_count().inc();
+                let start = std::time::Instant::now();
+                service.call(request).map(move |result| {
+                    match &result {
+                        Ok(response) => {
+                            let status_code = response.response().status();
+                            metrics::response_time_ms(&network)
+                                .observe(start.elapsed().as_millis() as f64);
+                            metrics::response_status_code_counter(&network, status_code.as_str())
+                                .inc();
+                        }
+                        Err(error) => {
+                            let status_code = error.as_response_error().status_code();
+                            metrics::response_time_ms(&network)
+                                .observe(start.elapsed().as_millis() as f64);
+                            metrics::response_status_code_counter(&network, status_code.as_str())
+                                .inc();
                         }
-                        metrics::connection_count().dec();
-                        result
-                    })
+                    }
+                    metrics::connection_count().dec();
+                    result
                 })
-                .service(era::era_validator_report_service)
-                .service(era::era_active_validator_list_report_service)
-                .service(era::era_inactive_validator_list_report_service)
-                .service(era::era_report_service)
-                .service(era::current_era_service)
-                .service(era::all_eras_service)
-                .service(session::current_session_service)
-                .service(onekv::get_onekv_nominator_summaries)
-                .service(session::validator::session_validator_report_service)
-                .service(session::validator::session_validator_para_vote_service)
-                .service(session::para::session_paras_vote_summaries_service)
-                .service(validator::validator_summary_service)
-                .service(validator::validator_details_service)
-                .service(validator::validator_list_service)
-                .service(validator::active_validator_list_service)
-                .service(validator::inactive_validator_list_service)
-                .service(validator::validator_search_service)
-                .service(validator::validator_era_rewards_service)
-                .service(validator::validator_era_payouts_service)
-                .service(validator::validator_reward_chart_service)
-                .service(validator::validator_monhtly_income_service)
-                .service(staking::controller_service)
-                .service(staking::bond_service)
-                .service(network::get_network_status)
+            });
+            // the root scope of the default network matches every path, so it's the last
+            let scopes = chains
+                .iter()
+                .map(|chain| (format!("/{chain}"), chain.clone()))
+                .chain(chains.first().map(|chain| (String::new(), chain.clone())));
+            for (path, chain) in scopes {
+                let network_states = network_states.clone();
+                app = app.service(
+                    web::scope(&path)
+                        .wrap_fn(move |mut request, service| {
+                            let maybe_state = network_states.read().unwrap().get(&chain).cloned();
+                            match maybe_state {
+                                Some(state) => {
+                                    let mut extensions = Extensions::new();
+                                    extensions.insert(state);
+                                    request.add_data_container(Rc::new(extensions));
+                                    Either::Left(service.call(request))
+                                }
+                                None => Either::Right(futures_util::future::ok(
+                                    request.into_response(
+                                        HttpResponse::ServiceUnavailable()
+                                            .json(ServiceError::from("Network is not available.")),
+                                    ),
+                                )),
+                            }
+                        })
+                        .configure(configure_services),
+                );
+            }
+            app
         })
         .workers(10)
         .disable_signals()
@@ -240,7 +368,7 @@ impl Service for ReportService {
             CONFIG.http.service_host, CONFIG.http.report_service_port,
         ))?
         .run();
-        let (server_result, _) = tokio::join!(server, on_server_ready());
+        let (server_result, _, _) = tokio::join!(server, on_server_ready(), start_networks);
         Ok(server_result?)
     }
 }
@@ -1,18 +1,19 @@
 use once_cell::sync::Lazy;
-use subvt_metrics::registry::{Histogram, IntCounter, IntCounterVec, IntGauge};
+use subvt_metrics::registry::{Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge};
 
 const METRIC_PREFIX: &str = "subvt_report_service";
 
-pub(crate) fn request_counter() -> IntCounter {
-    static METER: Lazy<IntCounter> = Lazy::new(|| {
-        subvt_metrics::registry::register_int_counter(
+pub(crate) fn request_counter(network: &str) -> IntCounter {
+    static METER: Lazy<IntCounterVec> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_counter_vec(
             METRIC_PREFIX,
             "request_count",
-            "The total number of requests made to the API",
+            "The total number of requests made to the API by network",
+            &["network"],
         )
         .unwrap()
     });
-    METER.clone()
+    METER.with_label_values(&[network])
 }
 
 pub(crate) fn connection_count() -> IntGauge {
@@ -27,12 +28,13 @@ pub(crate) fn connection_count() -> IntGauge {
     METER.clone()
 }
 
-pub(crate) fn response_time_ms() -> Histogram {
-    static METER: Lazy<Histogram> = Lazy::new(|| {
-        subvt_metrics::registry::register_histogram(
+pub(crate) fn response_time_ms(network: &str) -> Histogram {
+    static METER: Lazy<HistogramVec> = Lazy::new(|| {
+        subvt_metrics::registry::register_histogram_vec(
             METRIC_PREFIX,
             "response_time_ms",
-            "Response time in milliseconds",
+            "Response time in milliseconds by network",
+            &["network"],
             vec![
                 50.0, 100.0, 250.0, 500.0, 750.0, 1_000.0, 1_500.0, 2_500.0, 5_000.0, 10_000.0,
                 15_000.0, 30_000.0,
@@ -40,18 +42,18 @@ pub(crate) fn response_time_ms() -> Histogram {
         )
         .unwrap()
     });
-    METER.clone()
+    METER.with_label_values(&[network])
 }
 
-pub(crate) fn response_status_code_counter(status_code: &str) -> IntCounter {
+pub(crate) fn response_status_code_counter(network: &str, status_code: &str) -> IntCounter {
     static METER: Lazy<IntCounterVec> = Lazy::new(|| {
         subvt_metrics::registry::register_int_counter_vec(
             METRIC_PREFIX,
             "response_status_code_count",
-            "The number of response status codes",
-            &["status_code"],
+            "The number of response status codes by network",
+            &["network", "status_code"],
         )
         .unwrap()
     });
-    METER.with_label_values(&[status_code])
+    METER.with_label_values(&[network, status_code])
 }
@@ -1,7 +1,141 @@
 use crate::{ResultResponse, ServiceState};
 use actix_web::{get, web, HttpResponse};
+use anyhow::Context;
+use serde::Deserialize;
+use std::str::FromStr;
+use subvt_plotter::ChartFormat;
+use subvt_types::err::ServiceError;
+use subvt_types::report::{NetworkStatusMetric, NetworkStatusSnapshot};
+
+const DEFAULT_HISTORY_LIMIT: u32 = 100;
+const MAX_HISTORY_LIMIT: u32 = 1000;
 
 #[get("/network/status")]
 pub(crate) async fn get_network_status(data: web::Data<ServiceState>) -> ResultResponse {
     Ok(HttpResponse::Ok().json(data.redis.get_network_status().await?))
 }
+
+#[derive(Deserialize)]
+pub(crate) struct NetworkStatusHistoryQueryParameters {
+    /// `era` or `session`, defaults to `era`.
+    #[serde(rename(deserialize = "resolution"))]
+    maybe_resolution: Option<String>,
+    /// Inclusive.
+    #[serde(rename(deserialize = "start_era"))]
+    maybe_start_era_index: Option<u32>,
+    /// Inclusive.
+    #[serde(rename(deserialize = "end_era"))]
+    maybe_end_era_index: Option<u32>,
+    /// Maximum number of the latest snapshots in the range, defaults to 100.
+    #[serde(rename(deserialize = "limit"))]
+    maybe_limit: Option<u32>,
+    /// `png` or `svg` for the charts, defaults to `png`.
+    #[serde(rename(deserialize = "format"))]
+    maybe_format: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct NetworkStatusChartPathParameter {
+    metric: String,
+}
+
+/// Validates the query and fetches the snapshots. Returns whether the resolution is era along
+/// with the snapshots, or the bad request response.
+async fn get_network_status_history(
+    query: &NetworkStatusHistoryQueryParameters,
+    data: &ServiceState,
+) -> anyhow::Result<Result<(bool, Vec<NetworkStatusSnapshot>), HttpResponse>> {
+    let is_era_resolution = match query.maybe_resolution.as_deref() {
+        None | Some("era") => true,
+        Some("session") => false,
+        Some(resolution) => {
+            return Ok(Err(HttpResponse::BadRequest().json(ServiceError::from(
+                &format!("Invalid resolution: {resolution}"),
+            ))))
+        }
+    };
+    let start_era_index = query.maybe_start_era_index.unwrap_or(0);
+    let end_era_index = query.maybe_end_era_index.unwrap_or(u32::MAX);
+    if start_era_index > end_era_index {
+        return Ok(Err(HttpResponse::BadRequest()
+            .json(ServiceError::from("Start era cannot be after end era."))));
+    }
+    let limit = query.maybe_limit.unwrap_or(DEFAULT_HISTORY_LIMIT);
+    if limit == 0 || limit > MAX_HISTORY_LIMIT {
+        return Ok(Err(HttpResponse::BadRequest().json(ServiceError::from(
+            &format!("Limit should be between 1 and {MAX_HISTORY_LIMIT}."),
+        ))));
+    }
+    let snapshots = if is_era_resolution {
+        data.postgres
+            .get_era_network_status_snapshots(start_era_index, end_era_index, limit)
+            .await?
+    } else {
+        data.postgres
+            .get_session_network_status_snapshots(start_era_index, end_era_index, limit)
+            .await?
+    };
+    Ok(Ok((is_era_resolution, snapshots)))
+}
+
+/// Network status history per era (the last snapshot of each era) or per session, in the order
+/// of time.
+#[get("/network/status/history")]
+pub(crate) async fn network_status_history_service(
+    query: web::Query<NetworkStatusHistoryQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    match get_network_status_history(&query, &data).await? {
+        Ok((_, snapshots)) => Ok(HttpResponse::Ok().json(snapshots)),
+        Err(response) => Ok(response),
+    }
+}
+
+/// Line chart of a network status metric over the history, as a PNG or SVG image.
+#[get("/network/status/history/chart/{metric}")]
+pub(crate) async fn network_status_history_chart_service(
+    path: web::Path<NetworkStatusChartPathParameter>,
+    query: web::Query<NetworkStatusHistoryQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let metric = match NetworkStatusMetric::from_str(&path.into_inner().metric) {
+        Ok(metric) => metric,
+        Err(error) => {
+            return Ok(HttpResponse::BadRequest().json(ServiceError::from(&error.to_string())))
+        }
+    };
+    let (format, content_type) = match query.maybe_format.as_deref() {
+        None | Some("png") => (ChartFormat::Png, "image/png"),
+        Some("svg") => (ChartFormat::Svg, "image/svg+xml"),
+        Some(format) => {
+            return Ok(HttpResponse::BadRequest()
+                .json(ServiceError::from(&format!("Invalid format: {format}"))))
+        }
+    };
+    let (is_era_resolution, snapshots) = match get_network_status_history(&query, &data).await? {
+        Ok(history) => history,
+        Err(response) => return Ok(response),
+    };
+    if snapshots.is_empty() {
+        return Ok(HttpResponse::NotFound().json(ServiceError::from(
+            "No network status history found in the range.",
+        )));
+    }
+    let title = format!(
+        "{} {}",
+        data.network.substrate.chain_display,
+        metric.get_title()
+    );
+    let path = subvt_plotter::network_status::plot_network_status(
+        &title,
+        metric,
+        &snapshots,
+        is_era_resolution,
+        format,
+    )?;
+    let image = std::fs::read(&path).context("Cannot read network status chart file.");
+    if let Err(error) = std::fs::remove_file(&path) {
+        log::error!("Error while removing network status chart file: {error:?}");
+    }
+    Ok(HttpResponse::Ok().content_type(content_type).body(image?))
+}
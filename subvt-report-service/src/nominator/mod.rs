@@ -0,0 +1,173 @@
+//! Per-era and yearly reward reports of nominators, calculated from the era stakers.
+use crate::util::{validate_account_id_param, validate_quote_currency};
+use crate::{ResultResponse, ServiceState};
+use actix_web::{get, web, HttpResponse};
+use chrono::{DateTime, Datelike, Months, Utc};
+use rustc_hash::FxHashMap as HashMap;
+use serde::Deserialize;
+use subvt_types::report::{
+    NominatorEraReward, NominatorEraRewardReport, NominatorEraValidatorReward,
+    NominatorYearlyReward, NominatorYearlyRewardReport,
+};
+use subvt_types::substrate::Era;
+
+const MS_IN_DAY: u64 = 24 * 60 * 60 * 1000;
+
+#[derive(Deserialize)]
+pub(crate) struct NominatorPathParameter {
+    ss58_address_or_account_id: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct NominatorEraRewardQueryParameters {
+    /// Defaults to one year ago.
+    #[serde(rename(deserialize = "start_timestamp"))]
+    maybe_start_timestamp: Option<u64>,
+    #[serde(rename(deserialize = "quote_currency"))]
+    maybe_quote_currency: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct NominatorYearlyRewardQueryParameters {
+    #[serde(rename(deserialize = "quote_currency"))]
+    maybe_quote_currency: Option<String>,
+}
+
+fn get_day_start_timestamp(timestamp: u64) -> u64 {
+    timestamp - timestamp % MS_IN_DAY
+}
+
+/// Groups the validator rewards by era and values each era's reward with the daily close price
+/// of the day the era ended in.
+async fn get_nominator_era_rewards(
+    data: &web::Data<ServiceState>,
+    quote_currency: &str,
+    validator_rewards: Vec<(Era, NominatorEraValidatorReward)>,
+) -> anyhow::Result<Vec<NominatorEraReward>> {
+    let mut era_rewards: Vec<NominatorEraReward> = Vec::new();
+    for (era, validator_reward) in validator_rewards {
+        match era_rewards.last_mut() {
+            Some(era_reward) if era_reward.era.index == era.index => {
+                era_reward.reward += validator_reward.reward;
+                era_reward.validator_rewards.push(validator_reward);
+            }
+            _ => era_rewards.push(NominatorEraReward {
+                era,
+                reward: validator_reward.reward,
+                fiat_value: None,
+                validator_rewards: vec![validator_reward],
+            }),
+        }
+    }
+    let (first_era, last_era) = match (era_rewards.first(), era_rewards.last()) {
+        (Some(first), Some(last)) => (first.era.clone(), last.era.clone()),
+        _ => return Ok(era_rewards),
+    };
+    let prices: HashMap<u64, f64> = data
+        .postgres
+        .get_kline_close_prices(
+            &data.network.substrate.token_ticker,
+            quote_currency,
+            get_day_start_timestamp(first_era.end_timestamp),
+            get_day_start_timestamp(last_era.end_timestamp),
+        )
+        .await?
+        .into_iter()
+        .collect();
+    let denominator = f64::powi(10.0, data.network.substrate.token_decimals as i32);
+    for era_reward in era_rewards.iter_mut() {
+        era_reward.fiat_value = prices
+            .get(&get_day_start_timestamp(era_reward.era.end_timestamp))
+            .map(|price| era_reward.reward as f64 * price / denominator);
+    }
+    Ok(era_rewards)
+}
+
+/// Rewards of the nominator per era, broken down by the validators it was actively nominating.
+#[get("/nominator/{ss58_address_or_account_id}/era/reward")]
+pub(crate) async fn nominator_era_rewards_service(
+    path: web::Path<NominatorPathParameter>,
+    query: web::Query<NominatorEraRewardQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let account_id = match validate_account_id_param(&path.into_inner().ss58_address_or_account_id)
+    {
+        Ok(account_id) => account_id,
+        Err(response) => return Ok(response),
+    };
+    let quote_currency = match validate_quote_currency(&query.maybe_quote_currency) {
+        Ok(quote_currency) => quote_currency,
+        Err(response) => return Ok(response),
+    };
+    let start_timestamp = match query.maybe_start_timestamp {
+        Some(start_timestamp) => start_timestamp,
+        None => Utc::now()
+            .checked_sub_months(Months::new(12))
+            .unwrap()
+            .timestamp_millis() as u64,
+    };
+    let validator_rewards = data
+        .postgres
+        .get_nominator_era_rewards(&account_id, start_timestamp)
+        .await?;
+    Ok(HttpResponse::Ok().json(NominatorEraRewardReport {
+        account_id,
+        address: account_id.to_ss58_check_with_version(data.ss58_prefix),
+        era_rewards: get_nominator_era_rewards(&data, &quote_currency, validator_rewards).await?,
+        quote_currency,
+    }))
+}
+
+/// Total rewards of the nominator per calendar year (UTC, by era end), with the fiat value.
+#[get("/nominator/{ss58_address_or_account_id}/reward/yearly")]
+pub(crate) async fn nominator_yearly_rewards_service(
+    path: web::Path<NominatorPathParameter>,
+    query: web::Query<NominatorYearlyRewardQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let account_id = match validate_account_id_param(&path.into_inner().ss58_address_or_account_id)
+    {
+        Ok(account_id) => account_id,
+        Err(response) => return Ok(response),
+    };
+    let quote_currency = match validate_quote_currency(&query.maybe_quote_currency) {
+        Ok(quote_currency) => quote_currency,
+        Err(response) => return Ok(response),
+    };
+    let validator_rewards = data
+        .postgres
+        .get_nominator_era_rewards(&account_id, 0)
+        .await?;
+    let mut yearly_rewards: Vec<NominatorYearlyReward> = Vec::new();
+    for era_reward in get_nominator_era_rewards(&data, &quote_currency, validator_rewards).await? {
+        let year = DateTime::from_timestamp_millis(era_reward.era.end_timestamp as i64)
+            .map(|date_time| date_time.year() as u32)
+            .unwrap_or_default();
+        if yearly_rewards
+            .last()
+            .map(|yearly_reward| yearly_reward.year)
+            != Some(year)
+        {
+            yearly_rewards.push(NominatorYearlyReward {
+                year,
+                era_count: 0,
+                reward: 0,
+                fiat_value: 0.0,
+                unpriced_era_count: 0,
+            });
+        }
+        let yearly_reward = yearly_rewards.last_mut().unwrap();
+        yearly_reward.era_count += 1;
+        yearly_reward.reward += era_reward.reward;
+        match era_reward.fiat_value {
+            Some(fiat_value) => yearly_reward.fiat_value += fiat_value,
+            None => yearly_reward.unpriced_era_count += 1,
+        }
+    }
+    Ok(HttpResponse::Ok().json(NominatorYearlyRewardReport {
+        account_id,
+        address: account_id.to_ss58_check_with_version(data.ss58_prefix),
+        quote_currency,
+        yearly_rewards,
+    }))
+}
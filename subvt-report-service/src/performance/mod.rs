@@ -0,0 +1,141 @@
+use crate::util::validate_account_id_param;
+use crate::{ResultResponse, ServiceState};
+use actix_web::{get, web, HttpResponse};
+use serde::Deserialize;
+use subvt_types::err::ServiceError;
+use subvt_types::report::{ValidatorEraPerformanceReport, ValidatorSessionPerformanceReport};
+
+const DEFAULT_SESSION_COUNT: u64 = 30;
+const MAX_SESSION_COUNT: u64 = 200;
+const DEFAULT_ERA_COUNT: u32 = 10;
+const MAX_ERA_COUNT: u32 = 100;
+
+#[derive(Deserialize)]
+pub(crate) struct ValidatorPerformancePathParameter {
+    ss58_address_or_account_id: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SessionPerformanceQueryParameters {
+    #[serde(rename(deserialize = "start_session_index"))]
+    maybe_start_session_index: Option<u64>,
+    #[serde(rename(deserialize = "end_session_index"))]
+    maybe_end_session_index: Option<u64>,
+}
+
+/// Session performances of a validator along with the network percentiles of each session.
+/// Defaults to the last 30 sessions processed by the session validator performance updater.
+#[get("/report/validator/{ss58_address_or_account_id}/performance/session")]
+pub(crate) async fn validator_session_performance_service(
+    path: web::Path<ValidatorPerformancePathParameter>,
+    query: web::Query<SessionPerformanceQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let account_id = match validate_account_id_param(&path.into_inner().ss58_address_or_account_id)
+    {
+        Ok(account_id) => account_id,
+        Err(response) => return Ok(response),
+    };
+    let end_session_index = match query.maybe_end_session_index {
+        Some(end_session_index) => end_session_index,
+        None => match data
+            .postgres
+            .get_session_validator_performance_updater_last_processed_session_id()
+            .await?
+        {
+            Some(session_index) => session_index,
+            None => {
+                return Ok(HttpResponse::NotFound()
+                    .json(ServiceError::from("No session performance data found.")))
+            }
+        },
+    };
+    let start_session_index = query
+        .maybe_start_session_index
+        .unwrap_or_else(|| (end_session_index + 1).saturating_sub(DEFAULT_SESSION_COUNT));
+    if start_session_index > end_session_index {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from(
+            "Start session index cannot be greater than end session index.",
+        )));
+    }
+    if end_session_index - start_session_index >= MAX_SESSION_COUNT {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from(&format!(
+            "Session range cannot be longer than {MAX_SESSION_COUNT} sessions."
+        ))));
+    }
+    let performances = data
+        .postgres
+        .get_validator_session_performances(&account_id, start_session_index, end_session_index)
+        .await?;
+    let network = data
+        .postgres
+        .get_session_performance_percentiles(start_session_index, end_session_index)
+        .await?;
+    Ok(HttpResponse::Ok().json(ValidatorSessionPerformanceReport {
+        account_id,
+        address: account_id.to_ss58_check_with_version(data.ss58_prefix),
+        performances,
+        network,
+    }))
+}
+
+#[derive(Deserialize)]
+pub(crate) struct EraPerformanceQueryParameters {
+    #[serde(rename(deserialize = "start_era_index"))]
+    maybe_start_era_index: Option<u32>,
+    #[serde(rename(deserialize = "end_era_index"))]
+    maybe_end_era_index: Option<u32>,
+}
+
+/// Per-era aggregate performances of a validator along with the network percentiles of each
+/// era. Defaults to the last 10 eras.
+#[get("/report/validator/{ss58_address_or_account_id}/performance/era")]
+pub(crate) async fn validator_era_performance_service(
+    path: web::Path<ValidatorPerformancePathParameter>,
+    query: web::Query<EraPerformanceQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let account_id = match validate_account_id_param(&path.into_inner().ss58_address_or_account_id)
+    {
+        Ok(account_id) => account_id,
+        Err(response) => return Ok(response),
+    };
+    let end_era_index = match query.maybe_end_era_index {
+        Some(end_era_index) => end_era_index,
+        None => match data.postgres.get_current_epoch().await? {
+            Some(session) => session.era_index,
+            None => {
+                return Ok(HttpResponse::InternalServerError().json(ServiceError::from(
+                    "Internal Error: Cannot get current session.",
+                )))
+            }
+        },
+    };
+    let start_era_index = query
+        .maybe_start_era_index
+        .unwrap_or_else(|| (end_era_index + 1).saturating_sub(DEFAULT_ERA_COUNT));
+    if start_era_index > end_era_index {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from(
+            "Start era index cannot be greater than end era index.",
+        )));
+    }
+    if end_era_index - start_era_index >= MAX_ERA_COUNT {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from(&format!(
+            "Era range cannot be longer than {MAX_ERA_COUNT} eras."
+        ))));
+    }
+    let performances = data
+        .postgres
+        .get_validator_era_performances(&account_id, start_era_index, end_era_index)
+        .await?;
+    let network = data
+        .postgres
+        .get_era_performance_percentiles(start_era_index, end_era_index)
+        .await?;
+    Ok(HttpResponse::Ok().json(ValidatorEraPerformanceReport {
+        account_id,
+        address: account_id.to_ss58_check_with_version(data.ss58_prefix),
+        performances,
+        network,
+    }))
+}
@@ -0,0 +1,120 @@
+//! Accounting export of the reward payouts of validators and nominators.
+use crate::util::{validate_account_id_param, validate_quote_currency};
+use crate::{ResultResponse, ServiceState};
+use actix_web::{get, web, HttpResponse};
+use chrono::Utc;
+use serde::Deserialize;
+use subvt_substrate_client::SubstrateClient;
+use subvt_types::err::ServiceError;
+use subvt_types::report::RewardExportReport;
+
+const MS_IN_DAY: u64 = 24 * 60 * 60 * 1000;
+const DEFAULT_EXPORT_RANGE_DAYS: u64 = 365;
+const MAX_EXPORT_RANGE_DAYS: u64 = 2 * 366;
+
+#[derive(Deserialize)]
+pub(crate) struct RewardExportPathParameter {
+    ss58_address_or_account_id: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct RewardExportQueryParameters {
+    /// Inclusive, defaults to 365 days before the end timestamp.
+    #[serde(rename(deserialize = "start_timestamp"))]
+    maybe_start_timestamp: Option<u64>,
+    /// Exclusive, defaults to now.
+    #[serde(rename(deserialize = "end_timestamp"))]
+    maybe_end_timestamp: Option<u64>,
+    /// Ticker of the currency the prices are quoted in, defaults to `USDT`.
+    #[serde(rename(deserialize = "quote_currency"))]
+    maybe_quote_currency: Option<String>,
+    /// `json` or `csv`, defaults to `json`.
+    #[serde(rename(deserialize = "format"))]
+    maybe_format: Option<String>,
+}
+
+/// Per-payout line items of the rewards of a validator or a nominator in a time range, with the
+/// price of the token in the selected quote currency at the payout day. Returned as JSON or as
+/// a CSV attachment.
+#[get("/account/{ss58_address_or_account_id}/reward/export")]
+pub(crate) async fn reward_export_service(
+    path: web::Path<RewardExportPathParameter>,
+    query: web::Query<RewardExportQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let account_id = match validate_account_id_param(&path.into_inner().ss58_address_or_account_id)
+    {
+        Ok(account_id) => account_id,
+        Err(response) => return Ok(response),
+    };
+    let is_csv = match query.maybe_format.as_deref() {
+        None | Some("json") => false,
+        Some("csv") => true,
+        Some(format) => {
+            return Ok(HttpResponse::BadRequest()
+                .json(ServiceError::from(&format!("Invalid format: {format}"))))
+        }
+    };
+    let quote_currency = match validate_quote_currency(&query.maybe_quote_currency) {
+        Ok(quote_currency) => quote_currency,
+        Err(response) => return Ok(response),
+    };
+    let end_timestamp = query
+        .maybe_end_timestamp
+        .unwrap_or(Utc::now().timestamp_millis() as u64);
+    let start_timestamp = query
+        .maybe_start_timestamp
+        .unwrap_or_else(|| end_timestamp.saturating_sub(DEFAULT_EXPORT_RANGE_DAYS * MS_IN_DAY));
+    if start_timestamp >= end_timestamp {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from(
+            "Start timestamp should be less than end timestamp.",
+        )));
+    }
+    if end_timestamp - start_timestamp > MAX_EXPORT_RANGE_DAYS * MS_IN_DAY {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from(&format!(
+            "Time range cannot be longer than {MAX_EXPORT_RANGE_DAYS} days."
+        ))));
+    }
+    let mut items = data
+        .postgres
+        .get_reward_export_items(
+            &account_id,
+            start_timestamp,
+            end_timestamp,
+            &data.network.substrate.token_ticker,
+            &quote_currency,
+        )
+        .await?;
+    SubstrateClient::set_reward_export_extrinsic_hashes(
+        &[
+            data.substrate_client.as_ref(),
+            data.asset_hub_client.as_ref(),
+        ],
+        &mut items,
+    )
+    .await;
+    let report = RewardExportReport::new(
+        account_id,
+        data.ss58_prefix,
+        &data.network.substrate.token_ticker,
+        data.network.substrate.token_decimals,
+        &quote_currency,
+        start_timestamp,
+        end_timestamp,
+        items,
+    );
+    if is_csv {
+        Ok(HttpResponse::Ok()
+            .content_type("text/csv")
+            .insert_header((
+                "Content-Disposition",
+                format!(
+                    "attachment; filename=\"{}_rewards_{start_timestamp}_{end_timestamp}.csv\"",
+                    report.address
+                ),
+            ))
+            .body(report.to_csv()))
+    } else {
+        Ok(HttpResponse::Ok().json(report))
+    }
+}
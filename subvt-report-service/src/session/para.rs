@@ -1,4 +1,4 @@
-use crate::{ResultResponse, ServiceState, CONFIG};
+use crate::{ResultResponse, ServiceState};
 use actix_web::{get, web, HttpResponse};
 use serde::Deserialize;
 use subvt_types::report::SessionParasVoteReport;
@@ -9,6 +9,7 @@ async fn validate_params(
     maybe_end_session_index: Option<i64>,
     current_session: Epoch,
     min_para_vote_session_index: u64,
+    max_session_index_range: u32,
 ) -> Result<(u64, u64), HttpResponse> {
     // start session index
     let start_session_index = match maybe_start_session_index {
@@ -55,10 +56,10 @@ async fn validate_params(
         ))));
     }
     // check if era range is valid
-    if end_session_index - start_session_index + 1 > CONFIG.report.max_session_index_range as u64 {
+    if end_session_index - start_session_index + 1 > max_session_index_range as u64 {
         return Err(HttpResponse::BadRequest().json(ServiceError::from(&format!(
             "Bad Request: This report cannot span more than {} sessions.",
-            CONFIG.report.max_session_index_range,
+            max_session_index_range,
         ))));
     }
     Ok((start_session_index, end_session_index))
@@ -91,6 +92,7 @@ pub(crate) async fn session_paras_vote_summaries_service(
         query.maybe_end_session_index,
         current_session,
         min_para_vote_session_index,
+        data.network.report.max_session_index_range,
     )
     .await
     {
@@ -1,4 +1,4 @@
-use crate::{ResultResponse, ServiceState, CONFIG};
+use crate::{ResultResponse, ServiceState};
 use actix_web::{get, web, HttpResponse};
 use serde::Deserialize;
 use std::str::FromStr;
@@ -16,6 +16,7 @@ async fn validate_params(
     maybe_end_session_index: Option<i64>,
     current_session: Epoch,
     min_para_vote_session_index: u64,
+    max_session_index_range: u32,
 ) -> Result<(AccountId, u64, u64), HttpResponse> {
     // check valid address
     let account_id = match AccountId::from_str(ss58_address) {
@@ -71,10 +72,10 @@ async fn validate_params(
         ))));
     }
     // check if era range is valid
-    if end_session_index - start_session_index + 1 > CONFIG.report.max_session_index_range as u64 {
+    if end_session_index - start_session_index + 1 > max_session_index_range as u64 {
         return Err(HttpResponse::BadRequest().json(ServiceError::from(&format!(
             "Bad Request: This report cannot span more than {} sessions.",
-            CONFIG.report.max_session_index_range,
+            max_session_index_range,
         ))));
     }
     Ok((account_id, start_session_index, end_session_index))
@@ -95,6 +96,9 @@ async fn get_session_validator_report(
         validator_index: None,
         heartbeat_event: None,
         blocks_authored: None,
+        expected_block_count: None,
+        expected_primary_slot_count: None,
+        missed_block_count: None,
         para_validator_group_index: None,
         para_validator_index: None,
         para_votes_summary: None,
@@ -114,6 +118,16 @@ async fn get_session_validator_report(
             .get_blocks_by_validator_in_session(session_index, validator_account_id)
             .await?,
     );
+    // expected block production, available once the performance updater processes the session
+    if let Some(performance) = postgres
+        .get_validator_session_performances(validator_account_id, session_index, session_index)
+        .await?
+        .first()
+    {
+        report.expected_block_count = performance.expected_block_count;
+        report.expected_primary_slot_count = performance.expected_primary_slot_count;
+        report.missed_block_count = performance.get_missed_block_count();
+    }
     // get heartbeat event
     match postgres
         .get_session_para_validator(session_index, validator_account_id)
@@ -169,6 +183,7 @@ pub(crate) async fn session_validator_report_service(
         query.maybe_end_session_index,
         current_session,
         min_para_vote_session_index,
+        data.network.report.max_session_index_range,
     )
     .await
     {
@@ -251,6 +266,7 @@ pub(crate) async fn session_validator_para_vote_service(
         query.maybe_end_session_index,
         current_session,
         min_para_vote_session_index,
+        data.network.report.max_session_index_range,
     )
     .await
     {
@@ -45,7 +45,7 @@ pub(crate) async fn controller_service(
         };
     Ok(HttpResponse::Ok().json(Controller {
         controller_account_id,
-        controller_address: controller_account_id.to_ss58_check(),
+        controller_address: controller_account_id.to_ss58_check_with_version(data.ss58_prefix),
     }))
 }
 
@@ -75,7 +75,7 @@ pub(crate) async fn bond_service(
     };
     Ok(HttpResponse::Ok().json(Bond {
         controller_account_id,
-        controller_address: controller_account_id.to_ss58_check(),
+        controller_address: controller_account_id.to_ss58_check_with_version(data.ss58_prefix),
         bond,
     }))
 }
@@ -29,3 +29,26 @@ pub(crate) fn validate_block_hash(block_hash: &str) -> Result<(), HttpResponse>
     }
     Ok(())
 }
+
+/// Prices are quoted in this currency unless another one is requested.
+pub(crate) const DEFAULT_QUOTE_CURRENCY: &str = "USDT";
+
+/// Validates and upper-cases the quote currency query parameter, defaults to
+/// `DEFAULT_QUOTE_CURRENCY`.
+pub(crate) fn validate_quote_currency(
+    maybe_quote_currency: &Option<String>,
+) -> Result<String, HttpResponse> {
+    let quote_currency = maybe_quote_currency
+        .as_deref()
+        .unwrap_or(DEFAULT_QUOTE_CURRENCY)
+        .to_uppercase();
+    if quote_currency.is_empty()
+        || quote_currency.len() > 16
+        || !quote_currency.chars().all(|c| c.is_ascii_alphanumeric())
+    {
+        return Err(HttpResponse::BadRequest().json(ServiceError::from(&format!(
+            "Invalid quote currency: {quote_currency}"
+        ))));
+    }
+    Ok(quote_currency)
+}
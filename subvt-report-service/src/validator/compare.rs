@@ -0,0 +1,268 @@
+//! Side-by-side validator comparison over an era range, and the weighted composite score that
+//! ranks the compared set.
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use std::str::FromStr;
+use subvt_types::crypto::AccountId;
+use subvt_types::performance::EraValidatorPerformance;
+use subvt_types::report::{
+    EraCommission, EraValidatorReport, ValidatorComparison, ValidatorScoreMetric,
+    ValidatorScoreWeight,
+};
+use subvt_types::subvt::ValidatorDetails;
+
+const YEAR_MILLIS: f64 = 365.0 * 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// Parses comma-separated `metric:weight` pairs. Weights cannot be negative, and at least one
+/// of them should be positive.
+pub fn parse_score_weights(weights: &str) -> Result<Vec<ValidatorScoreWeight>, String> {
+    let mut result: Vec<ValidatorScoreWeight> = Vec::new();
+    for pair in weights
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+    {
+        let (metric, weight) = pair
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid score weight: {pair}"))?;
+        let metric = ValidatorScoreMetric::from_str(metric.trim()).map_err(|e| e.to_string())?;
+        let weight = weight
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|weight| weight.is_finite() && *weight >= 0.0)
+            .ok_or_else(|| format!("Invalid weight for {}: {weight}", metric.as_str()))?;
+        if result.iter().any(|existing| existing.metric == metric) {
+            return Err(format!("Duplicate score weight: {}", metric.as_str()));
+        }
+        result.push(ValidatorScoreWeight { metric, weight });
+    }
+    if !result.iter().any(|weight| weight.weight > 0.0) {
+        return Err("At least one score weight should be positive.".to_string());
+    }
+    Ok(result)
+}
+
+/// Aggregates the era reports and era performances of a validator in the range. Identity is
+/// taken from the current details of the validator, if it is still in the validator list.
+pub fn get_validator_comparison(
+    account_id: AccountId,
+    ss58_prefix: u16,
+    era_count: u32,
+    era_reports: &[EraValidatorReport],
+    era_performances: &[EraValidatorPerformance],
+    maybe_validator_details: Option<&ValidatorDetails>,
+) -> ValidatorComparison {
+    let active_reports: Vec<&EraValidatorReport> = era_reports
+        .iter()
+        .filter(|report| report.is_active.unwrap_or(false))
+        .collect();
+    let total_reward_points: u128 = active_reports
+        .iter()
+        .filter_map(|report| report.reward_points)
+        .sum();
+    let average_reward_points = if active_reports.is_empty() {
+        None
+    } else {
+        Some(total_reward_points as f64 / active_reports.len() as f64)
+    };
+    let annual_returns: Vec<f64> = active_reports
+        .iter()
+        .filter_map(|report| {
+            let era = report.era.as_ref()?;
+            let total_stake = report.total_stake.filter(|stake| *stake > 0)?;
+            let era_millis = era.end_timestamp.checked_sub(era.start_timestamp)?;
+            if era_millis == 0 {
+                return None;
+            }
+            let era_return =
+                (report.self_reward + report.staker_reward) as f64 / total_stake as f64;
+            Some(era_return * YEAR_MILLIS / era_millis as f64)
+        })
+        .collect();
+    let return_rate_per_billion = if annual_returns.is_empty() {
+        None
+    } else {
+        Some(
+            (annual_returns.iter().sum::<f64>() / annual_returns.len() as f64 * 1_000_000_000.0)
+                as u32,
+        )
+    };
+    let commission_history: Vec<EraCommission> = era_reports
+        .iter()
+        .filter_map(|report| {
+            Some(EraCommission {
+                era_index: report.era.as_ref()?.index,
+                commission_per_billion: report.commission_per_billion?,
+            })
+        })
+        .collect();
+    let average_commission_per_billion = if commission_history.is_empty() {
+        None
+    } else {
+        Some(
+            (commission_history
+                .iter()
+                .map(|commission| commission.commission_per_billion as u64)
+                .sum::<u64>()
+                / commission_history.len() as u64) as u32,
+        )
+    };
+    let attestation_count: u64 = era_performances
+        .iter()
+        .map(|performance| {
+            (performance.implicit_attestation_count + performance.explicit_attestation_count) as u64
+        })
+        .sum();
+    let attestation_slot_count: u64 = attestation_count
+        + era_performances
+            .iter()
+            .map(|performance| performance.missed_attestation_count as u64)
+            .sum::<u64>();
+    let attestations_per_billion = if attestation_slot_count > 0 {
+        Some((attestation_count * 1_000_000_000 / attestation_slot_count) as u32)
+    } else {
+        None
+    };
+    let (display, parent_display, identity_confirmed) = match maybe_validator_details {
+        Some(validator_details) => (
+            validator_details.account.get_display(),
+            validator_details.account.get_parent_display(),
+            validator_details.account.get_confirmed(),
+        ),
+        None => (None, None, false),
+    };
+    ValidatorComparison {
+        account_id,
+        address: account_id.to_ss58_check_with_version(ss58_prefix),
+        display,
+        parent_display,
+        identity_confirmed,
+        era_count: era_reports.len() as u32,
+        active_era_count: active_reports.len() as u32,
+        active_era_ratio: if era_count > 0 {
+            active_reports.len() as f64 / era_count as f64
+        } else {
+            0.0
+        },
+        total_reward_points,
+        average_reward_points,
+        return_rate_per_billion,
+        commission_history,
+        average_commission_per_billion,
+        self_stake: era_reports
+            .iter()
+            .rev()
+            .find_map(|report| report.self_stake),
+        attestations_per_billion,
+        offline_offence_count: era_reports
+            .iter()
+            .map(|report| report.offline_offence_count as u32)
+            .sum(),
+        chilling_count: era_reports
+            .iter()
+            .map(|report| report.chilling_count as u32)
+            .sum(),
+        slashed_amount: era_reports.iter().map(|report| report.slashed_amount).sum(),
+        score: 0.0,
+        rank: 0,
+    }
+}
+
+/// Comparisons of all the validators with a report in the era range, used as the reference set
+/// of the scores. Identity is taken from the set of the validators with a confirmed identity.
+pub fn get_reference_validators(
+    ss58_prefix: u16,
+    era_count: u32,
+    era_reports: &[EraValidatorReport],
+    era_performances: &[(AccountId, EraValidatorPerformance)],
+    identity_confirmed_account_ids: &HashSet<AccountId>,
+) -> Vec<ValidatorComparison> {
+    let mut validator_era_reports: HashMap<AccountId, Vec<EraValidatorReport>> = HashMap::default();
+    for report in era_reports {
+        if let Some(account_id) = report.account_id {
+            validator_era_reports
+                .entry(account_id)
+                .or_default()
+                .push(report.clone());
+        }
+    }
+    let mut validator_era_performances: HashMap<AccountId, Vec<EraValidatorPerformance>> =
+        HashMap::default();
+    for (account_id, performance) in era_performances {
+        validator_era_performances
+            .entry(*account_id)
+            .or_default()
+            .push(performance.clone());
+    }
+    validator_era_reports
+        .iter()
+        .map(|(account_id, era_reports)| {
+            let mut validator = get_validator_comparison(
+                *account_id,
+                ss58_prefix,
+                era_count,
+                era_reports,
+                validator_era_performances
+                    .get(account_id)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default(),
+                None,
+            );
+            validator.identity_confirmed = identity_confirmed_account_ids.contains(account_id);
+            validator
+        })
+        .collect()
+}
+
+/// Scores the validators and sorts them by rank. Each metric is min-max normalized over the
+/// reference set (i.e. the whole validator set in the era range) and the compared validators,
+/// so that the score of a validator doesn't depend on the others it's compared with. Normalized
+/// values are inverted for metrics where lower is better, and a missing value counts as the
+/// worst. The score is the weighted average of the normalized values, in `[0, 100]`.
+pub fn rank_validators(
+    validators: &mut [ValidatorComparison],
+    reference_validators: &[ValidatorComparison],
+    weights: &[ValidatorScoreWeight],
+) {
+    let total_weight: f64 = weights.iter().map(|weight| weight.weight).sum();
+    let mut scores = vec![0.0; validators.len()];
+    for weight in weights {
+        let (min, max) = validators
+            .iter()
+            .chain(reference_validators)
+            .filter_map(|validator| weight.metric.get_value(validator))
+            .fold((f64::MAX, f64::MIN), |(min, max), value| {
+                (min.min(value), max.max(value))
+            });
+        for (score, validator) in scores.iter_mut().zip(validators.iter()) {
+            let normalized = match weight.metric.get_value(validator) {
+                Some(_) if max <= min => 1.0,
+                Some(value) => {
+                    let normalized = (value - min) / (max - min);
+                    if weight.metric.is_lower_better() {
+                        1.0 - normalized
+                    } else {
+                        normalized
+                    }
+                }
+                None => 0.0,
+            };
+            *score += weight.weight * normalized;
+        }
+    }
+    for (validator, score) in validators.iter_mut().zip(scores) {
+        validator.score = if total_weight > 0.0 {
+            score / total_weight * 100.0
+        } else {
+            0.0
+        };
+    }
+    validators.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.account_id.to_string().cmp(&b.account_id.to_string()))
+    });
+    for (index, validator) in validators.iter_mut().enumerate() {
+        validator.rank = index as u32 + 1;
+    }
+}
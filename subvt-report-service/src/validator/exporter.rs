@@ -0,0 +1,240 @@
+//! Prometheus exporter of SubVT's view of a set of validators, for the operators' own Prometheus
+//! and Grafana. The metrics are gauges labelled with the network, the stash address and the
+//! identity display of the validator, produced on each scrape from the current validator details.
+use prometheus::{Encoder, GaugeVec, Opts, Registry, TextEncoder};
+use subvt_types::subvt::ValidatorDetails;
+
+const METRIC_PREFIX: &str = "subvt_validator";
+const LABELS: [&str; 3] = ["network", "stash", "display"];
+
+/// Index of the fields of a session's para validator performance, see `sub_get_validator_info`.
+const PERFORMANCE_SESSION_INDEX: usize = 1;
+const PERFORMANCE_IMPLICIT_ATTESTATION_COUNT: usize = 2;
+const PERFORMANCE_EXPLICIT_ATTESTATION_COUNT: usize = 3;
+const PERFORMANCE_MISSED_ATTESTATION_COUNT: usize = 4;
+const PERFORMANCE_ATTESTATIONS_PER_BILLION: usize = 5;
+
+fn register_gauge(registry: &Registry, name: &str, help: &str) -> anyhow::Result<GaugeVec> {
+    let gauge = GaugeVec::new(Opts::new(format!("{METRIC_PREFIX}::{name}"), help), &LABELS)?;
+    registry.register(Box::new(gauge.clone()))?;
+    Ok(gauge)
+}
+
+fn get_token_amount(amount: u128, token_decimals: usize) -> f64 {
+    amount as f64 / 10f64.powi(token_decimals as i32)
+}
+
+fn bool_value(value: bool) -> f64 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Encodes the metrics of the validators in the Prometheus text format. Metrics that the
+/// validator has no value for, e.g. the 1KV rank of a validator that's not a candidate, are
+/// omitted for that validator.
+pub(crate) fn encode_validator_metrics(
+    network: &str,
+    token_decimals: usize,
+    validators: &[ValidatorDetails],
+) -> anyhow::Result<String> {
+    let registry = Registry::new();
+    let is_active = register_gauge(
+        &registry,
+        "is_active",
+        "1 if the validator is in the active set",
+    )?;
+    let is_active_next_session = register_gauge(
+        &registry,
+        "is_active_next_session",
+        "1 if the validator is in the active set of the next session",
+    )?;
+    let is_para_validator = register_gauge(
+        &registry,
+        "is_para_validator",
+        "1 if the validator is a para validator in the current session",
+    )?;
+    let reward_points = register_gauge(&registry, "era_points", "Reward points in the active era")?;
+    let blocks_authored = register_gauge(
+        &registry,
+        "blocks_authored",
+        "Number of blocks authored in the current session",
+    )?;
+    let heartbeat_received = register_gauge(
+        &registry,
+        "heartbeat_received",
+        "1 if the validator has sent a heartbeat in the current session",
+    )?;
+    let self_stake = register_gauge(&registry, "self_stake", "Active self stake in tokens")?;
+    let total_stake = register_gauge(
+        &registry,
+        "total_stake",
+        "Total active stake in the active era in tokens",
+    )?;
+    let nomination_count = register_gauge(
+        &registry,
+        "nomination_count",
+        "Number of nominations, active and inactive",
+    )?;
+    let active_nominator_count = register_gauge(
+        &registry,
+        "active_nominator_count",
+        "Number of nominators backing the validator in the active era",
+    )?;
+    let oversubscribed = register_gauge(
+        &registry,
+        "oversubscribed",
+        "1 if the validator has more nominators than get rewards",
+    )?;
+    let commission = register_gauge(&registry, "commission", "Commission rate between 0 and 1")?;
+    let return_rate = register_gauge(
+        &registry,
+        "return_rate",
+        "Annual return rate for the nominators",
+    )?;
+    let unclaimed_era_count = register_gauge(
+        &registry,
+        "unclaimed_era_count",
+        "Number of eras with unclaimed rewards",
+    )?;
+    let slash_count = register_gauge(&registry, "slash_count", "Number of slashes")?;
+    let offline_offence_count = register_gauge(
+        &registry,
+        "offline_offence_count",
+        "Number of offline offences",
+    )?;
+    let onekv_rank = register_gauge(
+        &registry,
+        "dn_rank",
+        "Rank in the decentralized nodes program",
+    )?;
+    let onekv_is_valid = register_gauge(
+        &registry,
+        "dn_is_valid",
+        "1 if the validator is a valid decentralized nodes candidate",
+    )?;
+    let para_session_index = register_gauge(
+        &registry,
+        "para_session_index",
+        "Index of the last session that the validator was a para validator in",
+    )?;
+    let implicit_attestation_count = register_gauge(
+        &registry,
+        "para_implicit_attestation_count",
+        "Number of implicit attestations in the last para validator session",
+    )?;
+    let explicit_attestation_count = register_gauge(
+        &registry,
+        "para_explicit_attestation_count",
+        "Number of explicit attestations in the last para validator session",
+    )?;
+    let missed_attestation_count = register_gauge(
+        &registry,
+        "para_missed_attestation_count",
+        "Number of missed attestations in the last para validator session",
+    )?;
+    let attestation_rate = register_gauge(
+        &registry,
+        "para_attestation_rate",
+        "Ratio of the attestations to the expected in the last para validator session",
+    )?;
+    for validator in validators {
+        let display = validator.account.get_full_display().unwrap_or_default();
+        let labels = [
+            network,
+            validator.account.address.as_str(),
+            display.as_str(),
+        ];
+        is_active
+            .with_label_values(&labels)
+            .set(bool_value(validator.is_active));
+        is_active_next_session
+            .with_label_values(&labels)
+            .set(bool_value(validator.is_active_next_session));
+        is_para_validator
+            .with_label_values(&labels)
+            .set(bool_value(validator.is_para_validator));
+        if let Some(points) = validator.reward_points {
+            reward_points.with_label_values(&labels).set(points as f64);
+        }
+        if let Some(count) = validator.blocks_authored {
+            blocks_authored.with_label_values(&labels).set(count as f64);
+        }
+        if let Some(received) = validator.heartbeat_received {
+            heartbeat_received
+                .with_label_values(&labels)
+                .set(bool_value(received));
+        }
+        self_stake.with_label_values(&labels).set(get_token_amount(
+            validator.self_stake.active_amount,
+            token_decimals,
+        ));
+        if let Some(validator_stake) = &validator.validator_stake {
+            total_stake.with_label_values(&labels).set(get_token_amount(
+                validator_stake.total_stake,
+                token_decimals,
+            ));
+            active_nominator_count
+                .with_label_values(&labels)
+                .set(validator_stake.nominators.len() as f64);
+        }
+        nomination_count
+            .with_label_values(&labels)
+            .set(validator.nominations.len() as f64);
+        oversubscribed
+            .with_label_values(&labels)
+            .set(bool_value(validator.oversubscribed));
+        commission
+            .with_label_values(&labels)
+            .set(validator.preferences.commission_per_billion as f64 / 1_000_000_000.0);
+        if let Some(rate_per_billion) = validator.return_rate_per_billion {
+            return_rate
+                .with_label_values(&labels)
+                .set(rate_per_billion as f64 / 1_000_000_000.0);
+        }
+        unclaimed_era_count
+            .with_label_values(&labels)
+            .set(validator.unclaimed_era_indices.len() as f64);
+        slash_count
+            .with_label_values(&labels)
+            .set(validator.slash_count as f64);
+        offline_offence_count
+            .with_label_values(&labels)
+            .set(validator.offline_offence_count as f64);
+        if let Some(rank) = validator.onekv_rank {
+            onekv_rank.with_label_values(&labels).set(rank as f64);
+        }
+        if let Some(is_valid) = validator.onekv_is_valid {
+            onekv_is_valid
+                .with_label_values(&labels)
+                .set(bool_value(is_valid));
+        }
+        // the most recent session is the first
+        if let Some(performance) = validator
+            .performance
+            .first()
+            .filter(|performance| performance.len() > PERFORMANCE_ATTESTATIONS_PER_BILLION)
+        {
+            para_session_index
+                .with_label_values(&labels)
+                .set(performance[PERFORMANCE_SESSION_INDEX] as f64);
+            implicit_attestation_count
+                .with_label_values(&labels)
+                .set(performance[PERFORMANCE_IMPLICIT_ATTESTATION_COUNT] as f64);
+            explicit_attestation_count
+                .with_label_values(&labels)
+                .set(performance[PERFORMANCE_EXPLICIT_ATTESTATION_COUNT] as f64);
+            missed_attestation_count
+                .with_label_values(&labels)
+                .set(performance[PERFORMANCE_MISSED_ATTESTATION_COUNT] as f64);
+            attestation_rate
+                .with_label_values(&labels)
+                .set(performance[PERFORMANCE_ATTESTATIONS_PER_BILLION] as f64 / 1_000_000_000.0);
+        }
+    }
+    let mut buffer = Vec::new();
+    TextEncoder::new().encode(&registry.gather(), &mut buffer)?;
+    Ok(String::from_utf8(buffer)?)
+}
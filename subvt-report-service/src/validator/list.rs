@@ -0,0 +1,355 @@
+//! Filtering, sorting, cursor pagination and field projection for the validator list services.
+use serde::Deserialize;
+use std::cmp::Ordering;
+use std::str::FromStr;
+use subvt_types::subvt::ValidatorSummary;
+
+const MAX_PAGE_SIZE: usize = 1000;
+
+#[derive(Default, Deserialize)]
+pub struct ValidatorListQueryParameters {
+    /// Substring of the address or the identity display.
+    query: Option<String>,
+    min_commission_per_billion: Option<u32>,
+    max_commission_per_billion: Option<u32>,
+    /// Stake amounts are in the smallest unit of the token (e.g. Planck).
+    min_self_stake: Option<String>,
+    max_self_stake: Option<String>,
+    min_total_stake: Option<String>,
+    max_total_stake: Option<String>,
+    identity_confirmed: Option<bool>,
+    has_parent_identity: Option<bool>,
+    /// Substring of the parent identity display.
+    parent_display: Option<String>,
+    is_para_validator: Option<bool>,
+    oversubscribed: Option<bool>,
+    blocks_nominations: Option<bool>,
+    /// Whether the validator is a decentralized nodes (former 1KV) candidate.
+    #[serde(alias = "is_enrolled_in_1kv")]
+    is_dn_candidate: Option<bool>,
+    /// Whether the validator is a valid decentralized nodes candidate. Validators that aren't
+    /// candidates never match.
+    #[serde(alias = "onekv_is_valid")]
+    dn_is_valid: Option<bool>,
+    sort_by: Option<String>,
+    /// `asc` or `desc`, defaults to `asc`.
+    sort_order: Option<String>,
+    cursor: Option<String>,
+    limit: Option<usize>,
+    /// Comma-separated list of the `ValidatorSummary` fields to be returned. `account_id` is
+    /// always included.
+    fields: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ValidatorSortField {
+    AccountId,
+    CommissionPerBillion,
+    SelfStake,
+    TotalStake,
+    NominatorCount,
+    InactiveNominationCount,
+    InactiveNominationAmount,
+    ReturnRatePerBillion,
+    BlocksAuthored,
+    RewardPoints,
+    SlashCount,
+}
+
+impl FromStr for ValidatorSortField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "account_id" => Ok(Self::AccountId),
+            "commission_per_billion" => Ok(Self::CommissionPerBillion),
+            "self_stake" => Ok(Self::SelfStake),
+            "total_stake" => Ok(Self::TotalStake),
+            "nominator_count" => Ok(Self::NominatorCount),
+            "inactive_nomination_count" => Ok(Self::InactiveNominationCount),
+            "inactive_nomination_amount" => Ok(Self::InactiveNominationAmount),
+            "return_rate_per_billion" => Ok(Self::ReturnRatePerBillion),
+            "blocks_authored" => Ok(Self::BlocksAuthored),
+            "reward_points" => Ok(Self::RewardPoints),
+            "slash_count" => Ok(Self::SlashCount),
+            _ => Err(format!("Invalid sort field: {s}")),
+        }
+    }
+}
+
+impl ValidatorSortField {
+    fn get_value(&self, validator: &ValidatorSummary) -> Option<u128> {
+        match self {
+            Self::AccountId => Some(0),
+            Self::CommissionPerBillion => {
+                Some(validator.preferences.commission_per_billion as u128)
+            }
+            Self::SelfStake => Some(validator.self_stake.active_amount),
+            Self::TotalStake => Some(get_total_stake(validator)),
+            Self::NominatorCount => validator
+                .validator_stake
+                .as_ref()
+                .map(|stake| stake.nominator_count as u128),
+            Self::InactiveNominationCount => {
+                Some(validator.inactive_nominations.nomination_count as u128)
+            }
+            Self::InactiveNominationAmount => Some(validator.inactive_nominations.total_amount),
+            Self::ReturnRatePerBillion => validator.return_rate_per_billion.map(|r| r as u128),
+            Self::BlocksAuthored => validator.blocks_authored.map(|b| b as u128),
+            Self::RewardPoints => validator.reward_points.map(|r| r as u128),
+            Self::SlashCount => Some(validator.slash_count as u128),
+        }
+    }
+}
+
+/// Active stake for active validators, self stake plus all nominations for the others.
+fn get_total_stake(validator: &ValidatorSummary) -> u128 {
+    match &validator.validator_stake {
+        Some(validator_stake) => validator_stake.total_stake,
+        None => validator.self_stake.active_amount + validator.inactive_nominations.total_amount,
+    }
+}
+
+fn parse_balance(maybe_value: &Option<String>, name: &str) -> Result<Option<u128>, String> {
+    match maybe_value {
+        Some(value) => value
+            .parse::<u128>()
+            .map(Some)
+            .map_err(|_| format!("Invalid {name}: {value}")),
+        None => Ok(None),
+    }
+}
+
+fn is_in_range(value: u128, min: Option<u128>, max: Option<u128>) -> bool {
+    min.map(|min| value >= min).unwrap_or(true) && max.map(|max| value <= max).unwrap_or(true)
+}
+
+fn matches_flag(maybe_flag: Option<bool>, value: bool) -> bool {
+    maybe_flag.map(|flag| flag == value).unwrap_or(true)
+}
+
+/// The validity of the validators that aren't decentralized nodes candidates is meaningless, so
+/// they don't match either validity.
+fn matches_dn_validity(maybe_is_valid: Option<bool>, validator: &ValidatorSummary) -> bool {
+    match maybe_is_valid {
+        Some(is_valid) => {
+            validator.is_enrolled_in_1kv && validator.onekv_is_valid.unwrap_or(false) == is_valid
+        }
+        None => true,
+    }
+}
+
+/// Sort key of a validator. Validators without a value for the sort field always come last,
+/// ties are broken by the account id.
+type SortKey = (Option<u128>, String);
+
+pub struct ValidatorListQuery {
+    params: ValidatorListQueryParameters,
+    min_self_stake: Option<u128>,
+    max_self_stake: Option<u128>,
+    min_total_stake: Option<u128>,
+    max_total_stake: Option<u128>,
+    sort_field: ValidatorSortField,
+    is_descending: bool,
+    cursor: Option<SortKey>,
+    fields: Option<Vec<String>>,
+}
+
+/// A page of the validator list, along with the total number of matching validators and the
+/// cursor for the next page if there is one.
+pub struct ValidatorListPage {
+    pub total_count: usize,
+    pub validators: Vec<ValidatorSummary>,
+    pub next_cursor: Option<String>,
+}
+
+impl ValidatorListQueryParameters {
+    pub fn has_query(&self) -> bool {
+        self.query
+            .as_ref()
+            .map(|query| !query.is_empty())
+            .unwrap_or(false)
+    }
+}
+
+impl ValidatorListQuery {
+    pub fn new(params: ValidatorListQueryParameters) -> Result<Self, String> {
+        let sort_field = match &params.sort_by {
+            Some(sort_by) => ValidatorSortField::from_str(sort_by)?,
+            None => ValidatorSortField::AccountId,
+        };
+        let is_descending = match params.sort_order.as_deref() {
+            None | Some("asc") => false,
+            Some("desc") => true,
+            Some(sort_order) => return Err(format!("Invalid sort order: {sort_order}")),
+        };
+        if let Some(limit) = params.limit {
+            if limit == 0 || limit > MAX_PAGE_SIZE {
+                return Err(format!("Limit should be between 1 and {MAX_PAGE_SIZE}."));
+            }
+        }
+        let cursor = match &params.cursor {
+            Some(cursor) => Some(Self::decode_cursor(cursor, sort_field)?),
+            None => None,
+        };
+        let fields = params.fields.as_ref().map(|fields| {
+            fields
+                .split(',')
+                .map(|field| field.trim().to_string())
+                .filter(|field| !field.is_empty())
+                .collect()
+        });
+        Ok(Self {
+            min_self_stake: parse_balance(&params.min_self_stake, "min_self_stake")?,
+            max_self_stake: parse_balance(&params.max_self_stake, "max_self_stake")?,
+            min_total_stake: parse_balance(&params.min_total_stake, "min_total_stake")?,
+            max_total_stake: parse_balance(&params.max_total_stake, "max_total_stake")?,
+            params,
+            sort_field,
+            is_descending,
+            cursor,
+            fields,
+        })
+    }
+
+    fn encode_cursor(&self, key: &SortKey) -> String {
+        let value = key.0.map(|value| value.to_string()).unwrap_or_default();
+        hex::encode(format!("{:?}:{value}:{}", self.sort_field, key.1))
+    }
+
+    fn decode_cursor(cursor: &str, sort_field: ValidatorSortField) -> Result<SortKey, String> {
+        let invalid = || "Invalid cursor.".to_string();
+        let decoded = String::from_utf8(hex::decode(cursor).map_err(|_| invalid())?)
+            .map_err(|_| invalid())?;
+        let parts: Vec<&str> = decoded.split(':').collect();
+        if parts.len() != 3 || parts[0] != format!("{sort_field:?}") {
+            return Err(invalid());
+        }
+        let value = if parts[1].is_empty() {
+            None
+        } else {
+            Some(parts[1].parse::<u128>().map_err(|_| invalid())?)
+        };
+        Ok((value, parts[2].to_string()))
+    }
+
+    fn get_sort_key(&self, validator: &ValidatorSummary) -> SortKey {
+        (
+            self.sort_field.get_value(validator),
+            validator.account_id.to_string(),
+        )
+    }
+
+    fn compare(&self, a: &SortKey, b: &SortKey) -> Ordering {
+        let value_ordering = match (a.0, b.0) {
+            (Some(a_value), Some(b_value)) => {
+                if self.is_descending {
+                    b_value.cmp(&a_value)
+                } else {
+                    a_value.cmp(&b_value)
+                }
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        value_ordering.then_with(|| a.1.cmp(&b.1))
+    }
+
+    pub fn matches(&self, validator: &ValidatorSummary) -> bool {
+        let params = &self.params;
+        if let Some(query) = &params.query {
+            if !validator.filter(query) {
+                return false;
+            }
+        }
+        let commission = validator.preferences.commission_per_billion as u128;
+        if !is_in_range(
+            commission,
+            params.min_commission_per_billion.map(|c| c as u128),
+            params.max_commission_per_billion.map(|c| c as u128),
+        ) || !is_in_range(
+            validator.self_stake.active_amount,
+            self.min_self_stake,
+            self.max_self_stake,
+        ) || !is_in_range(
+            get_total_stake(validator),
+            self.min_total_stake,
+            self.max_total_stake,
+        ) {
+            return false;
+        }
+        if let Some(parent_display) = &params.parent_display {
+            let parent_display = parent_display.to_lowercase();
+            if !validator
+                .parent_display
+                .as_ref()
+                .map(|display| display.to_lowercase().contains(&parent_display))
+                .unwrap_or(false)
+            {
+                return false;
+            }
+        }
+        matches_flag(params.identity_confirmed, validator.confirmed)
+            && matches_flag(
+                params.has_parent_identity,
+                validator.parent_display.is_some(),
+            )
+            && matches_flag(params.is_para_validator, validator.is_para_validator)
+            && matches_flag(params.oversubscribed, validator.oversubscribed)
+            && matches_flag(
+                params.blocks_nominations,
+                validator.preferences.blocks_nominations,
+            )
+            && matches_flag(params.is_dn_candidate, validator.is_enrolled_in_1kv)
+            && matches_dn_validity(params.dn_is_valid, validator)
+    }
+
+    /// Filters and sorts the validators, then returns the page after the cursor.
+    pub fn apply(&self, validators: Vec<ValidatorSummary>) -> ValidatorListPage {
+        let mut keyed_validators: Vec<(SortKey, ValidatorSummary)> = validators
+            .into_iter()
+            .filter(|validator| self.matches(validator))
+            .map(|validator| (self.get_sort_key(&validator), validator))
+            .collect();
+        let total_count = keyed_validators.len();
+        keyed_validators.sort_by(|a, b| self.compare(&a.0, &b.0));
+        let start_index = match &self.cursor {
+            Some(cursor) => keyed_validators
+                .iter()
+                .position(|(key, _)| self.compare(key, cursor) == Ordering::Greater)
+                .unwrap_or(keyed_validators.len()),
+            None => 0,
+        };
+        let mut page: Vec<(SortKey, ValidatorSummary)> =
+            keyed_validators.into_iter().skip(start_index).collect();
+        let next_cursor = match self.params.limit {
+            Some(limit) if page.len() > limit => {
+                page.truncate(limit);
+                page.last().map(|(key, _)| self.encode_cursor(key))
+            }
+            _ => None,
+        };
+        ValidatorListPage {
+            total_count,
+            validators: page.into_iter().map(|(_, validator)| validator).collect(),
+            next_cursor,
+        }
+    }
+
+    /// Serializes the validators, keeping only the requested fields if there is a projection.
+    pub fn project(
+        &self,
+        validators: &[ValidatorSummary],
+    ) -> anyhow::Result<Vec<serde_json::Value>> {
+        let mut result = Vec::with_capacity(validators.len());
+        for validator in validators {
+            let mut value = serde_json::to_value(validator)?;
+            if let (Some(fields), Some(object)) = (&self.fields, value.as_object_mut()) {
+                object.retain(|key, _| key == "account_id" || fields.contains(key));
+            }
+            result.push(value);
+        }
+        Ok(result)
+    }
+}
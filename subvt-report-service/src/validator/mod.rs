@@ -1,18 +1,27 @@
-use crate::{ResultResponse, ServiceState, CONFIG};
+use crate::util::validate_quote_currency;
+use crate::{ResultResponse, ServiceState};
 use actix_web::{get, web, HttpResponse};
 use chrono::{DateTime, Datelike, Days, Months, NaiveDateTime, Utc};
+use list::{ValidatorListPage, ValidatorListQuery, ValidatorListQueryParameters};
+use rustc_hash::FxHashSet as HashSet;
 use serde::Deserialize;
 use std::str::FromStr;
 use subvt_substrate_client::SubstrateClient;
 use subvt_types::crypto::AccountId;
 use subvt_types::err::ServiceError;
+use subvt_types::kline::KLineInterval;
 use subvt_types::report::{
     BlockSummary, EraValidatorPayoutReport, EraValidatorRewardReport, MonthlyIncome,
-    MonthlyIncomeReport, ValidatorDetailsReport, ValidatorListReport, ValidatorSummaryReport,
+    MonthlyIncomeReport, ValidatorComparisonReport, ValidatorDetailsReport,
+    ValidatorDetailsSnapshotDiff, ValidatorListPageReport, ValidatorSummaryReport,
     ValidatorTotalRewardChartData,
 };
 use subvt_types::subvt::{ValidatorSearchSummary, ValidatorSummary};
 
+pub mod compare;
+mod exporter;
+pub mod list;
+
 fn validate_path_param(ss58_address_or_account_id: &str) -> Result<AccountId, HttpResponse> {
     let account_id = match AccountId::from_str(ss58_address_or_account_id) {
         Ok(account_id) => account_id,
@@ -84,15 +93,62 @@ pub(crate) async fn validator_summary_service(
     }
 }
 
+#[derive(Deserialize)]
+pub(crate) struct ValidatorDetailsQueryParameters {
+    #[serde(rename(deserialize = "era"))]
+    maybe_era_index: Option<u32>,
+    /// Relay chain block number, the snapshot of the block's era is returned.
+    #[serde(rename(deserialize = "block"))]
+    maybe_block_number: Option<u64>,
+}
+
+/// Current details of the validator, or its details snapshot at an era if the `era` or `block`
+/// query parameter is given.
 #[get("/validator/{ss58_address_or_account_id}/details")]
 pub(crate) async fn validator_details_service(
     path: web::Path<ValidatorPathParameter>,
+    query: web::Query<ValidatorDetailsQueryParameters>,
     data: web::Data<ServiceState>,
 ) -> ResultResponse {
     let account_id = match validate_path_param(&path.into_inner().ss58_address_or_account_id) {
         Ok(account_id) => account_id,
         Err(response) => return Ok(response),
     };
+    let maybe_era_index = match (query.maybe_era_index, query.maybe_block_number) {
+        (Some(_), Some(_)) => {
+            return Ok(HttpResponse::BadRequest().json(ServiceError::from(
+                "Only one of era and block can be given.",
+            )))
+        }
+        (Some(era_index), None) => Some(era_index),
+        (None, Some(block_number)) => {
+            match data
+                .postgres
+                .get_block_by_number("relay", block_number)
+                .await?
+            {
+                Some(block) => Some(block.era_index as u32),
+                None => {
+                    return Ok(HttpResponse::NotFound().json(ServiceError::from(&format!(
+                        "Block #{block_number} not found."
+                    ))))
+                }
+            }
+        }
+        (None, None) => None,
+    };
+    if let Some(era_index) = maybe_era_index {
+        return match data
+            .postgres
+            .get_validator_details_snapshot(&account_id, era_index)
+            .await?
+        {
+            Some(snapshot) => Ok(HttpResponse::Ok().json(snapshot)),
+            None => Ok(HttpResponse::NotFound().json(ServiceError::from(&format!(
+                "Validator details not found for era {era_index}."
+            )))),
+        };
+    }
     let finalized_block = data.redis.get_finalized_block_summary().await?;
     if let Some(validator_details) = data
         .redis
@@ -108,101 +164,261 @@ pub(crate) async fn validator_details_service(
     }
 }
 
-#[get("/validator/list")]
-pub(crate) async fn validator_list_service(data: web::Data<ServiceState>) -> ResultResponse {
-    let finalized_block = match get_finalized_block_summary(&data) {
-        Ok(block_summary) => block_summary,
+#[derive(Deserialize)]
+pub(crate) struct ValidatorDetailsDiffQueryParameters {
+    from_era: u32,
+    to_era: u32,
+}
+
+/// Changes in the details of the validator between its snapshots at two eras.
+#[get("/validator/{ss58_address_or_account_id}/details/diff")]
+pub(crate) async fn validator_details_diff_service(
+    path: web::Path<ValidatorPathParameter>,
+    query: web::Query<ValidatorDetailsDiffQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let account_id = match validate_path_param(&path.into_inner().ss58_address_or_account_id) {
+        Ok(account_id) => account_id,
         Err(response) => return Ok(response),
     };
-    let mut active_validator_list = {
-        match get_validator_list(&data, true) {
-            Ok(list) => list,
+    if query.from_era >= query.to_era {
+        return Ok(HttpResponse::BadRequest()
+            .json(ServiceError::from("from_era should be less than to_era.")));
+    }
+    let mut snapshots = Vec::with_capacity(2);
+    for era_index in [query.from_era, query.to_era] {
+        match data
+            .postgres
+            .get_validator_details_snapshot(&account_id, era_index)
+            .await?
+        {
+            Some(snapshot) => snapshots.push(snapshot),
+            None => {
+                return Ok(HttpResponse::NotFound().json(ServiceError::from(&format!(
+                    "Validator details not found for era {era_index}."
+                ))))
+            }
+        }
+    }
+    let (from, to) = (&snapshots[0], &snapshots[1]);
+    Ok(HttpResponse::Ok().json(ValidatorDetailsSnapshotDiff {
+        from_era_index: from.era_index,
+        from_block_number: from.block_number,
+        to_era_index: to.era_index,
+        to_block_number: to.block_number,
+        diff: from.validator_details.get_diff(&to.validator_details),
+    }))
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ValidatorComparisonQueryParameters {
+    /// Comma-separated SS58 addresses or account ids.
+    validators: String,
+    start_era_index: u32,
+    end_era_index: u32,
+    /// Comma-separated `metric:weight` pairs, overrides the default weights in the config.
+    #[serde(rename(deserialize = "weights"))]
+    maybe_weights: Option<String>,
+}
+
+/// Compares the validators side by side over the era range and ranks them by the weighted
+/// composite score. See `ValidatorComparison` in `subvt-types` for the metrics.
+#[get("/validator/compare")]
+pub(crate) async fn validator_comparison_service(
+    query: web::Query<ValidatorComparisonQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let mut account_ids: Vec<AccountId> = Vec::new();
+    for ss58_address_or_account_id in query
+        .validators
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+    {
+        let account_id = match validate_path_param(ss58_address_or_account_id) {
+            Ok(account_id) => account_id,
             Err(response) => return Ok(response),
+        };
+        if !account_ids.contains(&account_id) {
+            account_ids.push(account_id);
         }
+    }
+    if account_ids.is_empty()
+        || account_ids.len() > data.network.report.max_comparison_validator_count
+    {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from(&format!(
+            "Between 1 and {} validators can be compared.",
+            data.network.report.max_comparison_validator_count
+        ))));
+    }
+    if query.end_era_index < query.start_era_index {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from(
+            "End era index cannot be less than start era index.",
+        )));
+    }
+    let era_count = query.end_era_index - query.start_era_index + 1;
+    if era_count > data.network.report.max_era_index_range {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from(&format!(
+            "Comparison cannot span {era_count} eras. Maximum allowed is {}.",
+            data.network.report.max_era_index_range
+        ))));
+    }
+    let weights = match compare::parse_score_weights(
+        query
+            .maybe_weights
+            .as_deref()
+            .unwrap_or(&data.network.report.validator_score_weights),
+    ) {
+        Ok(weights) => weights,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(ServiceError::from(&error))),
     };
-    let mut inactive_validator_list = {
-        match get_validator_list(&data, false) {
-            Ok(list) => list,
+    let finalized_block = data.redis.get_finalized_block_summary().await?;
+    let mut validators = Vec::with_capacity(account_ids.len());
+    for account_id in account_ids {
+        let era_reports = data
+            .postgres
+            .get_era_validator_report(
+                query.start_era_index,
+                query.end_era_index,
+                &account_id.to_string(),
+            )
+            .await?;
+        let era_performances = data
+            .postgres
+            .get_validator_era_performances(&account_id, query.start_era_index, query.end_era_index)
+            .await?;
+        let maybe_validator_details = data
+            .redis
+            .fetch_validator_details(finalized_block.number, &account_id)
+            .await?;
+        validators.push(compare::get_validator_comparison(
+            account_id,
+            data.ss58_prefix,
+            era_count,
+            &era_reports,
+            &era_performances,
+            maybe_validator_details.as_ref(),
+        ));
+    }
+    let reference_era_reports = data
+        .postgres
+        .get_era_validator_reports(query.start_era_index, query.end_era_index, data.ss58_prefix)
+        .await?;
+    let reference_era_performances = data
+        .postgres
+        .get_era_validator_performances(query.start_era_index, query.end_era_index)
+        .await?;
+    let mut identity_confirmed_account_ids = HashSet::default();
+    for is_active in [true, false] {
+        match get_validator_list(&data, is_active) {
+            Ok(list) => identity_confirmed_account_ids.extend(
+                list.iter()
+                    .filter(|validator| validator.confirmed)
+                    .map(|validator| validator.account_id),
+            ),
             Err(response) => return Ok(response),
         }
-    };
-    active_validator_list.append(&mut inactive_validator_list);
-    Ok(HttpResponse::Ok().json(ValidatorListReport {
-        finalized_block,
-        validators: active_validator_list,
+    }
+    let reference_validators = compare::get_reference_validators(
+        data.ss58_prefix,
+        era_count,
+        &reference_era_reports,
+        &reference_era_performances,
+        &identity_confirmed_account_ids,
+    );
+    compare::rank_validators(&mut validators, &reference_validators, &weights);
+    Ok(HttpResponse::Ok().json(ValidatorComparisonReport {
+        start_era_index: query.start_era_index,
+        end_era_index: query.end_era_index,
+        weights,
+        validators,
     }))
 }
 
-#[get("/validator/list/active")]
-pub(crate) async fn active_validator_list_service(data: web::Data<ServiceState>) -> ResultResponse {
-    let finalized_block = match get_finalized_block_summary(&data) {
-        Ok(block_summary) => block_summary,
-        Err(response) => return Ok(response),
-    };
-    let active_validator_list = {
-        match get_validator_list(&data, true) {
-            Ok(list) => list,
-            Err(response) => return Ok(response),
-        }
+fn query_validator_list(
+    data: &web::Data<ServiceState>,
+    params: ValidatorListQueryParameters,
+    is_active: Option<bool>,
+) -> Result<(ValidatorListQuery, ValidatorListPage), HttpResponse> {
+    let query = match ValidatorListQuery::new(params) {
+        Ok(query) => query,
+        Err(error) => return Err(HttpResponse::BadRequest().json(ServiceError::from(&error))),
     };
-    Ok(HttpResponse::Ok().json(ValidatorListReport {
-        finalized_block,
-        validators: active_validator_list,
-    }))
+    let mut validators = Vec::new();
+    if is_active != Some(false) {
+        validators.append(&mut get_validator_list(data, true)?);
+    }
+    if is_active != Some(true) {
+        validators.append(&mut get_validator_list(data, false)?);
+    }
+    let page = query.apply(validators);
+    Ok((query, page))
 }
 
-#[get("/validator/list/inactive")]
-pub(crate) async fn inactive_validator_list_service(
+async fn get_validator_list_page_report(
     data: web::Data<ServiceState>,
+    params: ValidatorListQueryParameters,
+    is_active: Option<bool>,
 ) -> ResultResponse {
     let finalized_block = match get_finalized_block_summary(&data) {
         Ok(block_summary) => block_summary,
         Err(response) => return Ok(response),
     };
-    let inactive_validator_list = {
-        match get_validator_list(&data, false) {
-            Ok(list) => list,
-            Err(response) => return Ok(response),
-        }
+    let (query, page) = match query_validator_list(&data, params, is_active) {
+        Ok(result) => result,
+        Err(response) => return Ok(response),
     };
-    Ok(HttpResponse::Ok().json(ValidatorListReport {
+    Ok(HttpResponse::Ok().json(ValidatorListPageReport {
         finalized_block,
-        validators: inactive_validator_list,
+        total_count: page.total_count as u32,
+        next_cursor: page.next_cursor,
+        validators: query.project(&page.validators)?,
     }))
 }
 
-#[derive(Deserialize)]
-pub(crate) struct ValidatorSearchQueryParameters {
-    query: String,
+#[get("/validator/list")]
+pub(crate) async fn validator_list_service(
+    query: web::Query<ValidatorListQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    get_validator_list_page_report(data, query.into_inner(), None).await
 }
 
+#[get("/validator/list/active")]
+pub(crate) async fn active_validator_list_service(
+    query: web::Query<ValidatorListQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    get_validator_list_page_report(data, query.into_inner(), Some(true)).await
+}
+
+#[get("/validator/list/inactive")]
+pub(crate) async fn inactive_validator_list_service(
+    query: web::Query<ValidatorListQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    get_validator_list_page_report(data, query.into_inner(), Some(false)).await
+}
+
+/// Accepts the same filtering, sorting and pagination parameters as the list services, but
+/// requires the `query` parameter.
 #[get("/validator/search")]
 pub(crate) async fn validator_search_service(
-    query: web::Query<ValidatorSearchQueryParameters>,
+    query: web::Query<ValidatorListQueryParameters>,
     data: web::Data<ServiceState>,
 ) -> ResultResponse {
-    let mut active_validator_list = {
-        match get_validator_list(&data, true) {
-            Ok(list) => list,
-            Err(response) => return Ok(response),
-        }
-    };
-    let mut inactive_validator_list = {
-        match get_validator_list(&data, false) {
-            Ok(list) => list,
-            Err(response) => return Ok(response),
-        }
+    let params = query.into_inner();
+    if !params.has_query() {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from("Missing query.")));
+    }
+    let (_, page) = match query_validator_list(&data, params, None) {
+        Ok(result) => result,
+        Err(response) => return Ok(response),
     };
-    active_validator_list.append(&mut inactive_validator_list);
-    let list: Vec<ValidatorSearchSummary> = active_validator_list
+    let list: Vec<ValidatorSearchSummary> = page
+        .validators
         .iter()
-        .filter_map(|validator_summary| {
-            if validator_summary.filter(&query.query) {
-                Some(ValidatorSearchSummary::from(validator_summary))
-            } else {
-                None
-            }
-        })
+        .map(ValidatorSearchSummary::from)
         .collect();
     Ok(HttpResponse::Ok().json(list))
 }
@@ -258,10 +474,10 @@ pub(crate) async fn validator_reward_chart_service(
     data: web::Data<ServiceState>,
 ) -> ResultResponse {
     let people_client = SubstrateClient::new(
-        CONFIG.substrate.people_rpc_url.as_str(),
-        CONFIG.substrate.network_id,
-        CONFIG.substrate.connection_timeout_seconds,
-        CONFIG.substrate.request_timeout_seconds,
+        data.network.substrate.people_rpc_url.as_str(),
+        data.network.substrate.network_id,
+        data.network.substrate.connection_timeout_seconds,
+        data.network.substrate.request_timeout_seconds,
     )
     .await?;
     let rewards = data
@@ -320,16 +536,26 @@ pub(crate) async fn validator_reward_chart_service(
     }))
 }
 
+#[derive(Deserialize)]
+pub(crate) struct MonthlyIncomeQueryParameters {
+    #[serde(rename(deserialize = "quote_currency"))]
+    maybe_quote_currency: Option<String>,
+}
+
 #[get("/validator/{ss58_address_or_account_id}/income/monthly")]
 pub(crate) async fn validator_monhtly_income_service(
     path: web::Path<ValidatorPathParameter>,
+    query: web::Query<MonthlyIncomeQueryParameters>,
     data: web::Data<ServiceState>,
 ) -> ResultResponse {
-    let token_symbol = "USDT";
     let account_id = match validate_path_param(&path.into_inner().ss58_address_or_account_id) {
         Ok(account_id) => account_id,
         Err(response) => return Ok(response),
     };
+    let quote_currency = match validate_quote_currency(&query.maybe_quote_currency) {
+        Ok(quote_currency) => quote_currency,
+        Err(response) => return Ok(response),
+    };
     let now = Utc::now();
     let start_date = now
         .checked_sub_months(Months::new(12))
@@ -348,7 +574,7 @@ pub(crate) async fn validator_monhtly_income_service(
         .get_rewards_in_time_range(&account_id, start_timestamp as u64, end_timestamp as u64)
         .await?;
     let mut monthly_income: Vec<MonthlyIncome> = Vec::new();
-    let denominator = f64::powi(10.0, CONFIG.substrate.token_decimals as i32);
+    let denominator = f64::powi(10.0, data.network.substrate.token_decimals as i32);
     for reward in rewards.iter() {
         let reward_day = DateTime::from_timestamp_millis(reward.block_timestamp as i64)
             .unwrap()
@@ -357,16 +583,27 @@ pub(crate) async fn validator_monhtly_income_service(
         let reward_month = reward_day.month();
         let reward_day_begin_timestamp =
             NaiveDateTime::from(reward_day).and_utc().timestamp_millis();
-        let kline_close = data
+        let kline_close = match data
             .postgres
             .get_kline(
-                &CONFIG.substrate.token_ticker,
-                token_symbol,
+                &data.network.substrate.token_ticker,
+                &quote_currency,
+                KLineInterval::Day,
                 reward_day_begin_timestamp as u64,
             )
             .await?
-            .close_to_f64()
-            .unwrap();
+            .and_then(|kline| kline.close_to_f64())
+        {
+            Some(kline_close) => kline_close,
+            None => {
+                log::warn!(
+                    "No {}-{quote_currency} price for reward #{}, skip.",
+                    data.network.substrate.token_ticker,
+                    reward.id,
+                );
+                continue;
+            }
+        };
         let reward = (reward.amount as f64) * kline_close / denominator;
         if let Some(monthly_income_instance) =
             monthly_income.iter_mut().find(|monthly_income_instance| {
@@ -385,7 +622,67 @@ pub(crate) async fn validator_monhtly_income_service(
     }
     Ok(HttpResponse::Ok().json(MonthlyIncomeReport {
         rewardee: account_id,
-        token_symbol: token_symbol.to_string(),
+        token_symbol: quote_currency,
         monthly_income,
     }))
 }
+
+#[derive(Deserialize)]
+pub(crate) struct ValidatorMetricsQueryParameters {
+    /// Comma-separated SS58 addresses or account ids, overrides the validators in the config.
+    #[serde(rename(deserialize = "validators"))]
+    maybe_validators: Option<String>,
+}
+
+/// Prometheus exporter of the current state of the validators, see the `exporter` module. The
+/// validators not in the current validator list are skipped.
+#[get("/validator/metrics")]
+pub(crate) async fn validator_metrics_service(
+    query: web::Query<ValidatorMetricsQueryParameters>,
+    data: web::Data<ServiceState>,
+) -> ResultResponse {
+    let mut account_ids: Vec<AccountId> = Vec::new();
+    for ss58_address_or_account_id in query
+        .maybe_validators
+        .as_deref()
+        .unwrap_or(&data.network.report.exporter_validators)
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+    {
+        let account_id = match validate_path_param(ss58_address_or_account_id) {
+            Ok(account_id) => account_id,
+            Err(response) => return Ok(response),
+        };
+        if !account_ids.contains(&account_id) {
+            account_ids.push(account_id);
+        }
+    }
+    if account_ids.is_empty()
+        || account_ids.len() > data.network.report.max_exporter_validator_count
+    {
+        return Ok(HttpResponse::BadRequest().json(ServiceError::from(&format!(
+            "Between 1 and {} validators can be exported.",
+            data.network.report.max_exporter_validator_count
+        ))));
+    }
+    let finalized_block = data.redis.get_finalized_block_summary().await?;
+    let mut validators = Vec::with_capacity(account_ids.len());
+    for account_id in &account_ids {
+        if let Some(validator_details) = data
+            .redis
+            .fetch_validator_details(finalized_block.number, account_id)
+            .await?
+        {
+            validators.push(validator_details);
+        }
+    }
+    let metrics = exporter::encode_validator_metrics(
+        &data.network.substrate.chain,
+        data.network.substrate.token_decimals,
+        &validators,
+    )?;
+    Ok(HttpResponse::Ok()
+        .content_type(prometheus::TEXT_FORMAT)
+        .body(metrics))
+}
@@ -0,0 +1,170 @@
+use serde_json::json;
+use subvt_report_service::validator::compare::{
+    get_validator_comparison, parse_score_weights, rank_validators,
+};
+use subvt_report_service::validator::list::{ValidatorListQuery, ValidatorListQueryParameters};
+use subvt_types::crypto::AccountId;
+use subvt_types::report::{ValidatorComparison, ValidatorScoreMetric};
+use subvt_types::subvt::ValidatorSummary;
+
+fn validator(
+    id: u8,
+    self_stake: u128,
+    is_dn_candidate: bool,
+    dn_is_valid: bool,
+) -> ValidatorSummary {
+    let mut validator = ValidatorSummary {
+        account_id: AccountId::from([id; 32]),
+        is_enrolled_in_1kv: is_dn_candidate,
+        onekv_is_valid: Some(dn_is_valid),
+        ..Default::default()
+    };
+    validator.self_stake.active_amount = self_stake;
+    validator
+}
+
+fn get_validators() -> Vec<ValidatorSummary> {
+    vec![
+        validator(1, 300, true, true),
+        validator(2, 100, true, false),
+        validator(3, 200, false, false),
+    ]
+}
+
+fn query(params: serde_json::Value) -> Result<ValidatorListQuery, String> {
+    let params: ValidatorListQueryParameters = serde_json::from_value(params).unwrap();
+    ValidatorListQuery::new(params)
+}
+
+fn get_ids(params: serde_json::Value) -> Vec<u8> {
+    query(params)
+        .unwrap()
+        .apply(get_validators())
+        .validators
+        .iter()
+        .map(|validator| validator.account_id.as_ref()[0])
+        .collect()
+}
+
+#[test]
+fn test_dn_filter() {
+    assert_eq!(get_ids(json!({})), vec![1, 2, 3]);
+    assert_eq!(get_ids(json!({ "is_dn_candidate": true })), vec![1, 2]);
+    assert_eq!(get_ids(json!({ "is_dn_candidate": false })), vec![3]);
+    assert_eq!(get_ids(json!({ "dn_is_valid": true })), vec![1]);
+    // non-candidates are neither valid nor invalid
+    assert_eq!(get_ids(json!({ "dn_is_valid": false })), vec![2]);
+    assert_eq!(get_ids(json!({ "onekv_is_valid": true })), vec![1]);
+    assert_eq!(get_ids(json!({ "is_enrolled_in_1kv": false })), vec![3]);
+}
+
+#[test]
+fn test_sort() {
+    assert_eq!(get_ids(json!({ "sort_by": "self_stake" })), vec![2, 3, 1]);
+    assert_eq!(
+        get_ids(json!({ "sort_by": "self_stake", "sort_order": "desc" })),
+        vec![1, 3, 2]
+    );
+    assert_eq!(
+        get_ids(json!({ "sort_by": "self_stake", "sort_order": "asc" })),
+        vec![2, 3, 1]
+    );
+}
+
+#[test]
+fn test_invalid_sort_params() {
+    assert!(query(json!({ "sort_by": "display" })).is_err());
+    assert!(query(json!({ "sort_by": "self_stake", "sort_order": "descending" })).is_err());
+    assert!(query(json!({ "limit": 0 })).is_err());
+    assert!(query(json!({ "sort_by": "self_stake", "cursor": "invalid" })).is_err());
+}
+
+#[test]
+fn test_cursor_pagination() {
+    let first_query = query(json!({ "sort_by": "self_stake", "limit": 2 })).unwrap();
+    let first_page = first_query.apply(get_validators());
+    assert_eq!(first_page.total_count, 3);
+    assert_eq!(first_page.validators.len(), 2);
+    let cursor = first_page.next_cursor.unwrap();
+    let second_page =
+        query(json!({ "sort_by": "self_stake", "limit": 2, "cursor": cursor.clone() }))
+            .unwrap()
+            .apply(get_validators());
+    assert_eq!(second_page.validators.len(), 1);
+    assert_eq!(
+        second_page.validators[0].account_id,
+        AccountId::from([1; 32])
+    );
+    assert!(second_page.next_cursor.is_none());
+    // the cursor of a sort field doesn't apply to another
+    assert!(query(json!({ "sort_by": "total_stake", "cursor": cursor })).is_err());
+}
+
+#[test]
+fn test_parse_score_weights() {
+    let weights = parse_score_weights("reward_points:2, commission:0.5,").unwrap();
+    assert_eq!(weights.len(), 2);
+    assert_eq!(weights[0].metric, ValidatorScoreMetric::RewardPoints);
+    assert_eq!(weights[0].weight, 2.0);
+    assert_eq!(weights[1].metric, ValidatorScoreMetric::Commission);
+    assert_eq!(weights[1].weight, 0.5);
+    assert!(parse_score_weights("reward_points:1,identity:0").is_ok());
+    assert!(parse_score_weights("").is_err());
+    assert!(parse_score_weights("commission").is_err());
+    assert!(parse_score_weights("uptime:1").is_err());
+    assert!(parse_score_weights("commission:-1").is_err());
+    assert!(parse_score_weights("commission:NaN").is_err());
+    assert!(parse_score_weights("commission:0").is_err());
+    assert!(parse_score_weights("commission:1,commission:2").is_err());
+}
+
+fn comparison(id: u8, maybe_commission_per_billion: Option<u32>) -> ValidatorComparison {
+    let mut validator = get_validator_comparison(AccountId::from([id; 32]), 0, 1, &[], &[], None);
+    validator.average_commission_per_billion = maybe_commission_per_billion;
+    validator
+}
+
+fn get_scores(validators: &[ValidatorComparison]) -> Vec<(u8, f64, u32)> {
+    validators
+        .iter()
+        .map(|validator| {
+            (
+                validator.account_id.as_ref()[0],
+                validator.score,
+                validator.rank,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_rank_validators() {
+    let weights = parse_score_weights("commission:1").unwrap();
+    let reference_validators = vec![
+        comparison(10, Some(0)),
+        comparison(11, Some(200_000_000)),
+        comparison(12, None),
+    ];
+    let mut validators = vec![
+        comparison(1, Some(100_000_000)),
+        comparison(2, Some(50_000_000)),
+        comparison(3, None),
+    ];
+    rank_validators(&mut validators, &reference_validators, &weights);
+    assert_eq!(
+        get_scores(&validators),
+        vec![(2, 75.0, 1), (1, 50.0, 2), (3, 0.0, 3)]
+    );
+    // the score doesn't depend on the compared set
+    let mut validators = vec![comparison(1, Some(100_000_000))];
+    rank_validators(&mut validators, &reference_validators, &weights);
+    assert_eq!(get_scores(&validators), vec![(1, 50.0, 1)]);
+    // a compared validator out of the reference range extends it
+    let mut validators = vec![comparison(1, Some(400_000_000))];
+    rank_validators(&mut validators, &reference_validators, &weights);
+    assert_eq!(get_scores(&validators), vec![(1, 0.0, 1)]);
+    // equal scores are ordered by account id
+    let mut validators = vec![comparison(2, None), comparison(1, None)];
+    rank_validators(&mut validators, &[], &weights);
+    assert_eq!(get_scores(&validators), vec![(1, 0.0, 1), (2, 0.0, 2)]);
+}
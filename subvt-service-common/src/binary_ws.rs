@@ -0,0 +1,170 @@
+//! Binary WebSocket alternative to the JSON-RPC subscriptions for the compact message formats
+//! (see `encoding`), which cannot be sent in the text frames of JSON-RPC. `BinaryWsLayer` is an
+//! HTTP middleware of the JSON-RPC server like `sse::SseLayer`, and serves the events of the same
+//! `SseHandler`: a WebSocket upgrade request to the layer's path gets each event as a single
+//! binary frame, encoded in the format of the `encoding` and `compression` query parameters, and
+//! every other request goes on to the JSON-RPC server.
+//!
+//! The client doesn't send any messages. It can resume from the block in the `last_event_id`
+//! query parameter, and the other query parameters are the same as the event stream's. The
+//! connection is closed when the stream ends, e.g. when the client cannot keep up with the
+//! updates, and the client is expected to reconnect and resume.
+use crate::sse::{get_text_response, parse_request, ConnectionGuard, SseEvent, SseHandler};
+use futures_util::io::{BufReader, BufWriter};
+use futures_util::stream::BoxStream;
+use futures_util::StreamExt;
+use http::StatusCode;
+use hyper_util::rt::TokioIo;
+use jsonrpsee::server::{HttpBody, HttpRequest, HttpResponse};
+use soketto::data::ByteSlice125;
+use soketto::handshake::http::{is_upgrade_request, Server};
+use soketto::Incoming;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use subvt_metrics::registry::IntGauge;
+use tokio_util::compat::TokioAsyncReadCompatExt;
+
+/// HTTP middleware that serves the events of the handler as binary WebSocket frames at the given
+/// path.
+#[derive(Clone)]
+pub struct BinaryWsLayer<H: SseHandler> {
+    path: &'static str,
+    handler: H,
+    keep_alive_period: Duration,
+    connection_count: IntGauge,
+}
+
+impl<H: SseHandler> BinaryWsLayer<H> {
+    pub fn new(
+        path: &'static str,
+        handler: H,
+        keep_alive_period: Duration,
+        connection_count: IntGauge,
+    ) -> Self {
+        Self {
+            path,
+            handler,
+            keep_alive_period,
+            connection_count,
+        }
+    }
+}
+
+impl<S, H: SseHandler> tower::Layer<S> for BinaryWsLayer<H> {
+    type Service = BinaryWsService<S, H>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        BinaryWsService {
+            layer: self.clone(),
+            service,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct BinaryWsService<S, H: SseHandler> {
+    layer: BinaryWsLayer<H>,
+    service: S,
+}
+
+/// Sends the events until the stream ends or the client closes the connection, with a ping at
+/// every keep-alive period, so that the proxies don't close an idle connection.
+async fn send_events<T>(
+    mut sender: soketto::Sender<T>,
+    mut receiver: soketto::Receiver<T>,
+    mut events: BoxStream<'static, SseEvent>,
+    keep_alive_period: Duration,
+) where
+    T: futures_util::AsyncRead + futures_util::AsyncWrite + Unpin + Send + 'static,
+{
+    // the receiver answers the pings of the client, and it's read in its own task since
+    // dropping a pending read could lose a partially read frame
+    let (closed_sender, mut closed_receiver) = tokio::sync::oneshot::channel::<()>();
+    tokio::spawn(async move {
+        let mut message = Vec::new();
+        loop {
+            message.clear();
+            match receiver.receive(&mut message).await {
+                Ok(Incoming::Closed(_)) | Err(_) => break,
+                Ok(_) => (),
+            }
+        }
+        let _ = closed_sender.send(());
+    });
+    let mut keep_alive = tokio::time::interval(keep_alive_period);
+    keep_alive.reset();
+    loop {
+        let result = tokio::select! {
+            _ = &mut closed_receiver => break,
+            maybe_event = events.next() => match maybe_event {
+                Some(event) => sender.send_binary_mut(event.data).await,
+                None => break,
+            },
+            _ = keep_alive.tick() => sender.send_ping(ByteSlice125::try_from(&[][..]).unwrap()).await,
+        };
+        if result.is_err() || sender.flush().await.is_err() {
+            break;
+        }
+    }
+    let _ = sender.close().await;
+}
+
+impl<S, H> tower::Service<HttpRequest> for BinaryWsService<S, H>
+where
+    S: tower::Service<HttpRequest, Response = HttpResponse> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    H: SseHandler,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: HttpRequest) -> Self::Future {
+        if request.uri().path() != self.layer.path || !is_upgrade_request(&request) {
+            return Box::pin(self.service.call(request));
+        }
+        let layer = self.layer.clone();
+        Box::pin(async move {
+            let stream_request = match parse_request(&request) {
+                Ok(stream_request) => stream_request,
+                Err(error) => return Ok(get_text_response(StatusCode::BAD_REQUEST, error)),
+            };
+            let mut server = Server::new();
+            let response = match server.receive_request(&request) {
+                Ok(response) => response,
+                Err(error) => {
+                    return Ok(get_text_response(
+                        StatusCode::BAD_REQUEST,
+                        format!("WebSocket handshake failed: {error}"),
+                    ))
+                }
+            };
+            let connection_guard = ConnectionGuard::new(layer.connection_count.clone());
+            let events = match layer.handler.get_event_stream(stream_request).await {
+                Ok(events) => events,
+                Err(error) => return Ok(get_text_response(StatusCode::BAD_REQUEST, error)),
+            };
+            // the connection is upgraded once the response is sent
+            tokio::spawn(async move {
+                let upgraded = match hyper::upgrade::on(request).await {
+                    Ok(upgraded) => upgraded,
+                    Err(error) => {
+                        log::warn!("WebSocket upgrade failed: {error}");
+                        return;
+                    }
+                };
+                let stream = BufReader::new(BufWriter::new(TokioIo::new(upgraded).compat()));
+                let (sender, receiver) = server.into_builder(stream).finish();
+                send_events(sender, receiver, events, layer.keep_alive_period).await;
+                drop(connection_guard);
+            });
+            Ok(response.map(|()| HttpBody::default()))
+        })
+    }
+}
@@ -0,0 +1,100 @@
+//! Encodings of the subscription messages. Clients negotiate the format at subscription time.
+//! JSON without compression is the default, and it's the only format of the JSON-RPC
+//! subscriptions, since the JSON-RPC frames are text. Any other format is served as binary
+//! WebSocket frames of the encoded (and compressed) payload, see `binary_ws`. MessagePack maps
+//! and CBOR are produced through serde, so the payloads decode back to the same types in
+//! `subvt-types`.
+use flate2::write::{DeflateEncoder, GzEncoder};
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageEncoding {
+    #[default]
+    Json,
+    #[serde(rename = "msgpack")]
+    MessagePack,
+    Cbor,
+}
+
+impl MessageEncoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::MessagePack => "msgpack",
+            Self::Cbor => "cbor",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageCompression {
+    #[default]
+    None,
+    Deflate,
+    Gzip,
+}
+
+impl MessageCompression {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Deflate => "deflate",
+            Self::Gzip => "gzip",
+        }
+    }
+}
+
+/// Subscription message format, e.g. `{"encoding": "msgpack", "compression": "deflate"}`.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+pub struct MessageFormat {
+    #[serde(default)]
+    pub encoding: MessageEncoding,
+    #[serde(default)]
+    pub compression: MessageCompression,
+}
+
+impl MessageFormat {
+    /// Metric label of the format, e.g. `json` or `msgpack+deflate`.
+    pub fn label(&self) -> String {
+        match self.compression {
+            MessageCompression::None => self.encoding.as_str().to_string(),
+            _ => format!("{}+{}", self.encoding.as_str(), self.compression.as_str()),
+        }
+    }
+
+    /// Whether the messages are binary, i.e. anything but uncompressed JSON.
+    pub fn is_binary(&self) -> bool {
+        self.encoding != MessageEncoding::Json || self.compression != MessageCompression::None
+    }
+
+    pub fn encode<T: Serialize>(&self, value: &T) -> anyhow::Result<Vec<u8>> {
+        let bytes = match self.encoding {
+            MessageEncoding::Json => serde_json::to_vec(value)?,
+            // named, so that the fields skipped during serialization don't shift the others
+            MessageEncoding::MessagePack => rmp_serde::to_vec_named(value)?,
+            MessageEncoding::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes)
+                    .map_err(|error| anyhow::anyhow!("CBOR encoding error: {error:?}"))?;
+                bytes
+            }
+        };
+        Ok(match self.compression {
+            MessageCompression::None => bytes,
+            MessageCompression::Deflate => {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&bytes)?;
+                encoder.finish()?
+            }
+            MessageCompression::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&bytes)?;
+                encoder.finish()?
+            }
+        })
+    }
+}
@@ -3,33 +3,91 @@
 #![warn(clippy::disallowed_types)]
 use async_trait::async_trait;
 use std::str::FromStr;
+use std::time::{Duration, Instant};
 use subvt_config::Config;
+use subvt_metrics::health;
 use subvt_types::substrate::Chain;
 
+pub mod binary_ws;
+pub mod encoding;
 pub mod err;
+pub mod network;
+pub mod shutdown;
+pub mod sse;
+
+/// Wait before the next run after the given number of consecutive failures, doubled after each
+/// failure up to `common.recovery_max_retry_seconds`.
+pub(crate) fn get_retry_delay(config: &Config, failure_count: u32) -> Duration {
+    let delay_seconds = config
+        .common
+        .recovery_retry_seconds
+        .saturating_mul(2u64.saturating_pow(failure_count.saturating_sub(1)))
+        .min(config.common.recovery_max_retry_seconds);
+    Duration::from_secs(delay_seconds)
+}
 
 #[async_trait(?Send)]
 pub trait Service {
     fn get_metrics_server_addr() -> (&'static str, u16);
 
+    /// Main loop of the service. Signals readiness with `health::set_ready(true)` once it has
+    /// initialized, e.g. connected to its storage and nodes or started its server.
     async fn run(&'static self) -> anyhow::Result<()>;
 
+    /// Runs the service until `SIGTERM` or `SIGINT`, restarting it with exponential backoff when
+    /// `run` exits. The health and readiness of the service are served next to its metrics, see
+    /// `subvt_metrics::health`. Readiness is cleared when `run` exits, and the in-flight work is
+    /// given `common.shutdown_timeout_seconds` to finish on shutdown, see `shutdown`.
     async fn start(&'static self) {
         let config = Config::default();
         subvt_logging::init(&config);
         log::info!("Starting service...");
+        // the default address format of the single-network services, the multi-network ones
+        // encode each network's addresses with its own prefix
         Chain::from_str(&config.substrate.chain)
             .unwrap()
             .sp_core_set_default_ss58_version();
         tokio::spawn(subvt_metrics::server::start(Self::get_metrics_server_addr()));
-        let delay_seconds = config.common.recovery_retry_seconds;
+        tokio::spawn(shutdown::listen_for_signals());
+        let mut failure_count = 0;
         loop {
-            let result = self.run().await;
+            let run_start = Instant::now();
+            let result = tokio::select! {
+                result = self.run() => result,
+                _ = shutdown::requested() => break,
+            };
+            health::set_ready(false);
             if let Err(error) = result {
                 log::error!("{error:?}");
             }
-            log::error!("Process exited. Will try again in {delay_seconds} seconds.",);
-            tokio::time::sleep(std::time::Duration::from_secs(delay_seconds)).await;
+            // a run that lasted longer than the longest wait doesn't count as a consecutive failure
+            if run_start.elapsed() >= Duration::from_secs(config.common.recovery_max_retry_seconds)
+            {
+                failure_count = 0;
+            }
+            failure_count += 1;
+            health::set_healthy(failure_count < config.common.unhealthy_failure_count);
+            let delay = get_retry_delay(&config, failure_count);
+            log::error!(
+                "Process exited ({failure_count} consecutive failures). Will try again in {} seconds.",
+                delay.as_secs(),
+            );
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => (),
+                _ = shutdown::requested() => break,
+            }
+        }
+        health::set_ready(false);
+        log::info!("Shutting down. Wait for the in-flight work to finish.");
+        let in_flight_count = shutdown::wait_for_in_flight_work(Duration::from_secs(
+            config.common.shutdown_timeout_seconds,
+        ))
+        .await;
+        if in_flight_count == 0 {
+            log::info!("Service stopped.");
+        } else {
+            log::warn!("Service stopped with {in_flight_count} units of work still in flight.");
         }
+        subvt_logging::trace::shutdown();
     }
 }
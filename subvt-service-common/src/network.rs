@@ -0,0 +1,204 @@
+//! Network routing for the RPC servers that serve several networks, see `common.networks`. The
+//! network of a connection is selected by the first segment of the request path, e.g.
+//! `ws://host:7888/polkadot` or `http://host:7888/polkadot/sse`, and the requests without a
+//! network segment are served by the default (first) network. `NetworkLayer` removes the network
+//! segment from the path and puts the chain in the request extensions as a `RequestNetwork`, which
+//! jsonrpsee passes on to the subscription callbacks.
+//!
+//! The networks are started independently: a network is served once `start_network` succeeds for
+//! it, and `keep_running` restarts the updater of a network without stopping the others.
+use crate::get_retry_delay;
+use http::uri::PathAndQuery;
+use http::Uri;
+use jsonrpsee::server::HttpRequest;
+use rustc_hash::FxHashMap as HashMap;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use subvt_config::Config;
+
+/// Chain of the network of a request.
+#[derive(Clone, Debug)]
+pub struct RequestNetwork(pub String);
+
+/// States of the started networks of a multi-network server by chain.
+#[derive(Clone)]
+pub struct Networks<T> {
+    default_chain: String,
+    states: Arc<RwLock<HashMap<String, T>>>,
+}
+
+impl<T: Clone> Networks<T> {
+    /// Starts with no networks, see `insert`. The default network is the first of the chains,
+    /// whether it's started or not.
+    pub fn new(chains: &[String]) -> Self {
+        Self {
+            default_chain: chains.first().cloned().unwrap_or_default(),
+            states: Default::default(),
+        }
+    }
+
+    /// Serves the network once it's started.
+    pub fn insert(&self, chain: &str, state: T) {
+        self.states
+            .write()
+            .unwrap()
+            .insert(chain.to_string(), state);
+    }
+
+    /// State of the given network, or of the default network if no chain is given. `None` if
+    /// the network is not started.
+    pub fn get(&self, maybe_chain: Option<&str>) -> Option<T> {
+        self.states
+            .read()
+            .unwrap()
+            .get(maybe_chain.unwrap_or(self.default_chain.as_str()))
+            .cloned()
+    }
+
+    /// State of the network selected for a request by the `NetworkLayer`.
+    pub fn get_for_request(&self, extensions: &http::Extensions) -> Option<T> {
+        self.get(
+            extensions
+                .get::<RequestNetwork>()
+                .map(|network| network.0.as_str()),
+        )
+    }
+}
+
+/// Starts a network, retrying with the backoff of `Service::start` until it succeeds, so that a
+/// network that cannot be started doesn't keep the others from being served.
+pub async fn start_network<T, F, Fut>(config: &Config, chain: &str, mut start: F) -> T
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = anyhow::Result<T>>,
+{
+    let mut failure_count = 0;
+    loop {
+        match start().await {
+            Ok(state) => return state,
+            Err(error) => {
+                failure_count += 1;
+                let delay = get_retry_delay(config, failure_count);
+                log::error!(
+                    "Cannot start {chain} ({failure_count} consecutive failures), will try again in {} seconds: {error:?}",
+                    delay.as_secs(),
+                );
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+}
+
+/// Runs the updater of a network, and restarts it with the backoff of `Service::start` whenever
+/// it fails, without stopping the other networks.
+pub async fn keep_running<F, Fut>(config: &Config, chain: &str, mut run: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = anyhow::Error>,
+{
+    let mut failure_count = 0;
+    loop {
+        let run_start = Instant::now();
+        let error = run().await;
+        // a run that lasted longer than the longest wait doesn't count as a consecutive failure
+        if run_start.elapsed() >= Duration::from_secs(config.common.recovery_max_retry_seconds) {
+            failure_count = 0;
+        }
+        failure_count += 1;
+        let delay = get_retry_delay(config, failure_count);
+        log::error!(
+            "{chain} updates failed ({failure_count} consecutive failures), will restart in {} seconds: {error:?}",
+            delay.as_secs(),
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// The chain in the first segment of the path if it's one of the given chains, and the rest of
+/// the path.
+pub fn split_network_path<'a>(path: &'a str, chains: &[String]) -> Option<(&'a str, &'a str)> {
+    let path = path.strip_prefix('/')?;
+    let (chain, rest) = match path.find('/') {
+        Some(index) => path.split_at(index),
+        None => (path, ""),
+    };
+    chains
+        .iter()
+        .any(|network_chain| network_chain == chain)
+        .then_some((chain, rest))
+}
+
+/// The URI with the rest of the path after the network segment, keeping the query.
+pub fn get_uri_without_network(uri: &Uri, rest: &str) -> Option<Uri> {
+    let path = if rest.is_empty() { "/" } else { rest };
+    let path_and_query = match uri.query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path.to_string(),
+    };
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(PathAndQuery::try_from(path_and_query).ok()?);
+    Uri::from_parts(parts).ok()
+}
+
+/// HTTP middleware that selects the network of the request, see the module documentation.
+#[derive(Clone)]
+pub struct NetworkLayer {
+    chains: Arc<Vec<String>>,
+}
+
+impl NetworkLayer {
+    /// The first chain is the default network.
+    pub fn new(chains: Vec<String>) -> Self {
+        Self {
+            chains: Arc::new(chains),
+        }
+    }
+}
+
+impl<S> tower::Layer<S> for NetworkLayer {
+    type Service = NetworkService<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        NetworkService {
+            chains: self.chains.clone(),
+            service,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct NetworkService<S> {
+    chains: Arc<Vec<String>>,
+    service: S,
+}
+
+impl<S> tower::Service<HttpRequest> for NetworkService<S>
+where
+    S: tower::Service<HttpRequest>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: HttpRequest) -> Self::Future {
+        let maybe_routed =
+            split_network_path(request.uri().path(), &self.chains).and_then(|(chain, rest)| {
+                get_uri_without_network(request.uri(), rest).map(|uri| (chain.to_string(), uri))
+            });
+        let chain = match maybe_routed {
+            Some((chain, uri)) => {
+                *request.uri_mut() = uri;
+                chain
+            }
+            None => self.chains.first().cloned().unwrap_or_default(),
+        };
+        request.extensions_mut().insert(RequestNetwork(chain));
+        self.service.call(request)
+    }
+}
@@ -0,0 +1,74 @@
+//! Cooperative shutdown of the services. On `SIGTERM` or `SIGINT` the shutdown is requested,
+//! the main loop of the service is stopped and the process waits for the in-flight work to finish
+//! within `common.shutdown_timeout_seconds`. Work that shouldn't be cut halfway, like the
+//! processing of a block or the sending of a notification, holds an `InFlight` guard while it
+//! runs, and the loops that start new work check `is_requested` before starting.
+use once_cell::sync::Lazy;
+use std::time::Duration;
+use tokio::sync::watch;
+
+static SHUTDOWN_REQUESTED: Lazy<watch::Sender<bool>> = Lazy::new(|| watch::channel(false).0);
+static IN_FLIGHT_COUNT: Lazy<watch::Sender<usize>> = Lazy::new(|| watch::channel(0).0);
+
+/// Requests the shutdown of the service.
+pub fn request() {
+    SHUTDOWN_REQUESTED.send_replace(true);
+}
+
+pub fn is_requested() -> bool {
+    *SHUTDOWN_REQUESTED.borrow()
+}
+
+/// Completes when the shutdown is requested.
+pub async fn requested() {
+    let mut receiver = SHUTDOWN_REQUESTED.subscribe();
+    let _ = receiver.wait_for(|is_requested| *is_requested).await;
+}
+
+/// Marks a unit of work that the shutdown should wait for, until the guard is dropped.
+pub struct InFlight(());
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        IN_FLIGHT_COUNT.send_modify(|count| *count = count.saturating_sub(1));
+    }
+}
+
+pub fn in_flight() -> InFlight {
+    IN_FLIGHT_COUNT.send_modify(|count| *count += 1);
+    InFlight(())
+}
+
+/// Waits for the in-flight work to finish for at most the given timeout. Returns the number of
+/// units of work that were still in flight at the deadline.
+pub(crate) async fn wait_for_in_flight_work(timeout: Duration) -> usize {
+    let mut receiver = IN_FLIGHT_COUNT.subscribe();
+    let _ = tokio::time::timeout(timeout, receiver.wait_for(|count| *count == 0)).await;
+    *IN_FLIGHT_COUNT.borrow()
+}
+
+/// Requests the shutdown on the first `SIGTERM` or `SIGINT`.
+pub(crate) async fn listen_for_signals() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let mut sigterm = match signal(SignalKind::terminate()) {
+            Ok(sigterm) => sigterm,
+            Err(error) => {
+                log::error!("Cannot listen for SIGTERM: {error:?}");
+                return;
+            }
+        };
+        tokio::select! {
+            _ = sigterm.recv() => log::info!("SIGTERM received."),
+            _ = tokio::signal::ctrl_c() => log::info!("SIGINT received."),
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            log::info!("SIGINT received.");
+        }
+    }
+    request();
+}
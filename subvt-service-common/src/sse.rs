@@ -0,0 +1,275 @@
+//! Server-Sent Events alternative to the WebSocket subscriptions, for the clients behind proxies
+//! that don't support WebSockets and for the browser clients that would use `EventSource`.
+//! `SseLayer` is an HTTP middleware of the JSON-RPC server, so the events are served on the same
+//! port as the WebSocket subscriptions. A `GET` request to the layer's path gets a
+//! `text/event-stream` response of the events from the server's `SseHandler`, and every other
+//! request goes on to the JSON-RPC server.
+//!
+//! The id of each event is the block number of the update, and a reconnecting client resumes
+//! from the block in the `Last-Event-ID` header, which `EventSource` sends automatically. A
+//! client can also give the block in the `last_event_id` query parameter at first connection.
+//! The stream ends if the client cannot keep up with the updates, and the client is expected to
+//! reconnect and resume.
+//!
+//! The events of the same handler are served as binary WebSocket frames in the compact message
+//! formats by `binary_ws::BinaryWsLayer`.
+use crate::encoding::MessageFormat;
+use crate::network::RequestNetwork;
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::stream::BoxStream;
+use futures_util::StreamExt;
+use http::{header, Method, StatusCode};
+use http_body::Frame;
+use http_body_util::StreamBody;
+use jsonrpsee::server::{HttpBody, HttpRequest, HttpResponse};
+use serde::Serialize;
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use subvt_metrics::registry::IntGauge;
+
+/// A single event in the stream, the data is encoded in the format of the request.
+pub struct SseEvent {
+    pub id: Option<u64>,
+    pub data: Vec<u8>,
+}
+
+impl SseEvent {
+    pub fn json<T: Serialize>(id: Option<u64>, value: &T) -> anyhow::Result<Self> {
+        Self::encode(id, value, MessageFormat::default())
+    }
+
+    pub fn encode<T: Serialize>(
+        id: Option<u64>,
+        value: &T,
+        format: MessageFormat,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            id,
+            data: format.encode(value)?,
+        })
+    }
+
+    /// Event in the `text/event-stream` format. Server-Sent Events are always JSON.
+    fn into_bytes(self) -> Bytes {
+        let data = String::from_utf8_lossy(&self.data);
+        let mut event = String::with_capacity(data.len() + 32);
+        if let Some(id) = self.id {
+            event.push_str(&format!("id: {id}\n"));
+        }
+        for line in data.lines() {
+            event.push_str("data: ");
+            event.push_str(line);
+            event.push('\n');
+        }
+        event.push('\n');
+        Bytes::from(event)
+    }
+}
+
+/// Query parameters of an event stream request and the id of the last event the client has.
+pub struct SseRequest {
+    params: Vec<(String, String)>,
+    pub last_event_id: Option<u64>,
+    /// Chain of the network selected by the `NetworkLayer`, if the server has one.
+    pub network: Option<String>,
+    /// Format of the event data, given by the `encoding` and `compression` query parameters.
+    pub format: MessageFormat,
+}
+
+impl SseRequest {
+    pub fn get_param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[async_trait]
+pub trait SseHandler: Clone + Send + Sync + 'static {
+    /// Events after the client's last event, or starting with the complete state if the client
+    /// has no events or the last one is too old to resume from. An error is returned to the
+    /// client with status `400`.
+    async fn get_event_stream(
+        &self,
+        request: SseRequest,
+    ) -> Result<BoxStream<'static, SseEvent>, String>;
+}
+
+/// Decrements the connection gauge when the stream is dropped, however it ends.
+pub(crate) struct ConnectionGuard(IntGauge);
+
+impl ConnectionGuard {
+    pub(crate) fn new(connection_count: IntGauge) -> Self {
+        connection_count.inc();
+        Self(connection_count)
+    }
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
+
+pub(crate) fn get_text_response(status: StatusCode, text: String) -> HttpResponse {
+    HttpResponse::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "text/plain")
+        .body(HttpBody::from(text))
+        .unwrap()
+}
+
+pub(crate) fn parse_request(request: &HttpRequest) -> Result<SseRequest, String> {
+    let query = request.uri().query().unwrap_or_default();
+    let params: Vec<(String, String)> =
+        serde_urlencoded::from_str(query).map_err(|_| "Invalid query string.".to_string())?;
+    let format: MessageFormat = serde_urlencoded::from_str(query)
+        .map_err(|error| format!("Invalid message format: {error}"))?;
+    let maybe_last_event_id = match request.headers().get("Last-Event-ID") {
+        Some(header) => Some(header.to_str().unwrap_or_default().to_string()),
+        None => params
+            .iter()
+            .find(|(key, _)| key == "last_event_id")
+            .map(|(_, value)| value.clone()),
+    };
+    let last_event_id = match maybe_last_event_id {
+        Some(last_event_id) if !last_event_id.is_empty() => Some(
+            last_event_id
+                .parse::<u64>()
+                .map_err(|_| "Invalid last event id.".to_string())?,
+        ),
+        _ => None,
+    };
+    Ok(SseRequest {
+        params,
+        last_event_id,
+        network: request
+            .extensions()
+            .get::<RequestNetwork>()
+            .map(|network| network.0.clone()),
+        format,
+    })
+}
+
+/// Writes the events of the stream with a comment line at every keep-alive period, so that the
+/// proxies don't close an idle connection.
+fn get_event_stream_response(
+    events: BoxStream<'static, SseEvent>,
+    keep_alive_period: Duration,
+    connection_guard: ConnectionGuard,
+) -> HttpResponse {
+    let mut keep_alive = tokio::time::interval(keep_alive_period);
+    keep_alive.reset();
+    let frames = futures_util::stream::unfold(
+        (events, keep_alive, connection_guard),
+        |(mut events, mut keep_alive, connection_guard)| async move {
+            let bytes = tokio::select! {
+                maybe_event = events.next() => maybe_event?.into_bytes(),
+                _ = keep_alive.tick() => Bytes::from_static(b": keep-alive\n\n"),
+            };
+            Some((
+                Ok::<_, Infallible>(Frame::data(bytes)),
+                (events, keep_alive, connection_guard),
+            ))
+        },
+    );
+    HttpResponse::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "text/event-stream")
+        .header(header::CACHE_CONTROL, "no-cache")
+        .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+        // disables response buffering on nginx
+        .header("X-Accel-Buffering", "no")
+        .body(HttpBody::new(StreamBody::new(frames)))
+        .unwrap()
+}
+
+/// HTTP middleware that serves the events of the handler at the given path.
+#[derive(Clone)]
+pub struct SseLayer<H: SseHandler> {
+    path: &'static str,
+    handler: H,
+    keep_alive_period: Duration,
+    connection_count: IntGauge,
+}
+
+impl<H: SseHandler> SseLayer<H> {
+    pub fn new(
+        path: &'static str,
+        handler: H,
+        keep_alive_period: Duration,
+        connection_count: IntGauge,
+    ) -> Self {
+        Self {
+            path,
+            handler,
+            keep_alive_period,
+            connection_count,
+        }
+    }
+}
+
+impl<S, H: SseHandler> tower::Layer<S> for SseLayer<H> {
+    type Service = SseService<S, H>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        SseService {
+            layer: self.clone(),
+            service,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SseService<S, H: SseHandler> {
+    layer: SseLayer<H>,
+    service: S,
+}
+
+impl<S, H> tower::Service<HttpRequest> for SseService<S, H>
+where
+    S: tower::Service<HttpRequest, Response = HttpResponse> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    H: SseHandler,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: HttpRequest) -> Self::Future {
+        if request.method() != Method::GET || request.uri().path() != self.layer.path {
+            return Box::pin(self.service.call(request));
+        }
+        let layer = self.layer.clone();
+        Box::pin(async move {
+            let sse_request = match parse_request(&request) {
+                Ok(sse_request) if sse_request.format.is_binary() => {
+                    return Ok(get_text_response(
+                        StatusCode::BAD_REQUEST,
+                        "Server-Sent Events are JSON.".to_string(),
+                    ))
+                }
+                Ok(sse_request) => sse_request,
+                Err(error) => return Ok(get_text_response(StatusCode::BAD_REQUEST, error)),
+            };
+            let connection_guard = ConnectionGuard::new(layer.connection_count.clone());
+            match layer.handler.get_event_stream(sse_request).await {
+                Ok(events) => Ok(get_event_stream_response(
+                    events,
+                    layer.keep_alive_period,
+                    connection_guard,
+                )),
+                Err(error) => Ok(get_text_response(StatusCode::BAD_REQUEST, error)),
+            }
+        })
+    }
+}
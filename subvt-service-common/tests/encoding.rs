@@ -0,0 +1,83 @@
+use flate2::read::{DeflateDecoder, GzDecoder};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use subvt_service_common::encoding::{MessageCompression, MessageEncoding, MessageFormat};
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+struct Update {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    finalized_block_number: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display: Option<String>,
+    total_stake: u128,
+    remove_ids: Vec<[u8; 32]>,
+}
+
+fn decode(format: MessageFormat, bytes: &[u8]) -> Update {
+    let mut decompressed = Vec::new();
+    match format.compression {
+        MessageCompression::None => decompressed.extend_from_slice(bytes),
+        MessageCompression::Deflate => {
+            DeflateDecoder::new(bytes)
+                .read_to_end(&mut decompressed)
+                .unwrap();
+        }
+        MessageCompression::Gzip => {
+            GzDecoder::new(bytes)
+                .read_to_end(&mut decompressed)
+                .unwrap();
+        }
+    }
+    match format.encoding {
+        MessageEncoding::Json => serde_json::from_slice(&decompressed).unwrap(),
+        MessageEncoding::MessagePack => rmp_serde::from_slice(&decompressed).unwrap(),
+        MessageEncoding::Cbor => ciborium::from_reader(decompressed.as_slice()).unwrap(),
+    }
+}
+
+#[test]
+fn test_encoding_round_trip() {
+    // the skipped field comes before the others
+    let update = Update {
+        finalized_block_number: None,
+        display: Some("validator".to_string()),
+        total_stake: u128::MAX,
+        remove_ids: vec![[1; 32], [2; 32]],
+    };
+    for encoding in [
+        MessageEncoding::Json,
+        MessageEncoding::MessagePack,
+        MessageEncoding::Cbor,
+    ] {
+        for compression in [
+            MessageCompression::None,
+            MessageCompression::Deflate,
+            MessageCompression::Gzip,
+        ] {
+            let format = MessageFormat {
+                encoding,
+                compression,
+            };
+            let bytes = format.encode(&update).unwrap();
+            assert_eq!(decode(format, &bytes), update, "{}", format.label());
+            assert_eq!(
+                format.is_binary(),
+                format != MessageFormat::default(),
+                "{}",
+                format.label()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_message_format_params() {
+    let format: MessageFormat = serde_json::from_str("{}").unwrap();
+    assert_eq!(format, MessageFormat::default());
+    assert_eq!(format.label(), "json");
+    let format: MessageFormat =
+        serde_json::from_str(r#"{"encoding": "msgpack", "compression": "deflate"}"#).unwrap();
+    assert_eq!(format.encoding, MessageEncoding::MessagePack);
+    assert_eq!(format.label(), "msgpack+deflate");
+    assert!(serde_json::from_str::<MessageFormat>(r#"{"encoding": "xml"}"#).is_err());
+}
@@ -0,0 +1,57 @@
+use http::Uri;
+use subvt_service_common::network::{get_uri_without_network, split_network_path, Networks};
+
+fn get_chains() -> Vec<String> {
+    vec!["kusama".to_string(), "polkadot".to_string()]
+}
+
+/// The path and query of the request after the network segment is removed, and the chain.
+fn route(uri: &str, chains: &[String]) -> Option<(String, String)> {
+    let uri: Uri = uri.parse().unwrap();
+    let (chain, rest) = split_network_path(uri.path(), chains)?;
+    let uri = get_uri_without_network(&uri, rest)?;
+    Some((chain.to_string(), uri.path_and_query().unwrap().to_string()))
+}
+
+#[test]
+fn test_network_path() {
+    let chains = get_chains();
+    // served by the default network
+    assert_eq!(route("/", &chains), None);
+    assert_eq!(route("/sse?last_event_id=1", &chains), None);
+    // not a network, e.g. a polkadot path on a kusama-only server
+    assert_eq!(route("/westend/sse", &chains), None);
+    assert_eq!(route("/kusamax", &chains), None);
+    assert_eq!(
+        route("/kusama", &chains),
+        Some(("kusama".to_string(), "/".to_string()))
+    );
+    assert_eq!(
+        route("/polkadot/", &chains),
+        Some(("polkadot".to_string(), "/".to_string()))
+    );
+    assert_eq!(
+        route("/polkadot/sse?x=1", &chains),
+        Some(("polkadot".to_string(), "/sse?x=1".to_string()))
+    );
+    assert_eq!(
+        route("ws://host:7888/kusama/binary?encoding=cbor", &chains),
+        Some(("kusama".to_string(), "/binary?encoding=cbor".to_string()))
+    );
+    assert_eq!(route("/kusama/sse", &[]), None);
+}
+
+#[test]
+fn test_networks() {
+    let networks = Networks::new(&get_chains());
+    assert_eq!(networks.get(None), None);
+    networks.insert("polkadot", 0);
+    assert_eq!(networks.get(Some("polkadot")), Some(0));
+    // the default network is the first chain, whether it's started or not
+    assert_eq!(networks.get(None), None);
+    networks.insert("kusama", 2);
+    assert_eq!(networks.get(None), Some(2));
+    assert_eq!(networks.get(Some("westend")), None);
+    let empty_networks: Networks<u32> = Networks::new(&[]);
+    assert_eq!(empty_networks.get(None), None);
+}
@@ -5,6 +5,7 @@ use async_trait::async_trait;
 use lazy_static::lazy_static;
 use std::cmp::max;
 use subvt_config::Config;
+use subvt_metrics::health;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
 use subvt_service_common::Service;
 use subvt_types::performance::SessionValidatorPerformance;
@@ -19,6 +20,36 @@ lazy_static! {
 
 pub struct SessionValidatorPerformanceUpdater;
 
+/// Statistically expected number of blocks and primary slots per validator in a session.
+/// Primary slot eligibility is decided by each validator's VRF output, which needs the
+/// validator's secret key, so exact primary slots cannot be computed from chain data. With
+/// BABE's `c` constant, a validator wins a primary slot with probability `1 - (1 - c)^(1/n)`,
+/// and since every slot without a primary claim falls back to a secondary author, each
+/// validator is expected to author `1/n` of the slots in total. The authored block count is
+/// Poisson distributed around this mean, and the missed blocks notification evaluates it over
+/// a number of sessions with `BlockProduction`.
+fn get_expected_block_production(session: &Epoch, validator_count: usize) -> (f64, f64) {
+    if validator_count == 0 {
+        return (0.0, 0.0);
+    }
+    let slot_count = session
+        .end_timestamp
+        .saturating_sub(session.start_timestamp) as f64
+        / CONFIG
+            .session_validator_performance_updater
+            .slot_duration_millis as f64;
+    let primary_probability = 1.0
+        - (1.0
+            - CONFIG
+                .session_validator_performance_updater
+                .babe_primary_probability)
+            .powf(1.0 / validator_count as f64);
+    (
+        slot_count / validator_count as f64,
+        slot_count * primary_probability,
+    )
+}
+
 impl SessionValidatorPerformanceUpdater {
     async fn process_session(
         &self,
@@ -45,6 +76,8 @@ impl SessionValidatorPerformanceUpdater {
             };
             era_active_validators.push(era_validator);
         }
+        let (expected_block_count, expected_primary_slot_count) =
+            get_expected_block_production(session, era_active_validators.len());
         log::debug!("Get performance data.");
         let mut session_validator_performances = Vec::new();
         for era_active_validator in era_active_validators.iter() {
@@ -53,6 +86,8 @@ impl SessionValidatorPerformanceUpdater {
                 era_index: session.era_index,
                 session_index: session.index,
                 active_validator_index: era_active_validator.active_validator_index.unwrap(),
+                expected_block_count: Some(expected_block_count),
+                expected_primary_slot_count: Some(expected_primary_slot_count),
                 ..Default::default()
             };
             // block count
@@ -124,6 +159,7 @@ impl Service for SessionValidatorPerformanceUpdater {
         );
         let postgres =
             PostgreSQLNetworkStorage::new(&CONFIG, CONFIG.get_network_postgres_url()).await?;
+        health::set_ready(true);
         loop {
             let last_processed_session_index = postgres
                 .get_session_validator_performance_updater_last_processed_session_id()
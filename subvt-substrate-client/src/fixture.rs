@@ -0,0 +1,460 @@
+//! Record/replay support for offline Substrate client tests.
+//!
+//! `RpcRecorder` wraps the WebSocket transport of a `SubstrateClient` and captures every JSON-RPC
+//! call (storage, blocks, metadata, etc.) and subscription notification into an `RpcFixture`,
+//! which can be saved to a JSON fixture file. `RpcReplayServer` loads such a fixture and serves it
+//! deterministically over WebSocket, so any component that connects to a node RPC URL (the
+//! Substrate client, and through it the block processor, validator list updater, etc.) can be
+//! run against checked-in fixtures instead of a live node.
+use anyhow::Context;
+use async_trait::async_trait;
+use jsonrpsee::core::client::{ReceivedMessage, TransportReceiverT, TransportSenderT};
+use jsonrpsee::server::{RpcModule, ServerBuilder, ServerHandle};
+use jsonrpsee::types::{ErrorObjectOwned, Params};
+use jsonrpsee::{SubscriptionCloseResponse, SubscriptionMessage};
+use rustc_hash::FxHashMap as HashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// JSON-RPC error code returned by the replay server when there's no recorded response for a
+/// method and parameter combination.
+pub const FIXTURE_NOT_FOUND_ERROR_CODE: i32 = -32099;
+/// Sent by the replay server as the subscription error notification after all recorded
+/// notifications of a subscription have been served.
+const FIXTURE_SUBSCRIPTION_END_MESSAGE: &str = "End of recorded subscription.";
+
+/// A recorded JSON-RPC error response.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RpcFixtureError {
+    pub code: i32,
+    pub message: String,
+}
+
+/// A single recorded JSON-RPC method call and its response.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RpcFixtureCall {
+    pub method: String,
+    pub params: JsonValue,
+    #[serde(default)]
+    pub result: JsonValue,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcFixtureError>,
+}
+
+/// A recorded subscription and all the notifications received through it, in order.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RpcFixtureSubscription {
+    pub subscribe_method: String,
+    pub notification_method: String,
+    pub unsubscribe_method: String,
+    pub params: JsonValue,
+    pub notifications: Vec<JsonValue>,
+}
+
+/// Complete record of the RPC traffic of one or more client sessions.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct RpcFixture {
+    pub calls: Vec<RpcFixtureCall>,
+    pub subscriptions: Vec<RpcFixtureSubscription>,
+}
+
+impl RpcFixture {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .context(format!("Cannot read RPC fixture file {}.", path.display()))?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .context(format!("Cannot write RPC fixture file {}.", path.display()))?;
+        Ok(())
+    }
+}
+
+/// Path of the checked-in fixture with the given name. The recorded fixtures of the replay tests
+/// of all the services are kept under `tests/fixtures` of this crate.
+pub fn get_recorded_fixture_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(format!("{name}.json"))
+}
+
+/// Loads the checked-in fixture with the given name, see `get_recorded_fixture_path`.
+pub fn load_recorded_fixture(name: &str) -> anyhow::Result<RpcFixture> {
+    RpcFixture::load(get_recorded_fixture_path(name)).context(format!(
+        "Fixture {name} is not recorded. Record it with the ignored record test that uses it."
+    ))
+}
+
+/// Normalizes missing, null and empty parameters to an empty array, so that the recorded and the
+/// replayed parameters of the same call always match.
+fn normalize_params(params: Option<&JsonValue>) -> JsonValue {
+    match params {
+        None | Some(JsonValue::Null) => JsonValue::Array(Vec::new()),
+        Some(params) => params.clone(),
+    }
+}
+
+fn get_params_key(method: &str, params: &JsonValue) -> String {
+    format!("{method}:{params}")
+}
+
+fn is_subscribe_method(method: &str) -> bool {
+    method.contains("_subscribe")
+}
+
+fn is_unsubscribe_method(method: &str) -> bool {
+    method.contains("_unsubscribe")
+}
+
+#[derive(Default)]
+struct RecorderState {
+    /// Request id (as JSON string) to method and parameters.
+    pending_requests: HashMap<String, (String, JsonValue)>,
+    /// Subscription id (as JSON string) to the index in the fixture subscription list.
+    subscription_indices: HashMap<String, usize>,
+    fixture: RpcFixture,
+}
+
+impl RecorderState {
+    fn on_outgoing(&mut self, value: &JsonValue) {
+        let (Some(id), Some(method)) = (value.get("id"), value.get("method")) else {
+            return;
+        };
+        let Some(method) = method.as_str() else {
+            return;
+        };
+        if is_unsubscribe_method(method) {
+            return;
+        }
+        self.pending_requests.insert(
+            id.to_string(),
+            (method.to_string(), normalize_params(value.get("params"))),
+        );
+    }
+
+    fn on_incoming(&mut self, value: &JsonValue) {
+        if let Some(id) = value.get("id") {
+            let Some((method, params)) = self.pending_requests.remove(&id.to_string()) else {
+                return;
+            };
+            let result = value.get("result").cloned().unwrap_or(JsonValue::Null);
+            if is_subscribe_method(&method) {
+                if value.get("error").is_some() {
+                    return;
+                }
+                self.subscription_indices
+                    .insert(result.to_string(), self.fixture.subscriptions.len());
+                self.fixture.subscriptions.push(RpcFixtureSubscription {
+                    unsubscribe_method: method.replacen("_subscribe", "_unsubscribe", 1),
+                    subscribe_method: method,
+                    notification_method: String::new(),
+                    params,
+                    notifications: Vec::new(),
+                });
+                return;
+            }
+            let error = value.get("error").map(|error| RpcFixtureError {
+                code: error
+                    .get("code")
+                    .and_then(|code| code.as_i64())
+                    .unwrap_or_default() as i32,
+                message: error
+                    .get("message")
+                    .and_then(|message| message.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+            });
+            self.fixture.calls.push(RpcFixtureCall {
+                method,
+                params,
+                result,
+                error,
+            });
+        } else if let (Some(method), Some(params)) = (value.get("method"), value.get("params")) {
+            let Some(subscription_id) = params.get("subscription") else {
+                return;
+            };
+            // subscription close notification
+            if params.get("error").is_some() {
+                return;
+            }
+            let Some(index) = self.subscription_indices.get(&subscription_id.to_string()) else {
+                return;
+            };
+            let subscription = &mut self.fixture.subscriptions[*index];
+            if let Some(method) = method.as_str() {
+                subscription.notification_method = method.to_string();
+            }
+            subscription
+                .notifications
+                .push(params.get("result").cloned().unwrap_or(JsonValue::Null));
+        }
+    }
+}
+
+/// Collects the RPC traffic of the transports created through it. Cheap to clone, all clones
+/// share the same record.
+#[derive(Clone, Default)]
+pub struct RpcRecorder {
+    state: Arc<Mutex<RecorderState>>,
+}
+
+impl RpcRecorder {
+    fn process(&self, message: &str, outgoing: bool) {
+        let Ok(value) = serde_json::from_str::<JsonValue>(message) else {
+            log::warn!("Cannot parse RPC message for recording.");
+            return;
+        };
+        let values = match value {
+            JsonValue::Array(values) => values,
+            value => vec![value],
+        };
+        let mut state = self.state.lock().unwrap();
+        for value in values.iter() {
+            if outgoing {
+                state.on_outgoing(value);
+            } else {
+                state.on_incoming(value);
+            }
+        }
+    }
+
+    /// Wraps a transport sender/receiver pair so that all messages through it get recorded.
+    pub fn wrap<S, R>(&self, sender: S, receiver: R) -> (RecordingSender<S>, RecordingReceiver<R>)
+    where
+        S: TransportSenderT + Send,
+        R: TransportReceiverT + Send,
+    {
+        (
+            RecordingSender {
+                inner: sender,
+                recorder: self.clone(),
+            },
+            RecordingReceiver {
+                inner: receiver,
+                recorder: self.clone(),
+            },
+        )
+    }
+
+    /// Snapshot of everything recorded so far.
+    pub fn get_fixture(&self) -> RpcFixture {
+        self.state.lock().unwrap().fixture.clone()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        self.get_fixture().save(path)
+    }
+}
+
+/// Transport sender that records outgoing requests before passing them on.
+pub struct RecordingSender<S> {
+    inner: S,
+    recorder: RpcRecorder,
+}
+
+#[async_trait]
+impl<S> TransportSenderT for RecordingSender<S>
+where
+    S: TransportSenderT + Send,
+{
+    type Error = S::Error;
+
+    async fn send(&mut self, message: String) -> Result<(), Self::Error> {
+        self.recorder.process(&message, true);
+        self.inner.send(message).await
+    }
+
+    async fn send_ping(&mut self) -> Result<(), Self::Error> {
+        self.inner.send_ping().await
+    }
+
+    async fn close(&mut self) -> Result<(), Self::Error> {
+        self.inner.close().await
+    }
+}
+
+/// Transport receiver that records incoming responses and notifications.
+pub struct RecordingReceiver<R> {
+    inner: R,
+    recorder: RpcRecorder,
+}
+
+#[async_trait]
+impl<R> TransportReceiverT for RecordingReceiver<R>
+where
+    R: TransportReceiverT + Send,
+{
+    type Error = R::Error;
+
+    async fn receive(&mut self) -> Result<ReceivedMessage, Self::Error> {
+        let message = self.inner.receive().await?;
+        match &message {
+            ReceivedMessage::Text(text) => self.recorder.process(text, false),
+            ReceivedMessage::Bytes(bytes) => {
+                if let Ok(text) = std::str::from_utf8(bytes) {
+                    self.recorder.process(text, false);
+                }
+            }
+            ReceivedMessage::Pong => (),
+        }
+        Ok(message)
+    }
+}
+
+/// Recorded responses for one method and parameter combination. Responses are served in the
+/// recorded order, and the last one is repeated once the queue is down to a single response.
+type ResponseQueue = VecDeque<Result<JsonValue, RpcFixtureError>>;
+
+/// Serves a recorded `RpcFixture` over WebSocket.
+pub struct RpcReplayServer;
+
+impl RpcReplayServer {
+    pub async fn start(
+        fixture: RpcFixture,
+        host: &str,
+        port: u16,
+    ) -> anyhow::Result<(SocketAddr, ServerHandle)> {
+        let server = ServerBuilder::default()
+            .max_request_body_size(u32::MAX)
+            .max_response_body_size(u32::MAX)
+            .build(format!("{host}:{port}"))
+            .await?;
+        let address = server.local_addr()?;
+        let mut rpc_module = RpcModule::new(());
+        let mut response_map: HashMap<String, HashMap<String, ResponseQueue>> = HashMap::default();
+        for call in fixture.calls {
+            let response = match call.error {
+                Some(error) => Err(error),
+                None => Ok(call.result),
+            };
+            response_map
+                .entry(call.method)
+                .or_default()
+                .entry(call.params.to_string())
+                .or_default()
+                .push_back(response);
+        }
+        for (method, queues) in response_map {
+            let queues = Arc::new(Mutex::new(queues));
+            // method names have to be static for the RPC module, fixtures live for the test
+            let method: &'static str = Box::leak(method.into_boxed_str());
+            rpc_module.register_method(method, move |params, _, _| {
+                let params = get_request_params(&params);
+                let mut queues = queues.lock().unwrap();
+                let response = queues.get_mut(&params.to_string()).and_then(|queue| {
+                    if queue.len() > 1 {
+                        queue.pop_front()
+                    } else {
+                        queue.front().cloned()
+                    }
+                });
+                match response {
+                    Some(Ok(result)) => Ok(result),
+                    Some(Err(error)) => Err(ErrorObjectOwned::owned::<()>(
+                        error.code,
+                        error.message,
+                        None,
+                    )),
+                    None => {
+                        log::warn!(
+                            "No recorded response for {}.",
+                            get_params_key(method, &params)
+                        );
+                        Err(ErrorObjectOwned::owned::<()>(
+                            FIXTURE_NOT_FOUND_ERROR_CODE,
+                            format!("No recorded response for {method}."),
+                            None,
+                        ))
+                    }
+                }
+            })?;
+        }
+        let mut subscription_map: HashMap<String, Vec<RpcFixtureSubscription>> = HashMap::default();
+        for subscription in fixture.subscriptions {
+            subscription_map
+                .entry(subscription.subscribe_method.clone())
+                .or_default()
+                .push(subscription);
+        }
+        for (subscribe_method, subscriptions) in subscription_map {
+            let subscribe_method: &'static str = Box::leak(subscribe_method.into_boxed_str());
+            let notification_method: &'static str = Box::leak(
+                subscriptions[0]
+                    .notification_method
+                    .clone()
+                    .into_boxed_str(),
+            );
+            let unsubscribe_method: &'static str =
+                Box::leak(subscriptions[0].unsubscribe_method.clone().into_boxed_str());
+            let subscriptions = Arc::new(Mutex::new(VecDeque::from(subscriptions)));
+            rpc_module.register_subscription(
+                subscribe_method,
+                notification_method,
+                unsubscribe_method,
+                move |params, pending, _, _| {
+                    let params = get_request_params(&params);
+                    let notifications = {
+                        let mut subscriptions = subscriptions.lock().unwrap();
+                        let index = subscriptions
+                            .iter()
+                            .position(|subscription| subscription.params == params);
+                        match index {
+                            Some(index) if subscriptions.len() > 1 => subscriptions
+                                .remove(index)
+                                .map(|subscription| subscription.notifications),
+                            Some(index) => Some(subscriptions[index].notifications.clone()),
+                            None => None,
+                        }
+                    };
+                    async move {
+                        let Some(notifications) = notifications else {
+                            pending
+                                .reject(ErrorObjectOwned::owned::<()>(
+                                    FIXTURE_NOT_FOUND_ERROR_CODE,
+                                    format!("No recorded subscription for {subscribe_method}."),
+                                    None,
+                                ))
+                                .await;
+                            return SubscriptionCloseResponse::None;
+                        };
+                        let Ok(sink) = pending.accept().await else {
+                            return SubscriptionCloseResponse::None;
+                        };
+                        for notification in notifications.iter() {
+                            let Ok(message) = SubscriptionMessage::from_json(notification) else {
+                                return SubscriptionCloseResponse::None;
+                            };
+                            if sink.send(message).await.is_err() {
+                                return SubscriptionCloseResponse::None;
+                            }
+                        }
+                        // closes the subscription on the client side too
+                        SubscriptionCloseResponse::NotifErr(FIXTURE_SUBSCRIPTION_END_MESSAGE.into())
+                    }
+                },
+            )?;
+        }
+        log::info!("RPC replay server started at {address}.");
+        Ok((address, server.start(rpc_module)))
+    }
+}
+
+fn get_request_params(params: &Params) -> JsonValue {
+    let value = params
+        .as_str()
+        .and_then(|params| serde_json::from_str::<JsonValue>(params).ok());
+    normalize_params(value.as_ref())
+}
@@ -1,36 +1,48 @@
 //! SubVT Substrate client implementation.
 #![warn(clippy::disallowed_types)]
 
+use crate::fixture::RpcRecorder;
+use crate::storage_cache::StorageCache;
 use crate::storage_utility::{
-    get_rpc_paged_keys_params, get_rpc_paged_map_keys_params, get_rpc_storage_map_params,
-    get_rpc_storage_plain_params, get_storage_double_map_key, get_storage_map_key,
+    get_rpc_paged_keys_params, get_rpc_storage_map_params, get_rpc_storage_plain_params,
+    get_storage_double_map_key, get_storage_map_key, get_storage_plain_key,
 };
+use anyhow::Context;
 use async_recursion::async_recursion;
 use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed, RuntimeMetadataV14};
+use futures_util::{StreamExt, TryStreamExt};
+use jsonrpsee::client_transport::ws::{Url, WsTransportClientBuilder};
 use jsonrpsee::ws_client::WsClient;
 use jsonrpsee::{
-    core::client::{Client, ClientT, Subscription, SubscriptionClientT},
+    core::client::{Client, ClientBuilder, ClientT, Subscription, SubscriptionClientT},
+    core::params::{ArrayParams, BatchRequestBuilder},
     rpc_params,
     ws_client::WsClientBuilder,
 };
-use parity_scale_codec::Decode;
+use parity_scale_codec::{Decode, Encode};
 use rustc_hash::{FxHashMap as HashMap, FxHasher};
-use sp_core::storage::{StorageChangeSet, StorageKey};
+use serde::de::DeserializeOwned;
+use sp_core::storage::{StorageChangeSet, StorageData, StorageKey};
 use sp_core::ConstU32;
 use std::cmp::max;
 use std::collections::BTreeMap;
 use std::future::Future;
 use std::hash::{Hash, Hasher};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
 use subvt_types::app::event::democracy::{AccountVote, ConvictionVote};
 use subvt_types::crypto::AccountId;
+use subvt_types::election::{ElectionSnapshot, ElectionVoter};
+use subvt_types::report::RewardExportItem;
 use subvt_types::substrate::democracy::{
     get_democracy_conviction_u8, DelegatedVote, DirectVote, ReferendumVote, VoteType,
 };
 use subvt_types::substrate::error::DecodeError;
 use subvt_types::substrate::legacy::LegacyCoreOccupied;
 use subvt_types::substrate::metadata::{
-    get_metadata_epoch_duration_millis, get_metadata_era_duration_millis,
+    find_metadata_balancing_iterations, find_metadata_constant, get_metadata_epoch_duration_millis,
+    get_metadata_era_duration_millis,
 };
 use subvt_types::substrate::para::ParaCoreAssignment;
 use subvt_types::substrate::{
@@ -39,17 +51,53 @@ use subvt_types::substrate::{
     CoreAssignment, DemocracyVoting, Epoch, Era, EraRewardPoints, EraStakers, IdentityRegistration,
     LastRuntimeUpgradeInfo, Nomination, PagedExposureMetadata, RewardDestination,
     ScrapedOnChainVotes, Stake, SuperAccountId, SystemProperties, ValidatorPreferences,
-    ValidatorStake,
+    ValidatorStake, VoterListBag, VoterListNode,
 };
 /// Substrate client structure and its functions.
 /// This is the main gateway for SubVT to a Substrate node RPC interface.
 use subvt_types::subvt::ValidatorDetails;
 use subvt_utility::decode_hex_string;
+use tokio::sync::Semaphore;
 use tokio::time::timeout;
 
+pub mod fixture;
+pub mod storage_cache;
 mod storage_utility;
 
 const KEY_QUERY_PAGE_SIZE: usize = 500;
+/// Number of `state_queryStorageAt` calls, of `KEY_QUERY_PAGE_SIZE` keys each, sent in a single
+/// JSON-RPC batch request.
+const STORAGE_QUERY_BATCH_SIZE: usize = 8;
+/// Number of `chain_getBlock` calls sent in a single JSON-RPC batch request.
+const BLOCK_QUERY_BATCH_SIZE: usize = 16;
+/// Maximum number of concurrent in-flight RPC requests per client.
+const MAX_CONCURRENT_REQUESTS: usize = 16;
+
+/// Cumulative RPC request statistics of a client.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RpcStats {
+    /// Number of RPC requests sent to the node.
+    pub request_count: u64,
+    /// Number of storage keys queried from the node through `state_queryStorageAt`.
+    pub storage_key_count: u64,
+    /// Number of storage values served from the storage cache.
+    pub storage_cache_hit_count: u64,
+}
+
+impl RpcStats {
+    /// Statistics accumulated since the given earlier snapshot.
+    pub fn since(&self, earlier: &RpcStats) -> RpcStats {
+        RpcStats {
+            request_count: self.request_count.saturating_sub(earlier.request_count),
+            storage_key_count: self
+                .storage_key_count
+                .saturating_sub(earlier.storage_key_count),
+            storage_cache_hit_count: self
+                .storage_cache_hit_count
+                .saturating_sub(earlier.storage_cache_hit_count),
+        }
+    }
+}
 
 /// The client.
 pub struct SubstrateClient {
@@ -59,6 +107,12 @@ pub struct SubstrateClient {
     pub system_properties: SystemProperties,
     ws_client: Client,
     pub last_runtime_upgrade_info: LastRuntimeUpgradeInfo,
+    request_semaphore: Semaphore,
+    /// `None` when the storage cache is disabled.
+    storage_cache: Mutex<Option<StorageCache>>,
+    request_count: AtomicU64,
+    storage_key_count: AtomicU64,
+    storage_cache_hit_count: AtomicU64,
 }
 
 async fn get_metadata_at_block(
@@ -92,6 +146,31 @@ impl SubstrateClient {
             .request_timeout(std::time::Duration::from_secs(request_timeout_seconds))
             .build(rpc_url)
             .await?;
+        Self::from_ws_client(ws_client, network_id).await
+    }
+
+    /// Connect to the node and construct a new Substrate client that records all its RPC
+    /// traffic to the given recorder. See the `fixture` module for details.
+    pub async fn new_recording(
+        rpc_url: &str,
+        network_id: u32,
+        connection_timeout_seconds: u64,
+        request_timeout_seconds: u64,
+        recorder: &RpcRecorder,
+    ) -> anyhow::Result<Self> {
+        log::info!("Constructing recording Substrate client.");
+        let (sender, receiver) = WsTransportClientBuilder::default()
+            .connection_timeout(std::time::Duration::from_secs(connection_timeout_seconds))
+            .build(Url::parse(rpc_url)?)
+            .await?;
+        let (sender, receiver) = recorder.wrap(sender, receiver);
+        let ws_client = ClientBuilder::default()
+            .request_timeout(std::time::Duration::from_secs(request_timeout_seconds))
+            .build_with_tokio(sender, receiver);
+        Self::from_ws_client(ws_client, network_id).await
+    }
+
+    async fn from_ws_client(ws_client: Client, network_id: u32) -> anyhow::Result<Self> {
         log::info!("Substrate connection successful.");
         // get current block hash
         let block_hash: String = ws_client
@@ -122,9 +201,143 @@ impl SubstrateClient {
             system_properties,
             ws_client,
             last_runtime_upgrade_info,
+            request_semaphore: Semaphore::new(MAX_CONCURRENT_REQUESTS),
+            storage_cache: Mutex::new(None),
+            request_count: AtomicU64::new(0),
+            storage_key_count: AtomicU64::new(0),
+            storage_cache_hit_count: AtomicU64::new(0),
         })
     }
 
+    /// Enables or disables the per-block storage cache. Disabling also drops the cached values.
+    pub fn set_storage_cache_enabled(&self, enabled: bool) {
+        let mut storage_cache = self.storage_cache.lock().unwrap();
+        match (enabled, storage_cache.is_some()) {
+            (true, false) => *storage_cache = Some(StorageCache::default()),
+            (false, true) => *storage_cache = None,
+            _ => (),
+        }
+    }
+
+    /// Drops all cached storage values, if the storage cache is enabled.
+    pub fn clear_storage_cache(&self) {
+        if let Some(storage_cache) = self.storage_cache.lock().unwrap().as_mut() {
+            storage_cache.clear();
+        }
+    }
+
+    /// Cumulative RPC request statistics since the construction of the client.
+    pub fn get_rpc_stats(&self) -> RpcStats {
+        RpcStats {
+            request_count: self.request_count.load(Ordering::Relaxed),
+            storage_key_count: self.storage_key_count.load(Ordering::Relaxed),
+            storage_cache_hit_count: self.storage_cache_hit_count.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Sends an RPC request, keeping the number of concurrent requests within
+    /// `MAX_CONCURRENT_REQUESTS`.
+    async fn request<R>(&self, method: &str, params: ArrayParams) -> anyhow::Result<R>
+    where
+        R: DeserializeOwned,
+    {
+        let _permit = self.request_semaphore.acquire().await?;
+        self.request_count.fetch_add(1, Ordering::Relaxed);
+        Ok(self.ws_client.request(method, params).await?)
+    }
+
+    /// Sends the calls in the given batch as a single JSON-RPC batch request. Each call counts as
+    /// a request in the statistics. Fails if any of the calls fails.
+    async fn batch_request<R>(
+        &self,
+        batch: BatchRequestBuilder<'_>,
+        call_count: usize,
+    ) -> anyhow::Result<Vec<R>>
+    where
+        R: DeserializeOwned + std::fmt::Debug,
+    {
+        let _permit = self.request_semaphore.acquire().await?;
+        self.request_count
+            .fetch_add(call_count as u64, Ordering::Relaxed);
+        match self.ws_client.batch_request(batch).await?.into_ok() {
+            Ok(results) => Ok(results.collect()),
+            Err(mut errors) => Err(anyhow::anyhow!("Batch request failed: {:?}", errors.next())),
+        }
+    }
+
+    /// Get the values for the given storage keys at the given block. Keys get queried through
+    /// `state_queryStorageAt` in chunks of `KEY_QUERY_PAGE_SIZE`, with `STORAGE_QUERY_BATCH_SIZE`
+    /// chunks per JSON-RPC batch request and the batches sent concurrently. Values are served
+    /// from the storage cache when it's enabled.
+    async fn query_storage_at(
+        &self,
+        keys: &[String],
+        block_hash: &str,
+    ) -> anyhow::Result<Vec<(StorageKey, Option<StorageData>)>> {
+        let mut values: Vec<(StorageKey, Option<StorageData>)> = Vec::with_capacity(keys.len());
+        let mut query_keys: Vec<&String> = Vec::with_capacity(keys.len());
+        {
+            let storage_cache = self.storage_cache.lock().unwrap();
+            if let Some(storage_cache) = storage_cache.as_ref() {
+                for key in keys {
+                    let storage_key = StorageKey(hex::decode(key.trim_start_matches("0x"))?);
+                    if let Some(value) = storage_cache.get(block_hash, &storage_key) {
+                        values.push((storage_key, value.clone()));
+                    } else {
+                        query_keys.push(key);
+                    }
+                }
+                self.storage_cache_hit_count
+                    .fetch_add(values.len() as u64, Ordering::Relaxed);
+            } else {
+                query_keys.extend(keys.iter());
+            }
+        }
+        if query_keys.is_empty() {
+            return Ok(values);
+        }
+        self.storage_key_count
+            .fetch_add(query_keys.len() as u64, Ordering::Relaxed);
+        let chunks: Vec<&[&String]> = query_keys.chunks(KEY_QUERY_PAGE_SIZE).collect();
+        let batch_values: Vec<Vec<Vec<StorageChangeSet<String>>>> =
+            futures_util::stream::iter(chunks.chunks(STORAGE_QUERY_BATCH_SIZE))
+                .map(|batch_chunks| async move {
+                    let mut batch = BatchRequestBuilder::new();
+                    for chunk in batch_chunks {
+                        batch.insert("state_queryStorageAt", rpc_params!(chunk, block_hash))?;
+                    }
+                    self.batch_request(batch, batch_chunks.len()).await
+                })
+                .buffer_unordered(MAX_CONCURRENT_REQUESTS)
+                .try_collect()
+                .await?;
+        let mut storage_cache = self.storage_cache.lock().unwrap();
+        for change_set in batch_values.into_iter().flatten().flatten() {
+            for (storage_key, data) in change_set.changes {
+                if let Some(storage_cache) = storage_cache.as_mut() {
+                    storage_cache.insert(block_hash, storage_key.clone(), data.clone());
+                }
+                values.push((storage_key, data));
+            }
+        }
+        Ok(values)
+    }
+
+    /// Get the hex string value of a single storage key at the given block through
+    /// `query_storage_at`, so that it's served from the storage cache when it's enabled. `None` if
+    /// there's no value for the key.
+    async fn get_storage_hex_string(
+        &self,
+        key: String,
+        block_hash: &str,
+    ) -> anyhow::Result<Option<String>> {
+        let mut values = self.query_storage_at(&[key], block_hash).await?;
+        Ok(values
+            .pop()
+            .and_then(|(_, data)| data)
+            .map(|data| format!("0x{}", hex::encode(data.0))))
+    }
+
     pub async fn set_metadata_at_block(
         &mut self,
         block_number: u64,
@@ -138,17 +351,13 @@ impl SubstrateClient {
     }
 
     pub async fn get_current_block_hash(&self) -> anyhow::Result<String> {
-        let hash = self
-            .ws_client
-            .request("chain_getBlockHash", rpc_params!())
-            .await?;
+        let hash = self.request("chain_getBlockHash", rpc_params!()).await?;
         Ok(hash)
     }
 
     /// Get a block hash by its number.
     pub async fn get_block_hash(&self, block_number: u64) -> anyhow::Result<String> {
         let hash: String = self
-            .ws_client
             .request("chain_getBlockHash", rpc_params!(block_number))
             .await?;
         Ok(format!(
@@ -160,7 +369,6 @@ impl SubstrateClient {
     /// Get a block header by its hash.
     pub async fn get_block_header(&self, block_hash: &str) -> anyhow::Result<BlockHeader> {
         let mut header: BlockHeader = self
-            .ws_client
             .request("chain_getHeader", rpc_params!(&block_hash))
             .await?;
         header.parent_hash = format!(
@@ -184,7 +392,6 @@ impl SubstrateClient {
     /// Get the hash of the current finalized block.
     pub async fn get_finalized_block_hash(&self) -> anyhow::Result<String> {
         let hash: String = self
-            .ws_client
             .request("chain_getFinalizedHead", rpc_params!())
             .await?;
         Ok(format!(
@@ -196,7 +403,6 @@ impl SubstrateClient {
     /// Get a block.
     async fn get_block(&self, block_hash: &str) -> anyhow::Result<Block> {
         let mut block_wrapper: BlockWrapper = self
-            .ws_client
             .request("chain_getBlock", rpc_params!(&block_hash))
             .await?;
         block_wrapper.block.header.parent_hash = format!(
@@ -211,9 +417,106 @@ impl SubstrateClient {
         Ok(block_wrapper.block)
     }
 
+    /// Hashes of the extrinsics of a block, in the order of the extrinsic indices. The hash of an
+    /// extrinsic is the Blake2-256 hash of its SCALE encoding.
+    fn get_extrinsic_hashes(block: &Block) -> anyhow::Result<Vec<String>> {
+        let mut hashes = Vec::with_capacity(block.extrinsics.len());
+        for extrinsic_hex_string in &block.extrinsics {
+            let bytes = hex::decode(extrinsic_hex_string.trim_start_matches("0x"))?;
+            hashes.push(format!(
+                "0x{}",
+                hex::encode_upper(sp_core::blake2_256(&bytes))
+            ));
+        }
+        Ok(hashes)
+    }
+
+    /// Get the hashes of all the extrinsics in a block, in the order of the extrinsic indices.
+    pub async fn get_block_extrinsic_hashes(
+        &self,
+        block_hash: &str,
+    ) -> anyhow::Result<Vec<String>> {
+        let block = self.get_block(block_hash).await?;
+        Self::get_extrinsic_hashes(&block)
+    }
+
+    /// Get the extrinsic hashes of the given blocks by block hash, `BLOCK_QUERY_BATCH_SIZE`
+    /// blocks per JSON-RPC batch request. Blocks that don't exist in the chain of the client are
+    /// left out.
+    pub async fn get_blocks_extrinsic_hashes(
+        &self,
+        block_hashes: &[String],
+    ) -> anyhow::Result<HashMap<String, Vec<String>>> {
+        let batch_blocks: Vec<Vec<Option<BlockWrapper>>> =
+            futures_util::stream::iter(block_hashes.chunks(BLOCK_QUERY_BATCH_SIZE))
+                .map(|chunk| async move {
+                    let mut batch = BatchRequestBuilder::new();
+                    for block_hash in chunk {
+                        batch.insert("chain_getBlock", rpc_params!(block_hash))?;
+                    }
+                    self.batch_request(batch, chunk.len()).await
+                })
+                .buffered(MAX_CONCURRENT_REQUESTS)
+                .try_collect()
+                .await?;
+        let mut block_extrinsic_hashes = HashMap::default();
+        for (block_hash, maybe_block_wrapper) in
+            block_hashes.iter().zip(batch_blocks.into_iter().flatten())
+        {
+            if let Some(block_wrapper) = maybe_block_wrapper {
+                block_extrinsic_hashes.insert(
+                    block_hash.clone(),
+                    Self::get_extrinsic_hashes(&block_wrapper.block)?,
+                );
+            }
+        }
+        Ok(block_extrinsic_hashes)
+    }
+
+    /// Sets the extrinsic hashes of the reward export items. Rewards before the staking
+    /// migration to Asset Hub are in relay chain blocks and the later ones are in Asset Hub
+    /// blocks, so the blocks that are not found by a client are looked up using the next one.
+    pub async fn set_reward_export_extrinsic_hashes(
+        clients: &[&SubstrateClient],
+        items: &mut [RewardExportItem],
+    ) {
+        let mut block_hashes: Vec<String> = items
+            .iter()
+            .filter(|item| item.extrinsic_index.is_some())
+            .map(|item| item.block_hash.clone())
+            .collect();
+        block_hashes.sort();
+        block_hashes.dedup();
+        let mut block_extrinsic_hashes: HashMap<String, Vec<String>> = HashMap::default();
+        for client in clients {
+            if block_hashes.is_empty() {
+                break;
+            }
+            match client.get_blocks_extrinsic_hashes(&block_hashes).await {
+                Ok(client_block_extrinsic_hashes) => {
+                    block_hashes.retain(|block_hash| {
+                        !client_block_extrinsic_hashes.contains_key(block_hash)
+                    });
+                    block_extrinsic_hashes.extend(client_block_extrinsic_hashes);
+                }
+                Err(error) => log::warn!("Cannot get block extrinsic hashes: {error:?}"),
+            }
+        }
+        for block_hash in &block_hashes {
+            log::warn!("Cannot get extrinsic hashes for block {block_hash}.");
+        }
+        for item in items.iter_mut() {
+            if let Some(extrinsic_index) = item.extrinsic_index {
+                item.extrinsic_hash = block_extrinsic_hashes
+                    .get(&item.block_hash)
+                    .and_then(|hashes| hashes.get(extrinsic_index as usize))
+                    .cloned();
+            }
+        }
+    }
+
     pub async fn get_block_timestamp(&self, block_hash: &str) -> anyhow::Result<u64> {
         let hex_string: String = self
-            .ws_client
             .request(
                 "state_getStorage",
                 get_rpc_storage_plain_params("Timestamp", "Now", Some(block_hash)),
@@ -229,7 +532,6 @@ impl SubstrateClient {
         babe_metadata: &RuntimeMetadataV14,
     ) -> anyhow::Result<Era> {
         let hex_string: String = self
-            .ws_client
             .request(
                 "state_getStorage",
                 get_rpc_storage_plain_params("Staking", "ActiveEra", Some(block_hash)),
@@ -245,7 +547,6 @@ impl SubstrateClient {
     /// Get the index of the epoch at the given block hash.
     pub async fn get_current_epoch_index(&self, block_hash: &str) -> anyhow::Result<u64> {
         let hex_string: String = self
-            .ws_client
             .request(
                 "state_getStorage",
                 get_rpc_storage_plain_params("Babe", "EpochIndex", Some(block_hash)),
@@ -260,7 +561,6 @@ impl SubstrateClient {
         let index = self.get_current_epoch_index(block_hash).await?;
         let start_block_number = {
             let hex_string: String = self
-                .ws_client
                 .request(
                     "state_getStorage",
                     get_rpc_storage_plain_params("Babe", "EpochStart", Some(block_hash)),
@@ -304,10 +604,8 @@ impl SubstrateClient {
         if let Some(block_hash) = maybe_block_hash {
             params.insert(block_hash)?;
         }
-        let chunk_values: Vec<StorageChangeSet<String>> = self
-            .ws_client
-            .request("state_queryStorageAt", params)
-            .await?;
+        let chunk_values: Vec<StorageChangeSet<String>> =
+            self.request("state_queryStorageAt", params).await?;
         if let Some(value) = chunk_values.first() {
             if let Some((_, Some(data))) = value.changes.first() {
                 let bytes: [u8; 32] = (&data.0 as &[u8]).try_into()?;
@@ -329,10 +627,8 @@ impl SubstrateClient {
         if let Some(block_hash) = maybe_block_hash {
             params.insert(block_hash)?;
         }
-        let chunk_values: Vec<StorageChangeSet<String>> = self
-            .ws_client
-            .request("state_queryStorageAt", params)
-            .await?;
+        let chunk_values: Vec<StorageChangeSet<String>> =
+            self.request("state_queryStorageAt", params).await?;
         if let Some(value) = chunk_values.first() {
             if let Some((_, Some(data))) = value.changes.first() {
                 let stake = Stake::from_bytes(&data.0 as &[u8])?;
@@ -376,13 +672,10 @@ impl SubstrateClient {
         &self,
         block_hash: &str,
     ) -> anyhow::Result<Vec<AccountId>> {
-        let hex_string: String = self
-            .ws_client
-            .request(
-                "state_getStorage",
-                get_rpc_storage_plain_params("Session", "Validators", Some(block_hash)),
-            )
-            .await?;
+        let hex_string = self
+            .get_storage_hex_string(get_storage_plain_key("Session", "Validators"), block_hash)
+            .await?
+            .context("Session validators not found.")?;
         let account_ids = decode_hex_string(hex_string.as_str())?;
         Ok(account_ids)
     }
@@ -408,17 +701,11 @@ impl SubstrateClient {
         if keys.is_empty() {
             return Ok(HashMap::default());
         }
-        let values: Vec<StorageChangeSet<String>> = self
-            .ws_client
-            .request("state_queryStorageAt", rpc_params!(keys, &identity_hash))
-            .await?;
-        log::trace!(
-            "Got {} optional super accounts records.",
-            values[0].changes.len()
-        );
+        let values = self.query_storage_at(&keys, identity_hash).await?;
+        log::trace!("Got {} optional super accounts records.", values.len());
         let mut parent_account_map: HashMap<AccountId, (AccountId, Option<String>)> =
             HashMap::default();
-        for (storage_key, storage_data) in values[0].changes.iter() {
+        for (storage_key, storage_data) in values.iter() {
             if let Some(data) = storage_data {
                 let account_id = self.account_id_from_storage_key(storage_key);
                 let mut bytes: &[u8] = &data.0;
@@ -455,13 +742,10 @@ impl SubstrateClient {
         if keys.is_empty() {
             return Ok(HashMap::default());
         }
-        let values: Vec<StorageChangeSet<String>> = self
-            .ws_client
-            .request("state_queryStorageAt", rpc_params!(keys, block_hash))
-            .await?;
-        log::trace!("Got {} optional identities.", values[0].changes.len());
+        let values = self.query_storage_at(&keys, block_hash).await?;
+        log::trace!("Got {} optional identities.", values.len());
         let mut identity_map: HashMap<AccountId, IdentityRegistration> = HashMap::default();
-        for (storage_key, storage_data) in values[0].changes.iter() {
+        for (storage_key, storage_data) in values.iter() {
             let account_id = self.account_id_from_storage_key(storage_key);
             if let Some(data) = storage_data {
                 let bytes: &[u8] = &data.0;
@@ -478,8 +762,10 @@ impl SubstrateClient {
         fetch_parent_accounts: bool,
         block_hash: &str,
     ) -> anyhow::Result<Vec<Account>> {
-        let identity_map = { self.get_identities(account_ids, block_hash).await? };
-        let parent_account_id_map = { self.get_parent_account_ids(account_ids, block_hash).await? };
+        let (identity_map, parent_account_id_map) = tokio::try_join!(
+            self.get_identities(account_ids, block_hash),
+            self.get_parent_account_ids(account_ids, block_hash),
+        )?;
         let parent_account_identity_map = {
             let super_account_ids: Vec<AccountId> =
                 parent_account_id_map.values().map(|pair| pair.0).collect();
@@ -491,7 +777,7 @@ impl SubstrateClient {
             .map(|account_id| {
                 let mut account = Account {
                     id: account_id,
-                    address: account_id.to_ss58_check(),
+                    address: account_id.to_ss58_check_with_version(self.chain.get_ss58_prefix()),
                     ..Default::default()
                 };
                 if let Some(identity) = identity_map.get(&account_id) {
@@ -501,7 +787,9 @@ impl SubstrateClient {
                     if fetch_parent_accounts {
                         let mut parent_account = Account {
                             id: parent_account_id.0,
-                            address: parent_account_id.0.to_ss58_check(),
+                            address: parent_account_id
+                                .0
+                                .to_ss58_check_with_version(self.chain.get_ss58_prefix()),
                             ..Default::default()
                         };
                         if let Some(parent_account_identity) =
@@ -530,22 +818,49 @@ impl SubstrateClient {
         storage_name: &str,
         block_hash: &str,
     ) -> anyhow::Result<Vec<String>> {
+        self.get_all_keys_for_prefix(
+            &get_storage_plain_key(module_name, storage_name),
+            block_hash,
+        )
+        .await
+    }
+
+    /// Get the complete keys for the given map storage that start with the given first key.
+    /// An example would be the complete keys for `Staking.ErasStakersPaged` for an era.
+    async fn get_all_keys_for_map_prefix<T>(
+        &self,
+        module_name: &str,
+        storage_name: &str,
+        key: &T,
+        block_hash: &str,
+    ) -> anyhow::Result<Vec<String>>
+    where
+        T: Encode + Sync,
+    {
+        let prefix = get_storage_map_key(&self.metadata, module_name, storage_name, key);
+        self.get_all_keys_for_prefix(&prefix, block_hash).await
+    }
+
+    async fn get_all_keys_for_prefix(
+        &self,
+        prefix: &str,
+        block_hash: &str,
+    ) -> anyhow::Result<Vec<String>> {
+        if let Some(storage_cache) = self.storage_cache.lock().unwrap().as_ref() {
+            if let Some(keys) = storage_cache.get_keys(block_hash, prefix) {
+                return Ok(keys.clone());
+            }
+        }
         let mut all_keys: Vec<String> = Vec::new();
         loop {
             let last = all_keys.last();
             let mut keys: Vec<String> = self
-                .ws_client
                 .request(
                     "state_getKeysPaged",
                     get_rpc_paged_keys_params(
-                        module_name,
-                        storage_name,
+                        prefix,
                         KEY_QUERY_PAGE_SIZE,
-                        if let Some(last) = last {
-                            Some(last.as_str())
-                        } else {
-                            None
-                        },
+                        last.map(|last| last.as_str()),
                         Some(block_hash),
                     ),
                 )
@@ -556,6 +871,9 @@ impl SubstrateClient {
                 break;
             }
         }
+        if let Some(storage_cache) = self.storage_cache.lock().unwrap().as_mut() {
+            storage_cache.insert_keys(block_hash, prefix, all_keys.clone());
+        }
         Ok(all_keys)
     }
 
@@ -571,19 +889,12 @@ impl SubstrateClient {
                 get_storage_map_key(&self.metadata, "Session", "NextKeys", &validator.account.id)
             })
             .collect();
-        for chunk in keys.chunks(KEY_QUERY_PAGE_SIZE) {
-            let chunk_values: Vec<StorageChangeSet<String>> = self
-                .ws_client
-                .request("state_queryStorageAt", rpc_params!(chunk, &block_hash))
-                .await?;
-
-            for (storage_key, data) in chunk_values[0].changes.iter() {
-                if let Some(data) = data {
-                    let account_id = self.account_id_from_storage_key(storage_key);
-                    let session_keys = format!("0x{}", hex::encode_upper(&data.0));
-                    let validator = validator_map.get_mut(&account_id).unwrap();
-                    validator.next_session_keys = session_keys;
-                }
+        for (storage_key, data) in self.query_storage_at(&keys, block_hash).await?.iter() {
+            if let Some(data) = data {
+                let account_id = self.account_id_from_storage_key(storage_key);
+                let session_keys = format!("0x{}", hex::encode_upper(&data.0));
+                let validator = validator_map.get_mut(&account_id).unwrap();
+                validator.next_session_keys = session_keys;
             }
         }
         Ok(())
@@ -595,13 +906,10 @@ impl SubstrateClient {
         block_hash: &str,
     ) -> anyhow::Result<()> {
         log::debug!("Get queued session keys & find out which validators are active next session.");
-        let hex_string: String = self
-            .ws_client
-            .request(
-                "state_getStorage",
-                get_rpc_storage_plain_params("Session", "QueuedKeys", Some(block_hash)),
-            )
-            .await?;
+        let hex_string = self
+            .get_storage_hex_string(get_storage_plain_key("Session", "QueuedKeys"), block_hash)
+            .await?
+            .context("Queued session keys not found.")?;
         let maybe_session_key_pairs: anyhow::Result<Vec<(AccountId, [u8; 193])>> =
             decode_hex_string(&hex_string);
         if let Ok(session_key_pairs) = &maybe_session_key_pairs {
@@ -636,9 +944,10 @@ impl SubstrateClient {
         era: &Era,
     ) -> anyhow::Result<Vec<ValidatorDetails>> {
         log::info!("Getting all validators.");
-        let all_keys: Vec<String> = self
-            .get_all_keys_for_storage("Staking", "Validators", block_hash)
-            .await?;
+        let (all_keys, all_nominator_keys) = tokio::try_join!(
+            self.get_all_keys_for_storage("Staking", "Validators", block_hash),
+            self.get_all_keys_for_storage("Staking", "Nominators", block_hash),
+        )?;
         log::info!(
             "There are {} validators (active and waiting).",
             all_keys.len()
@@ -743,132 +1052,86 @@ impl SubstrateClient {
         relay_client
             .get_queued_session_keys(&mut validator_map, &last_relay_chain_block_hash)
             .await?;
-        // get reward destinations
-        {
-            log::debug!("Get reward destinations.");
-            let keys: Vec<String> = validator_map
-                .values()
-                .map(|validator| {
-                    get_storage_map_key(&self.metadata, "Staking", "Payee", &validator.account.id)
-                })
-                .collect();
-
-            for chunk in keys.chunks(KEY_QUERY_PAGE_SIZE) {
-                let chunk_values: Vec<StorageChangeSet<String>> = self
-                    .ws_client
-                    .request("state_queryStorageAt", rpc_params!(chunk, &block_hash))
-                    .await?;
-
-                for (storage_key, data) in chunk_values[0].changes.iter() {
-                    if let Some(data) = data {
-                        let account_id = self.account_id_from_storage_key(storage_key);
-                        let bytes: &[u8] = &data.0;
-                        let reward_destination = RewardDestination::from_bytes(bytes).unwrap();
-                        let validator = validator_map.get_mut(&account_id).unwrap();
-                        validator.reward_destination = reward_destination;
-                    }
-                }
+        // fetch reward destinations, nominations, nominator accounts, nomination amounts and
+        // self stakes, validator preferences and active stakers concurrently
+        log::debug!(
+            "Got {} nomination storage keys. Get reward destinations, nominations, stakes and validator preferences.",
+            all_nominator_keys.len()
+        );
+        let payee_keys: Vec<String> = validator_map
+            .values()
+            .map(|validator| {
+                get_storage_map_key(&self.metadata, "Staking", "Payee", &validator.account.id)
+            })
+            .collect();
+        let nominator_account_ids: Vec<AccountId> = all_nominator_keys
+            .iter()
+            .map(|key| self.account_id_from_storage_key_string(key))
+            .collect();
+        let ledger_storage_keys: Vec<String> = nominator_account_ids
+            .iter()
+            .chain(active_validator_account_ids.iter())
+            .map(|account_id| get_storage_map_key(&self.metadata, "Staking", "Ledger", account_id))
+            .collect();
+        let (
+            payee_values,
+            nomination_values,
+            nominator_accounts,
+            ledger_values,
+            preference_values,
+            era_stakers,
+            total_staked,
+            last_era_total_reward,
+        ) = tokio::try_join!(
+            self.query_storage_at(&payee_keys, block_hash),
+            self.query_storage_at(&all_nominator_keys, block_hash),
+            people_client.get_accounts(
+                &nominator_account_ids,
+                true,
+                people_finalized_block_hash.as_str()
+            ),
+            self.query_storage_at(&ledger_storage_keys, block_hash),
+            self.query_storage_at(&all_keys, block_hash),
+            self.get_era_stakers(era, block_hash),
+            self.get_era_total_stake(era.index, block_hash),
+            self.get_era_total_validator_reward(era.index - 1, block_hash),
+        )?;
+        // set reward destinations
+        for (storage_key, data) in payee_values.iter() {
+            if let Some(data) = data {
+                let account_id = self.account_id_from_storage_key(storage_key);
+                let bytes: &[u8] = &data.0;
+                let reward_destination = RewardDestination::from_bytes(bytes).unwrap();
+                let validator = validator_map.get_mut(&account_id).unwrap();
+                validator.reward_destination = reward_destination;
             }
         }
-        // get nominations
+        // set nominations
         {
-            log::debug!("Get all nominations.");
-            let mut all_keys: Vec<String> = Vec::new();
-            loop {
-                let last = all_keys.last();
-                let mut keys: Vec<String> = self
-                    .ws_client
-                    .request(
-                        "state_getKeysPaged",
-                        get_rpc_paged_keys_params(
-                            "Staking",
-                            "Nominators",
-                            KEY_QUERY_PAGE_SIZE,
-                            if let Some(last) = last {
-                                Some(last.as_str())
-                            } else {
-                                None
-                            },
-                            Some(block_hash),
-                        ),
-                    )
-                    .await?;
-                let keys_length = keys.len();
-                all_keys.append(&mut keys);
-                if keys_length < KEY_QUERY_PAGE_SIZE {
-                    break;
-                }
-            }
-
-            log::debug!(
-                "Got {} nomination storage keys. Accessing storage.",
-                all_keys.len()
-            );
             let mut nomination_map: HashMap<AccountId, Nomination> = HashMap::default();
-            for chunk in all_keys.chunks(KEY_QUERY_PAGE_SIZE) {
-                let chunk_values: Vec<StorageChangeSet<String>> = self
-                    .ws_client
-                    .request("state_queryStorageAt", rpc_params!(chunk, &block_hash))
-                    .await?;
-                for (storage_key, data) in chunk_values[0].changes.iter() {
-                    if let Some(data) = data {
-                        let account_id = self.account_id_from_storage_key(storage_key);
-                        let bytes: &[u8] = &data.0;
-                        let nomination = Nomination::from_bytes(bytes, account_id).unwrap();
-                        nomination_map.insert(nomination.stash_account.id, nomination);
-                    }
+            for (storage_key, data) in nomination_values.iter() {
+                if let Some(data) = data {
+                    let account_id = self.account_id_from_storage_key(storage_key);
+                    let bytes: &[u8] = &data.0;
+                    let nomination = Nomination::from_bytes(bytes, account_id).unwrap();
+                    nomination_map.insert(nomination.stash_account.id, nomination);
                 }
             }
-            log::debug!(
-                "Got {} nominations. Get nominator accounts.",
-                nomination_map.len()
-            );
-            // get nominator account details
-            {
-                let nominator_account_ids: Vec<AccountId> =
-                    nomination_map.keys().cloned().collect();
-                for account_id_chunk in nominator_account_ids.chunks(KEY_QUERY_PAGE_SIZE) {
-                    let accounts = people_client
-                        .get_accounts(account_id_chunk, true, people_finalized_block_hash.as_str())
-                        .await?;
-                    for account in accounts {
-                        nomination_map.get_mut(&account.id).unwrap().stash_account =
-                            account.clone();
-                    }
+            log::debug!("Got {} nominations.", nomination_map.len());
+            for account in nominator_accounts {
+                if let Some(nomination) = nomination_map.get_mut(&account.id) {
+                    nomination.stash_account = account;
                 }
             }
-
-            log::debug!("Get nomination amounts and self stakes.");
-            let mut ledger_storage_keys: Vec<String> = nomination_map
-                .keys()
-                .map(|stash_account_id| {
-                    get_storage_map_key(&self.metadata, "Staking", "Ledger", &stash_account_id)
-                })
-                .collect();
-            active_validator_account_ids.iter().for_each(|account_id| {
-                ledger_storage_keys.push(get_storage_map_key(
-                    &self.metadata,
-                    "Staking",
-                    "Ledger",
-                    account_id,
-                ))
-            });
-
-            for chunk in ledger_storage_keys.chunks(KEY_QUERY_PAGE_SIZE) {
-                let chunk_values: Vec<StorageChangeSet<String>> = self
-                    .ws_client
-                    .request("state_queryStorageAt", rpc_params!(chunk, &block_hash))
-                    .await?;
-                for (_, data) in chunk_values[0].changes.iter() {
-                    if let Some(data) = data {
-                        let bytes: &[u8] = &data.0;
-                        let stake = Stake::from_bytes(bytes).unwrap();
-                        let account_id = &stake.stash_account_id;
-                        if let Some(nomination) = nomination_map.get_mut(account_id) {
-                            nomination.stake = stake;
-                        } else if let Some(validator) = validator_map.get_mut(account_id) {
-                            validator.self_stake = stake;
-                        }
+            for (_, data) in ledger_values.iter() {
+                if let Some(data) = data {
+                    let bytes: &[u8] = &data.0;
+                    let stake = Stake::from_bytes(bytes).unwrap();
+                    let account_id = &stake.stash_account_id;
+                    if let Some(nomination) = nomination_map.get_mut(account_id) {
+                        nomination.stake = stake;
+                    } else if let Some(validator) = validator_map.get_mut(account_id) {
+                        validator.self_stake = stake;
                     }
                 }
             }
@@ -889,39 +1152,26 @@ impl SubstrateClient {
             }
             log::debug!("Nomination data complete.");
         }
-        // get validator prefs
-        {
-            log::debug!("Get validator preferences.");
-            let values: Vec<StorageChangeSet<String>> = self
-                .ws_client
-                .request("state_queryStorageAt", rpc_params!(all_keys, &block_hash))
-                .await?;
-            for (storage_key, data) in values[0].changes.iter() {
-                if let Some(data) = data {
-                    let mut bytes = &data.0.clone()[..];
-                    let preferences = Decode::decode(&mut bytes)?;
-                    let validator_account_id = self.account_id_from_storage_key(storage_key);
-                    let validator = validator_map.get_mut(&validator_account_id).unwrap();
-                    validator.preferences = preferences;
-                }
+        // set validator prefs
+        for (storage_key, data) in preference_values.iter() {
+            if let Some(data) = data {
+                let mut bytes = &data.0.clone()[..];
+                let preferences = Decode::decode(&mut bytes)?;
+                let validator_account_id = self.account_id_from_storage_key(storage_key);
+                let validator = validator_map.get_mut(&validator_account_id).unwrap();
+                validator.preferences = preferences;
             }
         }
-        // get active stakers
+        // set active stakers
         {
-            log::debug!("Get active stakers.");
-            let era_stakers = self.get_era_stakers(era, block_hash).await?;
             for validator_stake in era_stakers.stakers.iter() {
                 if let Some(validator) = validator_map.get_mut(&validator_stake.account.id) {
                     validator.validator_stake = Some(validator_stake.clone());
                 }
             }
             // calculate return rates
-            let total_staked = self.get_era_total_stake(era.index, block_hash).await?;
             let eras_per_day = 24 * 60 * 60 * 1000
                 / get_metadata_era_duration_millis(&relay_client.metadata, &self.metadata)? as u128;
-            let last_era_total_reward = self
-                .get_era_total_validator_reward(era.index - 1, block_hash)
-                .await?;
             let total_return_rate_per_billion =
                 (last_era_total_reward * eras_per_day * 365 * 1_000_000_000) / total_staked;
             let average_stake = era_stakers.average_stake();
@@ -955,7 +1205,6 @@ impl SubstrateClient {
     /// Get the number of all validation intents at the given block.
     pub async fn get_total_validator_count(&self, block_hash: &str) -> anyhow::Result<u32> {
         let hex_string: String = self
-            .ws_client
             .request(
                 "state_getStorage",
                 get_rpc_storage_plain_params("Staking", "CounterForValidators", Some(block_hash)),
@@ -964,20 +1213,227 @@ impl SubstrateClient {
         decode_hex_string(hex_string.as_str())
     }
 
+    /// Get the maximum number of winners allowed by the election provider, if the runtime
+    /// defines one.
+    fn get_max_election_winner_count(&self) -> anyhow::Result<Option<u32>> {
+        for (module_name, constant_name) in [
+            ("ElectionProviderMultiPhase", "MaxWinners"),
+            ("MultiBlockElectionVerifier", "MaxWinnersPerPage"),
+        ] {
+            if let Some(max_winner_count) =
+                find_metadata_constant::<u32>(&self.metadata, module_name, constant_name)?
+            {
+                return Ok(Some(max_winner_count));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Get the maximum number of voters that take part in the election, if the runtime defines
+    /// one. The multi-block election takes a page of voters per block.
+    fn get_max_electing_voter_count(&self) -> anyhow::Result<Option<u32>> {
+        if let (Some(voters_per_page), Some(page_count)) = (
+            find_metadata_constant::<u32>(
+                &self.metadata,
+                "MultiBlockElection",
+                "VoterSnapshotPerBlock",
+            )?,
+            find_metadata_constant::<u32>(&self.metadata, "MultiBlockElection", "Pages")?,
+        ) {
+            return Ok(Some(voters_per_page.saturating_mul(page_count)));
+        }
+        find_metadata_constant::<u32>(
+            &self.metadata,
+            "ElectionProviderMultiPhase",
+            "MaxElectingVoters",
+        )
+    }
+
+    /// Get the account ids in the voter list (`pallet-bags-list`) in the iteration order of the
+    /// staking pallet while taking the election snapshot: bags in the descending order of their
+    /// upper thresholds, and the voters in each bag from the head to the tail.
+    async fn get_voter_list_account_ids(&self, block_hash: &str) -> anyhow::Result<Vec<AccountId>> {
+        let (bag_keys, node_keys) = tokio::try_join!(
+            self.get_all_keys_for_storage("VoterList", "ListBags", block_hash),
+            self.get_all_keys_for_storage("VoterList", "ListNodes", block_hash),
+        )?;
+        let (bag_values, node_values) = tokio::try_join!(
+            self.query_storage_at(&bag_keys, block_hash),
+            self.query_storage_at(&node_keys, block_hash),
+        )?;
+        let mut bags: Vec<(u64, VoterListBag)> = Vec::with_capacity(bag_values.len());
+        for (storage_key, data) in bag_values.iter() {
+            if let Some(data) = data {
+                // twox64-concat key, the upper threshold is the last 8 bytes
+                let bag_upper = u64::decode(&mut &storage_key.0[storage_key.0.len() - 8..])?;
+                bags.push((bag_upper, VoterListBag::decode(&mut &data.0[..])?));
+            }
+        }
+        bags.sort_by(|a, b| b.0.cmp(&a.0));
+        let mut node_map: HashMap<AccountId, VoterListNode> = HashMap::default();
+        for data in node_values.iter().filter_map(|(_, data)| data.as_ref()) {
+            let node = VoterListNode::decode(&mut &data.0[..])?;
+            node_map.insert(node.id, node);
+        }
+        let mut account_ids = Vec::with_capacity(node_map.len());
+        for (_, bag) in bags {
+            let mut maybe_account_id = bag.head;
+            while let Some(account_id) = maybe_account_id {
+                // guard against a broken list
+                let Some(node) = node_map.remove(&account_id) else {
+                    break;
+                };
+                account_ids.push(account_id);
+                maybe_account_id = node.next;
+            }
+        }
+        Ok(account_ids)
+    }
+
+    /// Get the number of balancing iterations of the election miner, if the runtime exposes it.
+    /// The current `Option<BalancingConfig>` constant of the miner takes precedence over the
+    /// legacy `MinerMaxIterations` constant.
+    fn get_election_balancing_iterations(&self) -> anyhow::Result<Option<u32>> {
+        for module_name in [
+            "MultiBlockElectionUnsigned",
+            "MultiBlockElection",
+            "ElectionProviderMultiPhase",
+        ] {
+            if let Some((constant_name, iterations)) =
+                find_metadata_balancing_iterations(&self.metadata, module_name)?
+            {
+                log::info!(
+                    "Read {iterations} election balancing iterations from {module_name}.{constant_name}."
+                );
+                return Ok(Some(iterations));
+            }
+        }
+        if let Some(iterations) = find_metadata_constant::<u32>(
+            &self.metadata,
+            "ElectionProviderMultiPhase",
+            "MinerMaxIterations",
+        )? {
+            log::info!(
+                "Read {iterations} election balancing iterations from legacy ElectionProviderMultiPhase.MinerMaxIterations."
+            );
+            return Ok(Some(iterations));
+        }
+        log::info!("Runtime doesn't expose the election balancing iterations.");
+        Ok(None)
+    }
+
+    /// Get the input of the next validator election at the given block: all validation intents
+    /// as targets, all nominators and validators (voting for themselves) with their active
+    /// bonded amounts as voters. Voters are taken in the order of the voter list, up to the
+    /// maximum number of electing voters of the runtime. Assumes that stash and controller
+    /// accounts are the same.
+    pub async fn get_election_snapshot(
+        &self,
+        block_hash: &str,
+    ) -> anyhow::Result<ElectionSnapshot> {
+        log::info!("Get election snapshot.");
+        let (validator_keys, nominator_keys, validator_count_hex_string) = tokio::try_join!(
+            self.get_all_keys_for_storage("Staking", "Validators", block_hash),
+            self.get_all_keys_for_storage("Staking", "Nominators", block_hash),
+            self.request::<String>(
+                "state_getStorage",
+                get_rpc_storage_plain_params("Staking", "ValidatorCount", Some(block_hash)),
+            ),
+        )?;
+        let mut desired_target_count: u32 = decode_hex_string(validator_count_hex_string.as_str())?;
+        if let Some(max_winner_count) = self.get_max_election_winner_count()? {
+            desired_target_count = desired_target_count.min(max_winner_count);
+        }
+        let target_account_ids: Vec<AccountId> = validator_keys
+            .iter()
+            .map(|key| self.account_id_from_storage_key_string(key))
+            .collect();
+        let nominator_account_ids: Vec<AccountId> = nominator_keys
+            .iter()
+            .map(|key| self.account_id_from_storage_key_string(key))
+            .collect();
+        let ledger_storage_keys: Vec<String> = nominator_account_ids
+            .iter()
+            .chain(target_account_ids.iter())
+            .map(|account_id| get_storage_map_key(&self.metadata, "Staking", "Ledger", account_id))
+            .collect();
+        let (nomination_values, ledger_values) = tokio::try_join!(
+            self.query_storage_at(&nominator_keys, block_hash),
+            self.query_storage_at(&ledger_storage_keys, block_hash),
+        )?;
+        let mut stake_map: HashMap<AccountId, Balance> = HashMap::default();
+        for (_, data) in ledger_values.iter() {
+            if let Some(data) = data {
+                let stake = Stake::from_bytes(&data.0)?;
+                stake_map.insert(stake.stash_account_id, stake.active_amount);
+            }
+        }
+        let mut voters = Vec::with_capacity(nominator_keys.len() + target_account_ids.len());
+        for (storage_key, data) in nomination_values.iter() {
+            if let Some(data) = data {
+                let account_id = self.account_id_from_storage_key(storage_key);
+                let nomination = Nomination::from_bytes(&data.0, account_id)?;
+                voters.push(ElectionVoter {
+                    account_id,
+                    stake: stake_map.get(&account_id).cloned().unwrap_or(0),
+                    target_account_ids: nomination.target_account_ids,
+                });
+            }
+        }
+        for account_id in &target_account_ids {
+            voters.push(ElectionVoter {
+                account_id: *account_id,
+                stake: stake_map.get(account_id).cloned().unwrap_or(0),
+                target_account_ids: vec![*account_id],
+            });
+        }
+        if let Some(max_voter_count) = self.get_max_electing_voter_count()? {
+            let max_voter_count = max_voter_count as usize;
+            if voters.len() > max_voter_count {
+                let voter_list_account_ids = self.get_voter_list_account_ids(block_hash).await?;
+                let order_map: HashMap<AccountId, usize> = voter_list_account_ids
+                    .iter()
+                    .enumerate()
+                    .map(|(index, account_id)| (*account_id, index))
+                    .collect();
+                // voters missing in the voter list go last, in the descending order of stake
+                voters.sort_by(|a, b| {
+                    let a_order = order_map.get(&a.account_id).cloned().unwrap_or(usize::MAX);
+                    let b_order = order_map.get(&b.account_id).cloned().unwrap_or(usize::MAX);
+                    a_order.cmp(&b_order).then(b.stake.cmp(&a.stake))
+                });
+                voters.truncate(max_voter_count);
+            }
+        }
+        let balancing_iterations = self.get_election_balancing_iterations()?;
+        log::info!(
+            "Got election snapshot with {} targets and {} voters for {} seats.",
+            target_account_ids.len(),
+            voters.len(),
+            desired_target_count,
+        );
+        Ok(ElectionSnapshot {
+            block_hash: block_hash.to_string(),
+            desired_target_count,
+            target_account_ids,
+            voters,
+            balancing_iterations,
+        })
+    }
+
     /// Get total rewards earned by validators in the native currency at the given era.
     pub async fn get_era_total_validator_reward(
         &self,
         era_index: u32,
         block_hash: &str,
     ) -> anyhow::Result<Balance> {
-        let params = get_rpc_storage_map_params(
-            &self.metadata,
-            "Staking",
-            "ErasValidatorReward",
-            &era_index,
-            Some(block_hash),
-        );
-        let hex_string: String = self.ws_client.request("state_getStorage", params).await?;
+        let hex_string = self
+            .get_storage_hex_string(
+                get_storage_map_key(&self.metadata, "Staking", "ErasValidatorReward", &era_index),
+                block_hash,
+            )
+            .await?
+            .context("Era total validator reward not found.")?;
         decode_hex_string(hex_string.as_str())
     }
 
@@ -987,14 +1443,13 @@ impl SubstrateClient {
         era_index: u32,
         block_hash: &str,
     ) -> anyhow::Result<Balance> {
-        let params = get_rpc_storage_map_params(
-            &self.metadata,
-            "Staking",
-            "ErasTotalStake",
-            &era_index,
-            Some(block_hash),
-        );
-        let hex_string: String = self.ws_client.request("state_getStorage", params).await?;
+        let hex_string = self
+            .get_storage_hex_string(
+                get_storage_map_key(&self.metadata, "Staking", "ErasTotalStake", &era_index),
+                block_hash,
+            )
+            .await?
+            .context("Era total stake not found.")?;
         decode_hex_string(hex_string.as_str())
     }
 
@@ -1004,50 +1459,18 @@ impl SubstrateClient {
         block_hash: &str,
     ) -> anyhow::Result<HashMap<AccountId, PagedExposureMetadata<Balance>>> {
         // önce overview'ları çek, sonra her biri için sayfaları çek ve topla
-        let mut all_keys: Vec<String> = Vec::new();
-        loop {
-            let last = all_keys.last();
-            let mut keys: Vec<String> = self
-                .ws_client
-                .request(
-                    "state_getKeysPaged",
-                    get_rpc_paged_map_keys_params(
-                        &self.metadata,
-                        "Staking",
-                        "ErasStakersOverview",
-                        &era.index,
-                        KEY_QUERY_PAGE_SIZE,
-                        if let Some(last) = last {
-                            Some(last.as_str())
-                        } else {
-                            None
-                        },
-                        Some(block_hash),
-                    ),
-                )
-                .await?;
-            let keys_length = keys.len();
-            all_keys.append(&mut keys);
-            if keys_length < KEY_QUERY_PAGE_SIZE {
-                break;
-            }
-        }
+        let all_keys = self
+            .get_all_keys_for_map_prefix("Staking", "ErasStakersOverview", &era.index, block_hash)
+            .await?;
         let mut exposure_metadata_map: HashMap<AccountId, PagedExposureMetadata<Balance>> =
             HashMap::default();
-        for chunk in all_keys.chunks(KEY_QUERY_PAGE_SIZE) {
-            let chunk_values: Vec<StorageChangeSet<String>> = self
-                .ws_client
-                .request("state_queryStorageAt", rpc_params!(chunk, &block_hash))
-                .await?;
-
-            for (storage_key, data) in chunk_values[0].changes.iter() {
-                if let Some(data) = data {
-                    let validator_account_id: AccountId = storage_key.0[storage_key.0.len() - 32..]
-                        .try_into()
-                        .unwrap();
-                    let mut bytes: &[u8] = &data.0;
-                    exposure_metadata_map.insert(validator_account_id, Decode::decode(&mut bytes)?);
-                }
+        for (storage_key, data) in self.query_storage_at(&all_keys, block_hash).await?.iter() {
+            if let Some(data) = data {
+                let validator_account_id: AccountId = storage_key.0[storage_key.0.len() - 32..]
+                    .try_into()
+                    .unwrap();
+                let mut bytes: &[u8] = &data.0;
+                exposure_metadata_map.insert(validator_account_id, Decode::decode(&mut bytes)?);
             }
         }
         Ok(exposure_metadata_map)
@@ -1060,53 +1483,26 @@ impl SubstrateClient {
         clipped: bool,
         block_hash: &str,
     ) -> anyhow::Result<EraStakers> {
-        let mut all_keys: Vec<String> = Vec::new();
-        loop {
-            let last = all_keys.last();
-            let mut keys: Vec<String> = self
-                .ws_client
-                .request(
-                    "state_getKeysPaged",
-                    get_rpc_paged_map_keys_params(
-                        &self.metadata,
-                        "Staking",
-                        if clipped {
-                            "ErasStakersClipped"
-                        } else {
-                            "ErasStakers"
-                        },
-                        &era.index,
-                        KEY_QUERY_PAGE_SIZE,
-                        if let Some(last) = last {
-                            Some(last.as_str())
-                        } else {
-                            None
-                        },
-                        Some(block_hash),
-                    ),
-                )
-                .await?;
-            let keys_length = keys.len();
-            all_keys.append(&mut keys);
-            if keys_length < KEY_QUERY_PAGE_SIZE {
-                break;
-            }
-        }
+        let all_keys = self
+            .get_all_keys_for_map_prefix(
+                "Staking",
+                if clipped {
+                    "ErasStakersClipped"
+                } else {
+                    "ErasStakers"
+                },
+                &era.index,
+                block_hash,
+            )
+            .await?;
 
         let mut stakers: Vec<ValidatorStake> = Vec::new();
-        for chunk in all_keys.chunks(KEY_QUERY_PAGE_SIZE) {
-            let chunk_values: Vec<StorageChangeSet<String>> = self
-                .ws_client
-                .request("state_queryStorageAt", rpc_params!(chunk, &block_hash))
-                .await?;
-
-            for (storage_key, data) in chunk_values[0].changes.iter() {
-                if let Some(data) = data {
-                    let validator_account_id = self.account_id_from_storage_key(storage_key);
-                    let nomination =
-                        ValidatorStake::from_bytes_legacy(&data.0, validator_account_id).unwrap();
-                    stakers.push(nomination);
-                }
+        for (storage_key, data) in self.query_storage_at(&all_keys, block_hash).await?.iter() {
+            if let Some(data) = data {
+                let validator_account_id = self.account_id_from_storage_key(storage_key);
+                let nomination =
+                    ValidatorStake::from_bytes_legacy(&data.0, validator_account_id).unwrap();
+                stakers.push(nomination);
             }
         }
         stakers.sort_by_key(|validator_stake| validator_stake.total_stake);
@@ -1119,69 +1515,39 @@ impl SubstrateClient {
     /// Get all the active stakes for the given era.
     pub async fn get_era_stakers(&self, era: &Era, block_hash: &str) -> anyhow::Result<EraStakers> {
         let exposure_metadata_map = self.get_exposure_metadata_map(era, block_hash).await?;
-        let mut all_keys: Vec<String> = Vec::new();
-        loop {
-            let last = all_keys.last();
-            let mut keys: Vec<String> = self
-                .ws_client
-                .request(
-                    "state_getKeysPaged",
-                    get_rpc_paged_map_keys_params(
-                        &self.metadata,
-                        "Staking",
-                        "ErasStakersPaged",
-                        &era.index,
-                        KEY_QUERY_PAGE_SIZE,
-                        if let Some(last) = last {
-                            Some(last.as_str())
-                        } else {
-                            None
-                        },
-                        Some(block_hash),
-                    ),
-                )
-                .await?;
-            let keys_length = keys.len();
-            all_keys.append(&mut keys);
-            if keys_length < KEY_QUERY_PAGE_SIZE {
-                break;
-            }
-        }
+        let all_keys = self
+            .get_all_keys_for_map_prefix("Staking", "ErasStakersPaged", &era.index, block_hash)
+            .await?;
         if all_keys.is_empty() {
             return self.get_era_stakers_legacy(era, true, block_hash).await;
         }
 
         let mut stakers: Vec<ValidatorStake> = Vec::new();
-        for chunk in all_keys.chunks(KEY_QUERY_PAGE_SIZE) {
-            let chunk_values: Vec<StorageChangeSet<String>> = self
-                .ws_client
-                .request("state_queryStorageAt", rpc_params!(chunk, &block_hash))
-                .await?;
-
-            for (storage_key, data) in chunk_values[0].changes.iter() {
-                if let Some(data) = data {
-                    let validator_account_id: AccountId = storage_key.0
-                        [storage_key.0.len() - (32 + 12)..storage_key.0.len() - 12]
-                        .try_into()
-                        .unwrap();
-                    let validator_exposure =
-                        exposure_metadata_map.get(&validator_account_id).unwrap();
-                    let nomination = ValidatorStake::from_bytes(
-                        &data.0,
-                        validator_account_id,
-                        validator_exposure.own,
-                    )
+        for (storage_key, data) in self.query_storage_at(&all_keys, block_hash).await?.iter() {
+            if let Some(data) = data {
+                let validator_account_id: AccountId = storage_key.0
+                    [storage_key.0.len() - (32 + 12)..storage_key.0.len() - 12]
+                    .try_into()
                     .unwrap();
-                    if let Some(index) = stakers
-                        .iter()
-                        .position(|stake| stake.account.id == validator_account_id)
-                    {
-                        for nominator in nomination.nominators.iter() {
-                            stakers[index].nominators.push(nominator.clone());
-                        }
-                    } else {
-                        stakers.push(nomination);
+                let page_index =
+                    u32::decode(&mut &storage_key.0[storage_key.0.len() - 4..]).unwrap();
+                let validator_exposure = exposure_metadata_map.get(&validator_account_id).unwrap();
+                let nomination = ValidatorStake::from_bytes(
+                    &data.0,
+                    validator_account_id,
+                    validator_exposure.own,
+                    page_index,
+                )
+                .unwrap();
+                if let Some(index) = stakers
+                    .iter()
+                    .position(|stake| stake.account.id == validator_account_id)
+                {
+                    for nominator in nomination.nominators.iter() {
+                        stakers[index].nominators.push(nominator.clone());
                     }
+                } else {
+                    stakers.push(nomination);
                 }
             }
         }
@@ -1206,8 +1572,7 @@ impl SubstrateClient {
             &era_index,
             Some(block_hash),
         );
-        let maybe_hex_string: Option<String> =
-            self.ws_client.request("state_getStorage", params).await?;
+        let maybe_hex_string: Option<String> = self.request("state_getStorage", params).await?;
         let reward_points = if let Some(hex_string) = maybe_hex_string {
             decode_hex_string(hex_string.as_str())?
         } else {
@@ -1219,7 +1584,6 @@ impl SubstrateClient {
     /// Get the session index at the given block.
     pub async fn get_current_session_index(&self, block_hash: &str) -> anyhow::Result<u32> {
         let hex_string: String = self
-            .ws_client
             .request(
                 "state_getStorage",
                 get_rpc_storage_plain_params("Session", "CurrentIndex", Some(block_hash)),
@@ -1236,7 +1600,6 @@ impl SubstrateClient {
         let block = self.get_block(block_hash).await?;
         let mut event_bytes: &[u8] = {
             let events_hex_string: String = self
-                .ws_client
                 .request(
                     "state_getStorage",
                     get_rpc_storage_plain_params("System", "Events", Some(block_hash)),
@@ -1275,7 +1638,6 @@ impl SubstrateClient {
         block_hash: &str,
     ) -> anyhow::Result<LastRuntimeUpgradeInfo> {
         let hex_string: String = self
-            .ws_client
             .request(
                 "state_getStorage",
                 get_rpc_storage_plain_params("System", "LastRuntimeUpgrade", Some(block_hash)),
@@ -1290,10 +1652,12 @@ impl SubstrateClient {
         &self,
         block_hash: &str,
     ) -> anyhow::Result<Option<Vec<u32>>> {
-        let params =
-            get_rpc_storage_plain_params("ParasShared", "ActiveValidatorIndices", Some(block_hash));
-        let maybe_indices_vector_hex_string: Option<String> =
-            self.ws_client.request("state_getStorage", params).await?;
+        let maybe_indices_vector_hex_string = self
+            .get_storage_hex_string(
+                get_storage_plain_key("ParasShared", "ActiveValidatorIndices"),
+                block_hash,
+            )
+            .await?;
         if let Some(indices_vector_hex_string) = maybe_indices_vector_hex_string {
             Ok(Some(decode_hex_string(&indices_vector_hex_string)?))
         } else {
@@ -1302,12 +1666,13 @@ impl SubstrateClient {
     }
 
     pub async fn get_last_relay_chain_block_number(&self, block_hash: &str) -> anyhow::Result<u32> {
-        let params = get_rpc_storage_plain_params(
-            "ParachainSystem",
-            "LastRelayChainBlockNumber",
-            Some(block_hash),
-        );
-        let hex_string: String = self.ws_client.request("state_getStorage", params).await?;
+        let hex_string = self
+            .get_storage_hex_string(
+                get_storage_plain_key("ParachainSystem", "LastRelayChainBlockNumber"),
+                block_hash,
+            )
+            .await?
+            .context("Last relay chain block number not found.")?;
         decode_hex_string(&hex_string)
     }
 
@@ -1317,10 +1682,13 @@ impl SubstrateClient {
         &self,
         block_hash: &str,
     ) -> anyhow::Result<Vec<Vec<u32>>> {
-        let params =
-            get_rpc_storage_plain_params("ParaScheduler", "ValidatorGroups", Some(block_hash));
-        let group_double_vector_hex_string: String =
-            self.ws_client.request("state_getStorage", params).await?;
+        let group_double_vector_hex_string = self
+            .get_storage_hex_string(
+                get_storage_plain_key("ParaScheduler", "ValidatorGroups"),
+                block_hash,
+            )
+            .await?
+            .context("Para validator groups not found.")?;
         let groups = decode_hex_string(&group_double_vector_hex_string)?;
         Ok(groups)
     }
@@ -1331,14 +1699,14 @@ impl SubstrateClient {
     ) -> anyhow::Result<Option<Vec<ParaCoreAssignment>>> {
         let params = get_rpc_storage_plain_params("ParaInherent", "OnChainVotes", Some(block_hash));
         let maybe_votes_hex_string: Option<String> =
-            self.ws_client.request("state_getStorage", params).await?;
+            self.request("state_getStorage", params).await?;
         if let Some(hex_string) = maybe_votes_hex_string {
             let votes: ScrapedOnChainVotes = decode_hex_string(&hex_string)?;
             // get availability cores
             let params =
                 get_rpc_storage_plain_params("ParaScheduler", "ClaimQueue", Some(block_hash));
             let maybe_cores_hex_string: Option<String> =
-                self.ws_client.request("state_getStorage", params).await?;
+                self.request("state_getStorage", params).await?;
             if let Some(cores_hex_string) = &maybe_cores_hex_string {
                 let cores: Vec<LegacyCoreOccupied> = decode_hex_string(cores_hex_string)?;
                 Ok(Some(ParaCoreAssignment::from_on_chain_votes_legacy(
@@ -1356,9 +1724,12 @@ impl SubstrateClient {
         &self,
         block_hash: &str,
     ) -> anyhow::Result<Option<Vec<ParaCoreAssignment>>> {
-        let params = get_rpc_storage_plain_params("ParaInherent", "OnChainVotes", Some(block_hash));
-        let maybe_votes_hex_string: Option<String> =
-            self.ws_client.request("state_getStorage", params).await?;
+        let maybe_votes_hex_string = self
+            .get_storage_hex_string(
+                get_storage_plain_key("ParaInherent", "OnChainVotes"),
+                block_hash,
+            )
+            .await?;
         if let Some(hex_string) = maybe_votes_hex_string {
             let votes: ScrapedOnChainVotes = decode_hex_string(&hex_string)?;
             let mut group_size: u32 = 0;
@@ -1366,10 +1737,12 @@ impl SubstrateClient {
                 group_size = max(group_size, votes.len() as u32);
             }
             // get core claim queue
-            let params =
-                get_rpc_storage_plain_params("ParaScheduler", "ClaimQueue", Some(block_hash));
-            let maybe_cores_hex_string: Option<String> =
-                self.ws_client.request("state_getStorage", params).await?;
+            let maybe_cores_hex_string = self
+                .get_storage_hex_string(
+                    get_storage_plain_key("ParaScheduler", "ClaimQueue"),
+                    block_hash,
+                )
+                .await?;
             if let Some(cores_hex_string) = &maybe_cores_hex_string {
                 let claim_queue: BTreeMap<u32, Vec<CoreAssignment>> =
                     decode_hex_string(cores_hex_string)?;
@@ -1392,7 +1765,7 @@ impl SubstrateClient {
     ) -> anyhow::Result<Option<ScrapedOnChainVotes>> {
         let params = get_rpc_storage_plain_params("ParaInherent", "OnChainVotes", Some(block_hash));
         let maybe_votes_hex_string: Option<String> =
-            self.ws_client.request("state_getStorage", params).await?;
+            self.request("state_getStorage", params).await?;
         if let Some(hex_string) = maybe_votes_hex_string {
             Ok(Some(decode_hex_string(&hex_string)?))
         } else {
@@ -1406,61 +1779,27 @@ impl SubstrateClient {
         era_index: u32,
         block_hash: &str,
     ) -> anyhow::Result<HashMap<AccountId, ValidatorPreferences>> {
-        let mut all_keys: Vec<String> = Vec::new();
-        loop {
-            let last = all_keys.last();
-            let mut keys: Vec<String> = self
-                .ws_client
-                .request(
-                    "state_getKeysPaged",
-                    get_rpc_paged_map_keys_params(
-                        &self.metadata,
-                        "Staking",
-                        "ErasValidatorPrefs",
-                        &era_index,
-                        KEY_QUERY_PAGE_SIZE,
-                        if let Some(last) = last {
-                            Some(last.as_str())
-                        } else {
-                            None
-                        },
-                        Some(block_hash),
-                    ),
-                )
-                .await?;
-            let keys_length = keys.len();
-            all_keys.append(&mut keys);
-            if keys_length < KEY_QUERY_PAGE_SIZE {
-                break;
-            }
-        }
+        let all_keys = self
+            .get_all_keys_for_map_prefix("Staking", "ErasValidatorPrefs", &era_index, block_hash)
+            .await?;
         let mut validator_prefs_map: HashMap<AccountId, ValidatorPreferences> = HashMap::default();
-        for chunk in all_keys.chunks(KEY_QUERY_PAGE_SIZE) {
-            let chunk_values: Vec<StorageChangeSet<String>> = self
-                .ws_client
-                .request("state_queryStorageAt", rpc_params!(chunk, &block_hash))
-                .await?;
-
-            for (storage_key, data) in chunk_values[0].changes.iter() {
-                if let Some(data) = data {
-                    let validator_account_id = self.account_id_from_storage_key(storage_key);
-                    let mut bytes: &[u8] = &data.0.clone();
-                    let mut bytes_clone: &[u8] = &data.0.clone();
-                    let validator_prefs = match Decode::decode(&mut bytes) {
-                        Ok(validator_preferences) => validator_preferences,
-                        Err(_) => {
-                            let legacy_validator_prefs: LegacyValidatorPrefs =
-                                Decode::decode(&mut bytes_clone)?;
-                            ValidatorPreferences {
-                                commission_per_billion: legacy_validator_prefs
-                                    .commission
-                                    .deconstruct(),
-                                blocks_nominations: false,
-                            }
+        for (storage_key, data) in self.query_storage_at(&all_keys, block_hash).await?.iter() {
+            if let Some(data) = data {
+                let validator_account_id = self.account_id_from_storage_key(storage_key);
+                let mut bytes: &[u8] = &data.0.clone();
+                let mut bytes_clone: &[u8] = &data.0.clone();
+                let validator_prefs = match Decode::decode(&mut bytes) {
+                    Ok(validator_preferences) => validator_preferences,
+                    Err(_) => {
+                        let legacy_validator_prefs: LegacyValidatorPrefs =
+                            Decode::decode(&mut bytes_clone)?;
+                        ValidatorPreferences {
+                            commission_per_billion: legacy_validator_prefs.commission.deconstruct(),
+                            blocks_nominations: false,
                         }
-                    };
-                    validator_prefs_map.insert(validator_account_id, validator_prefs);
-                }
+                    }
+                };
+                validator_prefs_map.insert(validator_account_id, validator_prefs);
             }
         }
         Ok(validator_prefs_map)
@@ -1482,7 +1821,6 @@ impl SubstrateClient {
             &track_id,
         );
         let chunk_values: Vec<StorageChangeSet<String>> = self
-            .ws_client
             .request(
                 "state_queryStorageAt",
                 rpc_params!(vec![storage_key], block_hash),
@@ -1513,7 +1851,6 @@ impl SubstrateClient {
     > {
         let storage_key = get_storage_map_key(&self.metadata, "Democracy", "VotingOf", account_id);
         let chunk_values: Vec<StorageChangeSet<String>> = self
-            .ws_client
             .request(
                 "state_queryStorageAt",
                 rpc_params!(vec![storage_key], block_hash),
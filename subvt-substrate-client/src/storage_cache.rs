@@ -0,0 +1,56 @@
+//! In-memory storage cache for the Substrate client. Holds the storage values and the storage key
+//! listings of a single block, so that the calls in the same update cycle (i.e. at the same block)
+//! don't query the same keys more than once. Gets reset when an entry for another block is
+//! inserted.
+use rustc_hash::FxHashMap as HashMap;
+use sp_core::storage::{StorageData, StorageKey};
+
+#[derive(Default)]
+pub struct StorageCache {
+    block_hash: String,
+    entries: HashMap<StorageKey, Option<StorageData>>,
+    /// All the keys that start with a prefix, by prefix.
+    keys: HashMap<String, Vec<String>>,
+}
+
+impl StorageCache {
+    /// `None` for a cache miss, `Some(None)` for a cached empty storage value.
+    pub fn get(&self, block_hash: &str, key: &StorageKey) -> Option<&Option<StorageData>> {
+        if !self.block_hash.eq_ignore_ascii_case(block_hash) {
+            return None;
+        }
+        self.entries.get(key)
+    }
+
+    pub fn insert(&mut self, block_hash: &str, key: StorageKey, value: Option<StorageData>) {
+        self.set_block_hash(block_hash);
+        self.entries.insert(key, value);
+    }
+
+    /// All the storage keys with the given prefix, `None` for a cache miss.
+    pub fn get_keys(&self, block_hash: &str, prefix: &str) -> Option<&Vec<String>> {
+        if !self.block_hash.eq_ignore_ascii_case(block_hash) {
+            return None;
+        }
+        self.keys.get(prefix)
+    }
+
+    pub fn insert_keys(&mut self, block_hash: &str, prefix: &str, keys: Vec<String>) {
+        self.set_block_hash(block_hash);
+        self.keys.insert(prefix.to_string(), keys);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.keys.clear();
+        self.block_hash.clear();
+    }
+
+    fn set_block_hash(&mut self, block_hash: &str) {
+        if !self.block_hash.eq_ignore_ascii_case(block_hash) {
+            self.entries.clear();
+            self.keys.clear();
+            self.block_hash = block_hash.to_string();
+        }
+    }
+}
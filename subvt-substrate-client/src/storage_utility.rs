@@ -37,17 +37,16 @@ pub fn get_rpc_storage_plain_params<'a>(
     params
 }
 
-/// Get JSONRPSee parameters for a plain storage type at an optional given block.
-/// Will get current storage if `None` is supplied for `block_hash`.
+/// Get JSONRPSee parameters for a page of the storage keys that start with the given prefix at
+/// an optional given block. Will get current storage if `None` is supplied for `block_hash`.
 pub fn get_rpc_paged_keys_params<'a>(
-    module: &'a str,
-    name: &'a str,
+    prefix: &'a str,
     count: usize,
     start_key: Option<&'a str>,
     block_hash: Option<&'a str>,
 ) -> ArrayParams {
     let mut params = ArrayParams::new();
-    params.insert(get_storage_plain_key(module, name)).unwrap();
+    params.insert(prefix).unwrap();
     params.insert(count).unwrap();
     if let Some(start_key) = start_key {
         params.insert(start_key).unwrap();
@@ -136,41 +135,6 @@ where
     format!("{storage_key_hex}{map_key_1_hex}{map_key_2_hex}")
 }
 
-pub fn get_rpc_paged_map_keys_params<'a, T>(
-    metadata: &RuntimeMetadataV14,
-    module_name: &'a str,
-    storage_name: &'a str,
-    key: &T,
-    count: usize,
-    start_key: Option<&'a str>,
-    block_hash: Option<&'a str>,
-) -> ArrayParams
-where
-    T: Encode,
-{
-    let mut params = ArrayParams::new();
-    params
-        .insert(get_storage_map_key(
-            metadata,
-            module_name,
-            storage_name,
-            key,
-        ))
-        .unwrap();
-    params.insert(count).unwrap();
-    if let Some(start_key) = start_key {
-        params.insert(start_key).unwrap();
-    } else {
-        params.insert(JsonValue::Null).unwrap();
-    }
-    if let Some(block_hash) = block_hash {
-        params.insert(block_hash).unwrap();
-    } else {
-        params.insert(JsonValue::Null).unwrap();
-    }
-    params
-}
-
 /// Get JSONRPSee parameters for a map storage type at an optional given block.
 /// Will get current storage if `None` is supplied for `block_hash`.
 pub fn get_rpc_storage_map_params<T>(
@@ -0,0 +1,249 @@
+//! Record/replay RPC fixture tests. These run offline against the replay server.
+use jsonrpsee::client_transport::ws::{Url, WsTransportClientBuilder};
+use jsonrpsee::core::client::{ClientBuilder, ClientT, Subscription, SubscriptionClientT};
+use jsonrpsee::rpc_params;
+use serde_json::{json, Value as JsonValue};
+use subvt_config::Config;
+use subvt_substrate_client::fixture::{
+    get_recorded_fixture_path, load_recorded_fixture, RpcFixture, RpcFixtureCall,
+    RpcFixtureSubscription, RpcRecorder, RpcReplayServer,
+};
+use subvt_substrate_client::SubstrateClient;
+
+const BLOCK_HASH: &str = "0x9d95763d4119488779991da8d1b16874687a3308ffcf9f89284d0382e8ccd161";
+
+fn get_fixture() -> RpcFixture {
+    RpcFixture {
+        calls: vec![
+            RpcFixtureCall {
+                method: "chain_getBlockHash".to_string(),
+                params: json!([8_500_000]),
+                result: json!(BLOCK_HASH),
+                error: None,
+            },
+            RpcFixtureCall {
+                method: "chain_getFinalizedHead".to_string(),
+                params: json!([]),
+                result: json!("0x01"),
+                error: None,
+            },
+            RpcFixtureCall {
+                method: "chain_getFinalizedHead".to_string(),
+                params: json!([]),
+                result: json!("0x02"),
+                error: None,
+            },
+        ],
+        subscriptions: vec![RpcFixtureSubscription {
+            subscribe_method: "chain_subscribeFinalizedHeads".to_string(),
+            notification_method: "chain_finalizedHead".to_string(),
+            unsubscribe_method: "chain_unsubscribeFinalizedHeads".to_string(),
+            params: json!([]),
+            notifications: vec![json!({ "number": "0x1" }), json!({ "number": "0x2" })],
+        }],
+    }
+}
+
+#[tokio::test]
+async fn test_replay() {
+    let (address, server_handle) = RpcReplayServer::start(get_fixture(), "127.0.0.1", 0)
+        .await
+        .expect("Cannot start replay server.");
+    let client = jsonrpsee::ws_client::WsClientBuilder::default()
+        .build(format!("ws://{address}"))
+        .await
+        .expect("Cannot connect to replay server.");
+    let hash: String = client
+        .request("chain_getBlockHash", rpc_params!(8_500_000))
+        .await
+        .expect("Cannot get block hash.");
+    assert_eq!(hash, BLOCK_HASH);
+    // responses are served in order, the last one is repeated
+    for expected_hash in ["0x01", "0x02", "0x02"] {
+        let hash: String = client
+            .request("chain_getFinalizedHead", rpc_params!())
+            .await
+            .expect("Cannot get finalized head.");
+        assert_eq!(hash, expected_hash);
+    }
+    let missing_result: Result<String, _> =
+        client.request("chain_getBlockHash", rpc_params!(1)).await;
+    assert!(missing_result.is_err());
+    let mut subscription: Subscription<JsonValue> = client
+        .subscribe(
+            "chain_subscribeFinalizedHeads",
+            rpc_params!(),
+            "chain_unsubscribeFinalizedHeads",
+        )
+        .await
+        .expect("Cannot subscribe.");
+    for expected_number in ["0x1", "0x2"] {
+        let header = subscription.next().await.unwrap().unwrap();
+        assert_eq!(header["number"], expected_number);
+    }
+    assert!(subscription.next().await.is_none());
+    server_handle.stop().unwrap();
+}
+
+#[tokio::test]
+async fn test_record_replay_round_trip() {
+    let fixture = get_fixture();
+    let (address, server_handle) = RpcReplayServer::start(fixture.clone(), "127.0.0.1", 0)
+        .await
+        .expect("Cannot start replay server.");
+    let recorder = RpcRecorder::default();
+    let (sender, receiver) = WsTransportClientBuilder::default()
+        .build(Url::parse(&format!("ws://{address}")).unwrap())
+        .await
+        .expect("Cannot connect to replay server.");
+    let (sender, receiver) = recorder.wrap(sender, receiver);
+    let client = ClientBuilder::default().build_with_tokio(sender, receiver);
+    let _: String = client
+        .request("chain_getBlockHash", rpc_params!(8_500_000))
+        .await
+        .unwrap();
+    let _: String = client
+        .request("chain_getFinalizedHead", rpc_params!())
+        .await
+        .unwrap();
+    let mut subscription: Subscription<JsonValue> = client
+        .subscribe(
+            "chain_subscribeFinalizedHeads",
+            rpc_params!(),
+            "chain_unsubscribeFinalizedHeads",
+        )
+        .await
+        .unwrap();
+    while subscription.next().await.is_some() {}
+    let recorded = recorder.get_fixture();
+    assert_eq!(recorded.calls.len(), 2);
+    assert_eq!(recorded.calls[0].method, "chain_getBlockHash");
+    assert_eq!(recorded.calls[0].params, json!([8_500_000]));
+    assert_eq!(recorded.calls[0].result, json!(BLOCK_HASH));
+    assert_eq!(recorded.calls[1].params, json!([]));
+    assert_eq!(recorded.subscriptions.len(), 1);
+    assert_eq!(
+        recorded.subscriptions[0].notification_method,
+        fixture.subscriptions[0].notification_method
+    );
+    assert_eq!(
+        recorded.subscriptions[0].notifications,
+        fixture.subscriptions[0].notifications
+    );
+    // recorded fixture survives a save/load cycle
+    let path = std::env::temp_dir().join("subvt_substrate_client_fixture_test.json");
+    recorder.save(&path).unwrap();
+    let loaded = RpcFixture::load(&path).unwrap();
+    assert_eq!(loaded.calls.len(), recorded.calls.len());
+    std::fs::remove_file(&path).unwrap();
+    server_handle.stop().unwrap();
+}
+
+/// Real Kusama and Polkadot blocks, recorded as fixtures by `record_block_fixtures`.
+const FIXTURE_BLOCKS: [(&str, u64); 3] = [
+    ("kusama", 18_497_450),
+    ("kusama", 22_000_000),
+    ("polkadot", 22_000_000),
+];
+
+fn get_block_fixture_name(chain: &str, block_number: u64) -> String {
+    format!("{chain}_block_{block_number}")
+}
+
+fn get_network_config(chain: &str) -> Config {
+    let mut config = Config::test().expect("Cannot get test config.");
+    config.common.networks = chain.to_string();
+    config
+        .get_network_configs()
+        .expect("Cannot get network config.")
+        .remove(0)
+}
+
+/// Records the fixtures of the real blocks from the network nodes. Run with
+/// `cargo test -p subvt-substrate-client --test fixture -- --ignored` to (re-)record them.
+#[tokio::test]
+#[ignore]
+async fn record_block_fixtures() {
+    for (chain, block_number) in FIXTURE_BLOCKS {
+        let config = get_network_config(chain);
+        let recorder = RpcRecorder::default();
+        let mut substrate_client = SubstrateClient::new_recording(
+            config.substrate.rpc_url.as_str(),
+            config.substrate.network_id,
+            config.substrate.connection_timeout_seconds,
+            config.substrate.request_timeout_seconds,
+            &recorder,
+        )
+        .await
+        .expect("Cannot initialize recording client.");
+        decode_block(&mut substrate_client, block_number).await;
+        recorder
+            .save(get_recorded_fixture_path(&get_block_fixture_name(
+                chain,
+                block_number,
+            )))
+            .unwrap();
+    }
+}
+
+/// Decodes the events and the extrinsics of the block, and checks that they all decode.
+async fn decode_block(substrate_client: &mut SubstrateClient, block_number: u64) {
+    let block_hash = substrate_client
+        .get_block_hash(block_number)
+        .await
+        .unwrap_or_else(|_| panic!("Cannot get block hash for block #{block_number}."));
+    substrate_client
+        .set_metadata_at_block(block_number, &block_hash)
+        .await
+        .unwrap_or_else(|_| panic!("Cannot set metadata for block #{block_number}."));
+    let header = substrate_client
+        .get_block_header(&block_hash)
+        .await
+        .unwrap_or_else(|_| panic!("Cannot get header of block #{block_number}."));
+    assert_eq!(header.get_number().unwrap(), block_number);
+    let events = substrate_client
+        .get_block_events(&block_hash)
+        .await
+        .unwrap_or_else(|_| panic!("Cannot get events of block #{block_number}."));
+    assert!(!events.is_empty());
+    for event in events {
+        event.unwrap_or_else(|error| panic!("Cannot decode event: {error:?}"));
+    }
+    let extrinsics = substrate_client
+        .get_block_extrinsics(&block_hash)
+        .await
+        .unwrap_or_else(|_| panic!("Cannot get extrinsics of block #{block_number}."));
+    // every block has at least the timestamp inherent
+    assert!(!extrinsics.is_empty());
+    for extrinsic in extrinsics {
+        extrinsic.unwrap_or_else(|error| panic!("Cannot decode extrinsic: {error:?}"));
+    }
+    let timestamp = substrate_client
+        .get_block_timestamp(&block_hash)
+        .await
+        .unwrap_or_else(|_| panic!("Cannot get timestamp of block #{block_number}."));
+    assert!(timestamp > 0);
+}
+
+/// Decodes the recorded real blocks end to end through the replay server.
+#[tokio::test]
+async fn test_decode_recorded_blocks() {
+    for (chain, block_number) in FIXTURE_BLOCKS {
+        let config = get_network_config(chain);
+        let fixture = load_recorded_fixture(&get_block_fixture_name(chain, block_number))
+            .expect("Cannot load block fixture.");
+        let (address, server_handle) = RpcReplayServer::start(fixture, "127.0.0.1", 0)
+            .await
+            .expect("Cannot start replay server.");
+        let mut substrate_client = SubstrateClient::new(
+            &format!("ws://{address}"),
+            config.substrate.network_id,
+            config.substrate.connection_timeout_seconds,
+            config.substrate.request_timeout_seconds,
+        )
+        .await
+        .expect("Cannot connect to replay server.");
+        decode_block(&mut substrate_client, block_number).await;
+        server_handle.stop().unwrap();
+    }
+}
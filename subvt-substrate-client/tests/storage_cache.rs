@@ -0,0 +1,67 @@
+//! Storage cache tests.
+use sp_core::storage::{StorageData, StorageKey};
+use subvt_substrate_client::storage_cache::StorageCache;
+
+const BLOCK_HASH: &str = "0x9D95763D4119488779991DA8D1B16874687A3308FFCF9F89284D0382E8CCD161";
+const NEXT_BLOCK_HASH: &str = "0x9d95763d4119488779991da8d1b16874687a3308ffcf9f89284d0382e8ccd162";
+
+#[test]
+fn test_storage_cache_hit_and_miss() {
+    let mut cache = StorageCache::default();
+    let key = StorageKey(vec![1, 2, 3]);
+    let empty_key = StorageKey(vec![4, 5, 6]);
+    assert!(cache.get(BLOCK_HASH, &key).is_none());
+    cache.insert(BLOCK_HASH, key.clone(), Some(StorageData(vec![7])));
+    cache.insert(BLOCK_HASH, empty_key.clone(), None);
+    assert_eq!(
+        cache.get(BLOCK_HASH, &key),
+        Some(&Some(StorageData(vec![7])))
+    );
+    // block hash comparison is case-insensitive
+    assert_eq!(
+        cache.get(&BLOCK_HASH.to_lowercase(), &key),
+        Some(&Some(StorageData(vec![7])))
+    );
+    // cached empty value
+    assert_eq!(cache.get(BLOCK_HASH, &empty_key), Some(&None));
+    assert!(cache.get(BLOCK_HASH, &StorageKey(vec![0])).is_none());
+    assert!(cache.get(NEXT_BLOCK_HASH, &key).is_none());
+}
+
+#[test]
+fn test_storage_cache_keys() {
+    let mut cache = StorageCache::default();
+    let keys = vec!["0x0102".to_string(), "0x0103".to_string()];
+    assert!(cache.get_keys(BLOCK_HASH, "0x01").is_none());
+    cache.insert_keys(BLOCK_HASH, "0x01", keys.clone());
+    assert_eq!(cache.get_keys(BLOCK_HASH, "0x01"), Some(&keys));
+    assert!(cache.get_keys(BLOCK_HASH, "0x02").is_none());
+    assert!(cache.get_keys(NEXT_BLOCK_HASH, "0x01").is_none());
+}
+
+#[test]
+fn test_storage_cache_reset_on_new_block() {
+    let mut cache = StorageCache::default();
+    let key = StorageKey(vec![1]);
+    cache.insert(BLOCK_HASH, key.clone(), Some(StorageData(vec![1])));
+    cache.insert_keys(BLOCK_HASH, "0x01", vec!["0x0101".to_string()]);
+    cache.insert(NEXT_BLOCK_HASH, StorageKey(vec![2]), None);
+    assert!(cache.get(BLOCK_HASH, &key).is_none());
+    assert!(cache.get(NEXT_BLOCK_HASH, &key).is_none());
+    assert!(cache.get_keys(NEXT_BLOCK_HASH, "0x01").is_none());
+    assert_eq!(
+        cache.get(NEXT_BLOCK_HASH, &StorageKey(vec![2])),
+        Some(&None)
+    );
+}
+
+#[test]
+fn test_storage_cache_clear() {
+    let mut cache = StorageCache::default();
+    let key = StorageKey(vec![1]);
+    cache.insert(BLOCK_HASH, key.clone(), None);
+    cache.insert_keys(BLOCK_HASH, "0x01", Vec::new());
+    cache.clear();
+    assert!(cache.get(BLOCK_HASH, &key).is_none());
+    assert!(cache.get_keys(BLOCK_HASH, "0x01").is_none());
+}
@@ -11,6 +11,7 @@ mod opengov;
 mod payouts;
 mod remove_all_validators;
 mod remove_validator;
+mod reward_export;
 mod rewards;
 mod settings;
 mod summary;
@@ -170,6 +171,13 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                     .await?;
                 self.process_rewards_command(chat_id, args).await?;
             }
+            "/rewardexport" | "/export" => {
+                crate::metrics::command_call_counter(command).inc();
+                self.network_postgres
+                    .save_chat_command_log(chat_id, command)
+                    .await?;
+                self.process_reward_export_command(chat_id, args).await?;
+            }
             "/settings" => {
                 crate::metrics::command_call_counter(command).inc();
                 self.network_postgres
@@ -0,0 +1,38 @@
+//! `/rewardexport` command processor.
+use crate::query::QueryType;
+use crate::{Messenger, Query, TelegramBot};
+
+impl<M: Messenger + Send + Sync> TelegramBot<M> {
+    //! Sends the user the reward payouts of a selected validator in the last year as a CSV file,
+    //! with the fiat value of each payout.
+    pub(crate) async fn process_reward_export_command(
+        &self,
+        chat_id: i64,
+        args: &[String],
+    ) -> anyhow::Result<()> {
+        if let Some(validator_address) = args.first() {
+            if let Some(chat_validator) = self
+                .network_postgres
+                .get_chat_validator_by_address(chat_id, validator_address)
+                .await?
+            {
+                self.process_query(
+                    chat_id,
+                    None,
+                    &Query {
+                        query_type: QueryType::RewardExport,
+                        parameter: Some(chat_validator.id.to_string()),
+                    },
+                )
+                .await?;
+            } else {
+                self.process_validators_command(chat_id, QueryType::RewardExport)
+                    .await?;
+            }
+        } else {
+            self.process_validators_command(chat_id, QueryType::RewardExport)
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -23,6 +23,7 @@ use subvt_persistence::postgres::app::PostgreSQLAppStorage;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
 use subvt_persistence::redis::Redis;
 use subvt_service_common::Service;
+use subvt_substrate_client::SubstrateClient;
 use subvt_types::app::{
     notification::{
         NotificationChannel, NotificationPeriodType, NotificationTypeCode, UserNotificationChannel,
@@ -31,6 +32,7 @@ use subvt_types::app::{
 };
 use subvt_types::crypto::AccountId;
 use subvt_types::telegram::TelegramChatState;
+use tokio::sync::OnceCell;
 mod command;
 pub mod messenger;
 mod metrics;
@@ -196,6 +198,9 @@ pub struct TelegramBot<M: Messenger + Send + Sync> {
     api: Bot,
     /// Telegram messenger struct.
     messenger: M,
+    /// Relay chain and Asset Hub clients, shared by the reward exports and connected on first use.
+    relay_client: OnceCell<SubstrateClient>,
+    asset_hub_client: OnceCell<SubstrateClient>,
 }
 
 impl<M: Messenger + Send + Sync> TelegramBot<M> {
@@ -213,6 +218,8 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
             redis,
             api,
             messenger: MessengerImpl::new()?,
+            relay_client: OnceCell::new(),
+            asset_hub_client: OnceCell::new(),
         })
     }
 }
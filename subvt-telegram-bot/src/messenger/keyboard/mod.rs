@@ -1,6 +1,7 @@
 //! Module that manages the creation of different types of inline keyboards.
 pub mod confirmation;
 pub mod contact_type;
+pub mod network_status;
 pub mod nft;
 pub mod nomination_details;
 pub mod nomination_summary;
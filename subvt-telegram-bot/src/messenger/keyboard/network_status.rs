@@ -0,0 +1,48 @@
+use crate::query::{Query, QueryType};
+use frankenstein::types::{InlineKeyboardButton, InlineKeyboardMarkup, ReplyMarkup};
+use subvt_types::report::NetworkStatusMetric;
+use tera::{Context, Tera};
+
+/// Metrics that can be charted from the network status message, two per row.
+const CHART_METRICS: [NetworkStatusMetric; 6] = [
+    NetworkStatusMetric::TotalStake,
+    NetworkStatusMetric::ReturnRate,
+    NetworkStatusMetric::MinStake,
+    NetworkStatusMetric::AverageStake,
+    NetworkStatusMetric::ActiveValidatorCount,
+    NetworkStatusMetric::InactiveValidatorCount,
+];
+
+pub fn get_network_status_keyboard(renderer: &Tera) -> anyhow::Result<Option<ReplyMarkup>> {
+    let mut rows = vec![];
+    for metrics in CHART_METRICS.chunks(2) {
+        let mut row = vec![];
+        for metric in metrics {
+            let query = Query {
+                query_type: QueryType::NetworkStatusChart,
+                parameter: Some(metric.as_str().to_string()),
+            };
+            let mut context = Context::new();
+            context.insert("metric_title", metric.get_title());
+            row.push(InlineKeyboardButton {
+                text: renderer.render("view_network_status_chart.html", &context)?,
+                url: None,
+                login_url: None,
+                callback_data: Some(serde_json::to_string(&query)?),
+                web_app: None,
+                switch_inline_query: None,
+                switch_inline_query_current_chat: None,
+                switch_inline_query_chosen_chat: None,
+                callback_game: None,
+                pay: None,
+                copy_text: None,
+            });
+        }
+        rows.push(row);
+    }
+    Ok(Some(ReplyMarkup::InlineKeyboardMarkup(
+        InlineKeyboardMarkup {
+            inline_keyboard: rows,
+        },
+    )))
+}
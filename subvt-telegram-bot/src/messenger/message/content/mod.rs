@@ -120,6 +120,7 @@ impl MessageType {
                 self.fill_network_status_context(&mut context, network_status);
                 "network_status.html"
             }
+            Self::NoNetworkStatusHistory => "no_network_status_history.html",
             Self::NoPayoutsFound => "no_payouts_found.html",
             Self::NoRewardsFound => "no_rewards_found.html",
             Self::NoOpenReferendaFound(track) => {
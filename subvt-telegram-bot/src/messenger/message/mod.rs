@@ -58,6 +58,7 @@ pub enum MessageType {
     AllValidatorsRemoved,
     Settings,
     NetworkStatus(NetworkStatus),
+    NoNetworkStatusHistory,
     NoPayoutsFound,
     NoRewardsFound,
     NoOpenReferendaFound(Track),
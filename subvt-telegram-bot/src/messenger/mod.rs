@@ -4,6 +4,7 @@ use crate::messenger::keyboard::settings::referenda::get_referenda_settings_keyb
 use crate::messenger::keyboard::{
     confirmation::get_confirmation_keyboard,
     contact_type::get_contact_type_keyboard,
+    network_status::get_network_status_keyboard,
     nft::get_nft_collection_keyboard,
     nomination_details::get_nomination_details_keyboard,
     nomination_summary::get_nomination_summary_keyboard,
@@ -23,8 +24,8 @@ use async_trait::async_trait;
 use frankenstein::client_reqwest::Bot;
 use frankenstein::input_file::FileUpload::InputFile;
 use frankenstein::methods::{
-    AnswerCallbackQueryParams, DeleteMessageParams, EditMessageTextParams, SendMessageParams,
-    SendPhotoParams,
+    AnswerCallbackQueryParams, DeleteMessageParams, EditMessageTextParams, SendDocumentParams,
+    SendMessageParams, SendPhotoParams,
 };
 use frankenstein::response::{MessageOrBool, MethodResponse};
 use frankenstein::types::{ChatId, LinkPreviewOptions, Message as TelegramMessage, ReplyMarkup};
@@ -68,6 +69,14 @@ pub trait Messenger {
         path: &Path,
     ) -> anyhow::Result<MethodResponse<TelegramMessage>>;
 
+    async fn send_document(
+        &self,
+        app_postgres: &PostgreSQLAppStorage,
+        network_postgres: &PostgreSQLNetworkStorage,
+        chat_id: i64,
+        path: &Path,
+    ) -> anyhow::Result<MethodResponse<TelegramMessage>>;
+
     async fn send_message(
         &self,
         app_postgres: &PostgreSQLAppStorage,
@@ -198,6 +207,36 @@ impl Messenger for MessengerImpl {
         }
     }
 
+    /// Send a file as an attachment, such as the reward export CSV.
+    async fn send_document(
+        &self,
+        app_postgres: &PostgreSQLAppStorage,
+        network_postgres: &PostgreSQLNetworkStorage,
+        chat_id: i64,
+        path: &Path,
+    ) -> anyhow::Result<MethodResponse<TelegramMessage>> {
+        let params = SendDocumentParams::builder()
+            .chat_id(ChatId::Integer(chat_id))
+            .document(InputFile(frankenstein::input_file::InputFile {
+                path: path.into(),
+            }))
+            .build();
+        match self.api.send_document(&params).await {
+            Ok(response) => Ok(response),
+            Err(error) => {
+                if let Error::Api(ref api_error) = error {
+                    if api_error.error_code == FORBIDDEN_ERROR_CODE {
+                        // chat blocked, delete app user and chat
+                        let app_user_id = network_postgres.get_chat_app_user_id(chat_id).await?;
+                        app_postgres.delete_user(app_user_id).await?;
+                        network_postgres.delete_chat(chat_id).await?;
+                    }
+                }
+                Err(TelegramBotError::Error(format!("{error:?}")).into())
+            }
+        }
+    }
+
     /// Send a message with content indicated by the `message_type` parameter.
     async fn send_message(
         &self,
@@ -238,6 +277,7 @@ impl Messenger for MessengerImpl {
                 validator_details,
                 *is_full,
             )?,
+            MessageType::NetworkStatus(_) => get_network_status_keyboard(&self.renderer)?,
             MessageType::Settings => Some(ReplyMarkup::InlineKeyboardMarkup(
                 get_settings_keyboard(&self.renderer)?,
             )),
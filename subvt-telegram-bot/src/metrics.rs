@@ -62,6 +62,7 @@ pub fn query_call_counter(query: &QueryType) -> IntCounter {
         QueryType::NominationSummary => "NominationSummary",
         QueryType::NFTs(..) => "NFTs",
         QueryType::NoOp => "NoOp",
+        QueryType::NetworkStatusChart => "NetworkStatusChart",
         QueryType::Payouts => "Rewards",
         QueryType::ReferendumDetails => "ReferendumDetails",
         QueryType::ReferendumTracks => "ReferendumTrack",
@@ -69,6 +70,7 @@ pub fn query_call_counter(query: &QueryType) -> IntCounter {
         QueryType::RemoveValidator => "RemoveValidator",
         QueryType::ReportBug => "ReportBug",
         QueryType::ReportFeatureRequest => "ReportFeatureRequest",
+        QueryType::RewardExport => "RewardExport",
         QueryType::Rewards => "Rewards",
         QueryType::SettingsEdit(_) => "SettingsEdit",
         QueryType::SettingsNavigate(_) => "SettingsNavigate",
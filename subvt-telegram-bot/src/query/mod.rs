@@ -32,6 +32,8 @@ pub enum QueryType {
     NominationSummary,
     #[serde(rename = "NOP")]
     NoOp,
+    #[serde(rename = "NSC")]
+    NetworkStatusChart,
     #[serde(rename = "PA")]
     Payouts,
     #[serde(rename = "RD")]
@@ -42,6 +44,8 @@ pub enum QueryType {
     RemoveAllValidators,
     #[serde(rename = "RV")]
     RemoveValidator,
+    #[serde(rename = "RWE")]
+    RewardExport,
     #[serde(rename = "RW")]
     Rewards,
     #[serde(rename = "RB")]
@@ -2,6 +2,7 @@ use crate::query::{Query, QueryType};
 use crate::{Messenger, TelegramBot};
 
 mod broadcast;
+mod network_status_chart;
 mod nfts;
 mod nomination_details;
 mod nomination_summary;
@@ -12,6 +13,7 @@ mod remove_all_validators;
 mod remove_validator;
 mod report_bug;
 mod report_feature_request;
+mod reward_export;
 mod rewards;
 mod settings;
 mod settings_navigate;
@@ -50,6 +52,10 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                 )
                 .await?;
             }
+            QueryType::NetworkStatusChart => {
+                self.process_network_status_chart_query(chat_id, query)
+                    .await?;
+            }
             QueryType::NominationDetails => {
                 self.process_nomination_details_query(chat_id, original_message_id, query, false)
                     .await?;
@@ -90,6 +96,10 @@ impl<M: Messenger + Send + Sync> TelegramBot<M> {
                 self.process_report_feature_request_query(chat_id, original_message_id)
                     .await?;
             }
+            QueryType::RewardExport => {
+                self.process_reward_export_query(chat_id, original_message_id, query)
+                    .await?;
+            }
             QueryType::Rewards => {
                 self.process_rewards_query(chat_id, original_message_id, query)
                     .await?;
@@ -0,0 +1,57 @@
+use crate::query::Query;
+use crate::{messenger::message::MessageType, Messenger, TelegramBot, CONFIG};
+use std::str::FromStr;
+use subvt_plotter::ChartFormat;
+use subvt_types::report::NetworkStatusMetric;
+
+/// Number of the latest eras in the network status charts.
+const NETWORK_STATUS_CHART_ERA_COUNT: u32 = 90;
+
+impl<M: Messenger + Send + Sync> TelegramBot<M> {
+    /// Sends the chart of the selected network status metric over the latest eras. The network
+    /// status message stays in place so that other charts can be selected.
+    pub(crate) async fn process_network_status_chart_query(
+        &self,
+        chat_id: i64,
+        query: &Query,
+    ) -> anyhow::Result<()> {
+        let metric = match &query.parameter {
+            Some(metric) => NetworkStatusMetric::from_str(metric)?,
+            None => return Ok(()),
+        };
+        log::info!(
+            "Network status chart {} selected in chat {chat_id}.",
+            metric.as_str()
+        );
+        let snapshots = self
+            .network_postgres
+            .get_era_network_status_snapshots(0, u32::MAX, NETWORK_STATUS_CHART_ERA_COUNT)
+            .await?;
+        if snapshots.is_empty() {
+            self.messenger
+                .send_message(
+                    &self.app_postgres,
+                    &self.network_postgres,
+                    chat_id,
+                    Box::new(MessageType::NoNetworkStatusHistory),
+                )
+                .await?;
+            return Ok(());
+        }
+        let title = format!("{} {}", CONFIG.substrate.chain_display, metric.get_title());
+        let path = subvt_plotter::network_status::plot_network_status(
+            &title,
+            metric,
+            &snapshots,
+            true,
+            ChartFormat::Png,
+        )?;
+        self.messenger
+            .send_image(&self.app_postgres, &self.network_postgres, chat_id, &path)
+            .await?;
+        if let Err(error) = std::fs::remove_file(&path) {
+            log::error!("Error while removing network status chart PNG file: {error:?}");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use crate::query::Query;
+use crate::{messenger::message::MessageType, Messenger, TelegramBot, CONFIG};
+use chrono::Utc;
+use subvt_substrate_client::SubstrateClient;
+use subvt_types::report::RewardExportReport;
+
+const REWARD_EXPORT_QUOTE_CURRENCY: &str = "USDT";
+const REWARD_EXPORT_DAYS: u64 = 365;
+
+impl<M: Messenger + Send + Sync> TelegramBot<M> {
+    pub(crate) async fn process_reward_export_query(
+        &self,
+        chat_id: i64,
+        original_message_id: Option<i32>,
+        query: &Query,
+    ) -> anyhow::Result<()> {
+        if let Some(message_id) = original_message_id {
+            self.messenger.delete_message(chat_id, message_id).await?;
+        }
+        if let Some(id_str) = &query.parameter {
+            log::info!("Validator selected for reward export in chat {chat_id}.");
+            if let Some(validator) = self
+                .network_postgres
+                .get_chat_validator_by_id(chat_id, id_str.parse()?)
+                .await?
+            {
+                let end_timestamp = Utc::now().timestamp_millis() as u64;
+                let start_timestamp = end_timestamp - REWARD_EXPORT_DAYS * 24 * 60 * 60 * 1000;
+                let mut items = self
+                    .network_postgres
+                    .get_reward_export_items(
+                        &validator.account_id,
+                        start_timestamp,
+                        end_timestamp,
+                        &CONFIG.substrate.token_ticker,
+                        REWARD_EXPORT_QUOTE_CURRENCY,
+                    )
+                    .await?;
+                if items.is_empty() {
+                    self.messenger
+                        .send_message(
+                            &self.app_postgres,
+                            &self.network_postgres,
+                            chat_id,
+                            Box::new(MessageType::NoRewardsFound),
+                        )
+                        .await?;
+                } else {
+                    let relay_client = self
+                        .relay_client
+                        .get_or_try_init(|| {
+                            SubstrateClient::new(
+                                CONFIG.substrate.rpc_url.as_str(),
+                                CONFIG.substrate.network_id,
+                                CONFIG.substrate.connection_timeout_seconds,
+                                CONFIG.substrate.request_timeout_seconds,
+                            )
+                        })
+                        .await?;
+                    let asset_hub_client = self
+                        .asset_hub_client
+                        .get_or_try_init(|| {
+                            SubstrateClient::new(
+                                CONFIG.substrate.asset_hub_rpc_url.as_str(),
+                                CONFIG.substrate.network_id,
+                                CONFIG.substrate.connection_timeout_seconds,
+                                CONFIG.substrate.request_timeout_seconds,
+                            )
+                        })
+                        .await?;
+                    SubstrateClient::set_reward_export_extrinsic_hashes(
+                        &[relay_client, asset_hub_client],
+                        &mut items,
+                    )
+                    .await;
+                    let report = RewardExportReport::new(
+                        validator.account_id,
+                        relay_client.chain.get_ss58_prefix(),
+                        &CONFIG.substrate.token_ticker,
+                        CONFIG.substrate.token_decimals,
+                        REWARD_EXPORT_QUOTE_CURRENCY,
+                        start_timestamp,
+                        end_timestamp,
+                        items,
+                    );
+                    let path = std::path::PathBuf::from(format!(
+                        "{}{}{}_rewards_{}.csv",
+                        CONFIG.plotter.tmp_dir_path,
+                        std::path::MAIN_SEPARATOR,
+                        validator.address,
+                        Utc::now().format("%Y-%m-%d"),
+                    ));
+                    std::fs::write(&path, report.to_csv())?;
+                    self.messenger
+                        .send_document(&self.app_postgres, &self.network_postgres, chat_id, &path)
+                        .await?;
+                    if let Err(error) = std::fs::remove_file(&path) {
+                        log::error!("Error while removing reward export CSV file: {error:?}");
+                    }
+                }
+            } else {
+                self.messenger
+                    .send_message(
+                        &self.app_postgres,
+                        &self.network_postgres,
+                        chat_id,
+                        Box::new(MessageType::ValidatorNotFound {
+                            maybe_address: None,
+                        }),
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
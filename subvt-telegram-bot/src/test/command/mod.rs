@@ -12,6 +12,7 @@ mod nominations;
 mod payouts;
 mod remove_all_validators;
 mod remove_validator;
+mod reward_export;
 mod rewards;
 mod settings;
 mod start;
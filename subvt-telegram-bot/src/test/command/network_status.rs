@@ -1,4 +1,5 @@
 use crate::messenger::MockMessenger;
+use crate::query::{Query, QueryType};
 use crate::test::util::data::get_telegram_message_response;
 use crate::test::util::{get_random_chat_id, new_test_bot};
 use crate::MessageType;
@@ -29,3 +30,26 @@ async fn test_get_network_status_success() {
         .await
         .unwrap();
 }
+
+/// Tests the network status chart query with an unknown metric, which should fail without
+/// sending anything to the chat.
+#[tokio::test]
+async fn test_network_status_chart_invalid_metric() {
+    let chat_id = get_random_chat_id();
+    let mut messenger = MockMessenger::new();
+    messenger.expect_send_message().never();
+    messenger.expect_send_image().never();
+    let bot = new_test_bot(messenger).await.unwrap();
+    bot.save_or_restore_chat(chat_id).await.unwrap();
+    assert!(bot
+        .process_query(
+            chat_id,
+            None,
+            &Query {
+                query_type: QueryType::NetworkStatusChart,
+                parameter: Some("invalid_metric".to_string()),
+            },
+        )
+        .await
+        .is_err());
+}
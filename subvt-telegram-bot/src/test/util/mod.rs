@@ -8,6 +8,7 @@ use subvt_persistence::postgres::app::PostgreSQLAppStorage;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
 use subvt_persistence::redis::Redis;
 use subvt_types::crypto::AccountId;
+use tokio::sync::OnceCell;
 
 pub mod data;
 
@@ -23,6 +24,8 @@ pub async fn new_test_bot(messenger: MockMessenger) -> anyhow::Result<TelegramBo
         redis,
         api,
         messenger,
+        relay_client: OnceCell::new(),
+        asset_hub_client: OnceCell::new(),
     })
 }
 
@@ -11,6 +11,7 @@ use lazy_static::lazy_static;
 use rustc_hash::FxHashMap as HashMap;
 use std::sync::mpsc::{self, Receiver, Sender};
 use subvt_config::Config;
+use subvt_metrics::health;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
 use subvt_service_common::Service;
 use subvt_types::telemetry::{FeedMessage, NodeDetails, NodeLocation};
@@ -256,6 +257,7 @@ impl Service for TelemetryProcessor {
             }
         });
         let node_map: Mutex<HashMap<u64, NodeDetails>> = Default::default();
+        health::set_ready(true);
         if let Err(error) = TelemetryProcessor::process_messages(node_map, rx).await {
             log::error!("Error while processing feed messages: {error:?}");
         }
@@ -211,6 +211,7 @@ pub type PostgresNotification = (
     String,
     Option<String>,
     Option<String>,
+    Option<String>,
 );
 
 impl Notification {
@@ -234,6 +235,7 @@ impl Notification {
             notification_target: db_notification.11.clone(),
             data_json: db_notification.12.clone(),
             error_log: db_notification.13.clone(),
+            trace_parent: db_notification.14.clone(),
             created_at: None,
             sent_at: None,
             delivered_at: None,
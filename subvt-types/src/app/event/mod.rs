@@ -1,5 +1,6 @@
 //! These types are used when reading Substrate events from PostgreSQL into the SubVT domain.
 use crate::crypto::AccountId;
+use crate::substrate::Balance;
 use serde::{Deserialize, Serialize};
 
 pub mod democracy;
@@ -21,3 +22,46 @@ pub struct ChilledEvent {
     pub event_index: u32,
     pub stash_account_id: AccountId,
 }
+
+/// `Balances` pallet event for a tracked (validator stash or controller) account.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BalancesEvent {
+    pub id: u32,
+    pub block_hash: String,
+    pub extrinsic_index: Option<u32>,
+    pub event_index: u32,
+    pub event_name: String,
+    pub account_id: AccountId,
+    /// Recipient of a `Transfer`, `None` for other events.
+    pub counterparty_account_id: Option<AccountId>,
+    pub amount: Balance,
+}
+
+/// `Proxy` pallet event for a tracked account. The account is the delegator for
+/// `ProxyAdded`/`ProxyRemoved`, the real account for `Announced` and the spawner for
+/// `PureCreated`. The delegate is the delegatee, the announcing proxy, or the pure proxy.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ProxyEvent {
+    pub id: u32,
+    pub block_hash: String,
+    pub extrinsic_index: Option<u32>,
+    pub event_index: u32,
+    pub event_name: String,
+    pub account_id: AccountId,
+    pub delegate_account_id: AccountId,
+    pub proxy_type: Option<String>,
+    pub delay: Option<u32>,
+    pub call_hash: Option<String>,
+}
+
+/// `Multisig.NewMultisig` event for a tracked multisig account.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NewMultisigEvent {
+    pub id: u32,
+    pub block_hash: String,
+    pub extrinsic_index: Option<u32>,
+    pub event_index: u32,
+    pub multisig_account_id: AccountId,
+    pub approving_account_id: AccountId,
+    pub call_hash: String,
+}
@@ -59,15 +59,26 @@ pub enum NotificationTypeCode {
     ChainValidatorBlockAuthorship,
     ChainValidatorChilled,
     ChainValidatorIdentityChanged,
+    ChainValidatorDisputeConcluded,
+    ChainValidatorDisputeInitiated,
     ChainValidatorInactive,
     ChainValidatorInactiveNextSession,
     ChainValidatorLostNomination,
+    ChainValidatorLowParaPerformance,
+    ChainValidatorMissedBlocks,
+    ChainValidatorNewMultisig,
     ChainValidatorNewNomination,
     ChainValidatorNominationAmountChange,
     ChainValidatorOfflineOffence,
     ChainValidatorPayoutStakers,
+    ChainValidatorPredictedElection,
+    ChainValidatorProxyAdded,
+    ChainValidatorProxyAnnounced,
+    ChainValidatorProxyRemoved,
+    ChainValidatorPureProxyCreated,
     ChainValidatorSessionKeysChanged,
     ChainValidatorSetController,
+    ChainValidatorStashBalanceChanged,
     ChainValidatorUnclaimedPayout,
     ChainValidatorStartedParaValidating,
     ChainValidatorStoppedParaValidating,
@@ -128,6 +139,29 @@ impl Display for NotificationTypeCode {
                 "chain_validator_block_authorship"
             }
             NotificationTypeCode::ChainValidatorSetController => "chain_validator_set_controller",
+            NotificationTypeCode::ChainValidatorStashBalanceChanged => {
+                "chain_validator_stash_balance_changed"
+            }
+            NotificationTypeCode::ChainValidatorProxyAdded => "chain_validator_proxy_added",
+            NotificationTypeCode::ChainValidatorProxyRemoved => "chain_validator_proxy_removed",
+            NotificationTypeCode::ChainValidatorPureProxyCreated => {
+                "chain_validator_pure_proxy_created"
+            }
+            NotificationTypeCode::ChainValidatorProxyAnnounced => "chain_validator_proxy_announced",
+            NotificationTypeCode::ChainValidatorNewMultisig => "chain_validator_new_multisig",
+            NotificationTypeCode::ChainValidatorDisputeInitiated => {
+                "chain_validator_dispute_initiated"
+            }
+            NotificationTypeCode::ChainValidatorDisputeConcluded => {
+                "chain_validator_dispute_concluded"
+            }
+            NotificationTypeCode::ChainValidatorLowParaPerformance => {
+                "chain_validator_low_para_performance"
+            }
+            NotificationTypeCode::ChainValidatorMissedBlocks => "chain_validator_missed_blocks",
+            NotificationTypeCode::ChainValidatorPredictedElection => {
+                "chain_validator_predicted_election"
+            }
             NotificationTypeCode::ChainValidatorSessionKeysChanged => {
                 "chain_validator_session_keys_changed"
             }
@@ -217,6 +251,29 @@ impl From<&str> for NotificationTypeCode {
                 NotificationTypeCode::ChainValidatorBlockAuthorship
             }
             "chain_validator_set_controller" => NotificationTypeCode::ChainValidatorSetController,
+            "chain_validator_stash_balance_changed" => {
+                NotificationTypeCode::ChainValidatorStashBalanceChanged
+            }
+            "chain_validator_proxy_added" => NotificationTypeCode::ChainValidatorProxyAdded,
+            "chain_validator_proxy_removed" => NotificationTypeCode::ChainValidatorProxyRemoved,
+            "chain_validator_pure_proxy_created" => {
+                NotificationTypeCode::ChainValidatorPureProxyCreated
+            }
+            "chain_validator_proxy_announced" => NotificationTypeCode::ChainValidatorProxyAnnounced,
+            "chain_validator_new_multisig" => NotificationTypeCode::ChainValidatorNewMultisig,
+            "chain_validator_dispute_initiated" => {
+                NotificationTypeCode::ChainValidatorDisputeInitiated
+            }
+            "chain_validator_dispute_concluded" => {
+                NotificationTypeCode::ChainValidatorDisputeConcluded
+            }
+            "chain_validator_low_para_performance" => {
+                NotificationTypeCode::ChainValidatorLowParaPerformance
+            }
+            "chain_validator_missed_blocks" => NotificationTypeCode::ChainValidatorMissedBlocks,
+            "chain_validator_predicted_election" => {
+                NotificationTypeCode::ChainValidatorPredictedElection
+            }
             "chain_validator_session_keys_changed" => {
                 NotificationTypeCode::ChainValidatorSessionKeysChanged
             }
@@ -514,6 +571,8 @@ pub struct Notification {
     pub notification_target: String,
     pub data_json: Option<String>,
     pub error_log: Option<String>,
+    /// W3C `traceparent` of the span that generated the notification.
+    pub trace_parent: Option<String>,
     pub created_at: Option<NaiveDateTime>,
     pub sent_at: Option<NaiveDateTime>,
     pub delivered_at: Option<NaiveDateTime>,
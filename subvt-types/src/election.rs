@@ -0,0 +1,78 @@
+//! Types for the election (next active validator set) prediction.
+use crate::crypto::AccountId;
+use crate::substrate::Balance;
+use serde::{Deserialize, Serialize};
+
+/// A staker that takes part in the election. Validators are voters too, voting for themselves
+/// with their own bonded stake.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ElectionVoter {
+    pub account_id: AccountId,
+    pub stake: Balance,
+    pub target_account_ids: Vec<AccountId>,
+}
+
+/// Election input read from the chain state at a certain block.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ElectionSnapshot {
+    pub block_hash: String,
+    /// Number of validators to be elected, `Staking.ValidatorCount` capped by the maximum
+    /// number of winners allowed by the election provider.
+    pub desired_target_count: u32,
+    pub target_account_ids: Vec<AccountId>,
+    /// In the order of the voter list, capped by the maximum number of electing voters.
+    pub voters: Vec<ElectionVoter>,
+    /// Maximum number of balancing iterations of the election miner of the chain, `None` if the
+    /// runtime doesn't expose it.
+    pub balancing_iterations: Option<u32>,
+}
+
+/// A predicted winner of the election, along with its predicted backing.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ElectionWinner {
+    pub account_id: AccountId,
+    /// 1-based rank in the descending order of backing.
+    pub rank: u32,
+    pub total_backing: Balance,
+    pub self_backing: Balance,
+    pub backer_count: u32,
+}
+
+/// Predicted result of the next election.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ElectionPrediction {
+    /// Either `phragmen` (sequential Phragmén) or `phragmms`.
+    pub algorithm: String,
+    /// Relay chain block number at the time of the prediction.
+    pub block_number: u64,
+    /// Hash of the block of the staking chain where the snapshot was read.
+    pub block_hash: String,
+    pub active_era_index: u32,
+    pub timestamp: u64,
+    pub desired_target_count: u32,
+    pub candidate_count: u32,
+    pub voter_count: u32,
+    pub total_backing: Balance,
+    pub minimum_backing: Balance,
+    pub winners: Vec<ElectionWinner>,
+}
+
+impl ElectionPrediction {
+    pub fn get_winner(&self, account_id: &AccountId) -> Option<&ElectionWinner> {
+        self.winners
+            .iter()
+            .find(|winner| winner.account_id == *account_id)
+    }
+}
+
+/// Notification data for the change of the predicted election status of a validator.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ValidatorElectionPredictionChange {
+    pub validator_account_id: AccountId,
+    pub is_predicted_to_be_elected: bool,
+    pub winner: Option<ElectionWinner>,
+    pub active_era_index: u32,
+    pub block_number: u64,
+    pub desired_target_count: u32,
+    pub minimum_backing: Balance,
+}
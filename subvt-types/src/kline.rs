@@ -1,9 +1,58 @@
 use num_traits::ToPrimitive;
 pub use sqlx::types::BigDecimal;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Candle period of a k-line.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum KLineInterval {
+    Hour,
+    Day,
+}
+
+impl KLineInterval {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Hour => "1h",
+            Self::Day => "1d",
+        }
+    }
+
+    pub fn get_duration_millis(&self) -> u64 {
+        match self {
+            Self::Hour => 60 * 60 * 1000,
+            Self::Day => 24 * 60 * 60 * 1000,
+        }
+    }
+
+    /// Open time of the candle that contains the given timestamp.
+    pub fn get_open_time(&self, timestamp: u64) -> u64 {
+        timestamp - timestamp % self.get_duration_millis()
+    }
+}
+
+impl Display for KLineInterval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for KLineInterval {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1h" => Ok(Self::Hour),
+            "1d" => Ok(Self::Day),
+            _ => Err(anyhow::anyhow!("Unknown k-line interval: {s}")),
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct KLine {
     pub id: u32,
+    pub interval: KLineInterval,
     pub open_time: u64,
     pub source_ticker: String,
     pub target_ticker: String,
@@ -17,6 +66,8 @@ pub struct KLine {
     pub count: u32,
     pub taker_buy_volume: BigDecimal,
     pub taker_buy_quote_volume: BigDecimal,
+    /// Name of the price source the k-line was fetched from, e.g. `binance`.
+    pub source: String,
 }
 
 impl KLine {
@@ -3,6 +3,7 @@
 pub mod app;
 pub mod crypto;
 pub mod dn;
+pub mod election;
 pub mod err;
 pub mod governance;
 pub mod kline;
@@ -1,6 +1,7 @@
 use crate::crypto::AccountId;
+use serde::{Deserialize, Serialize};
 
-#[derive(Default)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct SessionValidatorPerformance {
     pub id: u64,
     pub validator_account_id: AccountId,
@@ -14,4 +15,162 @@ pub struct SessionValidatorPerformance {
     pub explicit_attestation_count: Option<u32>,
     pub missed_attestation_count: Option<u32>,
     pub attestations_per_billion: Option<u32>,
+    /// Statistically expected number of blocks authored in primary and secondary slots,
+    /// `None` for sessions processed before block production tracking.
+    pub expected_block_count: Option<f64>,
+    /// Statistically expected number of primary slots won in the session.
+    pub expected_primary_slot_count: Option<f64>,
+}
+
+impl SessionValidatorPerformance {
+    /// Number of expected blocks that the validator has not authored, rounded down.
+    pub fn get_missed_block_count(&self) -> Option<u32> {
+        let expected = self.expected_block_count?;
+        Some((expected - self.authored_block_count as f64).max(0.0) as u32)
+    }
+
+    /// Ratio of the expected blocks that the validator has not authored in the session, in
+    /// `[0, 1]`. Noisy for a single session, see `BlockProduction`.
+    pub fn get_missed_block_ratio(&self) -> Option<f64> {
+        BlockProduction::from_performances(std::slice::from_ref(self))?.get_missed_block_ratio()
+    }
+
+    /// Ratio of the missed attestations to all attestation slots in the session, `None` if the
+    /// validator was not a para validator in the session or had no attestation slots.
+    pub fn get_missed_attestation_ratio(&self) -> Option<f64> {
+        let missed = self.missed_attestation_count? as f64;
+        let total = missed
+            + self.implicit_attestation_count.unwrap_or(0) as f64
+            + self.explicit_attestation_count.unwrap_or(0) as f64;
+        if total > 0.0 {
+            Some(missed / total)
+        } else {
+            None
+        }
+    }
+}
+
+/// Probability of a Poisson distributed count with the given mean being at most `count`, i.e.
+/// the lower tail of the distribution. Calculated in log space so that large means don't
+/// underflow.
+pub fn get_poisson_lower_tail_probability(count: u32, mean: f64) -> f64 {
+    if mean <= 0.0 {
+        return 1.0;
+    }
+    let ln_mean = mean.ln();
+    let mut ln_term = -mean;
+    let mut ln_terms = vec![ln_term];
+    for k in 1..=count {
+        ln_term += ln_mean - (k as f64).ln();
+        ln_terms.push(ln_term);
+    }
+    let max = ln_terms.iter().copied().fold(f64::MIN, f64::max);
+    let sum: f64 = ln_terms.iter().map(|ln_term| (ln_term - max).exp()).sum();
+    (max + sum.ln()).exp().min(1.0)
+}
+
+/// Block production of a validator aggregated over a number of sessions. Block authorship in
+/// a session is a Poisson process, so a single session with a handful of expected blocks says
+/// little about the validator, and the sessions are evaluated together.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct BlockProduction {
+    pub expected_block_count: f64,
+    pub authored_block_count: u32,
+}
+
+impl BlockProduction {
+    /// Aggregates the sessions, `None` if the expected block count of any of them is unknown.
+    pub fn from_performances(performances: &[SessionValidatorPerformance]) -> Option<Self> {
+        let mut block_production = Self::default();
+        for performance in performances {
+            block_production.expected_block_count += performance.expected_block_count?;
+            block_production.authored_block_count += performance.authored_block_count;
+        }
+        Some(block_production)
+    }
+
+    /// Ratio of the expected blocks that the validator has not authored, in `[0, 1]`.
+    pub fn get_missed_block_ratio(&self) -> Option<f64> {
+        if self.expected_block_count <= 0.0 {
+            return None;
+        }
+        Some(
+            ((self.expected_block_count - self.authored_block_count as f64)
+                / self.expected_block_count)
+                .max(0.0),
+        )
+    }
+
+    /// Probability of a validator that is producing blocks normally authoring this many blocks
+    /// or fewer. A small probability means that the missed blocks are not due to chance.
+    pub fn get_probability(&self) -> f64 {
+        get_poisson_lower_tail_probability(self.authored_block_count, self.expected_block_count)
+    }
+}
+
+/// Performance of a validator aggregated over its active sessions in an era.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct EraValidatorPerformance {
+    pub era_index: u32,
+    pub active_session_count: u32,
+    pub para_validator_session_count: u32,
+    pub authored_block_count: u32,
+    pub implicit_attestation_count: u32,
+    pub explicit_attestation_count: u32,
+    pub missed_attestation_count: u32,
+    pub attestations_per_billion: Option<u32>,
+}
+
+/// Percentiles of a performance metric across the active validators of the network.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct PerformancePercentiles {
+    pub p10: f64,
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p90: f64,
+}
+
+impl PerformancePercentiles {
+    /// Expects the 10th, 25th, 50th, 75th and 90th percentiles in order.
+    pub fn from_values(values: &[f64]) -> Option<Self> {
+        match values {
+            [p10, p25, p50, p75, p90] => Some(Self {
+                p10: *p10,
+                p25: *p25,
+                p50: *p50,
+                p75: *p75,
+                p90: *p90,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Network-wide performance percentiles for a session or an era.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct NetworkPerformancePercentiles {
+    /// Session index for session reports, era index for era reports.
+    pub index: u64,
+    pub validator_count: u32,
+    pub para_validator_count: u32,
+    pub authored_block_count: Option<PerformancePercentiles>,
+    pub attestations_per_billion: Option<PerformancePercentiles>,
+}
+
+/// Notification data for the low para validation performance notification. Contains the
+/// performances of the consecutive sessions that crossed the threshold.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct LowParaPerformance {
+    pub missed_attestation_percent_threshold: f64,
+    pub performances: Vec<SessionValidatorPerformance>,
+}
+
+/// Notification data for the missed blocks notification. Contains the performances of the
+/// consecutive sessions over which the validator authored fewer blocks than expected by at
+/// least the threshold, with a low probability of that being due to chance.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MissedBlocks {
+    pub missed_block_percent_threshold: f64,
+    pub performances: Vec<SessionValidatorPerformance>,
 }
@@ -20,4 +20,11 @@ pub struct ValidatorInfo {
 pub struct BlockProcessedNotification {
     pub block_number: u64,
     pub block_hash: String,
+    /// W3C `traceparent` of the block processing span.
+    #[serde(default)]
+    pub trace_parent: Option<String>,
+    /// Time the block was observed finalized in milliseconds since the Unix epoch, or the block
+    /// timestamp for the past blocks processed on catch-up. Not set by the older versions.
+    #[serde(default)]
+    pub finalized_at: Option<u64>,
 }
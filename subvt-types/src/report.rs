@@ -1,8 +1,11 @@
 //! Report presentation types. Utilized by the `subvt-report-service` crate to server era and
 //! validator reports.
 use crate::crypto::AccountId;
+use crate::performance::{
+    EraValidatorPerformance, NetworkPerformancePercentiles, SessionValidatorPerformance,
+};
 use crate::substrate::{Account, Balance, Epoch, Era, Stake};
-use crate::subvt::{ValidatorDetails, ValidatorSummary};
+use crate::subvt::{NetworkStatus, ValidatorDetails, ValidatorDetailsDiff, ValidatorSummary};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -134,6 +137,10 @@ pub struct SessionValidatorReport {
     pub validator_index: Option<u64>,
     pub heartbeat_event: Option<HeartbeatEvent>,
     pub blocks_authored: Option<Vec<BlockSummary>>,
+    /// Statistically expected block count, see `SessionValidatorPerformance`.
+    pub expected_block_count: Option<f64>,
+    pub expected_primary_slot_count: Option<f64>,
+    pub missed_block_count: Option<u32>,
     pub para_validator_group_index: Option<u64>,
     pub para_validator_index: Option<u64>,
     pub para_votes_summary: Option<ParaVotesSummary>,
@@ -178,6 +185,18 @@ pub struct ValidatorListReport {
     pub validators: Vec<ValidatorSummary>,
 }
 
+/// A page of the filtered and sorted validator list. Validators are serialized
+/// `ValidatorSummary` objects, with only the requested fields if a projection is given.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ValidatorListPageReport {
+    pub finalized_block: BlockSummary,
+    /// Number of validators that match the filter, before pagination.
+    pub total_count: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    pub validators: Vec<serde_json::Value>,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct EraValidatorRewardReport {
     pub era: Era,
@@ -260,3 +279,641 @@ pub struct MonthlyIncomeReport {
     pub token_symbol: String,
     pub monthly_income: Vec<MonthlyIncome>,
 }
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountActivityType {
+    Event,
+    Extrinsic,
+}
+
+/// A single item in an account's activity feed. Event items have an event index, extrinsic
+/// items have a success flag.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AccountActivity {
+    pub block_hash: String,
+    pub block_number: u64,
+    pub block_timestamp: u64,
+    pub extrinsic_index: Option<u32>,
+    pub event_index: Option<u32>,
+    pub activity_type: AccountActivityType,
+    pub module: String,
+    pub name: String,
+    pub counterparty_account_id: Option<AccountId>,
+    pub amount: Option<Balance>,
+    pub is_successful: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AccountActivityReport {
+    pub account_id: AccountId,
+    pub address: String,
+    pub activities: Vec<AccountActivity>,
+}
+
+/// A parachain candidate dispute. Initiation and conclusion data is missing when the
+/// corresponding relay chain block has not been processed.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ParaDispute {
+    pub id: u32,
+    pub candidate_hash: String,
+    pub initiated_block: Option<BlockSummary>,
+    pub is_local: Option<bool>,
+    pub concluded_block: Option<BlockSummary>,
+    pub is_valid: Option<bool>,
+    pub valid_statement_count: u32,
+    pub invalid_statement_count: u32,
+}
+
+/// Statement of a validator in a parachain candidate dispute.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ValidatorParaDisputeStatement {
+    pub dispute: ParaDispute,
+    pub validator_account_id: AccountId,
+    pub session_index: u64,
+    pub para_validator_index: u64,
+    pub block: BlockSummary,
+    pub is_valid: bool,
+    pub kind: String,
+}
+
+impl ValidatorParaDisputeStatement {
+    /// Whether the validator's statement contradicts the concluded result of the dispute,
+    /// which is a slashable offence. `None` if the dispute hasn't concluded yet.
+    pub fn is_against_supermajority(&self) -> Option<bool> {
+        self.dispute
+            .is_valid
+            .map(|dispute_is_valid| dispute_is_valid != self.is_valid)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ParaDisputeReport {
+    pub disputes: Vec<ParaDispute>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ValidatorParaDisputeReport {
+    pub account_id: AccountId,
+    pub address: String,
+    pub statements: Vec<ValidatorParaDisputeStatement>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ValidatorSessionPerformanceReport {
+    pub account_id: AccountId,
+    pub address: String,
+    pub performances: Vec<SessionValidatorPerformance>,
+    pub network: Vec<NetworkPerformancePercentiles>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ValidatorEraPerformanceReport {
+    pub account_id: AccountId,
+    pub address: String,
+    pub performances: Vec<EraValidatorPerformance>,
+    pub network: Vec<NetworkPerformancePercentiles>,
+}
+
+/// Reward of a nominator from a single validator in an era.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NominatorEraValidatorReward {
+    pub validator_account_id: AccountId,
+    /// Active stake of the nominator on the validator.
+    pub stake: Balance,
+    /// Total of the validator's exposure over all the pages, including the self stake.
+    pub exposure_total: Balance,
+    pub commission_per_billion: u32,
+    pub validator_reward_points: u32,
+    /// Total era reward of the validator, before the commission.
+    pub validator_reward: Balance,
+    pub reward: Balance,
+    /// Whether the validator's rewards for the era have been paid out.
+    pub is_paid_out: bool,
+}
+
+impl NominatorEraValidatorReward {
+    /// Calculates the reward the same way the staking pallet does: the validator gets its share
+    /// of the era payout by its reward points, the commission is cut, and the rest is shared by
+    /// the stakers in proportion to their stake in the whole exposure. Paged exposures get the
+    /// same proportional share on each page, so the page doesn't affect the result.
+    #[allow(clippy::too_many_arguments)]
+    pub fn calculate(
+        validator_account_id: AccountId,
+        stake: Balance,
+        exposure_total: Balance,
+        commission_per_billion: u32,
+        validator_reward_points: u32,
+        era_total_reward_points: u32,
+        era_total_validator_reward: Balance,
+        is_paid_out: bool,
+    ) -> Self {
+        let validator_reward = if era_total_reward_points == 0 {
+            0
+        } else {
+            era_total_validator_reward * validator_reward_points as Balance
+                / era_total_reward_points as Balance
+        };
+        let commission =
+            validator_reward * commission_per_billion.min(1_000_000_000) as Balance / 1_000_000_000;
+        let reward = if exposure_total == 0 {
+            0
+        } else {
+            (validator_reward - commission) * stake / exposure_total
+        };
+        Self {
+            validator_account_id,
+            stake,
+            exposure_total,
+            commission_per_billion,
+            validator_reward_points,
+            validator_reward,
+            reward,
+            is_paid_out,
+        }
+    }
+}
+
+/// Whether the exposure page of a nominator has been paid out. `payout_stakers_by_page` pays the
+/// given page, and `payout_stakers` pays the lowest page that hasn't been paid yet, so the paid
+/// pages are the explicitly paid ones plus the lowest `unpaged_payout_count` of the others. A
+/// non-paged exposure (`None` page) is paid out by any payout.
+pub fn is_exposure_page_paid_out(
+    maybe_page_index: Option<u32>,
+    paid_page_indices: &[u32],
+    unpaged_payout_count: u32,
+) -> bool {
+    let page_index = match maybe_page_index {
+        Some(page_index) => page_index,
+        None => return unpaged_payout_count > 0 || !paid_page_indices.is_empty(),
+    };
+    if paid_page_indices.contains(&page_index) {
+        return true;
+    }
+    let lower_paid_page_count = paid_page_indices
+        .iter()
+        .filter(|paid_page_index| **paid_page_index < page_index)
+        .count() as u32;
+    unpaged_payout_count > page_index.saturating_sub(lower_paid_page_count)
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NominatorEraReward {
+    pub era: Era,
+    pub reward: Balance,
+    /// Value of the reward in the fiat currency at the end of the era, if the price is known.
+    pub fiat_value: Option<f64>,
+    pub validator_rewards: Vec<NominatorEraValidatorReward>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NominatorEraRewardReport {
+    pub account_id: AccountId,
+    pub address: String,
+    pub quote_currency: String,
+    pub era_rewards: Vec<NominatorEraReward>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NominatorYearlyReward {
+    pub year: u32,
+    pub era_count: u32,
+    pub reward: Balance,
+    /// Sum of the fiat values of the eras with a known price.
+    pub fiat_value: f64,
+    /// Number of eras whose reward couldn't be valued due to missing price data.
+    pub unpriced_era_count: u32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NominatorYearlyRewardReport {
+    pub account_id: AccountId,
+    pub address: String,
+    pub quote_currency: String,
+    pub yearly_rewards: Vec<NominatorYearlyReward>,
+}
+
+/// A single reward payout line item for accounting.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RewardExportItem {
+    pub block_hash: String,
+    pub block_number: u64,
+    pub block_timestamp: u64,
+    /// Era of the payout, if the reward was paid out by a `payout_stakers` extrinsic.
+    pub era_index: Option<u32>,
+    pub validator_account_id: Option<AccountId>,
+    pub extrinsic_index: Option<u32>,
+    pub extrinsic_hash: Option<String>,
+    pub event_index: u32,
+    pub amount: Balance,
+    /// Close price of the token in the quote currency at the hour, or the day, of the payout.
+    pub price: Option<f64>,
+    pub fiat_value: Option<f64>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RewardExportReport {
+    pub account_id: AccountId,
+    pub address: String,
+    /// SS58 prefix of the addresses in the report.
+    pub ss58_prefix: u16,
+    pub token_symbol: String,
+    pub token_decimals: usize,
+    pub quote_currency: String,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    pub total_amount: Balance,
+    /// Sum of the fiat values of the items with a known price.
+    pub total_fiat_value: f64,
+    pub items: Vec<RewardExportItem>,
+}
+
+const REWARD_EXPORT_CSV_HEADER: &str = "block_number,block_hash,timestamp,date,era_index,validator_address,extrinsic_index,extrinsic_hash,event_index,amount,token_symbol,price,fiat_value,quote_currency";
+
+impl RewardExportReport {
+    /// Sets the fiat values of the items from their prices and calculates the totals.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        account_id: AccountId,
+        ss58_prefix: u16,
+        token_symbol: &str,
+        token_decimals: usize,
+        quote_currency: &str,
+        start_timestamp: u64,
+        end_timestamp: u64,
+        mut items: Vec<RewardExportItem>,
+    ) -> Self {
+        let denominator = f64::powi(10.0, token_decimals as i32);
+        let mut total_amount = 0;
+        let mut total_fiat_value = 0.0;
+        for item in items.iter_mut() {
+            item.fiat_value = item
+                .price
+                .map(|price| item.amount as f64 * price / denominator);
+            total_amount += item.amount;
+            total_fiat_value += item.fiat_value.unwrap_or(0.0);
+        }
+        Self {
+            account_id,
+            address: account_id.to_ss58_check_with_version(ss58_prefix),
+            ss58_prefix,
+            token_symbol: token_symbol.to_string(),
+            token_decimals,
+            quote_currency: quote_currency.to_string(),
+            start_timestamp,
+            end_timestamp,
+            total_amount,
+            total_fiat_value,
+            items,
+        }
+    }
+
+    /// Token amount with all the decimals and without thousands separators.
+    fn format_amount(&self, amount: Balance) -> String {
+        if self.token_decimals == 0 {
+            return amount.to_string();
+        }
+        let unit = 10u128.pow(self.token_decimals as u32);
+        format!(
+            "{}.{:0>width$}",
+            amount / unit,
+            amount % unit,
+            width = self.token_decimals
+        )
+    }
+
+    /// One line per item with a header line. None of the fields can contain a comma or a quote,
+    /// so no escaping is needed.
+    pub fn to_csv(&self) -> String {
+        let mut lines = Vec::with_capacity(self.items.len() + 1);
+        lines.push(REWARD_EXPORT_CSV_HEADER.to_string());
+        for item in &self.items {
+            let date = chrono::DateTime::from_timestamp_millis(item.block_timestamp as i64)
+                .map(|date_time| date_time.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_default();
+            lines.push(format!(
+                "{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                item.block_number,
+                item.block_hash,
+                item.block_timestamp,
+                date,
+                item.era_index.map(|i| i.to_string()).unwrap_or_default(),
+                item.validator_account_id
+                    .map(|account_id| account_id.to_ss58_check_with_version(self.ss58_prefix))
+                    .unwrap_or_default(),
+                item.extrinsic_index
+                    .map(|i| i.to_string())
+                    .unwrap_or_default(),
+                item.extrinsic_hash.clone().unwrap_or_default(),
+                item.event_index,
+                self.format_amount(item.amount),
+                self.token_symbol,
+                item.price.map(|p| p.to_string()).unwrap_or_default(),
+                item.fiat_value
+                    .map(|value| format!("{value:.2}"))
+                    .unwrap_or_default(),
+                self.quote_currency,
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Network status at a session, persisted by `subvt-network-status-updater` to keep the history
+/// of the network status. The values of the last snapshot of an era represent the era.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct NetworkStatusSnapshot {
+    pub era_index: u32,
+    pub epoch_index: u64,
+    pub block_number: u64,
+    pub block_hash: String,
+    pub timestamp: u64,
+    pub active_validator_count: u32,
+    pub inactive_validator_count: u32,
+    pub last_era_total_reward: Balance,
+    pub total_stake: Balance,
+    pub return_rate_per_million: u32,
+    pub min_stake: Balance,
+    pub max_stake: Balance,
+    pub average_stake: Balance,
+    pub median_stake: Balance,
+    pub era_reward_points: u32,
+}
+
+impl NetworkStatusSnapshot {
+    pub fn from_network_status(status: &NetworkStatus, timestamp: u64) -> Self {
+        Self {
+            era_index: status.active_era.index,
+            epoch_index: status.current_epoch.index,
+            block_number: status.finalized_block_number,
+            block_hash: status.finalized_block_hash.clone(),
+            timestamp,
+            active_validator_count: status.active_validator_count,
+            inactive_validator_count: status.inactive_validator_count,
+            last_era_total_reward: status.last_era_total_reward,
+            total_stake: status.total_stake,
+            return_rate_per_million: status.return_rate_per_million,
+            min_stake: status.min_stake,
+            max_stake: status.max_stake,
+            average_stake: status.average_stake,
+            median_stake: status.median_stake,
+            era_reward_points: status.era_reward_points,
+        }
+    }
+}
+
+/// A chartable value of the network status history.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NetworkStatusMetric {
+    TotalStake,
+    MinStake,
+    MaxStake,
+    AverageStake,
+    MedianStake,
+    ReturnRate,
+    ActiveValidatorCount,
+    InactiveValidatorCount,
+    EraRewardPoints,
+    LastEraTotalReward,
+}
+
+impl NetworkStatusMetric {
+    pub fn all() -> [Self; 10] {
+        [
+            Self::TotalStake,
+            Self::MinStake,
+            Self::MaxStake,
+            Self::AverageStake,
+            Self::MedianStake,
+            Self::ReturnRate,
+            Self::ActiveValidatorCount,
+            Self::InactiveValidatorCount,
+            Self::EraRewardPoints,
+            Self::LastEraTotalReward,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TotalStake => "total_stake",
+            Self::MinStake => "min_stake",
+            Self::MaxStake => "max_stake",
+            Self::AverageStake => "average_stake",
+            Self::MedianStake => "median_stake",
+            Self::ReturnRate => "return_rate",
+            Self::ActiveValidatorCount => "active_validator_count",
+            Self::InactiveValidatorCount => "inactive_validator_count",
+            Self::EraRewardPoints => "era_reward_points",
+            Self::LastEraTotalReward => "last_era_total_reward",
+        }
+    }
+
+    pub fn get_title(&self) -> &'static str {
+        match self {
+            Self::TotalStake => "Total Stake",
+            Self::MinStake => "Minimum Stake",
+            Self::MaxStake => "Maximum Stake",
+            Self::AverageStake => "Average Stake",
+            Self::MedianStake => "Median Stake",
+            Self::ReturnRate => "Return Rate",
+            Self::ActiveValidatorCount => "Active Validators",
+            Self::InactiveValidatorCount => "Inactive Validators",
+            Self::EraRewardPoints => "Era Reward Points",
+            Self::LastEraTotalReward => "Era Total Reward",
+        }
+    }
+
+    /// Whether the values of the metric are token amounts.
+    pub fn is_balance(&self) -> bool {
+        matches!(
+            self,
+            Self::TotalStake
+                | Self::MinStake
+                | Self::MaxStake
+                | Self::AverageStake
+                | Self::MedianStake
+                | Self::LastEraTotalReward
+        )
+    }
+
+    /// Value of the metric in the snapshot. Token amounts are in whole tokens, the return rate is
+    /// in percent.
+    pub fn get_value(&self, snapshot: &NetworkStatusSnapshot, token_decimals: usize) -> f64 {
+        let to_tokens = |amount: Balance| amount as f64 / 10f64.powi(token_decimals as i32);
+        match self {
+            Self::TotalStake => to_tokens(snapshot.total_stake),
+            Self::MinStake => to_tokens(snapshot.min_stake),
+            Self::MaxStake => to_tokens(snapshot.max_stake),
+            Self::AverageStake => to_tokens(snapshot.average_stake),
+            Self::MedianStake => to_tokens(snapshot.median_stake),
+            Self::ReturnRate => snapshot.return_rate_per_million as f64 / 10_000.0,
+            Self::ActiveValidatorCount => snapshot.active_validator_count as f64,
+            Self::InactiveValidatorCount => snapshot.inactive_validator_count as f64,
+            Self::EraRewardPoints => snapshot.era_reward_points as f64,
+            Self::LastEraTotalReward => to_tokens(snapshot.last_era_total_reward),
+        }
+    }
+}
+
+impl std::str::FromStr for NetworkStatusMetric {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|metric| metric.as_str() == s)
+            .ok_or_else(|| anyhow::anyhow!("Unknown network status metric: {s}"))
+    }
+}
+
+/// Details of a validator at an era, as of the first block of the era processed by
+/// `subvt-validator-list-updater`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ValidatorDetailsSnapshot {
+    pub era_index: u32,
+    pub block_number: u64,
+    pub block_hash: String,
+    pub validator_details: ValidatorDetails,
+}
+
+/// Changes in the details of a validator from one era snapshot to another. Only the changed
+/// fields are present in the diff.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ValidatorDetailsSnapshotDiff {
+    pub from_era_index: u32,
+    pub from_block_number: u64,
+    pub to_era_index: u32,
+    pub to_block_number: u64,
+    pub diff: ValidatorDetailsDiff,
+}
+
+/// A metric of the validator comparison that can take part in the composite score.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidatorScoreMetric {
+    RewardPoints,
+    ReturnRate,
+    Commission,
+    SelfStake,
+    ActiveEraRatio,
+    AttestationRate,
+    OffenceCount,
+    Identity,
+}
+
+impl ValidatorScoreMetric {
+    pub fn all() -> [Self; 8] {
+        [
+            Self::RewardPoints,
+            Self::ReturnRate,
+            Self::Commission,
+            Self::SelfStake,
+            Self::ActiveEraRatio,
+            Self::AttestationRate,
+            Self::OffenceCount,
+            Self::Identity,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RewardPoints => "reward_points",
+            Self::ReturnRate => "return_rate",
+            Self::Commission => "commission",
+            Self::SelfStake => "self_stake",
+            Self::ActiveEraRatio => "active_era_ratio",
+            Self::AttestationRate => "attestation_rate",
+            Self::OffenceCount => "offence_count",
+            Self::Identity => "identity",
+        }
+    }
+
+    /// Whether a lower value of the metric is better, e.g. commission.
+    pub fn is_lower_better(&self) -> bool {
+        matches!(self, Self::Commission | Self::OffenceCount)
+    }
+
+    /// Value of the metric for the compared validator, `None` if there is no data.
+    pub fn get_value(&self, validator: &ValidatorComparison) -> Option<f64> {
+        match self {
+            Self::RewardPoints => validator.average_reward_points,
+            Self::ReturnRate => validator.return_rate_per_billion.map(|rate| rate as f64),
+            Self::Commission => validator
+                .average_commission_per_billion
+                .map(|commission| commission as f64),
+            Self::SelfStake => validator.self_stake.map(|stake| stake as f64),
+            Self::ActiveEraRatio => Some(validator.active_era_ratio),
+            Self::AttestationRate => validator.attestations_per_billion.map(|apb| apb as f64),
+            Self::OffenceCount => {
+                Some((validator.offline_offence_count + validator.chilling_count) as f64)
+            }
+            Self::Identity => Some(if validator.identity_confirmed {
+                1.0
+            } else {
+                0.0
+            }),
+        }
+    }
+}
+
+impl std::str::FromStr for ValidatorScoreMetric {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|metric| metric.as_str() == s)
+            .ok_or_else(|| anyhow::anyhow!("Unknown validator score metric: {s}"))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ValidatorScoreWeight {
+    pub metric: ValidatorScoreMetric,
+    pub weight: f64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EraCommission {
+    pub era_index: u32,
+    pub commission_per_billion: u32,
+}
+
+/// Metrics of a validator over an era range, aligned for side-by-side comparison.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ValidatorComparison {
+    pub account_id: AccountId,
+    pub address: String,
+    pub display: Option<String>,
+    pub parent_display: Option<String>,
+    pub identity_confirmed: bool,
+    /// Number of eras in the range with a record for the validator.
+    pub era_count: u32,
+    pub active_era_count: u32,
+    pub active_era_ratio: f64,
+    pub total_reward_points: u128,
+    /// Average reward points per active era.
+    pub average_reward_points: Option<f64>,
+    /// Average annualized return of the active eras.
+    pub return_rate_per_billion: Option<u32>,
+    pub commission_history: Vec<EraCommission>,
+    pub average_commission_per_billion: Option<u32>,
+    /// Self stake at the last era of the range with a record.
+    pub self_stake: Option<Balance>,
+    pub attestations_per_billion: Option<u32>,
+    pub offline_offence_count: u32,
+    pub chilling_count: u32,
+    pub slashed_amount: Balance,
+    /// Weighted composite score in `[0, 100]`, relative to the compared set.
+    pub score: f64,
+    pub rank: u32,
+}
+
+/// Comparison of a set of validators over an era range, ordered by rank.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ValidatorComparisonReport {
+    pub start_era_index: u32,
+    pub end_era_index: u32,
+    pub weights: Vec<ValidatorScoreWeight>,
+    pub validators: Vec<ValidatorComparison>,
+}
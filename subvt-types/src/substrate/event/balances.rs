@@ -0,0 +1,157 @@
+use crate::crypto::AccountId;
+use crate::substrate::error::DecodeError;
+use crate::substrate::event::SubstrateEvent;
+use crate::substrate::Balance;
+use parity_scale_codec::Decode;
+
+const LOCKED: &str = "Locked";
+const RESERVED: &str = "Reserved";
+const SLASHED: &str = "Slashed";
+const TRANSFER: &str = "Transfer";
+const UNRESERVED: &str = "Unreserved";
+const WITHDRAW: &str = "Withdraw";
+
+#[derive(Clone, Debug)]
+pub enum BalancesEvent {
+    Locked {
+        extrinsic_index: Option<u32>,
+        account_id: AccountId,
+        amount: Balance,
+    },
+    Reserved {
+        extrinsic_index: Option<u32>,
+        account_id: AccountId,
+        amount: Balance,
+    },
+    Slashed {
+        extrinsic_index: Option<u32>,
+        account_id: AccountId,
+        amount: Balance,
+    },
+    Transfer {
+        extrinsic_index: Option<u32>,
+        from_account_id: AccountId,
+        to_account_id: AccountId,
+        amount: Balance,
+    },
+    Unreserved {
+        extrinsic_index: Option<u32>,
+        account_id: AccountId,
+        amount: Balance,
+    },
+    Withdraw {
+        extrinsic_index: Option<u32>,
+        account_id: AccountId,
+        amount: Balance,
+    },
+}
+
+impl BalancesEvent {
+    pub fn get_extrinsic_index(&self) -> Option<u32> {
+        match self {
+            Self::Locked {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::Reserved {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::Slashed {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::Transfer {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::Unreserved {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::Withdraw {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+        }
+    }
+
+    /// Event name as it appears in the pallet metadata.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Locked { .. } => LOCKED,
+            Self::Reserved { .. } => RESERVED,
+            Self::Slashed { .. } => SLASHED,
+            Self::Transfer { .. } => TRANSFER,
+            Self::Unreserved { .. } => UNRESERVED,
+            Self::Withdraw { .. } => WITHDRAW,
+        }
+    }
+
+    /// Returns the account whose balance has changed, the counterparty account for transfers
+    /// (sender is the account, recipient is the counterparty) and the amount.
+    pub fn get_account_ids_and_amount(&self) -> (AccountId, Option<AccountId>, Balance) {
+        match self {
+            Self::Transfer {
+                from_account_id,
+                to_account_id,
+                amount,
+                ..
+            } => (*from_account_id, Some(*to_account_id), *amount),
+            Self::Locked {
+                account_id, amount, ..
+            }
+            | Self::Reserved {
+                account_id, amount, ..
+            }
+            | Self::Slashed {
+                account_id, amount, ..
+            }
+            | Self::Unreserved {
+                account_id, amount, ..
+            }
+            | Self::Withdraw {
+                account_id, amount, ..
+            } => (*account_id, None, *amount),
+        }
+    }
+}
+
+impl BalancesEvent {
+    pub fn decode(
+        _runtime_version: u32,
+        name: &str,
+        extrinsic_index: Option<u32>,
+        bytes: &mut &[u8],
+    ) -> Result<Option<SubstrateEvent>, DecodeError> {
+        let maybe_event = match name {
+            LOCKED => Some(SubstrateEvent::Balances(BalancesEvent::Locked {
+                extrinsic_index,
+                account_id: Decode::decode(bytes)?,
+                amount: Decode::decode(bytes)?,
+            })),
+            RESERVED => Some(SubstrateEvent::Balances(BalancesEvent::Reserved {
+                extrinsic_index,
+                account_id: Decode::decode(bytes)?,
+                amount: Decode::decode(bytes)?,
+            })),
+            SLASHED => Some(SubstrateEvent::Balances(BalancesEvent::Slashed {
+                extrinsic_index,
+                account_id: Decode::decode(bytes)?,
+                amount: Decode::decode(bytes)?,
+            })),
+            TRANSFER => Some(SubstrateEvent::Balances(BalancesEvent::Transfer {
+                extrinsic_index,
+                from_account_id: Decode::decode(bytes)?,
+                to_account_id: Decode::decode(bytes)?,
+                amount: Decode::decode(bytes)?,
+            })),
+            UNRESERVED => Some(SubstrateEvent::Balances(BalancesEvent::Unreserved {
+                extrinsic_index,
+                account_id: Decode::decode(bytes)?,
+                amount: Decode::decode(bytes)?,
+            })),
+            WITHDRAW => Some(SubstrateEvent::Balances(BalancesEvent::Withdraw {
+                extrinsic_index,
+                account_id: Decode::decode(bytes)?,
+                amount: Decode::decode(bytes)?,
+            })),
+            _ => None,
+        };
+        Ok(maybe_event)
+    }
+}
@@ -8,9 +8,11 @@ use crate::substrate::{
 use frame_metadata::RuntimeMetadataV14;
 use parity_scale_codec::{Compact, Decode};
 
+pub mod balances;
 pub mod democracy;
 pub mod identity;
 pub mod multisig;
+pub mod paras_disputes;
 pub mod proxy;
 pub mod referenda;
 pub mod staking;
@@ -19,9 +21,11 @@ pub mod utility;
 
 #[derive(Clone, Debug)]
 pub enum SubstrateEvent {
+    Balances(balances::BalancesEvent),
     Democracy(democracy::DemocracyEvent),
     Identity(identity::IdentityEvent),
     Multisig(multisig::MultisigEvent),
+    ParasDisputes(paras_disputes::ParasDisputesEvent),
     Proxy(proxy::ProxyEvent),
     Referenda(referenda::ReferendaEvent),
     Staking(staking::StakingEvent),
@@ -37,9 +41,11 @@ pub enum SubstrateEvent {
 impl SubstrateEvent {
     pub fn get_extrinsic_index(&self) -> Option<u32> {
         match self {
+            Self::Balances(event) => event.get_extrinsic_index(),
             Self::Democracy(event) => event.get_extrinsic_index(),
             Self::Identity(event) => event.get_extrinsic_index(),
             Self::Multisig(event) => event.get_extrinsic_index(),
+            Self::ParasDisputes(event) => event.get_extrinsic_index(),
             Self::Proxy(event) => event.get_extrinsic_index(),
             Self::Referenda(event) => event.get_extrinsic_index(),
             Self::Staking(event) => event.get_extrinsic_index(),
@@ -70,19 +76,32 @@ impl SubstrateEvent {
             .pallets
             .iter()
             .find(|p| p.index == module_index)
-            .unwrap();
+            .ok_or_else(|| DecodeError::Error(format!("Pallet #{module_index} not found.")))?;
+        let event_type_id = pallet
+            .event
+            .as_ref()
+            .ok_or_else(|| DecodeError::Error(format!("Pallet {} has no events.", pallet.name)))?
+            .ty
+            .id;
         let event_type = metadata
             .types
             .types
             .iter()
-            .find(|ty| ty.id == pallet.event.clone().unwrap().ty.id)
-            .unwrap();
+            .find(|ty| ty.id == event_type_id)
+            .ok_or_else(|| {
+                DecodeError::Error(format!("Event type of pallet {} not found.", pallet.name))
+            })?;
         let event_variant = match &event_type.ty.type_def {
             scale_info::TypeDef::Variant(variant) => variant
                 .variants
                 .iter()
                 .find(|variant| variant.index == event_index)
-                .unwrap(),
+                .ok_or_else(|| {
+                    DecodeError::Error(format!(
+                        "Event #{event_index} of pallet {} not found.",
+                        pallet.name
+                    ))
+                })?,
             _ => {
                 return Err(DecodeError::Error(format!(
                     "Unexpected non-variant event type: {:?}",
@@ -94,7 +113,12 @@ impl SubstrateEvent {
         // decode parameters
         for event_field in &event_variant.fields {
             let event_field_type = get_metadata_type(metadata, event_field.ty.id);
-            decode_field(metadata, event_field_type, bytes, false).unwrap();
+            decode_field(metadata, event_field_type, bytes, false).map_err(|error| {
+                DecodeError::Error(format!(
+                    "Cannot decode field of event {}.{}: {error:?}",
+                    pallet.name, event_variant.name
+                ))
+            })?;
         }
         // post bytes :: get bytes => decode by runtime
         let event_bytes_len = pre_event_bytes.len() - bytes.len();
@@ -103,6 +127,12 @@ impl SubstrateEvent {
         let _topics = Vec::<sp_core::H256>::decode(bytes)?;
         // decode events
         let maybe_event = match pallet.name.as_str() {
+            "Balances" => balances::BalancesEvent::decode(
+                runtime_version,
+                &event_variant.name,
+                extrinsic_index,
+                event_bytes,
+            )?,
             "Democracy" => democracy::DemocracyEvent::decode(
                 runtime_version,
                 &event_variant.name,
@@ -121,6 +151,12 @@ impl SubstrateEvent {
                 extrinsic_index,
                 event_bytes,
             )?,
+            "ParasDisputes" => paras_disputes::ParasDisputesEvent::decode(
+                runtime_version,
+                &event_variant.name,
+                extrinsic_index,
+                event_bytes,
+            )?,
             "Proxy" => proxy::ProxyEvent::decode(
                 runtime_version,
                 &event_variant.name,
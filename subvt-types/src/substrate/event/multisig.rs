@@ -8,6 +8,7 @@ use pallet_multisig::Timepoint;
 use parity_scale_codec::Decode;
 
 const MULTISIG_EXECUTED: &str = "MultisigExecuted";
+const NEW_MULTISIG: &str = "NewMultisig";
 
 #[derive(Clone, Debug)]
 pub enum MultisigEvent {
@@ -19,6 +20,12 @@ pub enum MultisigEvent {
         call_hash: CallHash,
         result: DispatchResult,
     },
+    NewMultisig {
+        extrinsic_index: Option<u32>,
+        approving_account_id: AccountId,
+        multisig_account_id: AccountId,
+        call_hash: CallHash,
+    },
 }
 
 impl MultisigEvent {
@@ -27,6 +34,9 @@ impl MultisigEvent {
             Self::MultisigExecuted {
                 extrinsic_index, ..
             } => *extrinsic_index,
+            Self::NewMultisig {
+                extrinsic_index, ..
+            } => *extrinsic_index,
         }
     }
 }
@@ -47,6 +57,12 @@ impl MultisigEvent {
                 call_hash: Decode::decode(bytes)?,
                 result: decode_dispatch_result(runtime_version, bytes)?,
             })),
+            NEW_MULTISIG => Some(SubstrateEvent::Multisig(MultisigEvent::NewMultisig {
+                extrinsic_index,
+                approving_account_id: Decode::decode(bytes)?,
+                multisig_account_id: Decode::decode(bytes)?,
+                call_hash: Decode::decode(bytes)?,
+            })),
             _ => None,
         };
         Ok(maybe_event)
@@ -0,0 +1,82 @@
+use crate::substrate::{error::DecodeError, event::SubstrateEvent, BlockNumber};
+use parity_scale_codec::Decode;
+use polkadot_primitives::CandidateHash;
+use polkadot_runtime_parachains::disputes::{DisputeLocation, DisputeResult};
+
+const DISPUTE_INITIATED: &str = "DisputeInitiated";
+const DISPUTE_CONCLUDED: &str = "DisputeConcluded";
+const REVERT: &str = "Revert";
+
+#[derive(Clone, Debug)]
+pub enum ParasDisputesEvent {
+    DisputeInitiated {
+        extrinsic_index: Option<u32>,
+        candidate_hash: CandidateHash,
+        is_local: bool,
+    },
+    DisputeConcluded {
+        extrinsic_index: Option<u32>,
+        candidate_hash: CandidateHash,
+        is_valid: bool,
+    },
+    Revert {
+        extrinsic_index: Option<u32>,
+        block_number: BlockNumber,
+    },
+}
+
+impl ParasDisputesEvent {
+    pub fn get_extrinsic_index(&self) -> Option<u32> {
+        match self {
+            Self::DisputeInitiated {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::DisputeConcluded {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::Revert {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+        }
+    }
+}
+
+impl ParasDisputesEvent {
+    pub fn decode(
+        _runtime_version: u32,
+        name: &str,
+        extrinsic_index: Option<u32>,
+        bytes: &mut &[u8],
+    ) -> Result<Option<SubstrateEvent>, DecodeError> {
+        let maybe_event = match name {
+            DISPUTE_INITIATED => {
+                let candidate_hash: CandidateHash = Decode::decode(bytes)?;
+                let location: DisputeLocation = Decode::decode(bytes)?;
+                Some(SubstrateEvent::ParasDisputes(
+                    ParasDisputesEvent::DisputeInitiated {
+                        extrinsic_index,
+                        candidate_hash,
+                        is_local: matches!(location, DisputeLocation::Local),
+                    },
+                ))
+            }
+            DISPUTE_CONCLUDED => {
+                let candidate_hash: CandidateHash = Decode::decode(bytes)?;
+                let result: DisputeResult = Decode::decode(bytes)?;
+                Some(SubstrateEvent::ParasDisputes(
+                    ParasDisputesEvent::DisputeConcluded {
+                        extrinsic_index,
+                        candidate_hash,
+                        is_valid: matches!(result, DisputeResult::Valid),
+                    },
+                ))
+            }
+            REVERT => Some(SubstrateEvent::ParasDisputes(ParasDisputesEvent::Revert {
+                extrinsic_index,
+                block_number: Decode::decode(bytes)?,
+            })),
+            _ => None,
+        };
+        Ok(maybe_event)
+    }
+}
@@ -1,28 +1,85 @@
+use crate::crypto::AccountId;
 use crate::substrate::error::DecodeError;
 use crate::substrate::event::SubstrateEvent;
 use crate::substrate::metadata::decode_dispatch_result;
+use crate::substrate::{BlockNumber, CallHash, ProxyType};
 use frame_support::dispatch::DispatchResult;
+use parity_scale_codec::Decode;
 
+const ANNOUNCED: &str = "Announced";
+const ANONYMOUS_CREATED: &str = "AnonymousCreated";
+const PROXY_ADDED: &str = "ProxyAdded";
 const PROXY_EXECUTED: &str = "ProxyExecuted";
+const PROXY_REMOVED: &str = "ProxyRemoved";
+const PURE_CREATED: &str = "PureCreated";
 
 #[derive(Clone, Debug)]
 pub enum ProxyEvent {
+    Announced {
+        extrinsic_index: Option<u32>,
+        real_account_id: AccountId,
+        proxy_account_id: AccountId,
+        call_hash: CallHash,
+    },
+    ProxyAdded {
+        extrinsic_index: Option<u32>,
+        delegator_account_id: AccountId,
+        delegatee_account_id: AccountId,
+        proxy_type: String,
+        delay: BlockNumber,
+    },
     ProxyExecuted {
         extrinsic_index: Option<u32>,
         result: DispatchResult,
     },
+    ProxyRemoved {
+        extrinsic_index: Option<u32>,
+        delegator_account_id: AccountId,
+        delegatee_account_id: AccountId,
+        proxy_type: String,
+        delay: BlockNumber,
+    },
+    PureCreated {
+        extrinsic_index: Option<u32>,
+        pure_account_id: AccountId,
+        spawner_account_id: AccountId,
+        proxy_type: String,
+        disambiguation_index: u16,
+    },
 }
 
 impl ProxyEvent {
     pub fn get_extrinsic_index(&self) -> Option<u32> {
         match self {
+            Self::Announced {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::ProxyAdded {
+                extrinsic_index, ..
+            } => *extrinsic_index,
             Self::ProxyExecuted {
                 extrinsic_index, ..
             } => *extrinsic_index,
+            Self::ProxyRemoved {
+                extrinsic_index, ..
+            } => *extrinsic_index,
+            Self::PureCreated {
+                extrinsic_index, ..
+            } => *extrinsic_index,
         }
     }
 }
 
+/// Proxy types differ between runtimes, so an unknown proxy type is not a decode error.
+fn decode_proxy_type(bytes: &mut &[u8]) -> Result<String, DecodeError> {
+    let index: u8 = Decode::decode(bytes)?;
+    let proxy_type = match ProxyType::decode(&mut &[index][..]) {
+        Ok(proxy_type) => format!("{proxy_type:?}"),
+        Err(_) => format!("Unknown({index})"),
+    };
+    Ok(proxy_type)
+}
+
 impl ProxyEvent {
     pub fn decode(
         runtime_version: u32,
@@ -31,10 +88,39 @@ impl ProxyEvent {
         bytes: &mut &[u8],
     ) -> Result<Option<SubstrateEvent>, DecodeError> {
         let maybe_event = match name {
+            ANNOUNCED => Some(SubstrateEvent::Proxy(ProxyEvent::Announced {
+                extrinsic_index,
+                real_account_id: Decode::decode(bytes)?,
+                proxy_account_id: Decode::decode(bytes)?,
+                call_hash: Decode::decode(bytes)?,
+            })),
+            PROXY_ADDED => Some(SubstrateEvent::Proxy(ProxyEvent::ProxyAdded {
+                extrinsic_index,
+                delegator_account_id: Decode::decode(bytes)?,
+                delegatee_account_id: Decode::decode(bytes)?,
+                proxy_type: decode_proxy_type(bytes)?,
+                delay: Decode::decode(bytes)?,
+            })),
             PROXY_EXECUTED => Some(SubstrateEvent::Proxy(ProxyEvent::ProxyExecuted {
                 extrinsic_index,
                 result: decode_dispatch_result(runtime_version, bytes)?,
             })),
+            PROXY_REMOVED => Some(SubstrateEvent::Proxy(ProxyEvent::ProxyRemoved {
+                extrinsic_index,
+                delegator_account_id: Decode::decode(bytes)?,
+                delegatee_account_id: Decode::decode(bytes)?,
+                proxy_type: decode_proxy_type(bytes)?,
+                delay: Decode::decode(bytes)?,
+            })),
+            PURE_CREATED | ANONYMOUS_CREATED => {
+                Some(SubstrateEvent::Proxy(ProxyEvent::PureCreated {
+                    extrinsic_index,
+                    pure_account_id: Decode::decode(bytes)?,
+                    spawner_account_id: Decode::decode(bytes)?,
+                    proxy_type: decode_proxy_type(bytes)?,
+                    disambiguation_index: Decode::decode(bytes)?,
+                }))
+            }
             _ => None,
         };
         Ok(maybe_event)
@@ -57,6 +57,31 @@ pub struct TransactionPayment {
 }
 
 impl SubstrateExtrinsic {
+    /// Whether this is a `Staking` call, or a `Proxy` call that wraps a `Staking` call.
+    pub fn is_staking_call(&self) -> bool {
+        match self {
+            Self::Staking(_) => true,
+            Self::Proxy(proxy_extrinsic) => {
+                matches!(proxy_extrinsic.get_call(), Self::Staking(_))
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether this call, or a call nested in it through `Proxy` or `Utility` batch calls, is a
+    /// multisig call with a staking call, see `is_staking_call`.
+    pub fn has_staking_multisig_call(&self) -> bool {
+        match self {
+            Self::Multisig(multisig_extrinsic) => multisig_extrinsic.get_call().is_staking_call(),
+            Self::Proxy(proxy_extrinsic) => proxy_extrinsic.get_call().has_staking_multisig_call(),
+            Self::Utility(utility_extrinsic) => utility_extrinsic
+                .get_calls()
+                .iter()
+                .any(|call| call.has_staking_multisig_call()),
+            _ => false,
+        }
+    }
+
     pub fn decode_extrinsic(
         chain: &Chain,
         runtime_version: u32,
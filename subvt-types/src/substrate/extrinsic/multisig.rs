@@ -28,6 +28,13 @@ pub enum MultisigExtrinsic {
 }
 
 impl MultisigExtrinsic {
+    /// The call to be executed by the multisig account.
+    pub fn get_call(&self) -> &SubstrateExtrinsic {
+        match self {
+            Self::AsMulti { call, .. } | Self::AsMultiThreshold1 { call, .. } => call,
+        }
+    }
+
     pub fn decode(
         chain: &Chain,
         runtime_version: u32,
@@ -25,6 +25,13 @@ pub enum ProxyExtrinsic {
 }
 
 impl ProxyExtrinsic {
+    /// The call to be executed on behalf of the real account.
+    pub fn get_call(&self) -> &SubstrateExtrinsic {
+        match self {
+            Self::Proxy { call, .. } | Self::ProxyAnnounced { call, .. } => call,
+        }
+    }
+
     pub fn decode(
         chain: &Chain,
         runtime_version: u32,
@@ -46,6 +46,14 @@ pub enum UtilityExtrinsic {
 }
 
 impl UtilityExtrinsic {
+    pub fn get_calls(&self) -> &[SubstrateExtrinsic] {
+        match self {
+            Self::Batch { calls, .. }
+            | Self::BatchAll { calls, .. }
+            | Self::ForceBatch { calls, .. } => calls,
+        }
+    }
+
     pub fn decode(
         chain: &Chain,
         runtime_version: u32,
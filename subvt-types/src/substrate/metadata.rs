@@ -354,6 +354,94 @@ pub fn get_metadata_constant<V: Decode>(
     Ok(Decode::decode(&mut &bytes[..])?)
 }
 
+/// Same as `get_metadata_constant`, but returns `None` if the pallet or the constant does not
+/// exist in the metadata.
+pub fn find_metadata_constant<V: Decode>(
+    metadata: &RuntimeMetadataV14,
+    module_name: &str,
+    constant_name: &str,
+) -> anyhow::Result<Option<V>> {
+    let maybe_constant = metadata
+        .pallets
+        .iter()
+        .find(|p| p.name == module_name)
+        .and_then(|p| p.constants.iter().find(|c| c.name == constant_name));
+    match maybe_constant {
+        Some(constant) => Ok(Some(Decode::decode(&mut &constant.value[..])?)),
+        None => Ok(None),
+    }
+}
+
+/// Find the `Option<BalancingConfig>` constant of the election miner in the given pallet. The
+/// constant is matched by its type, since its name is chosen by the runtime. Returns the
+/// constant name and the number of balancing iterations, `0` when balancing is disabled.
+pub fn find_metadata_balancing_iterations(
+    metadata: &RuntimeMetadataV14,
+    module_name: &str,
+) -> anyhow::Result<Option<(String, u32)>> {
+    let Some(pallet) = metadata.pallets.iter().find(|p| p.name == module_name) else {
+        return Ok(None);
+    };
+    for constant in &pallet.constants {
+        let constant_type = &metadata.types.types[constant.ty.id as usize].ty;
+        if constant_type.path.segments != ["Option"] {
+            continue;
+        }
+        let Some(config_type_id) = constant_type
+            .type_params
+            .first()
+            .and_then(|type_param| type_param.ty)
+        else {
+            continue;
+        };
+        let config_type = &metadata.types.types[config_type_id.id as usize].ty;
+        if config_type.path.segments.last().map(String::as_str) != Some("BalancingConfig") {
+            continue;
+        }
+        let mut bytes = &constant.value[..];
+        if u8::decode(&mut bytes)? == 0 {
+            return Ok(Some((constant.name.clone(), 0)));
+        }
+        // the iterations are the first field of the balancing config
+        let scale_info::TypeDef::Composite(composite_type_def) = &config_type.type_def else {
+            return Err(DecodeError::Error(format!(
+                "Unexpected balancing config type in {module_name}.{}.",
+                constant.name
+            ))
+            .into());
+        };
+        let Some(iterations_field) = composite_type_def
+            .fields
+            .first()
+            .filter(|field| field.name.as_deref() == Some("iterations"))
+        else {
+            return Err(DecodeError::Error(format!(
+                "Cannot find iterations in {module_name}.{}.",
+                constant.name
+            ))
+            .into());
+        };
+        let iterations = match &metadata.types.types[iterations_field.ty.id as usize]
+            .ty
+            .type_def
+        {
+            scale_info::TypeDef::Primitive(TypeDefPrimitive::U32) => u32::decode(&mut bytes)?,
+            scale_info::TypeDef::Primitive(TypeDefPrimitive::U64) => {
+                u32::try_from(u64::decode(&mut bytes)?)?
+            }
+            _ => {
+                return Err(DecodeError::Error(format!(
+                    "Unexpected iterations type in {module_name}.{}.",
+                    constant.name
+                ))
+                .into())
+            }
+        };
+        return Ok(Some((constant.name.clone(), iterations)));
+    }
+    Ok(None)
+}
+
 pub fn get_metadata_expected_block_time_millis(
     metadata: &RuntimeMetadataV14,
 ) -> anyhow::Result<u64> {
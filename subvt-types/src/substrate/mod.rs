@@ -105,6 +105,11 @@ impl Chain {
         }
     }
 
+    /// SS58 address prefix of the chain.
+    pub fn get_ss58_prefix(&self) -> u16 {
+        self.get_ss58_address_format().prefix()
+    }
+
     pub fn sp_core_set_default_ss58_version(&self) {
         sp_core::crypto::set_default_ss58_version(self.get_ss58_address_format())
     }
@@ -434,6 +439,9 @@ impl Epoch {
 pub struct NominatorStake {
     pub account: Account,
     pub stake: Balance,
+    /// Index of the exposure page of the nominator, `None` for a non-paged exposure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_index: Option<u32>,
 }
 
 /// Active staking information for a single active validator. Contains the validator account id,
@@ -460,7 +468,11 @@ impl ValidatorStake {
                 address: other.who.to_ss58_check(),
                 ..Default::default()
             };
-            nominators.push(NominatorStake { account, stake });
+            nominators.push(NominatorStake {
+                account,
+                stake,
+                page_index: None,
+            });
         }
         let validator_stake = Self {
             account: Account {
@@ -479,6 +491,7 @@ impl ValidatorStake {
         mut bytes: &[u8],
         validator_account_id: AccountId,
         self_stake: Balance,
+        page_index: u32,
     ) -> anyhow::Result<Self> {
         let stakers: ExposurePage<AccountId, Balance> = Decode::decode(&mut bytes)?;
         let mut nominators: Vec<NominatorStake> = Vec::new();
@@ -489,7 +502,11 @@ impl ValidatorStake {
                 address: other.who.to_ss58_check(),
                 ..Default::default()
             };
-            nominators.push(NominatorStake { account, stake });
+            nominators.push(NominatorStake {
+                account,
+                stake,
+                page_index: Some(page_index),
+            });
         }
         let validator_stake = Self {
             account: Account {
@@ -780,6 +797,23 @@ impl From<&Stake> for StakeSummary {
     }
 }
 
+/// A bag of the voter list (`pallet-bags-list`), i.e. the head and the tail of the linked list of
+/// the voters whose scores fall into the bag. The upper threshold of the bag is the storage key.
+#[derive(Clone, Debug, Decode)]
+pub struct VoterListBag {
+    pub head: Option<AccountId>,
+    pub tail: Option<AccountId>,
+}
+
+/// A node of the voter list, i.e. a voter in the linked list of its bag.
+#[derive(Clone, Debug, Decode)]
+pub struct VoterListNode {
+    pub id: AccountId,
+    pub prev: Option<AccountId>,
+    pub next: Option<AccountId>,
+    pub bag_upper: u64,
+}
+
 #[derive(Clone, Decode, Debug, Deserialize, Encode, Eq, Hash, PartialEq, Serialize, Default)]
 #[serde(tag = "destination_type", content = "destination")]
 pub enum RewardDestination {
@@ -1,6 +1,8 @@
 use crate::substrate::legacy::LegacyCoreOccupied;
 use crate::substrate::CoreAssignment;
-use polkadot_primitives::ScrapedOnChainVotes;
+use polkadot_primitives::{
+    DisputeStatement, InvalidDisputeStatementKind, ScrapedOnChainVotes, ValidDisputeStatementKind,
+};
 use polkadot_runtime_parachains::scheduler::common::Assignment;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -112,3 +114,52 @@ impl ParaCoreAssignment {
         Ok(result)
     }
 }
+
+/// A validator's statement in a dispute, as included in the `ParaInherent` of a relay chain block.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ParaDisputeStatement {
+    pub session_index: u32,
+    pub candidate_hash: String,
+    pub para_validator_index: u32,
+    pub is_valid: bool,
+    pub kind: String,
+}
+
+impl ParaDisputeStatement {
+    pub fn from_on_chain_votes(votes: &ScrapedOnChainVotes) -> Vec<ParaDisputeStatement> {
+        let mut statements = Vec::new();
+        for statement_set in &votes.disputes {
+            let candidate_hash = format!("0x{}", hex::encode(statement_set.candidate_hash.0));
+            for (statement, validator_index, _) in &statement_set.statements {
+                let (is_valid, kind) = match statement {
+                    DisputeStatement::Valid(kind) => (
+                        true,
+                        match kind {
+                            ValidDisputeStatementKind::Explicit => "explicit",
+                            ValidDisputeStatementKind::BackingSeconded(_) => "backing_seconded",
+                            ValidDisputeStatementKind::BackingValid(_) => "backing_valid",
+                            ValidDisputeStatementKind::ApprovalChecking => "approval_checking",
+                            ValidDisputeStatementKind::ApprovalCheckingMultipleCandidates(_) => {
+                                "approval_checking_multiple_candidates"
+                            }
+                        },
+                    ),
+                    DisputeStatement::Invalid(kind) => (
+                        false,
+                        match kind {
+                            InvalidDisputeStatementKind::Explicit => "explicit",
+                        },
+                    ),
+                };
+                statements.push(ParaDisputeStatement {
+                    session_index: statement_set.session,
+                    candidate_hash: candidate_hash.clone(),
+                    para_validator_index: validator_index.0,
+                    is_valid,
+                    kind: kind.to_string(),
+                });
+            }
+        }
+        statements
+    }
+}
@@ -112,6 +112,8 @@ pub struct ValidatorSummary {
     pub oversubscribed: bool,
     pub slash_count: u64,
     pub is_enrolled_in_1kv: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onekv_is_valid: Option<bool>,
     pub is_para_validator: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub para_id: Option<u32>,
@@ -169,6 +171,7 @@ impl From<&ValidatorDetails> for ValidatorSummary {
             oversubscribed: validator.oversubscribed,
             slash_count: validator.slash_count,
             is_enrolled_in_1kv: validator.onekv_candidate_record_id.is_some(),
+            onekv_is_valid: validator.onekv_is_valid,
             blocks_authored: validator.blocks_authored,
             reward_points: validator.reward_points,
             heartbeat_received: validator.heartbeat_received,
@@ -0,0 +1,51 @@
+use subvt_types::performance::{
+    get_poisson_lower_tail_probability, BlockProduction, SessionValidatorPerformance,
+};
+
+fn performance(
+    authored_block_count: u32,
+    expected_block_count: Option<f64>,
+) -> SessionValidatorPerformance {
+    SessionValidatorPerformance {
+        authored_block_count,
+        expected_block_count,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_poisson_lower_tail_probability() {
+    assert!((get_poisson_lower_tail_probability(0, 2.0) - (-2.0f64).exp()).abs() < 1e-12);
+    assert!((get_poisson_lower_tail_probability(1, 2.0) - 3.0 * (-2.0f64).exp()).abs() < 1e-12);
+    assert_eq!(get_poisson_lower_tail_probability(0, 0.0), 1.0);
+    // doesn't underflow for large means
+    let probability = get_poisson_lower_tail_probability(900, 1000.0);
+    assert!(probability > 0.0005 && probability < 0.001);
+    assert!(get_poisson_lower_tail_probability(10_000, 1000.0) <= 1.0);
+}
+
+#[test]
+fn test_block_production() {
+    // missing all the blocks of a single session with 3 expected is likely by chance
+    let block_production =
+        BlockProduction::from_performances(&[performance(0, Some(3.0))]).unwrap();
+    assert_eq!(block_production.get_missed_block_ratio(), Some(1.0));
+    assert!(block_production.get_probability() > 0.01);
+    // missing half of the blocks over 6 sessions is not
+    let performances: Vec<SessionValidatorPerformance> =
+        (0..6).map(|_| performance(4, Some(8.0))).collect();
+    let block_production = BlockProduction::from_performances(&performances).unwrap();
+    assert_eq!(block_production.authored_block_count, 24);
+    assert_eq!(block_production.expected_block_count, 48.0);
+    assert_eq!(block_production.get_missed_block_ratio(), Some(0.5));
+    assert!(block_production.get_probability() < 0.001);
+    // authoring more than expected
+    let block_production =
+        BlockProduction::from_performances(&[performance(10, Some(8.0))]).unwrap();
+    assert_eq!(block_production.get_missed_block_ratio(), Some(0.0));
+    // unknown expected block count
+    assert!(
+        BlockProduction::from_performances(&[performance(4, Some(8.0)), performance(4, None)])
+            .is_none()
+    );
+}
@@ -0,0 +1,135 @@
+use subvt_types::crypto::AccountId;
+use subvt_types::report::{
+    is_exposure_page_paid_out, NominatorEraValidatorReward, RewardExportItem, RewardExportReport,
+};
+
+#[test]
+fn test_nominator_era_validator_reward() {
+    // 1/4 of the era payout, 10% commission, 1/3 of the exposure
+    let reward = NominatorEraValidatorReward::calculate(
+        AccountId::from([1; 32]),
+        100,
+        300,
+        100_000_000,
+        25,
+        100,
+        4_000,
+        true,
+    );
+    assert_eq!(reward.validator_reward, 1_000);
+    assert_eq!(reward.reward, 300);
+    assert!(reward.is_paid_out);
+}
+
+#[test]
+fn test_nominator_era_validator_reward_edge_cases() {
+    let calculate = |exposure_total, commission_per_billion, era_total_reward_points| {
+        NominatorEraValidatorReward::calculate(
+            AccountId::from([1; 32]),
+            100,
+            exposure_total,
+            commission_per_billion,
+            25,
+            era_total_reward_points,
+            4_000,
+            false,
+        )
+    };
+    // no reward points in the era
+    let reward = calculate(300, 0, 0);
+    assert_eq!(reward.validator_reward, 0);
+    assert_eq!(reward.reward, 0);
+    // empty exposure
+    assert_eq!(calculate(0, 0, 100).reward, 0);
+    // full commission, and commission over 100% is capped
+    assert_eq!(calculate(300, 1_000_000_000, 100).reward, 0);
+    assert_eq!(calculate(300, 2_000_000_000, 100).reward, 0);
+    // no commission, sole staker
+    assert_eq!(calculate(100, 0, 100).reward, 1_000);
+}
+
+#[test]
+fn test_exposure_page_paid_out() {
+    // non-paged exposure
+    assert!(!is_exposure_page_paid_out(None, &[], 0));
+    assert!(is_exposure_page_paid_out(None, &[], 1));
+    assert!(is_exposure_page_paid_out(None, &[0], 0));
+    // paid by page
+    assert!(is_exposure_page_paid_out(Some(1), &[1], 0));
+    assert!(!is_exposure_page_paid_out(Some(0), &[1], 0));
+    // payout_stakers pays the lowest unpaid pages
+    assert!(is_exposure_page_paid_out(Some(0), &[], 1));
+    assert!(!is_exposure_page_paid_out(Some(1), &[], 1));
+    assert!(is_exposure_page_paid_out(Some(1), &[], 2));
+    // page 0 paid by page, so a single payout_stakers pays page 1 but not 2
+    assert!(is_exposure_page_paid_out(Some(1), &[0], 1));
+    assert!(!is_exposure_page_paid_out(Some(2), &[0], 1));
+    // page 1 paid by page, so a single payout_stakers pays page 0 but not 2
+    assert!(is_exposure_page_paid_out(Some(0), &[1], 1));
+    assert!(!is_exposure_page_paid_out(Some(2), &[1], 1));
+    assert!(is_exposure_page_paid_out(Some(2), &[1], 2));
+}
+
+#[test]
+fn test_reward_export_csv() {
+    let validator_account_id = AccountId::from([2; 32]);
+    let items = vec![
+        RewardExportItem {
+            block_hash: "0x01".to_string(),
+            block_number: 100,
+            block_timestamp: 86_400_000,
+            era_index: Some(10),
+            validator_account_id: Some(validator_account_id),
+            extrinsic_index: Some(2),
+            extrinsic_hash: Some("0xAB".to_string()),
+            event_index: 5,
+            amount: 15_000_000_000,
+            price: Some(4.0),
+            fiat_value: None,
+        },
+        RewardExportItem {
+            block_hash: "0x02".to_string(),
+            block_number: 200,
+            block_timestamp: 90_061_000,
+            era_index: None,
+            validator_account_id: None,
+            extrinsic_index: None,
+            extrinsic_hash: None,
+            event_index: 0,
+            amount: 25,
+            price: None,
+            fiat_value: None,
+        },
+    ];
+    let report = RewardExportReport::new(
+        AccountId::from([1; 32]),
+        0,
+        "DOT",
+        10,
+        "USDT",
+        0,
+        100_000_000,
+        items,
+    );
+    assert_eq!(report.total_amount, 15_000_000_025);
+    assert_eq!(report.items[0].fiat_value, Some(6.0));
+    assert_eq!(report.total_fiat_value, 6.0);
+    let csv = report.to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(
+        lines[0],
+        "block_number,block_hash,timestamp,date,era_index,validator_address,extrinsic_index,extrinsic_hash,event_index,amount,token_symbol,price,fiat_value,quote_currency"
+    );
+    assert_eq!(
+        lines[1],
+        format!(
+            "100,0x01,86400000,1970-01-02 00:00:00,10,{},2,0xAB,5,1.5000000000,DOT,4,6.00,USDT",
+            validator_account_id.to_ss58_check_with_version(0)
+        )
+    );
+    assert_eq!(
+        lines[2],
+        "200,0x02,90061000,1970-01-02 01:01:01,,,,,0,0.0000000025,DOT,,,USDT"
+    );
+}
@@ -1,34 +1,54 @@
 //! Validator details WebSocket server. Operates on the configured port.
 //!
 //! Supports two RPC methods: `subscribe_validatorDetails` and `unsubscribe_validatorDetails`.
-//! `subscribe_validatorDetails` accepts a single parameter: 0x-prefixed hex-encoded account id
-//! of the validator. Gives the complete details at first connection, then publishes only the
-//! changed fields after each update from `subvt-validator-list-updater`.
+//! `subscribe_validatorDetails` accepts the 0x-prefixed hex-encoded account id of the validator,
+//! and optionally the last finalized block number of the details that the client already has.
+//! Gives the complete details at first connection, or only the changed fields since the given
+//! block if it's still in the history kept by `subvt-validator-list-updater`, then publishes only
+//! the changed fields after each update from `subvt-validator-list-updater`.
+//!
+//! The same updates are served as Server-Sent Events at `GET /sse?account_id=0x...` on the same
+//! port, with the finalized block number as the event id, see `subvt_service_common::sse`. They
+//! are also served as binary WebSocket frames at `/binary?account_id=0x...`, where the `encoding`
+//! (`json`, `msgpack` or `cbor`) and `compression` (`none`, `deflate` or `gzip`) query parameters
+//! select a compact format, see `subvt_service_common::binary_ws`. The JSON-RPC subscription
+//! messages are JSON, and the subscriptions that ask for a binary format are rejected in favor of
+//! `/binary`.
+//!
+//! The server serves the validators of all the networks in `common.networks`. The network of a
+//! connection is selected by the path, e.g. `ws://host:7891/polkadot`, and the first network is
+//! served at the root path, see `subvt_service_common::network`.
 #![warn(clippy::disallowed_types)]
 use anyhow::Context;
 use async_trait::async_trait;
-use bus::Bus;
+use futures_util::stream::BoxStream;
 use futures_util::StreamExt as _;
 use jsonrpsee::server::{RpcModule, ServerBuilder, ServerHandle};
-use jsonrpsee_core::server::SubscriptionMessage;
+use jsonrpsee_core::server::{SubscriptionMessage, SubscriptionSink};
 use lazy_static::lazy_static;
-use redis::RedisResult;
+use redis::aio::ConnectionManager;
 use serde::Serialize;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Arc, Mutex, RwLock};
+use std::sync::Arc;
+use std::time::Duration;
 use subvt_config::Config;
+use subvt_metrics::health;
+use subvt_service_common::binary_ws::BinaryWsLayer;
+use subvt_service_common::encoding::MessageFormat;
+use subvt_service_common::network::{keep_running, start_network, NetworkLayer, Networks};
+use subvt_service_common::sse::{SseEvent, SseHandler, SseLayer, SseRequest};
 use subvt_service_common::Service;
 use subvt_types::crypto::AccountId;
 use subvt_types::subvt::{ValidatorDetails, ValidatorDetailsDiff};
+use tokio::sync::broadcast;
 
 mod metrics;
 
 lazy_static! {
     static ref CONFIG: Config = Config::default();
-    static ref LAST_FINALIZED_BLOCK_NUMBER: AtomicU64 = AtomicU64::new(0);
 }
 
 #[derive(Clone, Debug)]
@@ -46,64 +66,310 @@ struct ValidatorDetailsUpdate {
     validator_details_update: Option<ValidatorDetailsDiff>,
 }
 
+/// Redis connection, last finalized block and update bus of a network.
+#[derive(Clone)]
+struct NetworkContext {
+    chain: String,
+    redis_connection: ConnectionManager,
+    finalized_block_number: Arc<AtomicU64>,
+    bus: broadcast::Sender<BusEvent>,
+}
+
+impl NetworkContext {
+    /// Connects to the Redis of the network.
+    async fn new(config: &Config) -> anyhow::Result<Self> {
+        let redis_client = redis::Client::open(config.redis.url.as_str()).context(format!(
+            "Cannot connect to Redis at URL {}.",
+            config.redis.url
+        ))?;
+        let (bus, _) = broadcast::channel(CONFIG.rpc.bus_capacity);
+        Ok(Self {
+            chain: config.substrate.chain.clone(),
+            redis_connection: ConnectionManager::new(redis_client).await?,
+            finalized_block_number: Arc::new(AtomicU64::new(0)),
+            bus,
+        })
+    }
+}
+
 #[derive(Default)]
 pub struct ValidatorDetailsServer;
 
+/// Keeps the metrics of a subscription, and updates them when the subscription ends, however it
+/// ends.
+struct SubscriptionMetrics {
+    chain: String,
+    sent_byte_count: u64,
+}
+
+impl SubscriptionMetrics {
+    fn new(chain: &str) -> Self {
+        metrics::subscription_count()
+            .with_label_values(&[chain])
+            .inc();
+        Self {
+            chain: chain.to_string(),
+            sent_byte_count: 0,
+        }
+    }
+
+    /// Encodes the update as JSON and records the size of the message.
+    fn get_subscription_message(
+        &mut self,
+        update: &ValidatorDetailsUpdate,
+    ) -> anyhow::Result<SubscriptionMessage> {
+        let json = serde_json::value::to_raw_value(update)?;
+        let size = json.get().len() as u64;
+        self.sent_byte_count += size;
+        metrics::sent_byte_count()
+            .with_label_values(&[&MessageFormat::default().label()])
+            .inc_by(size);
+        Ok(SubscriptionMessage::from_json(&json)?)
+    }
+}
+
+impl Drop for SubscriptionMetrics {
+    fn drop(&mut self) {
+        metrics::subscription_sent_bytes()
+            .with_label_values(&[&MessageFormat::default().label()])
+            .observe(self.sent_byte_count as f64);
+        metrics::subscription_count()
+            .with_label_values(&[&self.chain])
+            .dec();
+    }
+}
+
+/// Sends the message, waiting for room in the subscriber's queue for at most the configured
+/// timeout. A subscriber that cannot keep up is disconnected.
+async fn send(sink: &SubscriptionSink, message: SubscriptionMessage) -> Result<(), String> {
+    let timeout = Duration::from_millis(CONFIG.rpc.subscriber_send_timeout_millis);
+    match sink.send_timeout(message, timeout).await {
+        Ok(()) => Ok(()),
+        Err(_) if sink.is_closed() => Err("Subscription connection closed.".to_string()),
+        Err(_) => {
+            metrics::slow_subscriber_disconnect_count().inc();
+            Err("Subscriber cannot keep up with the updates.".to_string())
+        }
+    }
+}
+
+fn get_validator_storage_key_prefix(
+    chain: &str,
+    account_id: &AccountId,
+    finalized_block_number: u64,
+    is_active: bool,
+) -> String {
+    format!(
+        "subvt:{}:validators:{}:{}:validator:{}",
+        chain,
+        finalized_block_number,
+        if is_active { "active" } else { "inactive" },
+        account_id,
+    )
+}
+
 impl ValidatorDetailsServer {
-    fn fetch_validator_details(
-        account_id: &str,
-        redis_client: &redis::Client,
+    async fn fetch_validator_details(
+        connection: &mut ConnectionManager,
+        chain: &str,
+        account_id: &AccountId,
+        finalized_block_number: u64,
     ) -> anyhow::Result<ValidatorDetails> {
-        let mut connection = redis_client.get_connection()?;
-        let active_validator_key = format!(
-            "subvt:{}:validators:{}:active:validator:{}",
-            CONFIG.substrate.chain,
-            LAST_FINALIZED_BLOCK_NUMBER.load(Ordering::SeqCst),
+        let mut maybe_validator_json_string: Option<String> = redis::cmd("GET")
+            .arg(get_validator_storage_key_prefix(
+                chain,
+                account_id,
+                finalized_block_number,
+                true,
+            ))
+            .query_async(connection)
+            .await?;
+        if maybe_validator_json_string.is_none() {
+            maybe_validator_json_string = redis::cmd("GET")
+                .arg(get_validator_storage_key_prefix(
+                    chain,
+                    account_id,
+                    finalized_block_number,
+                    false,
+                ))
+                .query_async(connection)
+                .await?;
+        }
+        match maybe_validator_json_string {
+            Some(validator_json_string) => Ok(serde_json::from_str(&validator_json_string)?),
+            None => Err(anyhow::anyhow!(
+                "Validator {account_id} not found at block #{finalized_block_number}."
+            )),
+        }
+    }
+
+    /// Compares the hash of the subscriber's copy of the details with the hash at the given
+    /// block, and fetches the details only if they're different.
+    async fn get_update(
+        connection: &mut ConnectionManager,
+        chain: &str,
+        account_id: &AccountId,
+        finalized_block_number: u64,
+        validator_details: &mut ValidatorDetails,
+    ) -> anyhow::Result<ValidatorDetailsUpdate> {
+        let hash = {
+            let mut hasher = DefaultHasher::new();
+            validator_details.hash(&mut hasher);
+            hasher.finish()
+        };
+        let mut maybe_db_hash = None;
+        for is_active in [true, false] {
+            let storage_key_prefix = get_validator_storage_key_prefix(
+                chain,
+                account_id,
+                finalized_block_number,
+                is_active,
+            );
+            let db_hash: Option<u64> = redis::cmd("GET")
+                .arg(format!("{storage_key_prefix}:hash"))
+                .query_async(connection)
+                .await?;
+            if let Some(db_hash) = db_hash {
+                maybe_db_hash = Some((storage_key_prefix, db_hash));
+                break;
+            }
+        }
+        let Some((storage_key_prefix, db_hash)) = maybe_db_hash else {
+            return Err(anyhow::anyhow!("Validator {account_id} not found."));
+        };
+        let mut update = ValidatorDetailsUpdate {
+            finalized_block_number: Some(finalized_block_number),
+            ..Default::default()
+        };
+        if hash != db_hash {
+            let validator_json_string: String = redis::cmd("GET")
+                .arg(&storage_key_prefix)
+                .query_async(connection)
+                .await
+                .context(format!(
+                    "Error while fetching validator JSON string for storage key {storage_key_prefix}."
+                ))?;
+            let db_validator_details: ValidatorDetails =
+                serde_json::from_str(&validator_json_string).context(format!(
+                "Error while deserializing validator details for storage key {storage_key_prefix}."
+            ))?;
+            update.validator_details_update =
+                Some(validator_details.get_diff(&db_validator_details));
+            *validator_details = db_validator_details;
+        }
+        Ok(update)
+    }
+
+    /// The details of the validator at the current finalized block, along with the first update
+    /// of a subscription: only the changed fields since the client's last finalized block if the
+    /// details at that block are still in the history, the complete details otherwise.
+    async fn get_first_update(
+        network: &NetworkContext,
+        connection: &mut ConnectionManager,
+        account_id: &AccountId,
+        maybe_base_block_number: Option<u64>,
+    ) -> anyhow::Result<(ValidatorDetails, ValidatorDetailsUpdate)> {
+        let finalized_block_number = network.finalized_block_number.load(Ordering::SeqCst);
+        let validator_details = ValidatorDetailsServer::fetch_validator_details(
+            connection,
+            &network.chain,
             account_id,
-        );
-        let active_validator_json_string_result: RedisResult<String> = redis::cmd("GET")
-            .arg(active_validator_key)
-            .query(&mut connection);
-        let validator_json_string = match active_validator_json_string_result {
-            Ok(validator_json_string) => validator_json_string,
-            Err(_) => {
-                let inactive_validator_key = format!(
-                    "subvt:{}:validators:{}:inactive:validator:{}",
-                    CONFIG.substrate.chain,
-                    LAST_FINALIZED_BLOCK_NUMBER.load(Ordering::SeqCst),
+            finalized_block_number,
+        )
+        .await?;
+        // the details at the client's last block are only available within the
+        // history depth of the updater, fall back to the complete details otherwise
+        let maybe_base_validator_details = match maybe_base_block_number {
+            Some(base_block_number) if base_block_number <= finalized_block_number => {
+                ValidatorDetailsServer::fetch_validator_details(
+                    connection,
+                    &network.chain,
                     account_id,
+                    base_block_number,
+                )
+                .await
+                .ok()
+            }
+            _ => None,
+        };
+        let update = match maybe_base_validator_details {
+            Some(base_validator_details) => {
+                log::info!(
+                    "Resume {} subscription {account_id} from finalized block #{}.",
+                    network.chain,
+                    maybe_base_block_number.unwrap_or_default(),
                 );
-                redis::cmd("GET")
-                    .arg(inactive_validator_key)
-                    .query(&mut connection)?
+                ValidatorDetailsUpdate {
+                    finalized_block_number: Some(finalized_block_number),
+                    validator_details: None,
+                    validator_details_update: Some(
+                        base_validator_details.get_diff(&validator_details),
+                    ),
+                }
             }
+            None => ValidatorDetailsUpdate {
+                finalized_block_number: Some(finalized_block_number),
+                validator_details: Some(validator_details.clone()),
+                validator_details_update: None,
+            },
         };
-        Ok(serde_json::from_str(&validator_json_string)?)
+        Ok((validator_details, update))
     }
 
-    pub async fn run_rpc_server(
+    /// Each subscription runs as a task that receives the finalized block numbers from the
+    /// broadcast channel, and reads the validator's details through a shared Redis connection.
+    /// A subscriber that falls more than `rpc.bus_capacity` blocks behind, or that cannot
+    /// receive a message within `rpc.subscriber_send_timeout_millis`, is disconnected.
+    async fn run_rpc_server(
         host: &str,
         port: u16,
-        redis_client: &redis::Client,
-        bus: Arc<Mutex<Bus<BusEvent>>>,
+        networks: &Networks<NetworkContext>,
     ) -> anyhow::Result<ServerHandle> {
+        let sse_handler = ValidatorDetailsSseHandler {
+            networks: networks.clone(),
+        };
         let rpc_ws_server = ServerBuilder::default()
             .max_request_body_size(u32::MAX)
+            .max_connections(CONFIG.rpc.max_connections)
+            .set_message_buffer_capacity(CONFIG.rpc.subscriber_queue_size)
+            .set_http_middleware(
+                tower::ServiceBuilder::new()
+                    .layer(NetworkLayer::new(CONFIG.get_network_chains()))
+                    .layer(SseLayer::new(
+                        "/sse",
+                        sse_handler.clone(),
+                        Duration::from_secs(CONFIG.rpc.sse_keep_alive_seconds),
+                        metrics::sse_connection_count(),
+                    ))
+                    .layer(BinaryWsLayer::new(
+                        "/binary",
+                        sse_handler,
+                        Duration::from_secs(CONFIG.rpc.sse_keep_alive_seconds),
+                        metrics::binary_ws_connection_count(),
+                    )),
+            )
             .build(format!("{host}:{port}"))
             .await?;
         let mut rpc_module = RpcModule::new(());
-        let redis_client = redis_client.clone();
-        let data_connection = Arc::new(RwLock::new(redis_client.get_connection()?));
+        let networks = networks.clone();
         rpc_module.register_subscription(
             "subscribe_validatorDetails",
             "subscribe_validatorDetails",
             "unsubscribe_validatorDetails",
-            move |params, pending, _, _| {
-                let redis_client = redis_client.clone();
-                let data_connection = data_connection.clone();
-                let bus = bus.clone();
+            move |params, pending, _, extensions| {
+                let maybe_network = networks.get_for_request(&extensions);
                 async move {
-                    let account_id = match params.one::<String>() {
+                    let network = match maybe_network {
+                        Some(network) => network,
+                        None => {
+                            pending.reject(jsonrpsee::types::error::ErrorCode::InvalidParams).await;
+                            return Ok(());
+                        }
+                    };
+                    let mut redis_connection = network.redis_connection.clone();
+                    let mut bus_receiver = network.bus.subscribe();
+                    let mut params = params.sequence();
+                    let account_id = match params.next::<String>() {
                         Ok(param) => {
                             if let Ok(account_id) = AccountId::from_str(&param) {
                                 account_id
@@ -117,141 +383,271 @@ impl ValidatorDetailsServer {
                             return Ok(());
                         }
                     };
-                    let mut sink = pending.accept().await?;
-                    log::info!("New subscription {account_id}.");
-                    metrics::subscription_count().inc();
-                    let mut validator_details = {
-                        let validator_details = match ValidatorDetailsServer::fetch_validator_details(
-                            &account_id.to_string(),
-                            &redis_client,
-                        ) {
-                            Ok(validator_details) => validator_details,
-                            Err(error) => {
-                                log::error!("Error while fetching validator details: {error:?}");
-                                let error_message = "Error while fetching validator details. Please make sure you are sending a valid validator account id.".to_string();
-                                let subscription_message = SubscriptionMessage::from_json(&error_message).unwrap();
-                                sink.try_send(subscription_message)?;
+                    let maybe_base_block_number = match params.optional_next::<u64>() {
+                        Ok(maybe_base_block_number) => maybe_base_block_number,
+                        Err(_) => {
+                            pending.reject(jsonrpsee::types::error::ErrorCode::InvalidParams).await;
+                            return Ok(());
+                        }
+                    };
+                    match params.optional_next::<MessageFormat>() {
+                        Ok(Some(format)) if format.is_binary() => {
+                            pending
+                                .reject(jsonrpsee::types::ErrorObjectOwned::owned(
+                                    jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                                    "Binary message formats are served at /binary.",
+                                    None::<()>,
+                                ))
+                                .await;
+                            return Ok(());
+                        }
+                        Ok(_) => (),
+                        Err(_) => {
+                            pending.reject(jsonrpsee::types::error::ErrorCode::InvalidParams).await;
+                            return Ok(());
+                        }
+                    }
+                    let sink = pending.accept().await?;
+                    log::info!("New {} subscription {account_id}.", network.chain);
+                    let mut subscription_metrics = SubscriptionMetrics::new(&network.chain);
+                    let (mut validator_details, update) = match ValidatorDetailsServer::get_first_update(
+                        &network,
+                        &mut redis_connection,
+                        &account_id,
+                        maybe_base_block_number,
+                    )
+                    .await
+                    {
+                        Ok(first_update) => first_update,
+                        Err(error) => {
+                            log::error!("Error while fetching validator details: {error:?}");
+                            let error_message = "Error while fetching validator details. Please make sure you are sending a valid validator account id.".to_string();
+                            let subscription_message = SubscriptionMessage::from_json(&error_message).unwrap();
+                            send(&sink, subscription_message).await?;
+                            return Ok(());
+                        }
+                    };
+                    let subscription_message = subscription_metrics.get_subscription_message(&update)?;
+                    send(&sink, subscription_message).await?;
+                    // the bus is subscribed to before the first update, skip the blocks it includes
+                    let first_block_number = update.finalized_block_number.unwrap_or_default();
+                    loop {
+                        let event = tokio::select! {
+                            _ = sink.closed() => {
+                                log::info!("Subscription connection closed.");
                                 return Ok(());
                             }
+                            event = bus_receiver.recv() => event,
                         };
-                        let update = ValidatorDetailsUpdate {
-                            finalized_block_number: Some(LAST_FINALIZED_BLOCK_NUMBER.load(Ordering::SeqCst)),
-                            validator_details: Some(validator_details.clone()),
-                            validator_details_update: None
-                        };
-                        let subscription_message = SubscriptionMessage::from_json(&update).unwrap();
-                        sink.try_send(subscription_message)?;
-                        validator_details
-                    };
-                    let mut bus_receiver = bus.lock().unwrap().add_rx();
-                    let data_connection = data_connection.clone();
-                    std::thread::spawn(move || {
-                        loop {
-                            if let Ok(update) = bus_receiver.recv() {
-                                if sink.is_closed() {
-                                    log::info!("Subscription connection closed.");
-                                    metrics::subscription_count().dec();
-                                    return;
-                                }
-                                match update {
-                                    BusEvent::NewFinalizedBlock(finalized_block_number) => {
-                                        let active_validator_storage_key_prefix =  format!(
-                                            "subvt:{}:validators:{}:active:validator:{}",
-                                            CONFIG.substrate.chain,
-                                            finalized_block_number,
-                                            account_id,
-                                        );
-                                        let inactive_validator_storage_key_prefix =  format!(
-                                            "subvt:{}:validators:{}:inactive:validator:{}",
-                                            CONFIG.substrate.chain,
-                                            finalized_block_number,
-                                            account_id,
-                                        );
-                                        let hash = {
-                                            let mut hasher = DefaultHasher::new();
-                                            validator_details.hash(&mut hasher);
-                                            hasher.finish()
-                                        };
-                                        let mut data_connection = data_connection.write().unwrap();
-                                        let (validator_storage_key_prefix, db_hash) = if let Ok(db_hash) = redis::cmd("GET")
-                                            .arg(format!(
-                                                "{active_validator_storage_key_prefix}:hash",
-                                            ))
-                                            .query::<u64>(&mut *data_connection) {
-                                            (active_validator_storage_key_prefix, db_hash)
-                                        } else if let Ok(db_hash) = redis::cmd("GET")
-                                            .arg(format!(
-                                                "{inactive_validator_storage_key_prefix}:hash",
-                                            ))
-                                            .query::<u64>(&mut *data_connection) {
-                                            (inactive_validator_storage_key_prefix, db_hash)
-                                        } else {
-                                            log::error!("Validator {account_id} not found.");
-                                            return;
-                                        };
-                                        let update = if hash != db_hash {
-                                            let validator_json_string_result = redis::cmd("GET")
-                                                .arg(&validator_storage_key_prefix)
-                                                .query::<String>(&mut *data_connection);
-                                            let validator_json_string = match validator_json_string_result {
-                                                Ok(validator_json_string) => validator_json_string,
-                                                Err(error) => {
-                                                    log::error!("Error while fetching validator JSON string for storage key {validator_storage_key_prefix}: {error:?}");
-                                                    return;
-                                                }
-                                            };
-                                            let db_validator_details_result =
-                                                serde_json::from_str::<ValidatorDetails>(&validator_json_string);
-                                            let db_validator_details = match db_validator_details_result {
-                                                Ok(db_validator_details) => db_validator_details,
-                                                Err(error) => {
-                                                    log::error!(
-                                                        "Error while deserializing validator details for storage key {validator_storage_key_prefix}: {error:?}",
-                                                    );
-                                                    return;
-                                                }
-                                            };
-                                            let update = ValidatorDetailsUpdate {
-                                                finalized_block_number: Some(finalized_block_number),
-                                                validator_details: None,
-                                                validator_details_update: Some(validator_details.get_diff(&db_validator_details)),
-                                            };
-                                            validator_details = db_validator_details;
-                                            update
-                                        } else {
-                                            ValidatorDetailsUpdate {
-                                                finalized_block_number: Some(finalized_block_number),
-                                                validator_details: None,
-                                                validator_details_update: None,
-                                            }
-                                        };
-                                        let subscription_message = SubscriptionMessage::from_json(&update).unwrap();
-                                        let send_result = sink.try_send(subscription_message);
-                                        match send_result {
-                                            Err(error) => {
-                                                log::warn!("Error during publish: {error:?}");
-                                                metrics::subscription_count().dec();
-                                                return;
-                                            }
-                                            Ok(()) => {
-                                                log::debug!("Diff published.");
-                                            }
-                                        }
+                        match event {
+                            Ok(BusEvent::NewFinalizedBlock(finalized_block_number))
+                                if finalized_block_number <= first_block_number => {}
+                            Ok(BusEvent::NewFinalizedBlock(finalized_block_number)) => {
+                                let update = match ValidatorDetailsServer::get_update(
+                                    &mut redis_connection,
+                                    &network.chain,
+                                    &account_id,
+                                    finalized_block_number,
+                                    &mut validator_details,
+                                )
+                                .await
+                                {
+                                    Ok(update) => update,
+                                    Err(error) => {
+                                        log::error!("{error:?}");
+                                        return Err(error.to_string().into());
                                     }
-                                    BusEvent::Error => {
-                                        log::error!("Bus update receive error.");
-                                        return;
+                                };
+                                let subscription_message = match subscription_metrics.get_subscription_message(&update) {
+                                    Ok(subscription_message) => subscription_message,
+                                    Err(error) => {
+                                        log::error!("Error while encoding update: {error:?}");
+                                        continue;
                                     }
+                                };
+                                if let Err(error) = send(&sink, subscription_message).await {
+                                    log::warn!("Error during publish: {error}");
+                                    return Err(error.into());
                                 }
+                                log::debug!("Diff published.");
                             }
+                            Ok(BusEvent::Error) => {
+                                log::error!("Bus update receive error.");
+                                return Err("Server error.".into());
+                            }
+                            Err(broadcast::error::RecvError::Lagged(skipped_block_count)) => {
+                                log::warn!("Disconnect slow subscriber {account_id}, {skipped_block_count} blocks behind.");
+                                metrics::slow_subscriber_disconnect_count().inc();
+                                return Err("Subscriber cannot keep up with the updates.".into());
+                            }
+                            Err(broadcast::error::RecvError::Closed) => return Ok(()),
                         }
-                    });
-                    Ok(())
+                    }
                 }
             },
         )?;
         Ok(rpc_ws_server.start(rpc_module))
     }
+    /// Receives the finalized block numbers of the network from Redis and publishes them to the
+    /// network's subscribers. Returns only on error.
+    async fn run_updater(config: &Config, network: &NetworkContext) -> anyhow::Error {
+        match ValidatorDetailsServer::publish_finalized_blocks(config, network).await {
+            Ok(()) => anyhow::anyhow!("{} finalized block updates ended.", network.chain),
+            Err(error) => error,
+        }
+    }
+
+    async fn publish_finalized_blocks(
+        config: &Config,
+        network: &NetworkContext,
+    ) -> anyhow::Result<()> {
+        let redis_client = redis::Client::open(config.redis.url.as_str()).context(format!(
+            "Cannot connect to Redis at URL {}.",
+            config.redis.url
+        ))?;
+        let mut pubsub_connection = redis_client.get_async_pubsub().await?;
+        pubsub_connection
+            .subscribe(format!(
+                "subvt:{}:validators:publish:finalized_block_number",
+                network.chain
+            ))
+            .await?;
+        let mut pubsub_stream = pubsub_connection.on_message();
+        while let Some(message) = pubsub_stream.next().await {
+            let finalized_block_number: u64 = message.get_payload()?;
+            if network.finalized_block_number.load(Ordering::SeqCst) == finalized_block_number {
+                log::warn!(
+                    "Skip duplicate {} finalized block #{finalized_block_number}.",
+                    network.chain
+                );
+                continue;
+            }
+            log::info!(
+                "New {} finalized block #{finalized_block_number}.",
+                network.chain
+            );
+            metrics::current_finalized_block_number()
+                .with_label_values(&[&network.chain])
+                .set(finalized_block_number as i64);
+            network
+                .finalized_block_number
+                .store(finalized_block_number, Ordering::SeqCst);
+            // fails only when there are no subscribers
+            let _ = network
+                .bus
+                .send(BusEvent::NewFinalizedBlock(finalized_block_number));
+            log::debug!("Update published to the bus.");
+        }
+        Ok(())
+    }
+}
+
+/// Encodes the update in the format of the event stream and records the size of the event.
+fn get_event(update: &ValidatorDetailsUpdate, format: MessageFormat) -> anyhow::Result<SseEvent> {
+    let event = SseEvent::encode(update.finalized_block_number, update, format)?;
+    metrics::sent_byte_count()
+        .with_label_values(&[&format.label()])
+        .inc_by(event.data.len() as u64);
+    Ok(event)
+}
+
+/// Serves the same updates as the JSON-RPC subscriptions as Server-Sent Events at `/sse` and as
+/// binary WebSocket frames at `/binary`. The `account_id` query parameter is the validator's
+/// account id, and the id of each event is its finalized block number.
+#[derive(Clone)]
+struct ValidatorDetailsSseHandler {
+    networks: Networks<NetworkContext>,
+}
+
+#[async_trait]
+impl SseHandler for ValidatorDetailsSseHandler {
+    async fn get_event_stream(
+        &self,
+        request: SseRequest,
+    ) -> Result<BoxStream<'static, SseEvent>, String> {
+        let account_id = request
+            .get_param("account_id")
+            .and_then(|account_id| AccountId::from_str(account_id).ok())
+            .ok_or_else(|| "Missing or invalid account id.".to_string())?;
+        let network = self
+            .networks
+            .get(request.network.as_deref())
+            .ok_or_else(|| "Network is not available.".to_string())?;
+        let mut redis_connection = network.redis_connection.clone();
+        let bus_receiver = network.bus.subscribe();
+        let (validator_details, update) = ValidatorDetailsServer::get_first_update(
+            &network,
+            &mut redis_connection,
+            &account_id,
+            request.last_event_id,
+        )
+        .await
+        .map_err(|error| {
+            log::error!("Error while fetching validator details: {error:?}");
+            "Error while fetching validator details. Please make sure you are sending a valid validator account id.".to_string()
+        })?;
+        // the bus is subscribed to before the first update, skip the blocks it includes
+        let first_block_number = update.finalized_block_number.unwrap_or_default();
+        let format = request.format;
+        let first_event = get_event(&update, format).map_err(|error| error.to_string())?;
+        log::info!(
+            "New {} event stream subscription {account_id} in {} format.",
+            network.chain,
+            format.label()
+        );
+        let chain = network.chain;
+        let events = futures_util::stream::unfold(
+            (bus_receiver, redis_connection, validator_details),
+            move |(mut bus_receiver, mut redis_connection, mut validator_details)| {
+                let chain = chain.clone();
+                async move {
+                    loop {
+                        match bus_receiver.recv().await {
+                            Ok(BusEvent::NewFinalizedBlock(finalized_block_number))
+                                if finalized_block_number <= first_block_number =>
+                            {
+                                continue
+                            }
+                            Ok(BusEvent::NewFinalizedBlock(finalized_block_number)) => {
+                                let event = ValidatorDetailsServer::get_update(
+                                    &mut redis_connection,
+                                    &chain,
+                                    &account_id,
+                                    finalized_block_number,
+                                    &mut validator_details,
+                                )
+                                .await
+                                .and_then(|update| get_event(&update, format));
+                                return match event {
+                                    Ok(event) => Some((
+                                        event,
+                                        (bus_receiver, redis_connection, validator_details),
+                                    )),
+                                    Err(error) => {
+                                        log::error!("{error:?}");
+                                        None
+                                    }
+                                };
+                            }
+                            Err(broadcast::error::RecvError::Lagged(skipped_block_count)) => {
+                                log::warn!(
+                                    "End slow event stream subscription {account_id}, {skipped_block_count} blocks behind."
+                                );
+                                metrics::slow_subscriber_disconnect_count().inc();
+                                return None;
+                            }
+                            Ok(BusEvent::Error) | Err(broadcast::error::RecvError::Closed) => {
+                                return None
+                            }
+                        }
+                    }
+                }
+            },
+        );
+        Ok(futures_util::stream::once(async { first_event })
+            .chain(events)
+            .boxed())
+    }
 }
 
 #[async_trait(?Send)]
@@ -264,60 +660,39 @@ impl Service for ValidatorDetailsServer {
     }
 
     async fn run(&'static self) -> anyhow::Result<()> {
-        // let last_finalized_block_number = Arc::new(AtomicU64::new(0));
-        let bus = Arc::new(Mutex::new(Bus::new(100)));
-        let redis_client = redis::Client::open(CONFIG.redis.url.as_str()).context(format!(
-            "Cannot connect to Redis at URL {}.",
-            CONFIG.redis.url
-        ))?;
-        let mut pubsub_connection = redis_client.get_async_pubsub().await?;
-        pubsub_connection
-            .subscribe(format!(
-                "subvt:{}:validators:publish:finalized_block_number",
-                CONFIG.substrate.chain
-            ))
-            .await?;
-        metrics::subscription_count().set(0);
+        let network_configs = CONFIG.get_network_configs()?;
+        let networks = Networks::new(&CONFIG.get_network_chains());
         let server_stop_handle = ValidatorDetailsServer::run_rpc_server(
             &CONFIG.rpc.host,
             CONFIG.rpc.validator_details_port,
-            &redis_client,
-            bus.clone(),
+            &networks,
         )
         .await?;
-        let mut pubsub_stream = pubsub_connection.on_message();
-        let error: anyhow::Error = loop {
-            let maybe_message = pubsub_stream.next().await;
-            let payload = if let Some(message) = maybe_message {
-                message.get_payload()
-            } else {
-                continue;
-            };
-            if let Err(error) = payload {
-                break error.into();
-            }
-            let finalized_block_number: u64 = payload.unwrap();
-            if LAST_FINALIZED_BLOCK_NUMBER.load(Ordering::SeqCst) == finalized_block_number {
-                log::warn!("Skip duplicate finalized block #{finalized_block_number}.",);
-                continue;
+        health::set_ready(true);
+        // each network is started, served and updated independently of the others
+        let network_runs = network_configs.iter().map(|config| {
+            let networks = networks.clone();
+            async move {
+                let chain = &config.substrate.chain;
+                let network = start_network(&CONFIG, chain, || NetworkContext::new(config)).await;
+                metrics::subscription_count()
+                    .with_label_values(&[chain])
+                    .set(0);
+                networks.insert(chain, network.clone());
+                log::info!("Serving the {chain} validator details.");
+                let network = &network;
+                keep_running(&CONFIG, chain, move || async move {
+                    let error = ValidatorDetailsServer::run_updater(config, network).await;
+                    let _ = network.bus.send(BusEvent::Error);
+                    error
+                })
+                .await;
             }
-            log::info!("New finalized block #{finalized_block_number}.");
-            metrics::current_finalized_block_number().set(finalized_block_number as i64);
-            {
-                let mut bus = bus.lock().unwrap();
-                bus.broadcast(BusEvent::NewFinalizedBlock(finalized_block_number));
-                log::debug!("Update published to the bus.");
-            }
-            LAST_FINALIZED_BLOCK_NUMBER.store(finalized_block_number, Ordering::SeqCst);
-        };
-        log::error!("{:?}", error);
-        {
-            let mut bus = bus.lock().unwrap();
-            bus.broadcast(BusEvent::Error);
-        }
+        });
+        futures_util::future::join_all(network_runs).await;
         log::info!("Stopping RPC server...");
         server_stop_handle.stop()?;
         log::info!("RPC server fully stopped.");
-        Err(error)
+        Ok(())
     }
 }
@@ -0,0 +1,263 @@
+//! Server-side filtering of the validator list subscriptions. A subscriber that provides filter
+//! parameters only receives the validators that match the filter, and gets synthesized inserts
+//! and removes when a validator enters or leaves the filter's result set after an update.
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::str::FromStr;
+use subvt_service_common::encoding::MessageFormat;
+use subvt_types::crypto::AccountId;
+use subvt_types::subvt::{ValidatorListUpdate, ValidatorSummary};
+
+/// Subscription parameters. All criteria are optional, and a validator has to satisfy all the
+/// given criteria to be included.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ValidatorListFilterParameters {
+    /// Watch list of SS58 addresses or account ids.
+    #[serde(default)]
+    account_ids: Vec<String>,
+    /// Case-insensitive substring of the identity display or the parent identity display.
+    identity: Option<String>,
+    /// Stake amounts are in the smallest unit of the token (e.g. Planck).
+    min_self_stake: Option<String>,
+    max_self_stake: Option<String>,
+    min_total_stake: Option<String>,
+    max_total_stake: Option<String>,
+    min_commission_per_billion: Option<u32>,
+    max_commission_per_billion: Option<u32>,
+    is_para_validator: Option<bool>,
+}
+
+impl ValidatorListFilterParameters {
+    pub fn is_empty(&self) -> bool {
+        self.account_ids.is_empty()
+            && self.identity.is_none()
+            && self.min_self_stake.is_none()
+            && self.max_self_stake.is_none()
+            && self.min_total_stake.is_none()
+            && self.max_total_stake.is_none()
+            && self.min_commission_per_billion.is_none()
+            && self.max_commission_per_billion.is_none()
+            && self.is_para_validator.is_none()
+    }
+}
+
+/// Filter parameters, along with the last finalized block number of the list that the client
+/// already has, if it's resuming a previous subscription, and the message format.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ValidatorListSubscriptionParameters {
+    #[serde(flatten)]
+    pub filter: ValidatorListFilterParameters,
+    pub last_finalized_block_number: Option<u64>,
+    #[serde(flatten)]
+    pub format: MessageFormat,
+}
+
+#[derive(Clone, Debug)]
+pub struct ValidatorListFilter {
+    account_ids: HashSet<AccountId>,
+    identity: Option<String>,
+    min_self_stake: Option<u128>,
+    max_self_stake: Option<u128>,
+    min_total_stake: Option<u128>,
+    max_total_stake: Option<u128>,
+    min_commission_per_billion: Option<u32>,
+    max_commission_per_billion: Option<u32>,
+    is_para_validator: Option<bool>,
+}
+
+fn parse_balance(maybe_value: &Option<String>, name: &str) -> Result<Option<u128>, String> {
+    match maybe_value {
+        Some(value) => value
+            .parse::<u128>()
+            .map(Some)
+            .map_err(|_| format!("Invalid {name}: {value}")),
+        None => Ok(None),
+    }
+}
+
+fn is_in_range<T: PartialOrd>(value: T, min: Option<T>, max: Option<T>) -> bool {
+    min.map(|min| value >= min).unwrap_or(true) && max.map(|max| value <= max).unwrap_or(true)
+}
+
+/// Active stake for active validators, self stake plus all nominations for the others.
+fn get_total_stake(validator: &ValidatorSummary) -> u128 {
+    match &validator.validator_stake {
+        Some(validator_stake) => validator_stake.total_stake,
+        None => validator.self_stake.active_amount + validator.inactive_nominations.total_amount,
+    }
+}
+
+impl ValidatorListFilter {
+    pub fn new(params: ValidatorListFilterParameters) -> Result<Self, String> {
+        let mut account_ids = HashSet::default();
+        for account_id in &params.account_ids {
+            account_ids.insert(
+                AccountId::from_str(account_id)
+                    .map_err(|_| format!("Invalid address or account id: {account_id}"))?,
+            );
+        }
+        Ok(Self {
+            account_ids,
+            identity: params
+                .identity
+                .as_ref()
+                .map(|identity| identity.to_lowercase())
+                .filter(|identity| !identity.is_empty()),
+            min_self_stake: parse_balance(&params.min_self_stake, "min_self_stake")?,
+            max_self_stake: parse_balance(&params.max_self_stake, "max_self_stake")?,
+            min_total_stake: parse_balance(&params.min_total_stake, "min_total_stake")?,
+            max_total_stake: parse_balance(&params.max_total_stake, "max_total_stake")?,
+            min_commission_per_billion: params.min_commission_per_billion,
+            max_commission_per_billion: params.max_commission_per_billion,
+            is_para_validator: params.is_para_validator,
+        })
+    }
+
+    pub fn matches(&self, validator: &ValidatorSummary) -> bool {
+        if !self.account_ids.is_empty() && !self.account_ids.contains(&validator.account_id) {
+            return false;
+        }
+        if let Some(identity) = &self.identity {
+            let contains = |display: &Option<String>| {
+                display
+                    .as_ref()
+                    .map(|display| display.to_lowercase().contains(identity))
+                    .unwrap_or(false)
+            };
+            if !contains(&validator.display) && !contains(&validator.parent_display) {
+                return false;
+            }
+        }
+        is_in_range(
+            validator.self_stake.active_amount,
+            self.min_self_stake,
+            self.max_self_stake,
+        ) && is_in_range(
+            get_total_stake(validator),
+            self.min_total_stake,
+            self.max_total_stake,
+        ) && is_in_range(
+            validator.preferences.commission_per_billion,
+            self.min_commission_per_billion,
+            self.max_commission_per_billion,
+        ) && self
+            .is_para_validator
+            .map(|is_para_validator| is_para_validator == validator.is_para_validator)
+            .unwrap_or(true)
+    }
+}
+
+/// State of a single subscription: its filter, if any, and the validators that the subscriber
+/// currently has in its list.
+pub struct ValidatorListSubscription {
+    filter: Option<ValidatorListFilter>,
+    validator_ids: HashSet<AccountId>,
+}
+
+impl ValidatorListSubscription {
+    pub fn new(filter: Option<ValidatorListFilter>) -> Self {
+        Self {
+            filter,
+            validator_ids: HashSet::default(),
+        }
+    }
+
+    pub fn is_filtered(&self) -> bool {
+        self.filter.is_some()
+    }
+
+    /// The complete (filtered) list sent at the start of the subscription.
+    pub fn get_initial_update(
+        &mut self,
+        finalized_block_number: Option<u64>,
+        validators: Vec<ValidatorSummary>,
+    ) -> ValidatorListUpdate {
+        let insert: Vec<ValidatorSummary> = match &self.filter {
+            Some(filter) => validators
+                .into_iter()
+                .filter(|validator| filter.matches(validator))
+                .collect(),
+            None => validators,
+        };
+        if self.filter.is_some() {
+            self.validator_ids = insert
+                .iter()
+                .map(|validator| validator.account_id)
+                .collect();
+        }
+        ValidatorListUpdate {
+            finalized_block_number,
+            insert,
+            ..Default::default()
+        }
+    }
+
+    /// The update sent at the start of a resumed subscription. `base_summaries` is the list the
+    /// client has from its previous subscription, which is assumed to have used the same filter.
+    pub fn get_resume_update(
+        &mut self,
+        base_summaries: &[ValidatorSummary],
+        update: &ValidatorListUpdate,
+        summaries: &HashMap<AccountId, ValidatorSummary>,
+    ) -> ValidatorListUpdate {
+        if let Some(filter) = &self.filter {
+            self.validator_ids = base_summaries
+                .iter()
+                .filter(|validator| filter.matches(validator))
+                .map(|validator| validator.account_id)
+                .collect();
+        }
+        self.filter_update(update, summaries).into_owned()
+    }
+
+    /// Filters an update for the subscriber. `summaries` contains the post-update summaries of
+    /// all the inserted and updated validators. An updated validator that starts matching the
+    /// filter is sent as an insert, and one that stops matching is sent as a remove. The update
+    /// is shared by all the subscribers, so it's only copied for the filtered subscriptions.
+    pub fn filter_update<'a>(
+        &mut self,
+        update: &'a ValidatorListUpdate,
+        summaries: &HashMap<AccountId, ValidatorSummary>,
+    ) -> Cow<'a, ValidatorListUpdate> {
+        let filter = match &self.filter {
+            Some(filter) => filter,
+            None => return Cow::Borrowed(update),
+        };
+        let mut filtered = ValidatorListUpdate {
+            finalized_block_number: update.finalized_block_number,
+            ..Default::default()
+        };
+        for account_id in &update.remove_ids {
+            if self.validator_ids.remove(account_id) {
+                filtered.remove_ids.push(*account_id);
+            }
+        }
+        for validator in &update.insert {
+            if filter.matches(validator) {
+                self.validator_ids.insert(validator.account_id);
+                filtered.insert.push(validator.clone());
+            }
+        }
+        for diff in &update.update {
+            let Some(validator) = summaries.get(&diff.account_id) else {
+                continue;
+            };
+            let is_match = filter.matches(validator);
+            let was_match = self.validator_ids.contains(&diff.account_id);
+            match (was_match, is_match) {
+                (true, true) => filtered.update.push(diff.clone()),
+                (false, true) => {
+                    self.validator_ids.insert(diff.account_id);
+                    filtered.insert.push(validator.clone());
+                }
+                (true, false) => {
+                    self.validator_ids.remove(&diff.account_id);
+                    filtered.remove_ids.push(diff.account_id);
+                }
+                (false, false) => (),
+            }
+        }
+        Cow::Owned(filtered)
+    }
+}
@@ -4,28 +4,59 @@
 //!
 //! Supports two RPC methods: `subscribe_validatorList` and `unsubscribe_validatorList`.
 //! Gives the complete list at first connection, then publishes only the changed validators' fields
-//! after each update from `subvt-validator-list-updater`.
+//! after each update from `subvt-validator-list-updater`. `subscribe_validatorList` optionally
+//! accepts filter parameters (see the `filter` module), in which case the list and the updates
+//! are filtered for the subscriber.
+//!
+//! A client that reconnects can provide the `last_finalized_block_number` of the list it already
+//! has. If that block is still within the history retained by `subvt-validator-list-updater`,
+//! the subscription starts with a single compacted update from that block to the current state
+//! instead of the complete list (see the `resume` module).
+//!
+//! The same updates are served as Server-Sent Events at `GET /sse` on the same port, with the
+//! finalized block number as the event id, see `subvt_service_common::sse`. They are also served
+//! as binary WebSocket frames at `/binary`, where the `encoding` (`json`, `msgpack` or `cbor`)
+//! and `compression` (`none`, `deflate` or `gzip`) query parameters select a compact format, see
+//! `subvt_service_common::binary_ws`. The JSON-RPC subscription messages are JSON, and the
+//! subscriptions that ask for a binary format are rejected in favor of `/binary`.
+//!
+//! The server serves the lists of all the networks in `common.networks`. The network of a
+//! connection is selected by the path, e.g. `ws://host:7889/polkadot`, and the first network is
+//! served at the root path, see `subvt_service_common::network`.
 #![warn(clippy::disallowed_types)]
 use anyhow::Context;
 use async_trait::async_trait;
-use bus::Bus;
 use clap::{arg, Command};
+use filter::{ValidatorListFilter, ValidatorListSubscription, ValidatorListSubscriptionParameters};
+use futures_util::stream::BoxStream;
 use futures_util::StreamExt as _;
 use jsonrpsee::server::{RpcModule, ServerBuilder, ServerHandle};
-use jsonrpsee::SubscriptionMessage;
+use jsonrpsee::types::error::ErrorCode;
+use jsonrpsee::types::{ErrorObjectOwned, Params};
+use jsonrpsee::{SubscriptionMessage, SubscriptionSink};
 use lazy_static::lazy_static;
-use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet, FxHasher};
-use std::hash::{Hash, Hasher};
-use std::str::FromStr;
-use std::sync::{Arc, Mutex, RwLock};
+use redis::aio::ConnectionManager;
+use resume::{get_summary_hash, RedisValidatorListHistory, ValidatorListHistory};
+use rustc_hash::FxHashMap as HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use subvt_config::Config;
+use subvt_metrics::health;
+use subvt_service_common::binary_ws::BinaryWsLayer;
+use subvt_service_common::encoding::MessageFormat;
+use subvt_service_common::network::{keep_running, start_network, NetworkLayer, Networks};
+use subvt_service_common::sse::{SseEvent, SseHandler, SseLayer, SseRequest};
 use subvt_service_common::Service;
 use subvt_types::{
     crypto::AccountId,
     subvt::{ValidatorDetails, ValidatorDetailsDiff, ValidatorListUpdate, ValidatorSummary},
 };
+use tokio::sync::broadcast;
 
+pub mod filter;
 mod metrics;
+pub mod resume;
 
 lazy_static! {
     static ref CONFIG: Config = Config::default();
@@ -33,7 +64,12 @@ lazy_static! {
 
 #[derive(Clone, Debug)]
 pub enum BusEvent {
-    Update(ValidatorListUpdate),
+    /// The update, along with the post-update summaries of the inserted and updated validators
+    /// for the filtered subscriptions.
+    Update(
+        Arc<ValidatorListUpdate>,
+        Arc<HashMap<AccountId, ValidatorSummary>>,
+    ),
     Error,
 }
 
@@ -51,160 +87,485 @@ fn is_inactive() -> bool {
         .get_flag("inactive")
 }
 
+/// Subscription parameters can be given either as the single positional parameter or as named
+/// parameters. No parameters, or a `null` parameter, means no filter and no resumption.
+fn parse_params(
+    params: &Params,
+) -> Result<(Option<ValidatorListFilter>, Option<u64>), ErrorObjectOwned> {
+    let invalid_params = |message: String| {
+        ErrorObjectOwned::owned(ErrorCode::InvalidParams.code(), message, None::<()>)
+    };
+    let maybe_params: Option<ValidatorListSubscriptionParameters> = match params.as_str() {
+        None => None,
+        Some(params_str) if params_str.trim() == "[]" => None,
+        Some(_) if params.is_object() => Some(params.parse()?),
+        Some(_) => params.one()?,
+    };
+    match maybe_params {
+        Some(params) => {
+            if params.format.is_binary() {
+                return Err(invalid_params(
+                    "Binary message formats are served at /binary.".to_string(),
+                ));
+            }
+            let filter = if params.filter.is_empty() {
+                None
+            } else {
+                Some(ValidatorListFilter::new(params.filter).map_err(invalid_params)?)
+            };
+            Ok((filter, params.last_finalized_block_number))
+        }
+        None => Ok((None, None)),
+    }
+}
+
+/// Keeps the metrics of a subscription, and updates them when the subscription ends, however it
+/// ends.
+struct SubscriptionMetrics {
+    chain: String,
+    is_filtered: bool,
+    sent_byte_count: u64,
+}
+
+impl SubscriptionMetrics {
+    fn new(chain: &str, is_filtered: bool) -> Self {
+        metrics::subscription_count()
+            .with_label_values(&[chain])
+            .inc();
+        if is_filtered {
+            metrics::filtered_subscription_count().inc();
+        }
+        Self {
+            chain: chain.to_string(),
+            is_filtered,
+            sent_byte_count: 0,
+        }
+    }
+
+    /// Encodes the update as JSON and records the size of the message.
+    fn get_subscription_message(
+        &mut self,
+        update: &ValidatorListUpdate,
+    ) -> anyhow::Result<SubscriptionMessage> {
+        let json = serde_json::value::to_raw_value(update)?;
+        let size = json.get().len() as u64;
+        self.sent_byte_count += size;
+        metrics::sent_byte_count()
+            .with_label_values(&[&MessageFormat::default().label()])
+            .inc_by(size);
+        Ok(SubscriptionMessage::from_json(&json)?)
+    }
+}
+
+impl Drop for SubscriptionMetrics {
+    fn drop(&mut self) {
+        metrics::subscription_sent_bytes()
+            .with_label_values(&[&MessageFormat::default().label()])
+            .observe(self.sent_byte_count as f64);
+        metrics::subscription_count()
+            .with_label_values(&[&self.chain])
+            .dec();
+        if self.is_filtered {
+            metrics::filtered_subscription_count().dec();
+        }
+    }
+}
+
+/// Sends the message, waiting for room in the subscriber's queue for at most the configured
+/// timeout. A subscriber that cannot keep up is disconnected.
+async fn send(sink: &SubscriptionSink, message: SubscriptionMessage) -> Result<(), String> {
+    let timeout = Duration::from_millis(CONFIG.rpc.subscriber_send_timeout_millis);
+    match sink.send_timeout(message, timeout).await {
+        Ok(()) => Ok(()),
+        Err(_) if sink.is_closed() => Err("Subscription connection closed.".to_string()),
+        Err(_) => {
+            metrics::slow_subscriber_disconnect_count().inc();
+            Err("Subscriber cannot keep up with the updates.".to_string())
+        }
+    }
+}
+
+/// State of a network, shared by the WebSocket and the Server-Sent Events subscriptions.
+#[derive(Clone)]
+struct SubscriptionContext {
+    chain: String,
+    is_active_list: bool,
+    redis_connection: ConnectionManager,
+    finalized_block_number: Arc<AtomicU64>,
+    validator_map: Arc<RwLock<HashMap<AccountId, ValidatorDetails>>>,
+    bus: broadcast::Sender<BusEvent>,
+}
+
+impl SubscriptionContext {
+    /// Connects to the Redis of the network. The list is loaded by the updater.
+    async fn new(config: &Config, is_active_list: bool) -> anyhow::Result<Self> {
+        let redis_client = redis::Client::open(config.redis.url.as_str()).context(format!(
+            "Cannot connect to Redis at URL {}.",
+            config.redis.url
+        ))?;
+        let (bus, _) = broadcast::channel(CONFIG.rpc.bus_capacity);
+        Ok(Self {
+            chain: config.substrate.chain.clone(),
+            is_active_list,
+            redis_connection: ConnectionManager::new(redis_client).await?,
+            finalized_block_number: Arc::new(AtomicU64::new(0)),
+            validator_map: Arc::new(RwLock::new(HashMap::default())),
+            bus,
+        })
+    }
+
+    /// The first update of a subscription: a single compacted update from the client's last
+    /// finalized block if the subscription can be resumed, the complete list otherwise.
+    async fn get_first_update(
+        &self,
+        subscription: &mut ValidatorListSubscription,
+        maybe_base_block_number: Option<u64>,
+    ) -> ValidatorListUpdate {
+        let (current_block_number, validator_summaries) = {
+            let validator_map = self.validator_map.read().unwrap();
+            (
+                self.finalized_block_number.load(Ordering::SeqCst),
+                validator_map
+                    .iter()
+                    .map(|(account_id, validator)| (*account_id, validator.into()))
+                    .collect::<HashMap<AccountId, ValidatorSummary>>(),
+            )
+        };
+        let maybe_resume = match maybe_base_block_number {
+            Some(base_block_number) if current_block_number > 0 => resume::get_resume(
+                &mut resume::RedisValidatorListHistory {
+                    connection: &mut self.redis_connection.clone(),
+                    chain: &self.chain,
+                    is_active_list: self.is_active_list,
+                },
+                base_block_number,
+                current_block_number,
+                &validator_summaries,
+            )
+            .await
+            .unwrap_or_else(|error| {
+                log::error!("Error while resuming subscription: {error:?}");
+                None
+            }),
+            _ => None,
+        };
+        match maybe_resume {
+            Some(resume) => {
+                log::info!(
+                    "Resume {} subscription from finalized block #{}.",
+                    self.chain,
+                    maybe_base_block_number.unwrap_or_default(),
+                );
+                metrics::resumed_subscription_count().inc();
+                subscription.get_resume_update(
+                    &resume.base_summaries,
+                    &resume.update,
+                    &resume.summaries,
+                )
+            }
+            None => subscription.get_initial_update(
+                if current_block_number > 0 {
+                    Some(current_block_number)
+                } else {
+                    None
+                },
+                validator_summaries.into_values().collect(),
+            ),
+        }
+    }
+}
+
+/// Whether the bus update is already in the first update of a subscription. The bus is subscribed
+/// to before the first update is built, so that no update is missed in between.
+fn is_in_first_update(first_block_number: Option<u64>, update: &ValidatorListUpdate) -> bool {
+    matches!(
+        (first_block_number, update.finalized_block_number),
+        (Some(first_block_number), Some(block_number)) if block_number <= first_block_number
+    )
+}
+
+/// Encodes the update in the format of the event stream and records the size of the event.
+fn get_event(update: &ValidatorListUpdate, format: MessageFormat) -> anyhow::Result<SseEvent> {
+    let event = SseEvent::encode(update.finalized_block_number, update, format)?;
+    metrics::sent_byte_count()
+        .with_label_values(&[&format.label()])
+        .inc_by(event.data.len() as u64);
+    Ok(event)
+}
+
+/// Serves the same updates as the JSON-RPC subscriptions as Server-Sent Events at `/sse` and as
+/// binary WebSocket frames at `/binary`. The optional `params` query parameter is the JSON of the
+/// filter parameters, and the id of each event is its finalized block number.
+#[async_trait]
+impl SseHandler for SubscriptionContext {
+    async fn get_event_stream(
+        &self,
+        request: SseRequest,
+    ) -> Result<BoxStream<'static, SseEvent>, String> {
+        let params: ValidatorListSubscriptionParameters = match request.get_param("params") {
+            Some(params) => serde_json::from_str(params)
+                .map_err(|error| format!("Invalid parameters: {error}"))?,
+            None => ValidatorListSubscriptionParameters::default(),
+        };
+        let filter = if params.filter.is_empty() {
+            None
+        } else {
+            Some(ValidatorListFilter::new(params.filter)?)
+        };
+        let bus_receiver = self.bus.subscribe();
+        let mut subscription = ValidatorListSubscription::new(filter);
+        let update = self
+            .get_first_update(
+                &mut subscription,
+                request.last_event_id.or(params.last_finalized_block_number),
+            )
+            .await;
+        let first_block_number = update.finalized_block_number;
+        let format = request.format;
+        let first_event = get_event(&update, format).map_err(|error| error.to_string())?;
+        log::info!(
+            "New {} event stream subscription in {} format.",
+            self.chain,
+            format.label()
+        );
+        let events = futures_util::stream::unfold(
+            (bus_receiver, subscription),
+            move |(mut bus_receiver, mut subscription)| async move {
+                loop {
+                    match bus_receiver.recv().await {
+                        Ok(BusEvent::Update(update, _))
+                            if is_in_first_update(first_block_number, &update) =>
+                        {
+                            continue
+                        }
+                        Ok(BusEvent::Update(update, summaries)) => {
+                            let update = subscription.filter_update(&update, &summaries);
+                            return match get_event(&update, format) {
+                                Ok(event) => Some((event, (bus_receiver, subscription))),
+                                Err(error) => {
+                                    log::error!("Error while encoding update: {error:?}");
+                                    None
+                                }
+                            };
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped_update_count)) => {
+                            log::warn!(
+                                "End slow event stream subscription, {skipped_update_count} updates behind."
+                            );
+                            metrics::slow_subscriber_disconnect_count().inc();
+                            return None;
+                        }
+                        Ok(BusEvent::Error) | Err(broadcast::error::RecvError::Closed) => {
+                            return None
+                        }
+                    }
+                }
+            },
+        );
+        Ok(futures_util::stream::once(async { first_event })
+            .chain(events)
+            .boxed())
+    }
+}
+
+/// Serves the event streams of the network selected by the path.
+#[derive(Clone)]
+struct ValidatorListSseHandler {
+    networks: Networks<SubscriptionContext>,
+}
+
+#[async_trait]
+impl SseHandler for ValidatorListSseHandler {
+    async fn get_event_stream(
+        &self,
+        request: SseRequest,
+    ) -> Result<BoxStream<'static, SseEvent>, String> {
+        let context = self
+            .networks
+            .get(request.network.as_deref())
+            .ok_or_else(|| "Network is not available.".to_string())?;
+        context.get_event_stream(request).await
+    }
+}
+
 impl ValidatorListServer {
-    pub async fn run_rpc_server(
+    /// Each subscription runs as a task that receives the updates from the broadcast channel.
+    /// A subscriber that falls more than `rpc.bus_capacity` updates behind, or that cannot
+    /// receive a message within `rpc.subscriber_send_timeout_millis`, is disconnected.
+    async fn run_rpc_server(
         host: &str,
         port: u16,
-        validator_map: &Arc<RwLock<HashMap<AccountId, ValidatorDetails>>>,
-        bus: &Arc<Mutex<Bus<BusEvent>>>,
+        networks: &Networks<SubscriptionContext>,
     ) -> anyhow::Result<ServerHandle> {
+        let sse_handler = ValidatorListSseHandler {
+            networks: networks.clone(),
+        };
         let rpc_ws_server = ServerBuilder::default()
             .max_request_body_size(u32::MAX)
+            .max_connections(CONFIG.rpc.max_connections)
+            .set_message_buffer_capacity(CONFIG.rpc.subscriber_queue_size)
+            .set_http_middleware(
+                tower::ServiceBuilder::new()
+                    .layer(NetworkLayer::new(CONFIG.get_network_chains()))
+                    .layer(SseLayer::new(
+                        "/sse",
+                        sse_handler.clone(),
+                        Duration::from_secs(CONFIG.rpc.sse_keep_alive_seconds),
+                        metrics::sse_connection_count(),
+                    ))
+                    .layer(BinaryWsLayer::new(
+                        "/binary",
+                        sse_handler,
+                        Duration::from_secs(CONFIG.rpc.sse_keep_alive_seconds),
+                        metrics::binary_ws_connection_count(),
+                    )),
+            )
             .build(format!("{host}:{port}"))
             .await?;
         let mut rpc_module = RpcModule::new(());
-        let validator_map = validator_map.clone();
-        let bus = bus.clone();
+        let networks = networks.clone();
         rpc_module.register_subscription(
             "subscribe_validatorList",
             "subscribe_validatorList",
             "unsubscribe_validatorList",
-            move |_params, pending, _, _| {
-                let validator_map = validator_map.clone();
-                let bus = bus.clone();
+            move |params, pending, _, extensions| {
+                let maybe_context = networks.get_for_request(&extensions);
                 async move {
-                    let mut sink = pending.accept().await?;
-                    log::info!("New subscription.");
-                    metrics::subscription_count().inc();
-                    let mut bus_receiver = bus.lock().unwrap().add_rx();
-                    {
-                        let validator_summaries: Vec<ValidatorSummary> = {
-                            let validator_map = validator_map.read().unwrap();
-                            validator_map.iter().map(|value| value.1.into()).collect()
-                        };
-                        let update = ValidatorListUpdate {
-                            insert: validator_summaries,
-                            ..Default::default()
-                        };
-                        let message = SubscriptionMessage::from_json(&update).unwrap();
-                        sink.try_send(message)?;
+                    let context = match maybe_context {
+                        Some(context) => context,
+                        None => {
+                            pending
+                                .reject(ErrorObjectOwned::owned(
+                                    ErrorCode::InvalidParams.code(),
+                                    "Network is not available.",
+                                    None::<()>,
+                                ))
+                                .await;
+                            return Ok(());
+                        }
+                    };
+                    let mut bus_receiver = context.bus.subscribe();
+                    let (filter, maybe_base_block_number) = match parse_params(&params) {
+                        Ok(params) => params,
+                        Err(error) => {
+                            log::warn!("Invalid subscription parameters: {}", error.message());
+                            pending.reject(error).await;
+                            return Ok(());
+                        }
+                    };
+                    let sink = pending.accept().await?;
+                    if filter.is_some() {
+                        log::info!("New filtered {} subscription.", context.chain);
+                    } else {
+                        log::info!("New {} subscription.", context.chain);
                     }
-                    std::thread::spawn(move || loop {
-                        if let Ok(update) = bus_receiver.recv() {
-                            if sink.is_closed() {
+                    let mut subscription_metrics =
+                        SubscriptionMetrics::new(&context.chain, filter.is_some());
+                    let mut subscription = ValidatorListSubscription::new(filter);
+                    let first_block_number = {
+                        let update = context
+                            .get_first_update(&mut subscription, maybe_base_block_number)
+                            .await;
+                        let message = subscription_metrics.get_subscription_message(&update)?;
+                        send(&sink, message).await?;
+                        update.finalized_block_number
+                    };
+                    loop {
+                        let event = tokio::select! {
+                            _ = sink.closed() => {
                                 log::info!("Subscription connection closed.");
-                                metrics::subscription_count().dec();
-                                return;
+                                return Ok(());
                             }
-                            match update {
-                                BusEvent::Update(update) => {
-                                    let message = SubscriptionMessage::from_json(&update).unwrap();
-                                    let send_result = sink.try_send(message);
-                                    match send_result {
-                                        Err(error) => {
-                                            log::warn!("Error during publish: {error:?}");
-                                            metrics::subscription_count().dec();
-                                            return;
-                                        }
-                                        Ok(()) => {
-                                            log::debug!("Diff published.");
-                                        }
+                            event = bus_receiver.recv() => event,
+                        };
+                        match event {
+                            Ok(BusEvent::Update(update, _))
+                                if is_in_first_update(first_block_number, &update) => {}
+                            Ok(BusEvent::Update(update, summaries)) => {
+                                let update = subscription.filter_update(&update, &summaries);
+                                let message = match subscription_metrics.get_subscription_message(&update) {
+                                    Ok(message) => message,
+                                    Err(error) => {
+                                        log::error!("Error while encoding update: {error:?}");
+                                        continue;
                                     }
+                                };
+                                if let Err(error) = send(&sink, message).await {
+                                    log::warn!("Error during publish: {error}");
+                                    return Err(error.into());
                                 }
-                                BusEvent::Error => {
-                                    log::error!("Bus update receive error.");
-                                    return;
-                                }
+                                log::debug!("Diff published.");
+                            }
+                            Ok(BusEvent::Error) => {
+                                log::error!("Bus update receive error.");
+                                return Err("Server error.".into());
                             }
+                            Err(broadcast::error::RecvError::Lagged(skipped_update_count)) => {
+                                log::warn!("Disconnect slow subscriber, {skipped_update_count} updates behind.");
+                                metrics::slow_subscriber_disconnect_count().inc();
+                                return Err("Subscriber cannot keep up with the updates.".into());
+                            }
+                            Err(broadcast::error::RecvError::Closed) => return Ok(()),
                         }
-                    });
-                    Ok(())
+                    }
                 }
             },
         )?;
         Ok(rpc_ws_server.start(rpc_module))
     }
-}
 
-#[async_trait(?Send)]
-impl Service for ValidatorListServer {
-    fn get_metrics_server_addr() -> (&'static str, u16) {
-        (
-            CONFIG.metrics.host.as_str(),
-            if is_inactive() {
-                CONFIG.metrics.inactive_validator_list_server_port
-            } else {
-                CONFIG.metrics.active_validator_list_server_port
-            },
-        )
+    /// Receives the finalized block numbers of the network from Redis and publishes the changes
+    /// in the network's list to its subscribers. Returns only on error.
+    async fn run_updater(config: &Config, context: &SubscriptionContext) -> anyhow::Error {
+        match ValidatorListServer::update_validator_list(config, context).await {
+            Ok(()) => anyhow::anyhow!("{} validator list updates ended.", context.chain),
+            Err(error) => error,
+        }
     }
 
-    async fn run(&'static self) -> anyhow::Result<()> {
-        let is_active_list = !is_inactive();
-        // init metrics
-        metrics::init(if is_active_list {
-            "subvt_active_validator_list_server"
-        } else {
-            "subvt_inactive_validator_list_server"
-        });
+    async fn update_validator_list(
+        config: &Config,
+        context: &SubscriptionContext,
+    ) -> anyhow::Result<()> {
+        let is_active_list = context.is_active_list;
         let mut last_finalized_block_number = 0;
-        let bus = Arc::new(Mutex::new(Bus::new(100)));
-        let validator_map = Arc::new(RwLock::new(
-            HashMap::<AccountId, ValidatorDetails>::default(),
-        ));
-
-        let redis_client = redis::Client::open(CONFIG.redis.url.as_str()).context(format!(
+        let redis_client = redis::Client::open(config.redis.url.as_str()).context(format!(
             "Cannot connect to Redis at URL {}.",
-            CONFIG.redis.url
+            config.redis.url
         ))?;
         let mut pubsub_connection = redis_client.get_async_pubsub().await?;
         pubsub_connection
             .subscribe(format!(
                 "subvt:{}:validators:publish:finalized_block_number",
-                CONFIG.substrate.chain
+                context.chain
             ))
             .await?;
-        let mut data_connection = redis_client.get_connection()?;
-        metrics::subscription_count().set(0);
-        let server_stop_handle = ValidatorListServer::run_rpc_server(
-            &CONFIG.rpc.host,
-            if is_active_list {
-                CONFIG.rpc.active_validator_list_port
-            } else {
-                CONFIG.rpc.inactive_validator_list_port
-            },
-            &validator_map,
-            &bus,
-        )
-        .await?;
-
+        let mut data_connection = context.redis_connection.clone();
         let mut pubsub_stream = pubsub_connection.on_message();
-        let error: anyhow::Error = 'outer: loop {
-            let maybe_message = pubsub_stream.next().await;
-            let payload = if let Some(message) = maybe_message {
-                message.get_payload()
-            } else {
-                continue;
-            };
-            if let Err(error) = payload {
-                break error.into();
-            }
-            let finalized_block_number: u64 = payload.unwrap();
+        while let Some(message) = pubsub_stream.next().await {
+            let finalized_block_number: u64 = message.get_payload()?;
             if last_finalized_block_number == finalized_block_number {
-                log::warn!("Skip duplicate finalized block #{finalized_block_number}.",);
-                continue 'outer;
+                log::warn!(
+                    "Skip duplicate {} finalized block #{finalized_block_number}.",
+                    context.chain
+                );
+                continue;
             }
-            log::info!("New finalized block #{finalized_block_number}.");
-            metrics::target_finalized_block_number().set(finalized_block_number as i64);
-            let prefix = format!(
-                "subvt:{}:validators:{}:{}",
-                CONFIG.substrate.chain,
-                finalized_block_number,
-                if is_active_list { "active" } else { "inactive" }
+            log::info!(
+                "New {} finalized block #{finalized_block_number}.",
+                context.chain
             );
-            let validator_account_ids: HashSet<String> = redis::cmd("SMEMBERS")
-                .arg(format!("{prefix}:account_id_set"))
-                .query(&mut data_connection)
+            metrics::target_finalized_block_number()
+                .with_label_values(&[&context.chain])
+                .set(finalized_block_number as i64);
+            let mut history = RedisValidatorListHistory {
+                connection: &mut data_connection,
+                chain: &context.chain,
+                is_active_list,
+            };
+            let validator_account_ids = history
+                .get_account_ids(finalized_block_number)
+                .await
                 .context("Can't read validator account ids from Redis.")?;
             log::info!(
                 "Got {} validator account ids. Checking for changes...",
@@ -214,90 +575,94 @@ impl Service for ValidatorListServer {
                 finalized_block_number: Some(finalized_block_number),
                 ..Default::default()
             };
-            {
+            // the lock isn't held across the Redis reads, the map is only written by this task
+            let (existing_account_ids, summary_hashes, mut fetch_account_ids) = {
+                let validator_map = context.validator_map.read().unwrap();
                 // find the ones to remove
-                let validator_map = validator_map.read().unwrap();
-                let current_validator_account_ids = validator_map.keys();
-                for validator_account_id in current_validator_account_ids {
-                    if !validator_account_ids.contains(&validator_account_id.to_string()) {
+                for validator_account_id in validator_map.keys() {
+                    if !validator_account_ids.contains(validator_account_id) {
                         update.remove_ids.push(*validator_account_id);
                     }
                 }
-            }
+                let mut existing_account_ids = Vec::new();
+                let mut summary_hashes = Vec::new();
+                let mut new_account_ids = Vec::new();
+                for validator_account_id in &validator_account_ids {
+                    if let Some(validator) = validator_map.get(validator_account_id) {
+                        existing_account_ids.push(*validator_account_id);
+                        summary_hashes.push(get_summary_hash(&ValidatorSummary::from(validator)));
+                    } else {
+                        new_account_ids.push(*validator_account_id);
+                    }
+                }
+                (existing_account_ids, summary_hashes, new_account_ids)
+            };
+            // check hashes, fetch the changed and the new validators in a single read each
+            let db_summary_hashes = history
+                .get_summary_hashes(finalized_block_number, &existing_account_ids)
+                .await
+                .context("Can't read validator summary hashes from Redis.")?;
+            let new_validator_count = fetch_account_ids.len();
+            for ((validator_account_id, summary_hash), db_summary_hash) in existing_account_ids
+                .iter()
+                .zip(summary_hashes)
+                .zip(db_summary_hashes)
             {
-                // remove
-                let mut validator_map = validator_map.write().unwrap();
-                for remove_id in &update.remove_ids {
-                    validator_map.remove(remove_id);
+                if Some(summary_hash) != db_summary_hash {
+                    log::info!("Summary hash changed for {validator_account_id}.");
+                    fetch_account_ids.push(*validator_account_id);
                 }
             }
+            let db_validators = history
+                .get_validators(finalized_block_number, &fetch_account_ids)
+                .await
+                .context("Can't read validator JSON strings from Redis.")?;
             let mut new_validators: Vec<ValidatorDetails> = Vec::new();
             let mut validator_updates: Vec<ValidatorDetailsDiff> = Vec::new();
             {
                 // update/insert
-                let validator_map = validator_map.read().unwrap();
-                for validator_account_id in validator_account_ids {
-                    let validator_account_id = AccountId::from_str(&validator_account_id).unwrap();
-                    let prefix = format!("{prefix}:validator:{validator_account_id}");
-                    if let Some(validator) = validator_map.get(&validator_account_id) {
-                        // check hash, if different, fetch, calculate and add to list
-                        let summary_hash = {
-                            let mut hasher = FxHasher::default();
-                            ValidatorSummary::from(validator).hash(&mut hasher);
-                            hasher.finish()
-                        };
-                        let db_summary_hash: u64 = redis::cmd("GET")
-                            .arg(format!("{prefix}:summary_hash"))
-                            .query(&mut data_connection)
-                            .context("Can't read validator summary hash from Redis.")?;
-                        if summary_hash != db_summary_hash {
-                            log::info!("Summary hash changed for {validator_account_id}.");
-                            let validator_json_string: String = redis::cmd("GET")
-                                .arg(prefix)
-                                .query(&mut data_connection)
-                                .context("Can't read validator JSON string (1) from Redis.")?;
-                            let db_validator: ValidatorDetails =
-                                serde_json::from_str(&validator_json_string)?;
-                            let db_validator_summary: ValidatorSummary =
-                                ValidatorSummary::from(&db_validator);
-                            let validator_summary: ValidatorSummary = validator.into();
-                            update
-                                .update
-                                .push(validator_summary.get_diff(&db_validator_summary));
-                            validator_updates.push(validator.get_diff(&db_validator));
-                        }
+                let validator_map = context.validator_map.read().unwrap();
+                for (index, (validator_account_id, db_validator)) in
+                    fetch_account_ids.iter().zip(db_validators).enumerate()
+                {
+                    let db_validator = db_validator.ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Validator {validator_account_id} is missing in Redis at block #{finalized_block_number}."
+                        )
+                    })?;
+                    if index < new_validator_count {
+                        update.insert.push(ValidatorSummary::from(&db_validator));
+                        new_validators.push(db_validator);
                     } else {
-                        let validator_json_string: String = redis::cmd("GET")
-                            .arg(&prefix)
-                            .query(&mut data_connection)
-                            .context(format!(
-                                "Can't read validator JSON string (2) from Redis :: {}",
-                                &prefix
-                            ))?;
-                        let validator_deser_result: serde_json::error::Result<ValidatorDetails> =
-                            serde_json::from_str(&validator_json_string);
-                        match validator_deser_result {
-                            Ok(validator) => {
-                                let validator_summary = ValidatorSummary::from(&validator);
-                                update.insert.push(validator_summary);
-                                new_validators.push(validator);
-                            }
-                            Err(error) => {
-                                break 'outer error.into();
-                            }
-                        }
+                        let validator = validator_map.get(validator_account_id).unwrap();
+                        let db_validator_summary = ValidatorSummary::from(&db_validator);
+                        let validator_summary: ValidatorSummary = validator.into();
+                        update
+                            .update
+                            .push(validator_summary.get_diff(&db_validator_summary));
+                        validator_updates.push(validator.get_diff(&db_validator));
                     }
                 }
             }
+            let mut summaries = HashMap::<AccountId, ValidatorSummary>::default();
             {
-                let mut validator_map = validator_map.write().unwrap();
+                // the map and its block number change together for the new subscriptions
+                let mut validator_map = context.validator_map.write().unwrap();
+                for remove_id in &update.remove_ids {
+                    validator_map.remove(remove_id);
+                }
                 for diff in validator_updates {
                     let validator = validator_map.get_mut(&diff.account.id).unwrap();
                     validator.apply_diff(&diff);
+                    summaries.insert(diff.account.id, ValidatorSummary::from(&*validator));
                 }
                 for validator in new_validators {
+                    summaries.insert(validator.account.id, ValidatorSummary::from(&validator));
                     validator_map.insert(validator.account.id, validator);
                 }
+                context
+                    .finalized_block_number
+                    .store(finalized_block_number, Ordering::SeqCst);
             }
             log::info!(
                 "Completed checks. Remove {} validators. {} new validators. {} updated validators.",
@@ -305,22 +670,81 @@ impl Service for ValidatorListServer {
                 update.insert.len(),
                 update.update.len(),
             );
-            {
-                let mut bus = bus.lock().unwrap();
-                bus.broadcast(BusEvent::Update(update));
-                log::info!("Update published to the bus.");
-            }
-            metrics::processed_finalized_block_number().set(finalized_block_number as i64);
+            // fails only when there are no subscribers
+            let _ = context
+                .bus
+                .send(BusEvent::Update(Arc::new(update), Arc::new(summaries)));
+            log::info!("Update published to the bus.");
+            metrics::processed_finalized_block_number()
+                .with_label_values(&[&context.chain])
+                .set(finalized_block_number as i64);
             last_finalized_block_number = finalized_block_number;
-        };
-        log::error!("{:?}", error);
-        {
-            let mut bus = bus.lock().unwrap();
-            bus.broadcast(BusEvent::Error);
         }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl Service for ValidatorListServer {
+    fn get_metrics_server_addr() -> (&'static str, u16) {
+        (
+            CONFIG.metrics.host.as_str(),
+            if is_inactive() {
+                CONFIG.metrics.inactive_validator_list_server_port
+            } else {
+                CONFIG.metrics.active_validator_list_server_port
+            },
+        )
+    }
+
+    async fn run(&'static self) -> anyhow::Result<()> {
+        let is_active_list = !is_inactive();
+        // init metrics
+        metrics::init(if is_active_list {
+            "subvt_active_validator_list_server"
+        } else {
+            "subvt_inactive_validator_list_server"
+        });
+        let network_configs = CONFIG.get_network_configs()?;
+        let networks = Networks::new(&CONFIG.get_network_chains());
+        let server_stop_handle = ValidatorListServer::run_rpc_server(
+            &CONFIG.rpc.host,
+            if is_active_list {
+                CONFIG.rpc.active_validator_list_port
+            } else {
+                CONFIG.rpc.inactive_validator_list_port
+            },
+            &networks,
+        )
+        .await?;
+        health::set_ready(true);
+        // each network is started, served and updated independently of the others
+        let network_runs = network_configs.iter().map(|config| {
+            let networks = networks.clone();
+            async move {
+                let chain = &config.substrate.chain;
+                let context = start_network(&CONFIG, chain, || {
+                    SubscriptionContext::new(config, is_active_list)
+                })
+                .await;
+                metrics::subscription_count()
+                    .with_label_values(&[chain])
+                    .set(0);
+                networks.insert(chain, context.clone());
+                log::info!("Serving the {chain} validator list.");
+                let context = &context;
+                keep_running(&CONFIG, chain, move || async move {
+                    let error = ValidatorListServer::run_updater(config, context).await;
+                    let _ = context.bus.send(BusEvent::Error);
+                    error
+                })
+                .await;
+            }
+        });
+        futures_util::future::join_all(network_runs).await;
         log::info!("Stopping RPC server...");
         server_stop_handle.stop()?;
         log::info!("RPC server fully stopped.");
-        Err(error)
+        Ok(())
     }
 }
@@ -1,51 +1,162 @@
 use once_cell::sync::OnceCell;
-use subvt_metrics::registry::IntGauge;
+use subvt_metrics::registry::{HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec};
 
-static TARGET_FINALIZED_BLOCK_NUMBER: OnceCell<IntGauge> = OnceCell::new();
-static PROCESSED_FINALIZED_BLOCK_NUMBER: OnceCell<IntGauge> = OnceCell::new();
-static SUBSCRIPTION_COUNT: OnceCell<IntGauge> = OnceCell::new();
+static TARGET_FINALIZED_BLOCK_NUMBER: OnceCell<IntGaugeVec> = OnceCell::new();
+static PROCESSED_FINALIZED_BLOCK_NUMBER: OnceCell<IntGaugeVec> = OnceCell::new();
+static SUBSCRIPTION_COUNT: OnceCell<IntGaugeVec> = OnceCell::new();
+static FILTERED_SUBSCRIPTION_COUNT: OnceCell<IntGauge> = OnceCell::new();
+static RESUMED_SUBSCRIPTION_COUNT: OnceCell<IntCounter> = OnceCell::new();
+static SENT_BYTE_COUNT: OnceCell<IntCounterVec> = OnceCell::new();
+static SLOW_SUBSCRIBER_DISCONNECT_COUNT: OnceCell<IntCounter> = OnceCell::new();
+static SUBSCRIPTION_SENT_BYTES: OnceCell<HistogramVec> = OnceCell::new();
+static SSE_CONNECTION_COUNT: OnceCell<IntGauge> = OnceCell::new();
+static BINARY_WS_CONNECTION_COUNT: OnceCell<IntGauge> = OnceCell::new();
 
 pub(crate) fn init(prefix: &str) {
     if TARGET_FINALIZED_BLOCK_NUMBER.get().is_none() {
         let _ = TARGET_FINALIZED_BLOCK_NUMBER.set(
-            subvt_metrics::registry::register_int_gauge(
+            subvt_metrics::registry::register_int_gauge_vec(
                 prefix,
                 "target_finalized_block_number",
-                "Number of the target finalized block on the node",
+                "Number of the target finalized block on the node by network",
+                &["network"],
             )
             .unwrap(),
         );
     }
     if PROCESSED_FINALIZED_BLOCK_NUMBER.get().is_none() {
         let _ = PROCESSED_FINALIZED_BLOCK_NUMBER.set(
-            subvt_metrics::registry::register_int_gauge(
+            subvt_metrics::registry::register_int_gauge_vec(
                 prefix,
                 "processed_finalized_block_number",
-                "Number of the target finalized block on the node",
+                "Number of the last processed finalized block by network",
+                &["network"],
             )
             .unwrap(),
         );
     }
     if SUBSCRIPTION_COUNT.get().is_none() {
         let _ = SUBSCRIPTION_COUNT.set(
-            subvt_metrics::registry::register_int_gauge(
+            subvt_metrics::registry::register_int_gauge_vec(
                 prefix,
                 "subscription_count",
-                "Number of the target finalized block on the node",
+                "Number of subscribers to the service by network",
+                &["network"],
+            )
+            .unwrap(),
+        );
+    }
+    if FILTERED_SUBSCRIPTION_COUNT.get().is_none() {
+        let _ = FILTERED_SUBSCRIPTION_COUNT.set(
+            subvt_metrics::registry::register_int_gauge(
+                prefix,
+                "filtered_subscription_count",
+                "Number of subscriptions with filter parameters",
+            )
+            .unwrap(),
+        );
+    }
+    if RESUMED_SUBSCRIPTION_COUNT.get().is_none() {
+        let _ = RESUMED_SUBSCRIPTION_COUNT.set(
+            subvt_metrics::registry::register_int_counter(
+                prefix,
+                "resumed_subscription_count",
+                "Number of subscriptions resumed from a past finalized block",
+            )
+            .unwrap(),
+        );
+    }
+    if SLOW_SUBSCRIBER_DISCONNECT_COUNT.get().is_none() {
+        let _ = SLOW_SUBSCRIBER_DISCONNECT_COUNT.set(
+            subvt_metrics::registry::register_int_counter(
+                prefix,
+                "slow_subscriber_disconnect_count",
+                "Number of subscribers disconnected for not keeping up with the updates",
+            )
+            .unwrap(),
+        );
+    }
+    if SENT_BYTE_COUNT.get().is_none() {
+        let _ = SENT_BYTE_COUNT.set(
+            subvt_metrics::registry::register_int_counter_vec(
+                prefix,
+                "sent_byte_count",
+                "Number of bytes sent to the subscribers by message format",
+                &["format"],
+            )
+            .unwrap(),
+        );
+    }
+    if SUBSCRIPTION_SENT_BYTES.get().is_none() {
+        let _ = SUBSCRIPTION_SENT_BYTES.set(
+            subvt_metrics::registry::register_histogram_vec(
+                prefix,
+                "subscription_sent_bytes",
+                "Number of bytes sent to a subscriber during its subscription by message format",
+                &["format"],
+                vec![1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9],
+            )
+            .unwrap(),
+        );
+    }
+    if SSE_CONNECTION_COUNT.get().is_none() {
+        let _ = SSE_CONNECTION_COUNT.set(
+            subvt_metrics::registry::register_int_gauge(
+                prefix,
+                "sse_connection_count",
+                "Number of open Server-Sent Events streams",
+            )
+            .unwrap(),
+        );
+    }
+    if BINARY_WS_CONNECTION_COUNT.get().is_none() {
+        let _ = BINARY_WS_CONNECTION_COUNT.set(
+            subvt_metrics::registry::register_int_gauge(
+                prefix,
+                "binary_ws_connection_count",
+                "Number of open binary WebSocket streams",
             )
             .unwrap(),
         );
     }
 }
 
-pub fn target_finalized_block_number() -> IntGauge {
+pub fn target_finalized_block_number() -> IntGaugeVec {
     TARGET_FINALIZED_BLOCK_NUMBER.get().unwrap().clone()
 }
 
-pub fn processed_finalized_block_number() -> IntGauge {
+pub fn processed_finalized_block_number() -> IntGaugeVec {
     PROCESSED_FINALIZED_BLOCK_NUMBER.get().unwrap().clone()
 }
 
-pub fn subscription_count() -> IntGauge {
+pub fn subscription_count() -> IntGaugeVec {
     SUBSCRIPTION_COUNT.get().unwrap().clone()
 }
+
+pub fn filtered_subscription_count() -> IntGauge {
+    FILTERED_SUBSCRIPTION_COUNT.get().unwrap().clone()
+}
+
+pub fn resumed_subscription_count() -> IntCounter {
+    RESUMED_SUBSCRIPTION_COUNT.get().unwrap().clone()
+}
+
+pub fn slow_subscriber_disconnect_count() -> IntCounter {
+    SLOW_SUBSCRIBER_DISCONNECT_COUNT.get().unwrap().clone()
+}
+
+pub fn sent_byte_count() -> IntCounterVec {
+    SENT_BYTE_COUNT.get().unwrap().clone()
+}
+
+pub fn subscription_sent_bytes() -> HistogramVec {
+    SUBSCRIPTION_SENT_BYTES.get().unwrap().clone()
+}
+
+pub fn sse_connection_count() -> IntGauge {
+    SSE_CONNECTION_COUNT.get().unwrap().clone()
+}
+
+pub fn binary_ws_connection_count() -> IntGauge {
+    BINARY_WS_CONNECTION_COUNT.get().unwrap().clone()
+}
@@ -0,0 +1,224 @@
+//! Resumption of a validator list subscription from a finalized block that the client has
+//! already seen. `subvt-validator-list-updater` keeps the last `history_record_depth` states of
+//! the list in Redis, so the list at that block can be compared with the current one to give a
+//! single compacted update instead of the whole list.
+use anyhow::Context;
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet, FxHasher};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use subvt_types::crypto::AccountId;
+use subvt_types::subvt::{ValidatorDetails, ValidatorListUpdate, ValidatorSummary};
+
+/// Compacted update from a past block to the current state of the list.
+pub struct ValidatorListResume {
+    /// Summaries of the validators at the past block, as the client has them.
+    pub base_summaries: Vec<ValidatorSummary>,
+    pub update: ValidatorListUpdate,
+    /// Current summaries of the inserted and updated validators.
+    pub summaries: HashMap<AccountId, ValidatorSummary>,
+}
+
+pub fn get_summary_hash(summary: &ValidatorSummary) -> u64 {
+    let mut hasher = FxHasher::default();
+    summary.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Retained past states of a validator list.
+#[async_trait]
+pub trait ValidatorListHistory {
+    /// Account ids of the validators in the list at the block, empty if the block is not in the
+    /// history.
+    async fn get_account_ids(&mut self, block_number: u64) -> anyhow::Result<HashSet<AccountId>>;
+
+    /// Summary hashes of the validators at the block, in the order of the account ids.
+    async fn get_summary_hashes(
+        &mut self,
+        block_number: u64,
+        account_ids: &[AccountId],
+    ) -> anyhow::Result<Vec<Option<u64>>>;
+
+    /// Details of the validators at the block, in the order of the account ids.
+    async fn get_validators(
+        &mut self,
+        block_number: u64,
+        account_ids: &[AccountId],
+    ) -> anyhow::Result<Vec<Option<ValidatorDetails>>>;
+}
+
+/// History of a list kept in Redis by `subvt-validator-list-updater`.
+pub struct RedisValidatorListHistory<'a> {
+    pub connection: &'a mut ConnectionManager,
+    pub chain: &'a str,
+    pub is_active_list: bool,
+}
+
+impl RedisValidatorListHistory<'_> {
+    fn get_prefix(&self, block_number: u64) -> String {
+        format!(
+            "subvt:{}:validators:{block_number}:{}",
+            self.chain,
+            if self.is_active_list {
+                "active"
+            } else {
+                "inactive"
+            }
+        )
+    }
+}
+
+#[async_trait]
+impl ValidatorListHistory for RedisValidatorListHistory<'_> {
+    async fn get_account_ids(&mut self, block_number: u64) -> anyhow::Result<HashSet<AccountId>> {
+        let account_ids: HashSet<String> = redis::cmd("SMEMBERS")
+            .arg(format!("{}:account_id_set", self.get_prefix(block_number)))
+            .query_async(self.connection)
+            .await
+            .context("Can't read base validator account ids from Redis.")?;
+        Ok(account_ids
+            .iter()
+            .map(|account_id| AccountId::from_str(account_id))
+            .collect::<Result<HashSet<AccountId>, _>>()?)
+    }
+
+    async fn get_summary_hashes(
+        &mut self,
+        block_number: u64,
+        account_ids: &[AccountId],
+    ) -> anyhow::Result<Vec<Option<u64>>> {
+        // MGET needs at least one key
+        if account_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let prefix = self.get_prefix(block_number);
+        let mut cmd = redis::cmd("MGET");
+        for account_id in account_ids {
+            cmd.arg(format!("{prefix}:validator:{account_id}:summary_hash"));
+        }
+        cmd.query_async(self.connection)
+            .await
+            .context("Can't read base validator summary hashes from Redis.")
+    }
+
+    async fn get_validators(
+        &mut self,
+        block_number: u64,
+        account_ids: &[AccountId],
+    ) -> anyhow::Result<Vec<Option<ValidatorDetails>>> {
+        // MGET needs at least one key
+        if account_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let prefix = self.get_prefix(block_number);
+        let mut cmd = redis::cmd("MGET");
+        for account_id in account_ids {
+            cmd.arg(format!("{prefix}:validator:{account_id}"));
+        }
+        let validator_json_strings: Vec<Option<String>> = cmd
+            .query_async(self.connection)
+            .await
+            .context("Can't read base validator JSON strings from Redis.")?;
+        let mut validators = Vec::with_capacity(validator_json_strings.len());
+        for validator_json_string in validator_json_strings {
+            validators.push(match validator_json_string {
+                Some(validator_json_string) => Some(serde_json::from_str(&validator_json_string)?),
+                None => None,
+            });
+        }
+        Ok(validators)
+    }
+}
+
+/// Returns `None` if the base block is not in the retained history, in which case the client
+/// should get the full list.
+pub async fn get_resume(
+    history: &mut (impl ValidatorListHistory + Send),
+    base_block_number: u64,
+    current_block_number: u64,
+    current_summaries: &HashMap<AccountId, ValidatorSummary>,
+) -> anyhow::Result<Option<ValidatorListResume>> {
+    if base_block_number > current_block_number {
+        return Ok(None);
+    }
+    let mut update = ValidatorListUpdate {
+        finalized_block_number: Some(current_block_number),
+        ..Default::default()
+    };
+    if base_block_number == current_block_number {
+        return Ok(Some(ValidatorListResume {
+            base_summaries: current_summaries.values().cloned().collect(),
+            update,
+            summaries: HashMap::default(),
+        }));
+    }
+    let base_account_ids = history.get_account_ids(base_block_number).await?;
+    if base_account_ids.is_empty() {
+        return Ok(None);
+    }
+    let common_account_ids: Vec<AccountId> = base_account_ids
+        .iter()
+        .filter(|account_id| current_summaries.contains_key(*account_id))
+        .cloned()
+        .collect();
+    // compare the summary hashes first to fetch only the changed and removed validators
+    let base_summary_hashes: Vec<Option<u64>> = if common_account_ids.is_empty() {
+        Vec::new()
+    } else {
+        history
+            .get_summary_hashes(base_block_number, &common_account_ids)
+            .await?
+    };
+    let mut fetch_account_ids: Vec<AccountId> = Vec::new();
+    let mut base_summaries: Vec<ValidatorSummary> = Vec::with_capacity(base_account_ids.len());
+    for (account_id, base_summary_hash) in common_account_ids.iter().zip(base_summary_hashes) {
+        let current_summary = &current_summaries[account_id];
+        if base_summary_hash == Some(get_summary_hash(current_summary)) {
+            base_summaries.push(current_summary.clone());
+        } else {
+            fetch_account_ids.push(*account_id);
+        }
+    }
+    fetch_account_ids.extend(
+        base_account_ids
+            .iter()
+            .filter(|account_id| !current_summaries.contains_key(*account_id))
+            .cloned(),
+    );
+    if !fetch_account_ids.is_empty() {
+        for base_validator in history
+            .get_validators(base_block_number, &fetch_account_ids)
+            .await?
+        {
+            let Some(base_validator) = base_validator else {
+                // history is being cleared
+                return Ok(None);
+            };
+            base_summaries.push(ValidatorSummary::from(&base_validator));
+        }
+    }
+    let mut summaries = HashMap::default();
+    for base_summary in &base_summaries {
+        match current_summaries.get(&base_summary.account_id) {
+            Some(current_summary) => {
+                if base_summary != current_summary {
+                    update.update.push(base_summary.get_diff(current_summary));
+                    summaries.insert(base_summary.account_id, current_summary.clone());
+                }
+            }
+            None => update.remove_ids.push(base_summary.account_id),
+        }
+    }
+    for (account_id, current_summary) in current_summaries {
+        if !base_account_ids.contains(account_id) {
+            update.insert.push(current_summary.clone());
+            summaries.insert(*account_id, current_summary.clone());
+        }
+    }
+    Ok(Some(ValidatorListResume {
+        base_summaries,
+        update,
+        summaries,
+    }))
+}
@@ -0,0 +1,335 @@
+use async_trait::async_trait;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use serde_json::json;
+use std::borrow::Cow;
+use subvt_types::crypto::AccountId;
+use subvt_types::subvt::{
+    ValidatorDetails, ValidatorListUpdate, ValidatorStakeSummary, ValidatorSummary,
+};
+use subvt_validator_list_server::filter::{
+    ValidatorListFilter, ValidatorListFilterParameters, ValidatorListSubscription,
+};
+use subvt_validator_list_server::resume::{get_resume, get_summary_hash, ValidatorListHistory};
+
+fn validator(id: u8, display: Option<&str>, self_stake: u128, commission: u32) -> ValidatorSummary {
+    let mut validator = ValidatorSummary {
+        account_id: AccountId::from([id; 32]),
+        display: display.map(str::to_string),
+        ..Default::default()
+    };
+    validator.self_stake.active_amount = self_stake;
+    validator.preferences.commission_per_billion = commission;
+    validator
+}
+
+fn filter(params: serde_json::Value) -> Result<ValidatorListFilter, String> {
+    let params: ValidatorListFilterParameters = serde_json::from_value(params).unwrap();
+    ValidatorListFilter::new(params)
+}
+
+fn is_match(params: serde_json::Value, validator: &ValidatorSummary) -> bool {
+    filter(params).unwrap().matches(validator)
+}
+
+#[test]
+fn test_filter_parameters() {
+    let params: ValidatorListFilterParameters = serde_json::from_value(json!({})).unwrap();
+    assert!(params.is_empty());
+    let params: ValidatorListFilterParameters =
+        serde_json::from_value(json!({ "is_para_validator": false })).unwrap();
+    assert!(!params.is_empty());
+    assert!(filter(json!({ "min_self_stake": "1.5" })).is_err());
+    assert!(filter(json!({ "max_total_stake": "-1" })).is_err());
+    assert!(filter(json!({ "account_ids": ["invalid"] })).is_err());
+}
+
+#[test]
+fn test_filter_account_ids() {
+    let validator = validator(1, None, 0, 0);
+    let account_id = AccountId::from([1; 32]);
+    assert!(is_match(
+        json!({ "account_ids": [account_id.to_string()] }),
+        &validator
+    ));
+    assert!(is_match(
+        json!({ "account_ids": [account_id.to_ss58_check()] }),
+        &validator
+    ));
+    assert!(!is_match(
+        json!({ "account_ids": [AccountId::from([2; 32]).to_string()] }),
+        &validator
+    ));
+}
+
+#[test]
+fn test_filter_identity() {
+    let mut validator = validator(1, Some("Validator"), 0, 0);
+    assert!(is_match(json!({ "identity": "VALID" }), &validator));
+    assert!(!is_match(json!({ "identity": "other" }), &validator));
+    // empty identity doesn't filter
+    assert!(is_match(json!({ "identity": "" }), &validator));
+    validator.display = None;
+    validator.parent_display = Some("Parent".to_string());
+    assert!(is_match(json!({ "identity": "parent" }), &validator));
+    validator.parent_display = None;
+    assert!(!is_match(json!({ "identity": "parent" }), &validator));
+}
+
+#[test]
+fn test_filter_ranges() {
+    let mut validator = validator(1, None, 100, 50_000_000);
+    validator.inactive_nominations.total_amount = 400;
+    assert!(is_match(
+        json!({ "min_self_stake": "100", "max_self_stake": "100" }),
+        &validator
+    ));
+    assert!(!is_match(json!({ "min_self_stake": "101" }), &validator));
+    // inactive validator's total stake is its self stake plus the nominations
+    assert!(is_match(json!({ "min_total_stake": "500" }), &validator));
+    assert!(!is_match(json!({ "min_total_stake": "501" }), &validator));
+    // active validator's total stake is its active stake
+    validator.validator_stake = Some(ValidatorStakeSummary {
+        self_stake: 100,
+        total_stake: 1000,
+        nominator_count: 2,
+    });
+    assert!(is_match(json!({ "min_total_stake": "1000" }), &validator));
+    assert!(!is_match(json!({ "max_total_stake": "999" }), &validator));
+    assert!(is_match(
+        json!({ "min_commission_per_billion": 50_000_000, "max_commission_per_billion": 100_000_000 }),
+        &validator
+    ));
+    assert!(!is_match(
+        json!({ "max_commission_per_billion": 10_000_000 }),
+        &validator
+    ));
+    assert!(is_match(json!({ "is_para_validator": false }), &validator));
+    assert!(!is_match(json!({ "is_para_validator": true }), &validator));
+    // all criteria should match
+    assert!(!is_match(
+        json!({ "min_self_stake": "100", "is_para_validator": true }),
+        &validator
+    ));
+}
+
+#[test]
+fn test_subscription_filter_update() {
+    let mut subscription =
+        ValidatorListSubscription::new(Some(filter(json!({ "min_self_stake": "100" })).unwrap()));
+    assert!(subscription.is_filtered());
+    let initial_update = subscription.get_initial_update(
+        Some(10),
+        vec![validator(1, None, 100, 0), validator(2, None, 50, 0)],
+    );
+    assert_eq!(initial_update.insert.len(), 1);
+    assert_eq!(
+        initial_update.insert[0].account_id,
+        AccountId::from([1; 32])
+    );
+    // validator #1 drops below the filter, #2 rises above it, #3 is inserted below it
+    let before = [validator(1, None, 100, 0), validator(2, None, 50, 0)];
+    let after = [validator(1, None, 90, 0), validator(2, None, 150, 0)];
+    let mut summaries = HashMap::default();
+    for validator in &after {
+        summaries.insert(validator.account_id, validator.clone());
+    }
+    let update = ValidatorListUpdate {
+        finalized_block_number: Some(11),
+        insert: vec![validator(3, None, 10, 0)],
+        update: vec![before[0].get_diff(&after[0]), before[1].get_diff(&after[1])],
+        remove_ids: vec![],
+    };
+    let filtered = subscription.filter_update(&update, &summaries);
+    assert_eq!(filtered.finalized_block_number, Some(11));
+    assert_eq!(filtered.insert, vec![after[1].clone()]);
+    assert!(filtered.update.is_empty());
+    assert_eq!(filtered.remove_ids, vec![AccountId::from([1; 32])]);
+    // removal of a validator that's not in the subscriber's list is not sent
+    let update = ValidatorListUpdate {
+        finalized_block_number: Some(12),
+        remove_ids: vec![AccountId::from([1; 32]), AccountId::from([2; 32])],
+        ..Default::default()
+    };
+    let filtered = subscription.filter_update(&update, &HashMap::default());
+    assert_eq!(filtered.remove_ids, vec![AccountId::from([2; 32])]);
+}
+
+#[test]
+fn test_unfiltered_subscription() {
+    let mut subscription = ValidatorListSubscription::new(None);
+    assert!(!subscription.is_filtered());
+    let initial_update = subscription.get_initial_update(
+        None,
+        vec![validator(1, None, 100, 0), validator(2, None, 50, 0)],
+    );
+    assert_eq!(initial_update.insert.len(), 2);
+    let update = ValidatorListUpdate {
+        finalized_block_number: Some(11),
+        insert: vec![validator(3, None, 10, 0)],
+        ..Default::default()
+    };
+    assert!(matches!(
+        subscription.filter_update(&update, &HashMap::default()),
+        Cow::Borrowed(_)
+    ));
+}
+
+/// In-memory history of the validator list, which records the validators it's asked for.
+#[derive(Default)]
+struct TestHistory {
+    blocks: HashMap<u64, HashMap<AccountId, ValidatorDetails>>,
+    fetched_account_ids: Vec<AccountId>,
+}
+
+#[async_trait]
+impl ValidatorListHistory for TestHistory {
+    async fn get_account_ids(&mut self, block_number: u64) -> anyhow::Result<HashSet<AccountId>> {
+        Ok(self
+            .blocks
+            .get(&block_number)
+            .map(|validators| validators.keys().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    async fn get_summary_hashes(
+        &mut self,
+        block_number: u64,
+        account_ids: &[AccountId],
+    ) -> anyhow::Result<Vec<Option<u64>>> {
+        Ok(account_ids
+            .iter()
+            .map(|account_id| {
+                self.blocks[&block_number]
+                    .get(account_id)
+                    .map(|validator| get_summary_hash(&validator.into()))
+            })
+            .collect())
+    }
+
+    async fn get_validators(
+        &mut self,
+        block_number: u64,
+        account_ids: &[AccountId],
+    ) -> anyhow::Result<Vec<Option<ValidatorDetails>>> {
+        self.fetched_account_ids.extend_from_slice(account_ids);
+        Ok(account_ids
+            .iter()
+            .map(|account_id| self.blocks[&block_number].get(account_id).cloned())
+            .collect())
+    }
+}
+
+fn validator_details(id: u8, self_stake: u128) -> ValidatorDetails {
+    let mut validator = ValidatorDetails::default();
+    validator.account.id = AccountId::from([id; 32]);
+    validator.self_stake.active_amount = self_stake;
+    validator
+}
+
+fn validator_details_map(validators: &[ValidatorDetails]) -> HashMap<AccountId, ValidatorDetails> {
+    validators
+        .iter()
+        .map(|validator| (validator.account.id, validator.clone()))
+        .collect()
+}
+
+fn summary_map(validators: &[ValidatorDetails]) -> HashMap<AccountId, ValidatorSummary> {
+    validators
+        .iter()
+        .map(|validator| (validator.account.id, validator.into()))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_resume_after_gap() {
+    let mut history = TestHistory::default();
+    history.blocks.insert(
+        10,
+        validator_details_map(&[
+            validator_details(1, 100),
+            validator_details(2, 100),
+            validator_details(3, 100),
+        ]),
+    );
+    // validator #1 is unchanged, #2 is updated, #3 is removed and #4 is inserted by block 15
+    let current = [
+        validator_details(1, 100),
+        validator_details(2, 200),
+        validator_details(4, 100),
+    ];
+    let current_summaries = summary_map(&current);
+    let resume = get_resume(&mut history, 10, 15, &current_summaries)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(resume.update.finalized_block_number, Some(15));
+    assert_eq!(resume.base_summaries.len(), 3);
+    let inserted_ids: Vec<AccountId> = resume
+        .update
+        .insert
+        .iter()
+        .map(|validator| validator.account_id)
+        .collect();
+    assert_eq!(inserted_ids, vec![AccountId::from([4; 32])]);
+    assert_eq!(resume.update.update.len(), 1);
+    assert_eq!(resume.update.update[0].account_id, AccountId::from([2; 32]));
+    assert_eq!(
+        resume.update.update[0]
+            .self_stake
+            .as_ref()
+            .map(|stake| stake.active_amount),
+        Some(200)
+    );
+    assert_eq!(resume.update.remove_ids, vec![AccountId::from([3; 32])]);
+    let mut summary_ids: Vec<u8> = resume
+        .summaries
+        .keys()
+        .map(|account_id| account_id.as_ref()[0])
+        .collect();
+    summary_ids.sort();
+    assert_eq!(summary_ids, vec![2, 4]);
+    // only the changed and the removed validators are fetched
+    let mut fetched_ids: Vec<u8> = history
+        .fetched_account_ids
+        .iter()
+        .map(|account_id| account_id.as_ref()[0])
+        .collect();
+    fetched_ids.sort();
+    assert_eq!(fetched_ids, vec![2, 3]);
+    // the subscriber gets the same update, and its list at the base block is restored
+    let mut subscription = ValidatorListSubscription::new(None);
+    let update =
+        subscription.get_resume_update(&resume.base_summaries, &resume.update, &resume.summaries);
+    assert_eq!(update.insert.len(), 1);
+    assert_eq!(update.update.len(), 1);
+    assert_eq!(update.remove_ids.len(), 1);
+}
+
+#[tokio::test]
+async fn test_resume_unknown_block() {
+    let mut history = TestHistory::default();
+    history
+        .blocks
+        .insert(10, validator_details_map(&[validator_details(1, 100)]));
+    let current_summaries = summary_map(&[validator_details(1, 100)]);
+    // not in history
+    assert!(get_resume(&mut history, 5, 15, &current_summaries)
+        .await
+        .unwrap()
+        .is_none());
+    // ahead of the current block
+    assert!(get_resume(&mut history, 16, 15, &current_summaries)
+        .await
+        .unwrap()
+        .is_none());
+    // at the current block
+    let resume = get_resume(&mut history, 15, 15, &current_summaries)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(resume.update.insert.is_empty());
+    assert!(resume.update.update.is_empty());
+    assert!(resume.update.remove_ids.is_empty());
+    assert_eq!(resume.base_summaries.len(), 1);
+    assert!(history.fetched_account_ids.is_empty());
+}
@@ -1,23 +1,28 @@
-//! Updates the Redis database with the complete validator list after every block.
+//! Updates the Redis database with the complete validator list after every block, and saves
+//! the per-era validator details snapshots to the PostgreSQL network database.
 //! Subscribes to the new blocks using the Substrate client in `subvt-substrate-client`.
 #![warn(clippy::disallowed_types)]
 use anyhow::Context;
-use async_lock::RwLock;
+use async_lock::{Mutex, RwLock};
 use async_trait::async_trait;
 use lazy_static::lazy_static;
 use once_cell::sync::OnceCell;
 use redis::Pipeline;
-use rustc_hash::{FxHashSet as HashSet, FxHasher};
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet, FxHasher};
 use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicU64, Ordering},
     Arc,
 };
 use subvt_config::Config;
+use subvt_election::{BalancingConfig, ElectionAlgorithm};
+use subvt_metrics::health;
 use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
 use subvt_service_common::Service;
-use subvt_substrate_client::SubstrateClient;
+use subvt_substrate_client::{RpcStats, SubstrateClient};
 use subvt_types::crypto::AccountId;
+use subvt_types::election::ElectionPrediction;
 use subvt_types::rdb::ValidatorInfo;
 use subvt_types::substrate::{BlockHeader, Era};
 use subvt_types::subvt::{ValidatorDetails, ValidatorSummary};
@@ -27,6 +32,11 @@ mod metrics;
 lazy_static! {
     static ref CONFIG: Config = Config::default();
     static ref IS_BUSY: AtomicBool = AtomicBool::new(false);
+    static ref LAST_ELECTION_PREDICTION_BLOCK_NUMBER: AtomicU64 = AtomicU64::new(0);
+    /// Era index and the validator details of the last saved era snapshot, the base of the
+    /// diffs in the next era's snapshot.
+    static ref LAST_DETAILS_SNAPSHOT: Mutex<Option<(u32, HashMap<AccountId, ValidatorDetails>)>> =
+        Mutex::new(None);
 }
 
 #[derive(Default)]
@@ -201,6 +211,151 @@ impl ValidatorListUpdater {
         Ok(())
     }
 
+    /// Predicts the result of the next election and writes it to Redis, at most once in the
+    /// configured number of blocks.
+    async fn update_election_prediction(
+        asset_hub_client: &SubstrateClient,
+        asset_hub_block_hash: &str,
+        active_era: &Era,
+        block_number: u64,
+        block_timestamp: u64,
+    ) -> anyhow::Result<()> {
+        let last_prediction_block_number =
+            LAST_ELECTION_PREDICTION_BLOCK_NUMBER.load(Ordering::SeqCst);
+        if last_prediction_block_number > 0
+            && block_number
+                < last_prediction_block_number + CONFIG.election.prediction_block_interval
+        {
+            return Ok(());
+        }
+        let algorithm = ElectionAlgorithm::from_str(&CONFIG.election.algorithm)?;
+        let snapshot = asset_hub_client
+            .get_election_snapshot(asset_hub_block_hash)
+            .await?;
+        asset_hub_client.clear_storage_cache();
+        // the chain's balancing iterations take precedence over the configuration
+        let balancing_iterations = snapshot
+            .balancing_iterations
+            .map(|iterations| iterations as usize)
+            .unwrap_or(CONFIG.election.balancing_iterations);
+        let balancing = if balancing_iterations > 0 {
+            Some(BalancingConfig {
+                iterations: balancing_iterations,
+                tolerance: CONFIG.election.balancing_tolerance as u128,
+            })
+        } else {
+            None
+        };
+        let prediction = ElectionPrediction {
+            block_number,
+            active_era_index: active_era.index,
+            timestamp: block_timestamp,
+            ..tokio::task::spawn_blocking(move || {
+                subvt_election::predict(&snapshot, algorithm, balancing)
+            })
+            .await?
+        };
+        let redis_client = redis::Client::open(CONFIG.redis.url.as_str())?;
+        let mut redis_connection = redis_client
+            .get_multiplexed_async_connection()
+            .await
+            .context(format!(
+                "Cannot connect to Redis at URL {}.",
+                CONFIG.redis.url
+            ))?;
+        redis::cmd("SET")
+            .arg(format!(
+                "subvt:{}:election_prediction",
+                CONFIG.substrate.chain
+            ))
+            .arg(serde_json::to_string(&prediction)?)
+            .query_async::<()>(&mut redis_connection)
+            .await
+            .context("Error while setting Redis election prediction.")?;
+        metrics::election_predicted_winner_count().set(prediction.winners.len() as i64);
+        LAST_ELECTION_PREDICTION_BLOCK_NUMBER.store(block_number, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Saves the details of all validators at the first processed block of each era. The details
+    /// of a validator are saved in full once in the configured number of eras, or when the
+    /// validator has no snapshot in the previous snapshot era, such as after a restart. Otherwise
+    /// only the diff from the previous snapshot is saved.
+    async fn save_era_details_snapshot(
+        postgres: &PostgreSQLNetworkStorage,
+        active_era: &Era,
+        block_number: u64,
+        block_hash: &str,
+        validators: &[ValidatorDetails],
+    ) -> anyhow::Result<()> {
+        let mut last_snapshot = LAST_DETAILS_SNAPSHOT.lock().await;
+        let maybe_last_era_index = last_snapshot.as_ref().map(|(era_index, _)| *era_index);
+        match maybe_last_era_index {
+            Some(last_era_index) if last_era_index >= active_era.index => return Ok(()),
+            None if postgres
+                .validator_details_snapshot_exists(active_era.index)
+                .await? =>
+            {
+                // saved before a restart, the next era's snapshot will be in full
+                *last_snapshot = Some((active_era.index, HashMap::default()));
+                return Ok(());
+            }
+            _ => (),
+        }
+        let full_interval = CONFIG
+            .validator_list_updater
+            .details_snapshot_full_interval_eras
+            .max(1);
+        let is_full_era = active_era.index % full_interval == 0;
+        let previous_validators = last_snapshot
+            .take()
+            .map(|(_, validators)| validators)
+            .unwrap_or_default();
+        let mut snapshots = Vec::with_capacity(validators.len());
+        let mut full_count = 0;
+        for validator in validators {
+            match previous_validators.get(&validator.account.id) {
+                Some(previous_validator) if !is_full_era => {
+                    snapshots.push((
+                        validator.account.id,
+                        false,
+                        serde_json::to_string(&previous_validator.get_diff(validator))?,
+                    ));
+                }
+                _ => {
+                    full_count += 1;
+                    snapshots.push((
+                        validator.account.id,
+                        true,
+                        serde_json::to_string(validator)?,
+                    ));
+                }
+            }
+        }
+        postgres
+            .save_validator_details_snapshots(
+                active_era.index,
+                block_number,
+                block_hash,
+                &snapshots,
+            )
+            .await?;
+        log::info!(
+            "Saved era {} validator details snapshot with {} full and {} diff records.",
+            active_era.index,
+            full_count,
+            snapshots.len() - full_count,
+        );
+        *last_snapshot = Some((
+            active_era.index,
+            validators
+                .iter()
+                .map(|validator| (validator.account.id, validator.clone()))
+                .collect(),
+        ));
+        Ok(())
+    }
+
     async fn fetch_and_update_validator_list(
         relay_client: &SubstrateClient,
         asset_hub_client: &SubstrateClient,
@@ -211,6 +366,15 @@ impl ValidatorListUpdater {
         block_hash: &str,
         _header: &BlockHeader,
     ) -> anyhow::Result<Vec<ValidatorDetails>> {
+        let clients = [
+            ("relay", relay_client),
+            ("asset_hub", asset_hub_client),
+            ("people", people_client),
+        ];
+        let rpc_stats_before: Vec<RpcStats> = clients
+            .iter()
+            .map(|(_, client)| client.get_rpc_stats())
+            .collect();
         let relay_finalized_block_timestamp = relay_client.get_block_timestamp(block_hash).await?;
         // asset hub block
         let asset_hub_finalized_block_hash = asset_hub_client.get_finalized_block_hash().await?;
@@ -227,6 +391,26 @@ impl ValidatorListUpdater {
             )
             .await
             .context("Error while getting validators.")?;
+        for ((label, client), before) in clients.iter().zip(rpc_stats_before.iter()) {
+            // cached values belong to this update's blocks only
+            client.clear_storage_cache();
+            let rpc_stats = client.get_rpc_stats().since(before);
+            log::info!(
+                "{label} client: {} RPC requests, {} storage keys, {} storage cache hits.",
+                rpc_stats.request_count,
+                rpc_stats.storage_key_count,
+                rpc_stats.storage_cache_hit_count,
+            );
+            metrics::rpc_request_count()
+                .with_label_values(&[label])
+                .set(rpc_stats.request_count as i64);
+            metrics::rpc_storage_key_count()
+                .with_label_values(&[label])
+                .set(rpc_stats.storage_key_count as i64);
+            metrics::rpc_storage_cache_hit_count()
+                .with_label_values(&[label])
+                .set(rpc_stats.storage_cache_hit_count as i64);
+        }
         // enrich data with data from the relational database
         log::info!("Get RDB content for {} validators.", validators.len());
         let mut db_validator_infos: Vec<ValidatorInfo> = Vec::new();
@@ -281,6 +465,31 @@ impl ValidatorListUpdater {
         .await?;
         let elapsed = start.elapsed();
         log::info!("Redis updated. Took {} ms.", elapsed.as_millis());
+        // a failed snapshot should not fail the validator list update, it gets retried with the
+        // next block
+        if let Err(error) = ValidatorListUpdater::save_era_details_snapshot(
+            postgres,
+            &active_era,
+            block_number,
+            block_hash,
+            &validators,
+        )
+        .await
+        {
+            log::error!("Error while saving the era validator details snapshot: {error:?}");
+        }
+        // a failed prediction should not fail the validator list update
+        if let Err(error) = ValidatorListUpdater::update_election_prediction(
+            asset_hub_client,
+            &asset_hub_finalized_block_hash,
+            &active_era,
+            block_number,
+            relay_finalized_block_timestamp,
+        )
+        .await
+        {
+            log::error!("Error while predicting the next election: {error:?}");
+        }
         {
             let mut processed_block_numbers = processed_block_numbers.write().await;
             processed_block_numbers.push(block_number);
@@ -294,6 +503,35 @@ impl ValidatorListUpdater {
         Ok(validators)
     }
 
+    /// Updates the validator list once for the current finalized relay block, outside the
+    /// finalized block subscription, and returns the block number and the validators. The replay
+    /// tests run the updater through this.
+    pub async fn update_finalized_validator_list(
+        relay_client: &SubstrateClient,
+        asset_hub_client: &SubstrateClient,
+        people_client: &SubstrateClient,
+        postgres: &PostgreSQLNetworkStorage,
+    ) -> anyhow::Result<(u64, Vec<ValidatorDetails>)> {
+        let block_hash = relay_client.get_finalized_block_hash().await?;
+        let header = relay_client.get_block_header(&block_hash).await?;
+        let block_number = header.get_number()?;
+        let processed_block_numbers: Arc<RwLock<Vec<u64>>> = Arc::new(RwLock::new(
+            ValidatorListUpdater::fetch_processed_block_numbers().await?,
+        ));
+        let validators = ValidatorListUpdater::fetch_and_update_validator_list(
+            relay_client,
+            asset_hub_client,
+            people_client,
+            postgres,
+            &processed_block_numbers,
+            block_number,
+            &block_hash,
+            &header,
+        )
+        .await?;
+        Ok((block_number, validators))
+    }
+
     async fn store_processed_block_numbers(processed_block_numbers: &[u64]) -> anyhow::Result<()> {
         let redis_client = redis::Client::open(CONFIG.redis.url.as_str())?;
         let mut redis_connection = redis_client
@@ -394,9 +632,18 @@ impl Service for ValidatorListUpdater {
                 )
                 .await?,
             );
+            for client in [
+                &substrate_client,
+                &asset_hub_substrate_client,
+                &people_substrate_client,
+            ] {
+                client
+                    .set_storage_cache_enabled(CONFIG.validator_list_updater.storage_cache_enabled);
+            }
             let processed_block_numbers: Arc<RwLock<Vec<u64>>> = Arc::new(RwLock::new(
                 ValidatorListUpdater::fetch_processed_block_numbers().await?,
             ));
+            health::set_ready(true);
             substrate_client.subscribe_to_finalized_blocks(
                 CONFIG.substrate.request_timeout_seconds,
                 |finalized_block_header| async {
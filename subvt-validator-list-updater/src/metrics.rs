@@ -1,5 +1,5 @@
 use once_cell::sync::Lazy;
-use subvt_metrics::registry::{Histogram, IntGauge};
+use subvt_metrics::registry::{Histogram, IntGauge, IntGaugeVec};
 
 const METRIC_PREFIX: &str = "subvt_validator_list_updater";
 
@@ -42,3 +42,54 @@ pub fn processing_time_ms() -> Histogram {
     });
     METER.clone()
 }
+
+pub fn rpc_request_count() -> IntGaugeVec {
+    static METER: Lazy<IntGaugeVec> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_gauge_vec(
+            METRIC_PREFIX,
+            "rpc_request_count",
+            "Number of RPC requests sent during the last validator list update",
+            &["client"],
+        )
+        .unwrap()
+    });
+    METER.clone()
+}
+
+pub fn rpc_storage_key_count() -> IntGaugeVec {
+    static METER: Lazy<IntGaugeVec> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_gauge_vec(
+            METRIC_PREFIX,
+            "rpc_storage_key_count",
+            "Number of storage keys queried during the last validator list update",
+            &["client"],
+        )
+        .unwrap()
+    });
+    METER.clone()
+}
+
+pub fn rpc_storage_cache_hit_count() -> IntGaugeVec {
+    static METER: Lazy<IntGaugeVec> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_gauge_vec(
+            METRIC_PREFIX,
+            "rpc_storage_cache_hit_count",
+            "Number of storage values served from the cache during the last validator list update",
+            &["client"],
+        )
+        .unwrap()
+    });
+    METER.clone()
+}
+
+pub fn election_predicted_winner_count() -> IntGauge {
+    static METER: Lazy<IntGauge> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_gauge(
+            METRIC_PREFIX,
+            "election_predicted_winner_count",
+            "Number of winners in the last next election prediction",
+        )
+        .unwrap()
+    });
+    METER.clone()
+}
@@ -0,0 +1,100 @@
+//! Updates the validator list from recorded real Kusama state, against the RPC replay server.
+use subvt_config::Config;
+use subvt_persistence::postgres::network::PostgreSQLNetworkStorage;
+use subvt_substrate_client::fixture::{
+    get_recorded_fixture_path, load_recorded_fixture, RpcRecorder, RpcReplayServer,
+};
+use subvt_substrate_client::SubstrateClient;
+use subvt_validator_list_updater::ValidatorListUpdater;
+
+const FIXTURE_NAMES: [&str; 3] = [
+    "kusama_validator_list_updater_relay",
+    "kusama_validator_list_updater_asset_hub",
+    "kusama_validator_list_updater_people",
+];
+
+/// Records the fixtures of the current finalized state from the network nodes. Run with
+/// `cargo test -p subvt-validator-list-updater --test replay -- --ignored` to (re-)record them.
+#[tokio::test]
+#[ignore]
+async fn record_validator_list_updater_fixtures() {
+    // the updater writes to the Redis instance of the test environment
+    std::env::set_var("SUBVT_ENV", "test");
+    let config = Config::test().expect("Cannot get test config.");
+    let mut recorders = Vec::new();
+    let mut clients = Vec::new();
+    for rpc_url in [
+        &config.substrate.rpc_url,
+        &config.substrate.asset_hub_rpc_url,
+        &config.substrate.people_rpc_url,
+    ] {
+        let recorder = RpcRecorder::default();
+        let client = SubstrateClient::new_recording(
+            rpc_url.as_str(),
+            config.substrate.network_id,
+            config.substrate.connection_timeout_seconds,
+            config.substrate.request_timeout_seconds,
+            &recorder,
+        )
+        .await
+        .expect("Cannot initialize recording client.");
+        recorders.push(recorder);
+        clients.push(client);
+    }
+    let postgres = PostgreSQLNetworkStorage::new(&config, config.get_network_postgres_url())
+        .await
+        .expect("Cannot connect to the network database.");
+    ValidatorListUpdater::update_finalized_validator_list(
+        &clients[0],
+        &clients[1],
+        &clients[2],
+        &postgres,
+    )
+    .await
+    .expect("Cannot update validator list.");
+    for (recorder, fixture_name) in recorders.iter().zip(FIXTURE_NAMES) {
+        recorder
+            .save(get_recorded_fixture_path(fixture_name))
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_update_recorded_validator_list() {
+    std::env::set_var("SUBVT_ENV", "test");
+    let config = Config::test().expect("Cannot get test config.");
+    let mut clients = Vec::new();
+    let mut server_handles = Vec::new();
+    for fixture_name in FIXTURE_NAMES {
+        let fixture = load_recorded_fixture(fixture_name).expect("Cannot load fixture.");
+        let (address, server_handle) = RpcReplayServer::start(fixture, "127.0.0.1", 0)
+            .await
+            .expect("Cannot start replay server.");
+        let client = SubstrateClient::new(
+            &format!("ws://{address}"),
+            config.substrate.network_id,
+            config.substrate.connection_timeout_seconds,
+            config.substrate.request_timeout_seconds,
+        )
+        .await
+        .expect("Cannot connect to replay server.");
+        clients.push(client);
+        server_handles.push(server_handle);
+    }
+    let postgres = PostgreSQLNetworkStorage::new(&config, config.get_network_postgres_url())
+        .await
+        .expect("Cannot connect to the network database.");
+    let (_, validators) = ValidatorListUpdater::update_finalized_validator_list(
+        &clients[0],
+        &clients[1],
+        &clients[2],
+        &postgres,
+    )
+    .await
+    .expect("Cannot update validator list from recorded state.");
+    assert!(validators.iter().any(|validator| validator.is_active));
+    assert!(validators.iter().any(|validator| !validator.is_active));
+    for server_handle in server_handles {
+        server_handle.stop().unwrap();
+    }
+}
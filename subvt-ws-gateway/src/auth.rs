@@ -0,0 +1,167 @@
+//! Authentication of the gateway connections. The WebSocket handshake request carries the same
+//! `SubVT-Public-Key`, `SubVT-Signature` and `SubVT-Nonce` headers as the secure requests to the
+//! app service, and the signed message is the method, the path and the nonce of the handshake
+//! request (the body is empty). The public key should belong to a registered app user.
+use crate::metrics;
+use http::StatusCode;
+use jsonrpsee::server::{HttpBody, HttpRequest, HttpResponse};
+use libsecp256k1::{verify, Message, PublicKey, PublicKeyFormat, Signature};
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use subvt_persistence::postgres::app::PostgreSQLAppStorage;
+use subvt_types::app::User;
+use subvt_types::err::ServiceError;
+
+/// Authentication error with the status code of the handshake response.
+struct AuthError(StatusCode, &'static str);
+
+impl AuthError {
+    fn forbidden(description: &'static str) -> Self {
+        Self(StatusCode::FORBIDDEN, description)
+    }
+
+    fn into_response(self) -> HttpResponse {
+        let body = serde_json::to_string(&ServiceError::from(self.1)).unwrap();
+        HttpResponse::builder()
+            .status(self.0)
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(HttpBody::from(body))
+            .unwrap()
+    }
+}
+
+async fn authenticate(
+    postgres: &PostgreSQLAppStorage,
+    request: &HttpRequest,
+) -> Result<User, AuthError> {
+    let public_key_header = request
+        .headers()
+        .get("SubVT-Public-Key")
+        .ok_or(AuthError::forbidden("Public key header is missing."))?;
+    let signature_header = request
+        .headers()
+        .get("SubVT-Signature")
+        .ok_or(AuthError::forbidden("Signature header is missing."))?;
+    let nonce_header = request
+        .headers()
+        .get("SubVT-Nonce")
+        .ok_or(AuthError::forbidden("Nonce header is missing."))?;
+    // extract public key
+    let public_key_hex = public_key_header
+        .to_str()
+        .map(|public_key_hex| {
+            format!(
+                "0x{}",
+                public_key_hex.trim_start_matches("0x").to_uppercase()
+            )
+        })
+        .map_err(|_| AuthError::forbidden("Invalid public key header."))?;
+    let public_key = hex::decode(public_key_hex.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| PublicKey::parse_slice(&bytes, Some(PublicKeyFormat::Compressed)).ok())
+        .ok_or(AuthError::forbidden("Invalid public key header."))?;
+    // extract signature
+    let signature = signature_header
+        .to_str()
+        .ok()
+        .and_then(|hex| hex::decode(hex.trim_start_matches("0x")).ok())
+        .and_then(|bytes| Signature::parse_der(&bytes).ok())
+        .ok_or(AuthError::forbidden("Invalid signature."))?;
+    // extract nonce
+    let nonce = nonce_header
+        .to_str()
+        .ok()
+        .and_then(|number_str| number_str.parse::<u64>().ok())
+        .ok_or(AuthError::forbidden("Invalid nonce."))?;
+    // verify signature
+    let message_to_sign = format!(
+        "{}{}{}",
+        request.method().as_str(),
+        request.uri().path(),
+        nonce
+    );
+    let mut hasher = Sha256::new();
+    hasher.update(message_to_sign.as_bytes());
+    let hash = hasher.finalize();
+    let message = Message::parse_slice(&hash).unwrap();
+    if !verify(&message, &signature, &public_key) {
+        return Err(AuthError::forbidden("Invalid signature."));
+    }
+    match postgres.get_user_by_public_key(&public_key_hex).await {
+        Ok(Some(user)) => Ok(user),
+        Ok(None) => Err(AuthError::forbidden("User not found.")),
+        Err(error) => {
+            log::error!("Error while fetching user: {error:?}");
+            Err(AuthError(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Internal error.",
+            ))
+        }
+    }
+}
+
+/// HTTP middleware that authenticates the handshake requests before the connection upgrade.
+#[derive(Clone)]
+pub(crate) struct AuthLayer {
+    postgres: Arc<PostgreSQLAppStorage>,
+}
+
+impl AuthLayer {
+    pub(crate) fn new(postgres: Arc<PostgreSQLAppStorage>) -> Self {
+        Self { postgres }
+    }
+}
+
+impl<S> tower::Layer<S> for AuthLayer {
+    type Service = AuthService<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        AuthService {
+            postgres: self.postgres.clone(),
+            service,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct AuthService<S> {
+    postgres: Arc<PostgreSQLAppStorage>,
+    service: S,
+}
+
+impl<S> tower::Service<HttpRequest> for AuthService<S>
+where
+    S: tower::Service<HttpRequest, Response = HttpResponse> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: HttpRequest) -> Self::Future {
+        // the clone might not be ready, keep the service that is
+        let clone = self.service.clone();
+        let mut service = std::mem::replace(&mut self.service, clone);
+        let postgres = self.postgres.clone();
+        Box::pin(async move {
+            match authenticate(&postgres, &request).await {
+                Ok(user) => {
+                    request.extensions_mut().insert(user);
+                    service.call(request).await
+                }
+                Err(error) => {
+                    log::warn!("Reject connection: {}", error.1);
+                    metrics::rejected_connection_count().inc();
+                    Ok(error.into_response())
+                }
+            }
+        })
+    }
+}
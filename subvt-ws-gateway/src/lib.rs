@@ -0,0 +1,299 @@
+//! WebSocket gateway that serves the real-time feeds of all the networks in the app database
+//! over a single connection, so that a client can hold one connection for all its
+//! subscriptions. The gateway forwards each subscription to the network status, active and
+//! inactive validator list and validator details servers of the network, and relays the
+//! messages without decoding them.
+//!
+//! The connections are authenticated at the handshake with the app service keys, see
+//! `auth.rs`. The number of subscriptions on a connection is limited by
+//! `rpc.gateway_max_subscriptions_per_connection`.
+//!
+//! The first parameter of a subscription is the chain of the network, e.g. `polkadot`. The
+//! optional second parameter is the parameters of the upstream subscription, forwarded as they
+//! are, e.g. `["kusama", {"last_finalized_block_number": 10859839}]` for the active validator
+//! list of Kusama.
+#![warn(clippy::disallowed_types)]
+
+use crate::auth::AuthLayer;
+use crate::upstream::{Feed, Upstream, UpstreamParams};
+use async_trait::async_trait;
+use jsonrpsee::core::client::SubscriptionClientT;
+use jsonrpsee::core::ClientError;
+use jsonrpsee::server::{RpcModule, ServerBuilder, ServerHandle};
+use jsonrpsee::types::error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE};
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::{SubscriptionMessage, SubscriptionSink};
+use lazy_static::lazy_static;
+use rustc_hash::FxHashMap as HashMap;
+use serde_json::value::RawValue;
+use std::sync::Arc;
+use std::time::Duration;
+use subvt_config::Config;
+use subvt_metrics::health;
+use subvt_persistence::postgres::app::PostgreSQLAppStorage;
+use subvt_service_common::Service;
+use subvt_types::app::{Network, User};
+
+mod auth;
+mod metrics;
+mod upstream;
+
+lazy_static! {
+    static ref CONFIG: Config = Config::default();
+}
+
+/// Upstream connection pools by network chain and feed.
+type Upstreams = HashMap<String, HashMap<Feed, Arc<Upstream>>>;
+
+#[derive(Default)]
+pub struct WSGateway;
+
+impl WSGateway {
+    fn get_upstreams(networks: &[Network]) -> Upstreams {
+        let mut upstreams = Upstreams::default();
+        for network in networks {
+            let mut network_upstreams = HashMap::default();
+            for feed in Feed::ALL {
+                let (maybe_host, maybe_port) = match feed {
+                    Feed::NetworkStatus => (
+                        &network.network_status_service_host,
+                        network.network_status_service_port,
+                    ),
+                    Feed::ActiveValidatorList => (
+                        &network.active_validator_list_service_host,
+                        network.active_validator_list_service_port,
+                    ),
+                    Feed::InactiveValidatorList => (
+                        &network.inactive_validator_list_service_host,
+                        network.inactive_validator_list_service_port,
+                    ),
+                    Feed::ValidatorDetails => (
+                        &network.validator_details_service_host,
+                        network.validator_details_service_port,
+                    ),
+                };
+                if let (Some(host), Some(port)) = (maybe_host, maybe_port) {
+                    // the network path selects the network on a multi-network server
+                    let upstream = Upstream::new(
+                        format!("ws://{host}:{port}/{}", network.chain),
+                        CONFIG.rpc.gateway_upstream_connection_count,
+                    );
+                    log::info!(
+                        "Serve {} {} from {}.",
+                        network.chain,
+                        feed.name(),
+                        upstream.url(),
+                    );
+                    network_upstreams.insert(feed, Arc::new(upstream));
+                } else {
+                    log::warn!("No {} server for {}.", feed.name(), network.chain);
+                }
+            }
+            upstreams.insert(network.chain.clone(), network_upstreams);
+        }
+        upstreams
+    }
+
+    async fn run_rpc_server(
+        postgres: Arc<PostgreSQLAppStorage>,
+        upstreams: Arc<Upstreams>,
+    ) -> anyhow::Result<ServerHandle> {
+        let rpc_ws_server = ServerBuilder::default()
+            .ws_only()
+            .max_connections(CONFIG.rpc.max_connections)
+            .max_subscriptions_per_connection(CONFIG.rpc.gateway_max_subscriptions_per_connection)
+            .set_message_buffer_capacity(CONFIG.rpc.subscriber_queue_size)
+            .set_http_middleware(tower::ServiceBuilder::new().layer(AuthLayer::new(postgres)))
+            .build(format!("{}:{}", CONFIG.rpc.host, CONFIG.rpc.gateway_port))
+            .await?;
+        let mut rpc_module = RpcModule::new(());
+        for feed in Feed::ALL {
+            let (subscribe_method_name, unsubscribe_method_name) = feed.methods();
+            let upstreams = upstreams.clone();
+            rpc_module.register_subscription(
+                subscribe_method_name,
+                subscribe_method_name,
+                unsubscribe_method_name,
+                move |params, pending, _, extensions| {
+                    let upstreams = upstreams.clone();
+                    let maybe_user_id = extensions.get::<User>().map(|user| user.id);
+                    async move {
+                        let mut params = params.sequence();
+                        let (network, upstream_params) =
+                            match params.next::<String>().and_then(|network| {
+                                Ok((network, params.optional_next::<Box<RawValue>>()?))
+                            }) {
+                                Ok(params) => params,
+                                Err(error) => {
+                                    log::warn!(
+                                        "Invalid subscription parameters: {}",
+                                        error.message()
+                                    );
+                                    pending.reject(error).await;
+                                    return Ok(());
+                                }
+                            };
+                        let upstream = match upstreams
+                            .get(&network)
+                            .and_then(|network_upstreams| network_upstreams.get(&feed))
+                        {
+                            Some(upstream) => upstream.clone(),
+                            None => {
+                                pending
+                                    .reject(ErrorObjectOwned::owned(
+                                        INVALID_PARAMS_CODE,
+                                        format!("No {} feed for network {network}.", feed.name()),
+                                        None::<()>,
+                                    ))
+                                    .await;
+                                return Ok(());
+                            }
+                        };
+                        let (upstream_subscribe_method_name, upstream_unsubscribe_method_name) =
+                            feed.upstream_methods();
+                        let upstream_subscription = match upstream.get_client().await {
+                            Ok(client) => {
+                                client
+                                    .subscribe::<Box<RawValue>, _>(
+                                        upstream_subscribe_method_name,
+                                        UpstreamParams(upstream_params),
+                                        upstream_unsubscribe_method_name,
+                                    )
+                                    .await
+                            }
+                            Err(error) => Err(ClientError::Custom(error.to_string())),
+                        };
+                        let mut upstream_subscription = match upstream_subscription {
+                            Ok(upstream_subscription) => upstream_subscription,
+                            // rejected by the upstream server, e.g. for invalid parameters
+                            Err(ClientError::Call(error)) => {
+                                pending.reject(error).await;
+                                return Ok(());
+                            }
+                            Err(error) => {
+                                log::error!(
+                                    "Upstream subscription error {}: {error:?}",
+                                    upstream.url()
+                                );
+                                metrics::upstream_error_count()
+                                    .with_label_values(&[&network, feed.name()])
+                                    .inc();
+                                pending
+                                    .reject(ErrorObjectOwned::owned(
+                                        INTERNAL_ERROR_CODE,
+                                        "Feed is temporarily unavailable.",
+                                        None::<()>,
+                                    ))
+                                    .await;
+                                return Ok(());
+                            }
+                        };
+                        let sink = pending.accept().await?;
+                        log::info!(
+                            "New {network} {} subscription by user #{}.",
+                            feed.name(),
+                            maybe_user_id.unwrap_or_default(),
+                        );
+                        let _subscription_metrics = SubscriptionMetrics::new(&network, feed);
+                        loop {
+                            let message = tokio::select! {
+                                _ = sink.closed() => {
+                                    log::info!("Subscription connection closed.");
+                                    return Ok(());
+                                }
+                                message = upstream_subscription.next() => message,
+                            };
+                            match message {
+                                Some(Ok(message)) => {
+                                    send(&sink, SubscriptionMessage::from_json(&message)?).await?;
+                                }
+                                Some(Err(error)) => {
+                                    log::error!("Upstream message error: {error:?}");
+                                    metrics::upstream_error_count()
+                                        .with_label_values(&[&network, feed.name()])
+                                        .inc();
+                                    return Err("Upstream error.".into());
+                                }
+                                None => {
+                                    log::warn!(
+                                        "Upstream subscription to {} ended.",
+                                        upstream.url()
+                                    );
+                                    metrics::upstream_error_count()
+                                        .with_label_values(&[&network, feed.name()])
+                                        .inc();
+                                    return Err("Upstream subscription ended.".into());
+                                }
+                            }
+                        }
+                    }
+                },
+            )?;
+        }
+        Ok(rpc_ws_server.start(rpc_module))
+    }
+}
+
+/// Counts the subscription in the gauge of its network and feed while it lives.
+struct SubscriptionMetrics {
+    network: String,
+    feed: Feed,
+}
+
+impl SubscriptionMetrics {
+    fn new(network: &str, feed: Feed) -> Self {
+        metrics::subscription_count()
+            .with_label_values(&[network, feed.name()])
+            .inc();
+        Self {
+            network: network.to_string(),
+            feed,
+        }
+    }
+}
+
+impl Drop for SubscriptionMetrics {
+    fn drop(&mut self) {
+        metrics::subscription_count()
+            .with_label_values(&[&self.network, self.feed.name()])
+            .dec();
+    }
+}
+
+/// Sends the message, waiting for room in the subscriber's queue for at most the configured
+/// timeout. A subscriber that cannot keep up is disconnected.
+async fn send(sink: &SubscriptionSink, message: SubscriptionMessage) -> Result<(), String> {
+    let timeout = Duration::from_millis(CONFIG.rpc.subscriber_send_timeout_millis);
+    match sink.send_timeout(message, timeout).await {
+        Ok(()) => Ok(()),
+        Err(_) if sink.is_closed() => Err("Subscription connection closed.".to_string()),
+        Err(_) => {
+            metrics::slow_subscriber_disconnect_count().inc();
+            Err("Subscriber cannot keep up with the updates.".to_string())
+        }
+    }
+}
+
+/// Service implementation.
+#[async_trait(? Send)]
+impl Service for WSGateway {
+    fn get_metrics_server_addr() -> (&'static str, u16) {
+        (CONFIG.metrics.host.as_str(), CONFIG.metrics.ws_gateway_port)
+    }
+
+    async fn run(&'static self) -> anyhow::Result<()> {
+        let postgres =
+            Arc::new(PostgreSQLAppStorage::new(&CONFIG, CONFIG.get_app_postgres_url()).await?);
+        let networks = postgres.get_networks().await?;
+        let upstreams = Arc::new(WSGateway::get_upstreams(&networks));
+        let server_handle = WSGateway::run_rpc_server(postgres, upstreams).await?;
+        health::set_ready(true);
+        log::info!(
+            "WS gateway started on port {} for {} networks.",
+            CONFIG.rpc.gateway_port,
+            networks.len(),
+        );
+        server_handle.stopped().await;
+        Err(anyhow::anyhow!("WS gateway stopped."))
+    }
+}
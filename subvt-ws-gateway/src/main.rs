@@ -0,0 +1,14 @@
+//! See `./lib.rs` for details.
+
+use lazy_static::lazy_static;
+use subvt_service_common::Service;
+use subvt_ws_gateway::WSGateway;
+
+lazy_static! {
+    static ref SERVICE: WSGateway = WSGateway;
+}
+
+#[tokio::main]
+async fn main() {
+    SERVICE.start().await;
+}
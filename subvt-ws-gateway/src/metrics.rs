@@ -0,0 +1,54 @@
+use once_cell::sync::Lazy;
+use subvt_metrics::registry::{IntCounter, IntCounterVec, IntGaugeVec};
+
+const METRIC_PREFIX: &str = "subvt_ws_gateway";
+
+pub fn subscription_count() -> IntGaugeVec {
+    static METER: Lazy<IntGaugeVec> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_gauge_vec(
+            METRIC_PREFIX,
+            "subscription_count",
+            "Number of subscriptions by network and feed",
+            &["network", "feed"],
+        )
+        .unwrap()
+    });
+    METER.clone()
+}
+
+pub fn rejected_connection_count() -> IntCounter {
+    static METER: Lazy<IntCounter> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_counter(
+            METRIC_PREFIX,
+            "rejected_connection_count",
+            "Number of connections rejected for failed authentication",
+        )
+        .unwrap()
+    });
+    METER.clone()
+}
+
+pub fn upstream_error_count() -> IntCounterVec {
+    static METER: Lazy<IntCounterVec> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_counter_vec(
+            METRIC_PREFIX,
+            "upstream_error_count",
+            "Number of upstream connection and subscription errors by network and feed",
+            &["network", "feed"],
+        )
+        .unwrap()
+    });
+    METER.clone()
+}
+
+pub fn slow_subscriber_disconnect_count() -> IntCounter {
+    static METER: Lazy<IntCounter> = Lazy::new(|| {
+        subvt_metrics::registry::register_int_counter(
+            METRIC_PREFIX,
+            "slow_subscriber_disconnect_count",
+            "Number of subscribers disconnected for not keeping up with the updates",
+        )
+        .unwrap()
+    });
+    METER.clone()
+}
@@ -0,0 +1,120 @@
+//! Connections from the gateway to the WS servers of a network. Each feed of each network has a
+//! fixed number of client connections, and the gateway subscriptions are spread over them in
+//! round-robin order. A dropped connection is re-established by the next subscription that is
+//! assigned to it.
+use jsonrpsee::core::traits::ToRpcParams;
+use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
+use serde_json::value::RawValue;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Real-time feeds served by the gateway.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum Feed {
+    NetworkStatus,
+    ActiveValidatorList,
+    InactiveValidatorList,
+    ValidatorDetails,
+}
+
+impl Feed {
+    pub(crate) const ALL: [Feed; 4] = [
+        Self::NetworkStatus,
+        Self::ActiveValidatorList,
+        Self::InactiveValidatorList,
+        Self::ValidatorDetails,
+    ];
+
+    /// Name of the feed in the gateway methods and the metric labels.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::NetworkStatus => "networkStatus",
+            Self::ActiveValidatorList => "activeValidatorList",
+            Self::InactiveValidatorList => "inactiveValidatorList",
+            Self::ValidatorDetails => "validatorDetails",
+        }
+    }
+
+    /// Subscription and unsubscription methods on the gateway.
+    pub(crate) fn methods(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::NetworkStatus => ("subscribe_networkStatus", "unsubscribe_networkStatus"),
+            Self::ActiveValidatorList => (
+                "subscribe_activeValidatorList",
+                "unsubscribe_activeValidatorList",
+            ),
+            Self::InactiveValidatorList => (
+                "subscribe_inactiveValidatorList",
+                "unsubscribe_inactiveValidatorList",
+            ),
+            Self::ValidatorDetails => {
+                ("subscribe_validatorDetails", "unsubscribe_validatorDetails")
+            }
+        }
+    }
+
+    /// Subscription and unsubscription methods on the upstream server.
+    pub(crate) fn upstream_methods(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::NetworkStatus => ("subscribe_networkStatus", "unsubscribe_networkStatus"),
+            Self::ActiveValidatorList | Self::InactiveValidatorList => {
+                ("subscribe_validatorList", "unsubscribe_validatorList")
+            }
+            Self::ValidatorDetails => {
+                ("subscribe_validatorDetails", "unsubscribe_validatorDetails")
+            }
+        }
+    }
+}
+
+/// Subscription parameters that are forwarded to the upstream server as they are.
+pub(crate) struct UpstreamParams(pub Option<Box<RawValue>>);
+
+impl ToRpcParams for UpstreamParams {
+    fn to_rpc_params(self) -> Result<Option<Box<RawValue>>, serde_json::Error> {
+        Ok(self.0)
+    }
+}
+
+pub(crate) struct Upstream {
+    url: String,
+    clients: Vec<Mutex<Option<Arc<WsClient>>>>,
+    next_client_index: AtomicUsize,
+}
+
+impl Upstream {
+    pub(crate) fn new(url: String, connection_count: usize) -> Self {
+        Self {
+            url,
+            clients: (0..connection_count.max(1))
+                .map(|_| Mutex::new(None))
+                .collect(),
+            next_client_index: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Next connection in the pool, connected if it was not or it got disconnected.
+    pub(crate) async fn get_client(&self) -> anyhow::Result<Arc<WsClient>> {
+        let index = self.next_client_index.fetch_add(1, Ordering::Relaxed) % self.clients.len();
+        let mut maybe_client = self.clients[index].lock().await;
+        if let Some(client) = maybe_client.as_ref() {
+            if client.is_connected() {
+                return Ok(client.clone());
+            }
+            log::warn!("Reconnect to upstream {} #{index}.", self.url);
+        }
+        let client = Arc::new(
+            WsClientBuilder::default()
+                .max_response_size(u32::MAX)
+                .build(&self.url)
+                .await?,
+        );
+        *maybe_client = Some(client.clone());
+        Ok(client)
+    }
+}
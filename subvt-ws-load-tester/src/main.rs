@@ -0,0 +1,231 @@
+//! Load tester for the WebSocket servers. Opens the given number of connections with a single
+//! subscription each, keeps them open for the given duration, and reports the number of live
+//! subscriptions, the received messages and bytes, and the latency of the initial message.
+//! Exits with an error code if any subscription fails or ends before the end of the test.
+//!
+//! e.g. `subvt-ws-load-tester --url ws://127.0.0.1:7889 --method subscribe_validatorList
+//! --unsubscribe-method unsubscribe_validatorList --subscribers 10000`
+#![warn(clippy::disallowed_types)]
+use clap::{arg, value_parser, Command};
+use jsonrpsee::core::client::SubscriptionClientT;
+use jsonrpsee::core::params::{ArrayParams, ObjectParams};
+use jsonrpsee::core::traits::ToRpcParams;
+use jsonrpsee::ws_client::WsClientBuilder;
+use serde_json::value::RawValue;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+struct Args {
+    url: String,
+    subscribe_method: String,
+    unsubscribe_method: String,
+    params: serde_json::Value,
+    subscriber_count: u64,
+    connect_concurrency: usize,
+    duration: Duration,
+}
+
+#[derive(Default)]
+struct Stats {
+    subscription_count: AtomicU64,
+    failed_subscription_count: AtomicU64,
+    ended_subscription_count: AtomicU64,
+    message_count: AtomicU64,
+    byte_count: AtomicU64,
+    initial_message_latencies: Mutex<Vec<Duration>>,
+}
+
+fn get_args() -> anyhow::Result<Args> {
+    let matches = Command::new("SubVT WS Load Tester")
+        .version("0.1.0")
+        .about("Opens concurrent subscriptions to a SubVT WebSocket server.")
+        .arg(arg!(--url <URL> "Server URL, e.g. ws://127.0.0.1:7889").required(true))
+        .arg(arg!(--method <METHOD> "Subscription method, e.g. subscribe_validatorList").required(true))
+        .arg(
+            arg!(--"unsubscribe-method" <METHOD> "Unsubscription method, e.g. unsubscribe_validatorList")
+                .required(true),
+        )
+        .arg(arg!(--params <JSON> "Subscription parameters as a JSON array or object").default_value("[]"))
+        .arg(
+            arg!(--subscribers <COUNT> "Number of subscribers, each on its own connection")
+                .value_parser(value_parser!(u64))
+                .default_value("10000"),
+        )
+        .arg(
+            arg!(--"connect-concurrency" <COUNT> "Number of subscribers that connect at the same time")
+                .value_parser(value_parser!(usize))
+                .default_value("250"),
+        )
+        .arg(
+            arg!(--"duration-seconds" <SECONDS> "Duration of the test after the first connection")
+                .value_parser(value_parser!(u64))
+                .default_value("120"),
+        )
+        .get_matches();
+    let params: serde_json::Value =
+        serde_json::from_str(matches.get_one::<String>("params").unwrap())?;
+    if !params.is_array() && !params.is_object() {
+        return Err(anyhow::anyhow!(
+            "Parameters should be a JSON array or object."
+        ));
+    }
+    Ok(Args {
+        url: matches.get_one::<String>("url").unwrap().clone(),
+        subscribe_method: matches.get_one::<String>("method").unwrap().clone(),
+        unsubscribe_method: matches
+            .get_one::<String>("unsubscribe-method")
+            .unwrap()
+            .clone(),
+        params,
+        subscriber_count: *matches.get_one::<u64>("subscribers").unwrap(),
+        connect_concurrency: *matches.get_one::<usize>("connect-concurrency").unwrap(),
+        duration: Duration::from_secs(*matches.get_one::<u64>("duration-seconds").unwrap()),
+    })
+}
+
+/// Subscribes on a new connection and counts the received messages until the deadline.
+async fn run_subscriber<P: ToRpcParams + Send>(
+    args: &Args,
+    params: P,
+    stats: &Stats,
+    semaphore: &Semaphore,
+    deadline: tokio::time::Instant,
+) -> anyhow::Result<()> {
+    let (client, mut subscription, start) = {
+        let _permit = semaphore.acquire().await?;
+        let start = Instant::now();
+        let client = WsClientBuilder::default()
+            .max_response_size(u32::MAX)
+            .build(&args.url)
+            .await?;
+        let subscription = client
+            .subscribe::<Box<RawValue>, _>(&args.subscribe_method, params, &args.unsubscribe_method)
+            .await?;
+        (client, subscription, start)
+    };
+    stats.subscription_count.fetch_add(1, Ordering::SeqCst);
+    let mut is_initial = true;
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep_until(deadline) => break,
+            message = subscription.next() => match message {
+                Some(Ok(message)) => {
+                    if is_initial {
+                        stats.initial_message_latencies.lock().unwrap().push(start.elapsed());
+                        is_initial = false;
+                    }
+                    stats.message_count.fetch_add(1, Ordering::SeqCst);
+                    stats.byte_count.fetch_add(message.get().len() as u64, Ordering::SeqCst);
+                }
+                Some(Err(error)) => {
+                    stats.ended_subscription_count.fetch_add(1, Ordering::SeqCst);
+                    return Err(error.into());
+                }
+                None => {
+                    stats.ended_subscription_count.fetch_add(1, Ordering::SeqCst);
+                    return Err(anyhow::anyhow!("Subscription ended by the server."));
+                }
+            }
+        }
+    }
+    drop(subscription);
+    drop(client);
+    Ok(())
+}
+
+fn print_stats(stats: &Stats, elapsed: Duration) {
+    let subscription_count = stats.subscription_count.load(Ordering::SeqCst);
+    let ended_subscription_count = stats.ended_subscription_count.load(Ordering::SeqCst);
+    println!(
+        "[{:>4}s] live: {} failed: {} (ended by the server: {}) messages: {} bytes: {}",
+        elapsed.as_secs(),
+        subscription_count - ended_subscription_count,
+        stats.failed_subscription_count.load(Ordering::SeqCst),
+        ended_subscription_count,
+        stats.message_count.load(Ordering::SeqCst),
+        stats.byte_count.load(Ordering::SeqCst),
+    );
+}
+
+fn get_percentile(sorted_latencies: &[Duration], percentile: usize) -> Duration {
+    if sorted_latencies.is_empty() {
+        return Duration::ZERO;
+    }
+    let index = (sorted_latencies.len() * percentile / 100).min(sorted_latencies.len() - 1);
+    sorted_latencies[index]
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Arc::new(get_args()?);
+    let stats = Arc::new(Stats::default());
+    let semaphore = Arc::new(Semaphore::new(args.connect_concurrency));
+    let start = Instant::now();
+    let deadline = tokio::time::Instant::now() + args.duration;
+    let mut tasks = Vec::with_capacity(args.subscriber_count as usize);
+    for _ in 0..args.subscriber_count {
+        let args = args.clone();
+        let stats = stats.clone();
+        let semaphore = semaphore.clone();
+        tasks.push(tokio::spawn(async move {
+            let result = match &args.params {
+                serde_json::Value::Object(map) => {
+                    let mut params = ObjectParams::new();
+                    for (key, value) in map {
+                        params.insert(key, value)?;
+                    }
+                    run_subscriber(&args, params, &stats, &semaphore, deadline).await
+                }
+                _ => {
+                    let mut params = ArrayParams::new();
+                    for value in args.params.as_array().into_iter().flatten() {
+                        params.insert(value)?;
+                    }
+                    run_subscriber(&args, params, &stats, &semaphore, deadline).await
+                }
+            };
+            if let Err(error) = &result {
+                stats
+                    .failed_subscription_count
+                    .fetch_add(1, Ordering::SeqCst);
+                println!("Subscriber error: {error:?}");
+            }
+            result
+        }));
+    }
+    let reporter = {
+        let stats = stats.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(10));
+            loop {
+                interval.tick().await;
+                print_stats(&stats, start.elapsed());
+            }
+        })
+    };
+    for task in tasks {
+        let _ = task.await;
+    }
+    reporter.abort();
+    print_stats(&stats, start.elapsed());
+    let mut latencies = stats.initial_message_latencies.lock().unwrap().clone();
+    latencies.sort();
+    println!(
+        "Initial message latency p50: {:?} p90: {:?} p99: {:?} max: {:?}",
+        get_percentile(&latencies, 50),
+        get_percentile(&latencies, 90),
+        get_percentile(&latencies, 99),
+        latencies.last().cloned().unwrap_or_default(),
+    );
+    let failed_subscription_count = stats.failed_subscription_count.load(Ordering::SeqCst);
+    if failed_subscription_count > 0 {
+        return Err(anyhow::anyhow!(
+            "{failed_subscription_count} of {} subscribers failed.",
+            args.subscriber_count
+        ));
+    }
+    println!("All {} subscribers completed.", args.subscriber_count);
+    Ok(())
+}